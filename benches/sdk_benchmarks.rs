@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
 use goldrush_sdk::{
-    GoldRushClient, ClientConfig, MetricsCollector, MemoryCache,
+    GoldRushClient, ClientConfig, MetricsCollector, MemoryCache, Endpoint,
     Validator, Sanitizer, RateLimiter, RateLimitConfig,
 };
 use std::sync::Arc;
@@ -74,6 +74,7 @@ fn bench_rate_limiting(c: &mut Criterion) {
         burst_capacity: 200,
         enable_backoff: true,
         max_retries: 3,
+        ..Default::default()
     });
     
     group.bench_function("rate_limit_acquire", |b| {
@@ -94,13 +95,13 @@ fn bench_metrics(c: &mut Criterion) {
     
     group.bench_function("record_success", |b| {
         b.to_async(&rt).iter(|| async {
-            metrics.record_success("test_endpoint", Duration::from_millis(100)).await;
+            metrics.record_success(Endpoint::BalancesV2, Duration::from_millis(100)).await;
         })
     });
     
     group.bench_function("record_error", |b| {
         b.to_async(&rt).iter(|| async {
-            metrics.record_error("test_endpoint", "test_error").await;
+            metrics.record_error(Endpoint::BalancesV2, "test_error").await;
         })
     });
     
@@ -1,4 +1,5 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, BalancesOptions};
+use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, BalancesOptions, QuoteCurrency};
+use goldrush_sdk::decimal::QuoteValue;
 use std::env;
 
 /// Example demonstrating how to fetch token balances for a wallet address.
@@ -59,7 +60,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nFetching filtered balances (USD quotes, no spam, limited)...");
 
     let options = BalancesOptions::new()
-        .quote_currency("USD")
+        .quote_currency(QuoteCurrency::USD)
         .no_spam(true)
         .page_size(10);
 
@@ -74,7 +75,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mut total_value = 0.0;
         for item in &data.items {
             if let Some(quote) = item.quote {
-                total_value += quote;
+                total_value += QuoteValue::as_f64(&quote);
             }
         }
 
@@ -82,7 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // Show top tokens by value
         let mut tokens_with_value: Vec<_> = data.items.iter()
-            .filter(|item| item.quote.unwrap_or(0.0) > 0.0)
+            .filter(|item| item.has_quote_value())
             .collect();
         tokens_with_value.sort_by(|a, b|
             b.quote.partial_cmp(&a.quote).unwrap_or(std::cmp::Ordering::Equal)
@@ -91,7 +92,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("\nTop tokens by value:");
         for (i, token) in tokens_with_value.iter().take(5).enumerate() {
             let symbol = token.contract_ticker_symbol.as_deref().unwrap_or("Unknown");
-            let quote = token.quote.unwrap();
+            let quote = QuoteValue::as_f64(&token.quote.unwrap());
             let percentage = (quote / total_value) * 100.0;
 
             println!(
@@ -116,6 +117,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Historical portfolio data received");
             if let Some(data) = portfolio.data {
                 println!("Portfolio items: {}", data.items.len());
+                if let Some((day, total)) = data.total_quote_series().last() {
+                    println!("Total value on {}: ${:.2}", day, total);
+                }
             }
         }
         Err(e) => {
@@ -127,7 +131,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nChecking Polygon balances...");
 
     let polygon_options = BalancesOptions::new()
-        .quote_currency("USD")
+        .quote_currency(QuoteCurrency::USD)
         .page_size(5);
 
     match client
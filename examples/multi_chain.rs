@@ -1,4 +1,4 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, MultiChainBalancesOptions};
+use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, MultiChainBalancesOptions, QuoteCurrency};
 use std::env;
 
 /// Example demonstrating cross-chain endpoints.
@@ -32,7 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Example 2: Multi-chain balances
     println!("\nFetching multi-chain balances...");
     let opts = MultiChainBalancesOptions::new()
-        .quote_currency("USD");
+        .quote_currency(QuoteCurrency::USD);
 
     match client.all_chains_service().get_multi_chain_balances(address, Some(opts)).await {
         Ok(response) => {
@@ -62,9 +62,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         {
             Ok(response) => {
                 if let Some(data) = response.data {
-                    let total: f64 = data.items.iter()
-                        .filter_map(|item| item.quote)
-                        .sum();
+                    let total = data.total_value();
                     println!("  {}: {} tokens, total value: ${:.2}", name, data.items.len(), total);
                 }
             }
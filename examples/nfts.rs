@@ -40,7 +40,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let collection = nft.contract_name.as_deref()
                 .or(nft.contract_ticker_symbol.as_deref())
                 .unwrap_or("Unknown Collection");
-            let balance = nft.token_balance.as_deref().unwrap_or("1");
+            let balance = nft.token_balance.as_ref().map_or_else(|| "1".to_string(), ToString::to_string);
 
             println!(
                 "  {}: {} #{} ({}x) - {}",
@@ -242,7 +242,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             let mut collection_counts: Vec<_> = collections.into_iter().collect();
-            collection_counts.sort_by(|a, b| b.1.cmp(&a.1));
+            collection_counts.sort_by_key(|c| std::cmp::Reverse(c.1));
 
             for (collection, count) in collection_counts.iter().take(10) {
                 println!("  {}: {} NFTs", collection, count);
@@ -1,4 +1,4 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, TxOptions};
+use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, TxOptions, QuoteCurrency};
 use std::env;
 
 /// Example demonstrating how to fetch transactions for a wallet address.
@@ -67,7 +67,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let detailed_options = TxOptions::new()
         .page_size(5)
-        .quote_currency("USD")
+        .quote_currency(QuoteCurrency::USD)
         .no_logs(false);
 
     let detailed_txs = client
@@ -0,0 +1,582 @@
+//! Method selector lookup and ABI decoding of transaction input data.
+//!
+//! Transactions fetched with `with_input_data(true)` carry raw call data in
+//! [`TransactionItem::input`](crate::TransactionItem). A [`SelectorRegistry`]
+//! maps 4-byte selectors to method signatures (registered by hand, from a JSON
+//! ABI, or from the bundled database behind the `selector-db` feature) and
+//! decodes the call arguments.
+//!
+//! ```rust
+//! use goldrush_sdk::{AbiValue, SelectorRegistry};
+//!
+//! let mut registry = SelectorRegistry::new();
+//! registry.register("transfer(address,uint256)").unwrap();
+//!
+//! let input = concat!(
+//!     "0xa9059cbb",
+//!     "000000000000000000000000742d35cc6634c0532925a3b8d4fc24f3c4ad6a8b",
+//!     "00000000000000000000000000000000000000000000000000000000000003e8",
+//! );
+//! let call = registry.decode(input).unwrap();
+//! assert_eq!(call.name, "transfer");
+//! assert_eq!(call.args[1].value, AbiValue::Uint("1000".to_string()));
+//! ```
+
+use crate::{Error, Result};
+use std::collections::HashMap;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Keccak-256 hash of `data`.
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut out = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut out);
+    out
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Compute the `0x`-prefixed 4-byte selector of a canonical method signature.
+pub fn selector(signature: &str) -> String {
+    format!("0x{}", to_hex(&keccak256(signature.as_bytes())[..4]))
+}
+
+/// Compute the `0x`-prefixed topic hash of a canonical event signature, e.g.
+/// `Transfer(address,address,uint256)`.
+pub fn event_topic(signature: &str) -> String {
+    format!("0x{}", to_hex(&keccak256(signature.as_bytes())))
+}
+
+/// Decode hex-encoded ABI data, such as a log's data or one of its indexed
+/// topics, as a sequence of canonical types.
+pub(crate) fn decode_hex(types: &[&str], hex: &str) -> Option<Vec<AbiValue>> {
+    let types = types.iter().map(|t| AbiType::parse(t)).collect::<Option<Vec<_>>>()?;
+    decode_sequence(&types, &from_hex(hex)?)
+}
+
+/// A Solidity ABI type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AbiType {
+    Address,
+    Bool,
+    Uint(usize),
+    Int(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<AbiType>),
+    FixedArray(Box<AbiType>, usize),
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Some(stripped) = s.strip_suffix(']') {
+            let open = stripped.rfind('[')?;
+            let inner = AbiType::parse(&stripped[..open])?;
+            let size = &stripped[open + 1..];
+            return if size.is_empty() {
+                Some(AbiType::Array(Box::new(inner)))
+            } else {
+                Some(AbiType::FixedArray(Box::new(inner), size.parse().ok()?))
+            };
+        }
+        if let Some(inner) = s.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+            return split_top_level(inner)
+                .into_iter()
+                .map(AbiType::parse)
+                .collect::<Option<Vec<_>>>()
+                .map(AbiType::Tuple);
+        }
+        match s {
+            "address" => Some(AbiType::Address),
+            "bool" => Some(AbiType::Bool),
+            "string" => Some(AbiType::String),
+            "bytes" => Some(AbiType::Bytes),
+            "uint" => Some(AbiType::Uint(256)),
+            "int" => Some(AbiType::Int(256)),
+            "function" => Some(AbiType::FixedBytes(24)),
+            _ => {
+                if let Some(bits) = s.strip_prefix("uint") {
+                    bits.parse().ok().filter(|b| (8..=256).contains(b) && b % 8 == 0).map(AbiType::Uint)
+                } else if let Some(bits) = s.strip_prefix("int") {
+                    bits.parse().ok().filter(|b| (8..=256).contains(b) && b % 8 == 0).map(AbiType::Int)
+                } else if let Some(len) = s.strip_prefix("bytes") {
+                    len.parse().ok().filter(|l| (1..=32).contains(l)).map(AbiType::FixedBytes)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::Bytes | AbiType::String | AbiType::Array(_) => true,
+            AbiType::FixedArray(inner, _) => inner.is_dynamic(),
+            AbiType::Tuple(items) => items.iter().any(AbiType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Number of bytes this type occupies in the head of its enclosing block.
+    fn head_size(&self) -> usize {
+        if self.is_dynamic() {
+            return 32;
+        }
+        match self {
+            AbiType::FixedArray(inner, len) => inner.head_size() * len,
+            AbiType::Tuple(items) => items.iter().map(AbiType::head_size).sum(),
+            _ => 32,
+        }
+    }
+}
+
+/// Split a comma-separated type list, ignoring commas nested in parentheses.
+fn split_top_level(s: &str) -> Vec<&str> {
+    if s.trim().is_empty() {
+        return Vec::new();
+    }
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+/// A decoded ABI value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    /// `0x`-prefixed lower-case address.
+    Address(String),
+    Bool(bool),
+    /// Unsigned integer as a decimal string.
+    Uint(String),
+    /// Signed integer as a decimal string.
+    Int(String),
+    /// `bytesN` as `0x`-prefixed hex.
+    FixedBytes(String),
+    /// Dynamic `bytes` as `0x`-prefixed hex.
+    Bytes(String),
+    String(String),
+    /// Dynamic or fixed-size array elements.
+    Array(Vec<AbiValue>),
+    /// Tuple (struct) components in declaration order.
+    Tuple(Vec<AbiValue>),
+}
+
+/// One decoded call argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedArg {
+    /// Canonical Solidity type, e.g. `uint256`.
+    pub kind: String,
+    pub value: AbiValue,
+}
+
+/// A decoded method call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall {
+    /// `0x`-prefixed 4-byte selector.
+    pub selector: String,
+    /// Method name, e.g. `transfer`.
+    pub name: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`.
+    pub signature: String,
+    pub args: Vec<DecodedArg>,
+}
+
+/// A parsed method signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodSignature {
+    pub name: String,
+    /// Canonical input types.
+    pub inputs: Vec<String>,
+    types: Vec<AbiType>,
+}
+
+impl MethodSignature {
+    /// Parse a canonical signature such as `transfer(address,uint256)`.
+    pub fn parse(signature: &str) -> Result<Self> {
+        let invalid = || Error::Config(format!("Invalid method signature: {}", signature));
+        let signature = signature.trim();
+        let open = signature.find('(').ok_or_else(invalid)?;
+        let name = &signature[..open];
+        let params = signature[open + 1..].strip_suffix(')').ok_or_else(invalid)?;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+            return Err(invalid());
+        }
+
+        let inputs: Vec<String> = split_top_level(params).into_iter().map(str::to_string).collect();
+        let types = inputs.iter()
+            .map(|t| AbiType::parse(t))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        Ok(Self { name: name.to_string(), inputs, types })
+    }
+
+    /// Canonical signature string.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, self.inputs.join(","))
+    }
+
+    /// `0x`-prefixed 4-byte selector.
+    pub fn selector(&self) -> String {
+        selector(&self.signature())
+    }
+
+    /// Decode ABI-encoded arguments (call data without the selector).
+    pub fn decode_args(&self, data: &[u8]) -> Option<Vec<DecodedArg>> {
+        let values = decode_sequence(&self.types, data)?;
+        Some(self.inputs.iter().cloned().zip(values)
+            .map(|(kind, value)| DecodedArg { kind, value })
+            .collect())
+    }
+}
+
+fn word(data: &[u8], pos: usize) -> Option<&[u8]> {
+    data.get(pos..pos.checked_add(32)?)
+}
+
+fn read_usize(data: &[u8], pos: usize) -> Option<usize> {
+    let w = word(data, pos)?;
+    if w[..24].iter().any(|&b| b != 0) {
+        return None;
+    }
+    let value = u64::from_be_bytes(w[24..].try_into().ok()?);
+    usize::try_from(value).ok()
+}
+
+/// Render a big-endian unsigned integer as a decimal string.
+fn to_decimal(bytes: &[u8]) -> String {
+    let mut num = bytes.to_vec();
+    let mut digits = Vec::new();
+    while num.iter().any(|&b| b != 0) {
+        let mut rem = 0u32;
+        for b in num.iter_mut() {
+            let cur = rem * 256 + *b as u32;
+            *b = (cur / 10) as u8;
+            rem = cur % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+fn decode_sequence(types: &[AbiType], block: &[u8]) -> Option<Vec<AbiValue>> {
+    let mut pos = 0;
+    let mut values = Vec::with_capacity(types.len());
+    for ty in types {
+        values.push(decode_at(ty, block, pos)?);
+        pos += ty.head_size();
+    }
+    Some(values)
+}
+
+fn decode_at(ty: &AbiType, block: &[u8], pos: usize) -> Option<AbiValue> {
+    if ty.is_dynamic() {
+        let offset = read_usize(block, pos)?;
+        return decode_tail(ty, block.get(offset..)?);
+    }
+
+    let w = word(block, pos);
+    match ty {
+        AbiType::Address => Some(AbiValue::Address(format!("0x{}", to_hex(&w?[12..])))),
+        AbiType::Bool => Some(AbiValue::Bool(w?[31] != 0)),
+        AbiType::Uint(_) => Some(AbiValue::Uint(to_decimal(w?))),
+        AbiType::Int(_) => {
+            let w = w?;
+            if w[0] & 0x80 == 0 {
+                return Some(AbiValue::Int(to_decimal(w)));
+            }
+            // Two's complement: negate by inverting and adding one.
+            let mut neg: Vec<u8> = w.iter().map(|b| !b).collect();
+            for b in neg.iter_mut().rev() {
+                let (sum, overflow) = b.overflowing_add(1);
+                *b = sum;
+                if !overflow {
+                    break;
+                }
+            }
+            Some(AbiValue::Int(format!("-{}", to_decimal(&neg))))
+        }
+        AbiType::FixedBytes(len) => Some(AbiValue::FixedBytes(format!("0x{}", to_hex(&w?[..*len])))),
+        AbiType::FixedArray(inner, len) => {
+            let types = vec![(**inner).clone(); *len];
+            decode_sequence(&types, block.get(pos..)?).map(AbiValue::Array)
+        }
+        AbiType::Tuple(items) => decode_sequence(items, block.get(pos..)?).map(AbiValue::Tuple),
+        AbiType::Bytes | AbiType::String | AbiType::Array(_) => None,
+    }
+}
+
+fn decode_tail(ty: &AbiType, block: &[u8]) -> Option<AbiValue> {
+    match ty {
+        AbiType::Bytes | AbiType::String => {
+            let len = read_usize(block, 0)?;
+            let bytes = block.get(32..32usize.checked_add(len)?)?;
+            if *ty == AbiType::String {
+                Some(AbiValue::String(String::from_utf8_lossy(bytes).into_owned()))
+            } else {
+                Some(AbiValue::Bytes(format!("0x{}", to_hex(bytes))))
+            }
+        }
+        AbiType::Array(inner) => {
+            let len = read_usize(block, 0)?;
+            let elements = block.get(32..)?;
+            // Every element needs at least one head word; reject absurd lengths early.
+            if len > elements.len() / 32 {
+                return None;
+            }
+            let types = vec![(**inner).clone(); len];
+            decode_sequence(&types, elements).map(AbiValue::Array)
+        }
+        AbiType::FixedArray(inner, len) => {
+            let types = vec![(**inner).clone(); *len];
+            decode_sequence(&types, block).map(AbiValue::Array)
+        }
+        AbiType::Tuple(items) => decode_sequence(items, block).map(AbiValue::Tuple),
+        _ => None,
+    }
+}
+
+/// Canonical type string for a JSON ABI parameter, expanding tuples.
+fn canonical_param_type(param: &serde_json::Value) -> Option<String> {
+    let ty = param.get("type")?.as_str()?;
+    match ty.strip_prefix("tuple") {
+        Some(suffix) => {
+            let components = param.get("components")?.as_array()?
+                .iter()
+                .map(canonical_param_type)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("({}){}", components.join(","), suffix))
+        }
+        None => Some(ty.to_string()),
+    }
+}
+
+/// Registry mapping 4-byte selectors to method signatures.
+#[derive(Debug, Clone, Default)]
+pub struct SelectorRegistry {
+    signatures: HashMap<String, MethodSignature>,
+}
+
+impl SelectorRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry preloaded with the bundled signature database.
+    #[cfg(feature = "selector-db")]
+    pub fn bundled() -> Self {
+        let mut registry = Self::new();
+        for signature in BUNDLED_SIGNATURES {
+            registry.register(signature).expect("bundled signatures are valid");
+        }
+        registry
+    }
+
+    /// Register a canonical method signature, returning its selector.
+    pub fn register(&mut self, signature: &str) -> Result<String> {
+        let method = MethodSignature::parse(signature)?;
+        let selector = method.selector();
+        self.signatures.insert(selector.clone(), method);
+        Ok(selector)
+    }
+
+    /// Register every function in a JSON ABI, returning how many were added.
+    pub fn register_abi_json(&mut self, abi: &str) -> Result<usize> {
+        let entries: Vec<serde_json::Value> = serde_json::from_str(abi)?;
+        let mut count = 0;
+        for entry in entries.iter().filter(|e| e.get("type").and_then(|t| t.as_str()) == Some("function")) {
+            let name = entry.get("name").and_then(|n| n.as_str())
+                .ok_or_else(|| Error::Config("ABI function is missing a name".to_string()))?;
+            let inputs = entry.get("inputs").and_then(|i| i.as_array()).cloned().unwrap_or_default()
+                .iter()
+                .map(canonical_param_type)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| Error::Config(format!("Invalid ABI inputs for function {}", name)))?;
+            self.register(&format!("{}({})", name, inputs.join(",")))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Look up a signature by `0x`-prefixed selector (case-insensitive).
+    pub fn get(&self, selector: &str) -> Option<&MethodSignature> {
+        self.signatures.get(&selector.trim().to_ascii_lowercase())
+    }
+
+    /// Number of registered signatures.
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Decode hex-encoded call data. Returns `None` if the selector is unknown
+    /// or the arguments do not match the registered signature.
+    pub fn decode(&self, input: &str) -> Option<DecodedCall> {
+        let bytes = from_hex(input)?;
+        let selector = format!("0x{}", to_hex(bytes.get(..4)?));
+        let method = self.get(&selector)?;
+        let args = method.decode_args(&bytes[4..])?;
+        Some(DecodedCall {
+            selector,
+            name: method.name.clone(),
+            signature: method.signature(),
+            args,
+        })
+    }
+}
+
+/// Commonly seen method signatures bundled with the `selector-db` feature.
+#[cfg(feature = "selector-db")]
+const BUNDLED_SIGNATURES: &[&str] = &[
+    // ERC-20
+    "transfer(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "approve(address,uint256)",
+    "increaseAllowance(address,uint256)",
+    "decreaseAllowance(address,uint256)",
+    "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+    // ERC-721 / ERC-1155
+    "safeTransferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+    "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+    "setApprovalForAll(address,bool)",
+    // WETH and common token operations
+    "deposit()",
+    "withdraw(uint256)",
+    "mint(address,uint256)",
+    "burn(uint256)",
+    // Uniswap V2 router
+    "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+    "swapExactETHForTokens(uint256,address[],address,uint256)",
+    "swapExactTokensForETH(uint256,uint256,address[],address,uint256)",
+    "swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256,uint256,address[],address,uint256)",
+    "addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)",
+    "addLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    // Uniswap V3 router and universal router
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactInput((bytes,address,uint256,uint256,uint256))",
+    "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "multicall(bytes[])",
+    "multicall(uint256,bytes[])",
+    "execute(bytes,bytes[])",
+    "execute(bytes,bytes[],uint256)",
+    // Staking, governance and ownership
+    "stake(uint256)",
+    "unstake(uint256)",
+    "claim()",
+    "delegate(address)",
+    "transferOwnership(address)",
+    "renounceOwnership()",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector() {
+        assert_eq!(selector("transfer(address,uint256)"), "0xa9059cbb");
+        assert_eq!(selector("approve(address,uint256)"), "0x095ea7b3");
+        assert_eq!(MethodSignature::parse("balanceOf(address)").unwrap().selector(), "0x70a08231");
+        assert!(MethodSignature::parse("bad(uint7)").is_err());
+    }
+
+    #[test]
+    fn test_decode_dynamic_and_signed_args() {
+        let mut registry = SelectorRegistry::new();
+        let sel = registry.register("f(int256,string,address[])").unwrap();
+
+        let input = format!(
+            "{}{}{}{}{}{}{}{}",
+            sel,
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff9c", // -100
+            "0000000000000000000000000000000000000000000000000000000000000060", // string offset
+            "00000000000000000000000000000000000000000000000000000000000000a0", // array offset
+            "0000000000000000000000000000000000000000000000000000000000000002", // "hi"
+            "6869000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000001", // one address
+            "000000000000000000000000742d35cc6634c0532925a3b8d4fc24f3c4ad6a8b",
+        );
+
+        let call = registry.decode(&input).unwrap();
+        assert_eq!(call.signature, "f(int256,string,address[])");
+        assert_eq!(call.args[0].value, AbiValue::Int("-100".to_string()));
+        assert_eq!(call.args[1].value, AbiValue::String("hi".to_string()));
+        assert_eq!(call.args[2].value, AbiValue::Array(vec![
+            AbiValue::Address("0x742d35cc6634c0532925a3b8d4fc24f3c4ad6a8b".to_string()),
+        ]));
+
+        assert!(registry.decode("0xdeadbeef").is_none());
+        assert!(registry.decode(&input[..40]).is_none());
+    }
+
+    #[cfg(feature = "selector-db")]
+    #[test]
+    fn test_bundled_registry() {
+        let registry = SelectorRegistry::bundled();
+        assert_eq!(registry.len(), BUNDLED_SIGNATURES.len());
+        assert_eq!(registry.get("0xa9059cbb").unwrap().name, "transfer");
+        assert_eq!(registry.get("0x38ed1739").unwrap().name, "swapExactTokensForTokens");
+    }
+
+    #[test]
+    fn test_register_abi_json() {
+        let abi = r#"[
+            {"type": "function", "name": "exactInputSingle", "inputs": [{"type": "tuple", "components": [
+                {"type": "address"}, {"type": "address"}, {"type": "uint24"}, {"type": "address"},
+                {"type": "uint256"}, {"type": "uint256"}, {"type": "uint256"}, {"type": "uint160"}
+            ]}]},
+            {"type": "event", "name": "Transfer", "inputs": []}
+        ]"#;
+        let mut registry = SelectorRegistry::new();
+        assert_eq!(registry.register_abi_json(abi).unwrap(), 1);
+        assert_eq!(
+            registry.get("0x414BF389").unwrap().signature(),
+            "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Per-chain activity calendars for GitHub-style heatmaps.
+//!
+//! The bulk time-bucket endpoint returns an address's transactions in
+//! 15-minute buckets. [`ActivityCalendar`] folds bucket counts into days or
+//! ISO weeks, and [`ActivityCalendar::cells`] fills in the empty days so a
+//! heatmap can be drawn directly.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{ActivityCalendarOptions, CalendarGranularity, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let options = ActivityCalendarOptions::new().days(14).granularity(CalendarGranularity::Day);
+//! for calendar in client.all_chains_service().get_activity_calendars("0x...", options).await? {
+//!     for (day, count) in calendar.cells() {
+//!         println!("{} {} {}", calendar.chain_name, day, count);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Width of one bulk time bucket, in seconds.
+pub const TIME_BUCKET_SECONDS: u64 = 900;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// How an [`ActivityCalendar`] groups transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CalendarGranularity {
+    #[default]
+    Day,
+    /// ISO weeks, keyed by their Monday.
+    Week,
+}
+
+/// A UTC calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    /// Days since 1970-01-01.
+    days: i64,
+}
+
+impl CalendarDate {
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { days: days_from_civil(year as i64, month, day) })
+    }
+
+    /// The date of a Unix timestamp.
+    pub fn from_unix(seconds: u64) -> Self {
+        Self { days: (seconds / SECONDS_PER_DAY) as i64 }
+    }
+
+    /// The date a time bucket starts on.
+    pub fn from_time_bucket(bucket: u64) -> Self {
+        Self::from_unix(bucket * TIME_BUCKET_SECONDS)
+    }
+
+    /// Parse the date part of `YYYY-MM-DD` or an RFC 3339 timestamp.
+    pub fn parse(value: &str) -> Option<Self> {
+        let date = value.get(..10)?;
+        let mut parts = date.split('-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Self::from_ymd(year, month, day)
+    }
+
+    /// Unix timestamp of midnight UTC on this date; `None` before 1970.
+    pub fn unix_start(&self) -> Option<u64> {
+        u64::try_from(self.days).ok().map(|days| days * SECONDS_PER_DAY)
+    }
+
+    /// `(year, month, day)`.
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        civil_from_days(self.days)
+    }
+
+    /// Day of the week, Monday = 0.
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday.
+        (self.days + 3).rem_euclid(7) as u32
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Self { days: self.days + days }
+    }
+
+    /// The first date of the bucket containing this date.
+    pub fn bucket_start(&self, granularity: CalendarGranularity) -> Self {
+        match granularity {
+            CalendarGranularity::Day => *self,
+            CalendarGranularity::Week => self.add_days(-(self.weekday() as i64)),
+        }
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// Transaction counts for one chain, bucketed by day or week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityCalendar {
+    pub chain_name: String,
+    pub granularity: CalendarGranularity,
+    /// First and last date covered, whether or not they saw activity.
+    pub range: Option<(CalendarDate, CalendarDate)>,
+    counts: BTreeMap<CalendarDate, u32>,
+}
+
+impl ActivityCalendar {
+    pub fn new(chain_name: impl Into<String>, granularity: CalendarGranularity) -> Self {
+        Self { chain_name: chain_name.into(), granularity, range: None, counts: BTreeMap::new() }
+    }
+
+    /// Widen the covered range to include `from..=to`.
+    pub fn cover(&mut self, from: CalendarDate, to: CalendarDate) {
+        let (from, to) = (from.min(to), from.max(to));
+        self.range = Some(match self.range {
+            Some((start, end)) => (start.min(from), end.max(to)),
+            None => (from, to),
+        });
+    }
+
+    /// Add `count` transactions on `date`.
+    pub fn record(&mut self, date: CalendarDate, count: u32) {
+        self.cover(date, date);
+        if count > 0 {
+            *self.counts.entry(date.bucket_start(self.granularity)).or_default() += count;
+        }
+    }
+
+    /// Add the transactions of one bulk time bucket.
+    pub fn record_time_bucket(&mut self, bucket: u64, count: u32) {
+        self.record(CalendarDate::from_time_bucket(bucket), count);
+    }
+
+    /// Transactions in the bucket containing `date`.
+    pub fn count(&self, date: CalendarDate) -> u32 {
+        self.counts.get(&date.bucket_start(self.granularity)).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.values().map(|&c| c as u64).sum()
+    }
+
+    /// The busiest bucket's count, for scaling heatmap colours.
+    pub fn max_count(&self) -> u32 {
+        self.counts.values().copied().max().unwrap_or(0)
+    }
+
+    /// Buckets with at least one transaction, oldest first.
+    pub fn active(&self) -> impl Iterator<Item = (CalendarDate, u32)> + '_ {
+        self.counts.iter().map(|(&date, &count)| (date, count))
+    }
+
+    /// Every bucket in the covered range, oldest first, including empty ones.
+    pub fn cells(&self) -> Vec<(CalendarDate, u32)> {
+        let Some((start, end)) = self.range else { return Vec::new() };
+        let step = match self.granularity {
+            CalendarGranularity::Day => 1,
+            CalendarGranularity::Week => 7,
+        };
+        let mut cells = Vec::new();
+        let mut date = start.bucket_start(self.granularity);
+        while date <= end {
+            cells.push((date, self.count(date)));
+            date = date.add_days(step);
+        }
+        cells
+    }
+}
+
+/// Parse a `YYYY-MM-DDTHH:MM:SS` timestamp (any suffix ignored) to Unix seconds.
+pub(crate) fn parse_unix(value: &str) -> Option<u64> {
+    let midnight = CalendarDate::parse(value)?.unix_start()?;
+    let Some(time) = value.get(11..19) else { return Some(midnight) };
+    let mut parts = time.split(':').map(|p| p.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+    Some(midnight + hours * 3600 + minutes * 60 + seconds)
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Howard Hinnant's `days_from_civil` / `civil_from_days` for the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> CalendarDate {
+        CalendarDate::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_date_round_trip() {
+        for s in ["1970-01-01", "2000-02-29", "2024-12-31", "2025-03-01"] {
+            assert_eq!(date(s).to_string(), s);
+        }
+        assert!(CalendarDate::parse("2023-02-29").is_none());
+        assert_eq!(date("2024-06-17").weekday(), 0);
+        assert_eq!(date("2024-06-23T10:00:00Z").bucket_start(CalendarGranularity::Week), date("2024-06-17"));
+        assert_eq!(parse_unix("1970-01-02T01:00:30Z"), Some(86_400 + 3_600 + 30));
+    }
+
+    #[test]
+    fn test_calendar_cells_fill_gaps() {
+        let start = date("2024-06-17").unix_start().unwrap() / TIME_BUCKET_SECONDS;
+        let mut daily = ActivityCalendar::new("eth-mainnet", CalendarGranularity::Day);
+        let mut weekly = ActivityCalendar::new("eth-mainnet", CalendarGranularity::Week);
+        for (bucket, count) in [(start, 2), (start + 1, 1), (start + 96 * 2, 4), (start + 96 * 8, 0)] {
+            daily.record_time_bucket(bucket, count);
+            weekly.record_time_bucket(bucket, count);
+        }
+
+        let cells = daily.cells();
+        assert_eq!(cells.len(), 9);
+        assert_eq!(cells[0], (date("2024-06-17"), 3));
+        assert_eq!(cells[1].1, 0);
+        assert_eq!(cells[2], (date("2024-06-19"), 4));
+        assert_eq!(daily.total(), 7);
+        assert_eq!(daily.max_count(), 4);
+        assert_eq!(daily.active().count(), 2);
+
+        assert_eq!(weekly.cells(), vec![(date("2024-06-17"), 7), (date("2024-06-24"), 0)]);
+    }
+}
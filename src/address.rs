@@ -0,0 +1,294 @@
+//! Typed address newtypes for each address family supported by the API.
+//!
+//! Service methods accept `impl Into<AddressParam<_>>`, so plain strings keep
+//! working while a typed address of the wrong family fails to compile:
+//!
+//! ```rust,compile_fail
+//! use goldrush_sdk::{AddressParam, SolanaAddress};
+//!
+//! fn evm_only(_address: impl Into<AddressParam>) {}
+//!
+//! let sol: SolanaAddress = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".parse().unwrap();
+//! evm_only(sol);
+//! ```
+//!
+//! EVM addresses are case-insensitive, so the SDK keys, deduplicates and
+//! compares them in the lowercase form of [`normalize_address`] and shows them
+//! in the EIP-55 form of [`display_address`]. Other address families are
+//! case-sensitive and kept as given.
+
+use crate::utils::{strip_0x, to_checksum_address};
+use crate::validation::Validator;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// An address format that can be validated from its string form.
+pub trait AddressFormat {
+    /// Validate the string representation of an address of this family.
+    fn validate(address: &str) -> Result<()>;
+}
+
+macro_rules! address_newtype {
+    ($(#[$meta:meta])* $name:ident, $validate:path, $normalize:path) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Parse and validate an address.
+            pub fn new(address: impl AsRef<str>) -> Result<Self> {
+                let address = $normalize(address.as_ref().trim());
+                <Self as AddressFormat>::validate(&address)?;
+                Ok(Self(address))
+            }
+
+            /// The address as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AddressFormat for $name {
+            fn validate(address: &str) -> Result<()> {
+                $validate(address)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                Self::new(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(s: String) -> Result<Self> {
+                Self::new(s)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(address: $name) -> String {
+                address.0
+            }
+        }
+
+        impl From<$name> for AddressParam<$name> {
+            fn from(address: $name) -> Self {
+                Self::new(address.0)
+            }
+        }
+
+        impl From<&$name> for AddressParam<$name> {
+            fn from(address: &$name) -> Self {
+                Self::new(address.0.clone())
+            }
+        }
+    };
+}
+
+address_newtype!(
+    /// A hex-encoded EVM account or contract address (`0x` + 40 hex characters).
+    ///
+    /// Stored lowercase, so addresses differing only in case are equal;
+    /// displayed with its EIP-55 checksum.
+    EvmAddress,
+    Validator::validate_address,
+    normalize_address
+);
+
+address_newtype!(
+    /// A base58-encoded Solana account or mint address.
+    SolanaAddress,
+    Validator::validate_solana_address,
+    String::from
+);
+
+address_newtype!(
+    /// A Bitcoin address (legacy base58 or bech32).
+    BtcAddress,
+    Validator::validate_btc_address,
+    String::from
+);
+
+impl fmt::Display for EvmAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&display_address(&self.0))
+    }
+}
+
+impl fmt::Display for SolanaAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for BtcAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Whether `address` is `0x`-prefixed, the mark of EVM hex; no other
+/// address family starts with `0x`.
+fn is_hex_prefixed(address: &str) -> bool {
+    address.len() > 2 && strip_0x(address).len() + 2 == address.len()
+}
+
+/// Canonical form of an address, for cache keys, deduplication and comparison.
+///
+/// `0x`-prefixed addresses are lowercased, prefix included. Anything else,
+/// such as Solana and Bitcoin addresses or ENS names, is only trimmed.
+pub fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    if is_hex_prefixed(address) {
+        address.to_ascii_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+/// Whether two addresses are the same once normalized.
+pub fn same_address(a: &str, b: &str) -> bool {
+    let (a, b) = (a.trim(), b.trim());
+    if is_hex_prefixed(a) && is_hex_prefixed(b) {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+/// Form of an address to show to users: EIP-55 checksummed for valid EVM
+/// addresses, otherwise as normalized.
+pub fn display_address(address: &str) -> String {
+    let address = normalize_address(address);
+    if is_hex_prefixed(&address) {
+        to_checksum_address(&address).unwrap_or(address)
+    } else {
+        address
+    }
+}
+
+/// An address argument for a service method of a given address family.
+///
+/// Built from either the matching typed address or a plain string. Strings are
+/// passed through as-is so ENS names, xpubs and other identifiers the API
+/// resolves server-side keep working.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddressParam<A = EvmAddress> {
+    value: String,
+    _family: PhantomData<A>,
+}
+
+impl<A> AddressParam<A> {
+    fn new(value: String) -> Self {
+        Self { value, _family: PhantomData }
+    }
+
+    /// The address as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<A: AddressFormat> AddressParam<A> {
+    /// Validate the address against its family's format.
+    pub fn validate(&self) -> Result<()> {
+        A::validate(&self.value)
+    }
+}
+
+impl<A> From<&str> for AddressParam<A> {
+    fn from(value: &str) -> Self {
+        Self::new(normalize_address(value))
+    }
+}
+
+impl<A> From<String> for AddressParam<A> {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl<A> From<&String> for AddressParam<A> {
+    fn from(value: &String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl<A> fmt::Display for AddressParam<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl<A> AsRef<str> for AddressParam<A> {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evm_address_parsing() {
+        let address: EvmAddress = " 0x742d35Cc6634C0532925a3b8D4fc24f3C4aD6a8b ".parse().unwrap();
+        assert_eq!(address.as_str(), "0x742d35cc6634c0532925a3b8d4fc24f3c4ad6a8b");
+        assert_eq!(address.to_string(), "0x742D35CC6634c0532925A3B8D4Fc24F3c4aD6a8b");
+        assert_eq!(address, "0X742D35CC6634C0532925A3B8D4FC24F3C4AD6A8B".parse().unwrap());
+        assert!("0x123".parse::<EvmAddress>().is_err());
+        assert!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".parse::<EvmAddress>().is_err());
+    }
+
+    #[test]
+    fn test_solana_and_btc_address_parsing() {
+        assert!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".parse::<SolanaAddress>().is_ok());
+        assert!("bc1qm34lsc65zpw79lxes69zkqmk6ee3ewf0j77s3h".parse::<BtcAddress>().is_ok());
+        assert!("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".parse::<BtcAddress>().is_ok());
+        assert!("0x742d35Cc6634C0532925a3b8D4fc24f3C4aD6a8b".parse::<BtcAddress>().is_err());
+    }
+
+    #[test]
+    fn test_address_param_conversions() {
+        let evm = EvmAddress::new("0x742d35Cc6634C0532925a3b8D4fc24f3C4aD6a8b").unwrap();
+        let param: AddressParam = (&evm).into();
+        assert_eq!(param.as_str(), evm.as_str());
+
+        let param: AddressParam = " 0xABCDEF0123456789ABCDEF0123456789ABCDEF01".into();
+        assert_eq!(param.as_str(), "0xabcdef0123456789abcdef0123456789abcdef01");
+
+        let param: AddressParam = "vitalik.eth".into();
+        assert_eq!(param.to_string(), "vitalik.eth");
+
+        let param: AddressParam<SolanaAddress> = "not-base58!".into();
+        assert!(param.validate().is_err());
+    }
+
+    #[test]
+    fn test_address_serde() {
+        let json = "\"0x742d35Cc6634C0532925a3b8D4fc24f3C4aD6a8b\"";
+        let address: EvmAddress = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&address).unwrap(), json.to_ascii_lowercase());
+        assert!(serde_json::from_str::<EvmAddress>("\"0x12\"").is_err());
+    }
+
+    #[test]
+    fn test_normalize_and_compare() {
+        let sol = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+        assert_eq!(normalize_address(sol), sol);
+        assert_eq!(normalize_address(" Vitalik.eth "), "Vitalik.eth");
+        assert!(same_address("0xAbC0000000000000000000000000000000000001", "0xabc0000000000000000000000000000000000001 "));
+        assert!(!same_address(sol, &sol.to_ascii_lowercase()));
+        assert_eq!(display_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(display_address(sol), sol);
+    }
+}
@@ -0,0 +1,254 @@
+//! Address labels merged from API responses and user input.
+//!
+//! The API attaches labels (`from_address_label`, resolved ENS names, ...) to
+//! some responses but not others. An [`AddressBook`] remembers every label it
+//! has seen, lets callers add their own (which always win), persists them as
+//! JSON, and uses them when describing transactions and transfers.
+//!
+//! ```rust
+//! use goldrush_sdk::AddressBook;
+//!
+//! let mut book = AddressBook::new();
+//! book.learn("0xE592427A0AEce92De3Edee1F18E0157C05861564", "Uniswap V3: Router");
+//! book.set_label("0x1111111111111111111111111111111111111111", "Treasury");
+//!
+//! assert_eq!(book.label_of("0xe592427a0aece92de3edee1f18e0157c05861564"), Some("Uniswap V3: Router"));
+//! assert_eq!(book.display("0x1111111111111111111111111111111111111111"), "Treasury (0x1111…1111)");
+//! ```
+
+use crate::address::normalize_address;
+use crate::amount::AmountValue;
+use crate::models::balances::{Erc20TransferItem, Erc20TransfersData};
+use crate::models::base::ResolvedAddressData;
+use crate::models::transactions::TransactionItem;
+use crate::utils::{format_ether, short_address};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Where a label came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelSource {
+    /// Set by the caller; never overwritten by API labels.
+    User,
+    /// Learned from an API response.
+    Api,
+}
+
+/// A stored label.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressLabel {
+    pub label: String,
+    pub source: LabelSource,
+}
+
+/// Label lookup keyed by lowercase address.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AddressBook {
+    labels: HashMap<String, AddressLabel>,
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a book previously written with [`save`](Self::save).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Load a book if the file exists, otherwise start empty.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the book as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Set a user label, replacing any existing label.
+    pub fn set_label(&mut self, address: &str, label: impl Into<String>) {
+        self.labels.insert(
+            normalize_address(address),
+            AddressLabel { label: label.into(), source: LabelSource::User },
+        );
+    }
+
+    /// Record an API-provided label. User labels are kept.
+    pub fn learn(&mut self, address: &str, label: impl Into<String>) {
+        let label = label.into();
+        if label.trim().is_empty() {
+            return;
+        }
+        let entry = self.labels.entry(normalize_address(address));
+        let entry = entry.or_insert_with(|| AddressLabel { label: label.clone(), source: LabelSource::Api });
+        if entry.source == LabelSource::Api {
+            entry.label = label;
+        }
+    }
+
+    pub fn remove(&mut self, address: &str) -> Option<AddressLabel> {
+        self.labels.remove(&normalize_address(address))
+    }
+
+    pub fn get(&self, address: &str) -> Option<&AddressLabel> {
+        self.labels.get(&normalize_address(address))
+    }
+
+    /// The label for an address, if any.
+    pub fn label_of(&self, address: &str) -> Option<&str> {
+        self.get(address).map(|l| l.label.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Merge another book. Its user labels win over this book's API labels.
+    pub fn merge(&mut self, other: AddressBook) {
+        for (address, entry) in other.labels {
+            match entry.source {
+                LabelSource::User => self.set_label(&address, entry.label),
+                LabelSource::Api => self.learn(&address, entry.label),
+            }
+        }
+    }
+
+    /// Learn the sender, recipient and internal-transfer labels of a transaction.
+    pub fn learn_from_transaction(&mut self, tx: &TransactionItem) {
+        if let Some(label) = &tx.from_address_label {
+            self.learn(&tx.from_address, label.clone());
+        }
+        if let (Some(address), Some(label)) = (&tx.to_address, &tx.to_address_label) {
+            self.learn(address, label.clone());
+        }
+        for transfer in tx.internal_transfers.iter().flatten() {
+            if let (Some(address), Some(label)) = (&transfer.from_address, &transfer.from_address_label) {
+                self.learn(address, label.clone());
+            }
+            if let (Some(address), Some(label)) = (&transfer.to_address, &transfer.to_address_label) {
+                self.learn(address, label.clone());
+            }
+        }
+    }
+
+    /// Learn sender and recipient labels from a page of ERC-20 transfers.
+    pub fn learn_from_transfers(&mut self, data: &Erc20TransfersData) {
+        for item in &data.items {
+            if let (Some(address), Some(label)) = (&item.from_address, &item.from_address_label) {
+                self.learn(address, label.clone());
+            }
+            if let (Some(address), Some(label)) = (&item.to_address, &item.to_address_label) {
+                self.learn(address, label.clone());
+            }
+        }
+    }
+
+    /// Learn a name from `get_resolved_address`.
+    pub fn learn_resolved(&mut self, resolved: &ResolvedAddressData) {
+        if let (Some(address), Some(name)) = (&resolved.address, &resolved.name) {
+            self.learn(address, name.clone());
+        }
+    }
+
+    /// `label (0x1234…abcd)` for labelled addresses, otherwise the shortened address.
+    pub fn display(&self, address: &str) -> String {
+        match self.label_of(address) {
+            Some(label) => format!("{} ({})", label, short_address(address)),
+            None => short_address(address),
+        }
+    }
+
+    /// One-line description of a transaction using known labels.
+    pub fn describe_transaction(&self, tx: &TransactionItem) -> String {
+        let to = tx.to_address.as_deref().map_or_else(|| "contract creation".to_string(), |a| self.display(a));
+        let mut line = format!("{} → {}", self.display(&tx.from_address), to);
+        if let Some(wei) = tx.value.trim().parse::<u128>().ok().filter(|v| *v > 0) {
+            line.push_str(&format!(": {} native", format_ether(wei)));
+        }
+        if tx.successful == Some(false) {
+            line.push_str(" (failed)");
+        }
+        line
+    }
+
+    /// One-line description of an ERC-20 transfer using known labels.
+    pub fn describe_transfer(&self, transfer: &Erc20TransferItem) -> String {
+        let from = transfer.from_address.as_deref().map_or_else(|| "?".to_string(), |a| self.display(a));
+        let to = transfer.to_address.as_deref().map_or_else(|| "?".to_string(), |a| self.display(a));
+        let symbol = transfer.contract_ticker_symbol.as_deref().unwrap_or("tokens");
+        let amount = transfer
+            .delta
+            .as_ref()
+            .and_then(|d| d.as_f64_units(transfer.contract_decimals.unwrap_or(18)));
+        match amount {
+            Some(amount) => format!("{} → {}: {} {}", from, to, amount, symbol),
+            None => format!("{} → {}: {}", from, to, symbol),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx() -> TransactionItem {
+        serde_json::from_value(serde_json::json!({
+            "tx_hash": "0x1",
+            "from_address": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "from_address_label": null,
+            "to_address": "0xE592427A0AEce92De3Edee1F18E0157C05861564",
+            "to_address_label": "Uniswap V3: Router",
+            "value": "1500000000000000000",
+            "successful": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_user_labels_win_over_api_labels() {
+        let mut book = AddressBook::new();
+        book.set_label("0xE592427A0AEce92De3Edee1F18E0157C05861564", "My Router");
+        book.learn_from_transaction(&tx());
+        assert_eq!(book.label_of("0xe592427a0aece92de3edee1f18e0157c05861564"), Some("My Router"));
+
+        book.remove("0xe592427a0aece92de3edee1f18e0157c05861564");
+        book.learn_from_transaction(&tx());
+        assert_eq!(book.get("0xe592427a0aece92de3edee1f18e0157c05861564").unwrap().source, LabelSource::Api);
+    }
+
+    #[test]
+    fn test_describe_transaction() {
+        let mut book = AddressBook::new();
+        book.learn_from_transaction(&tx());
+        assert_eq!(
+            book.describe_transaction(&tx()),
+            "0xaaaa…aaaa → Uniswap V3: Router (0xE592…1564): 1.5 native (failed)"
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("address-book-{}.json", uuid::Uuid::new_v4()));
+        let mut book = AddressBook::load_or_default(&path).unwrap();
+        book.set_label("0xabc", "Cold wallet");
+        book.save(&path).unwrap();
+
+        let loaded = AddressBook::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.label_of("0xABC"), Some("Cold wallet"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Type of raw token amount fields.
+//!
+//! Balances, transfer deltas and supplies of the balance, transaction and NFT
+//! models are integers in the token's smallest unit, sent by the API as
+//! strings and often too large for `u128` or `f64`. They are typed as
+//! [`Amount`]. By default that is the `String` received. With the `bignum`
+//! feature it is [`U256`], parsed when the response is decoded, so malformed
+//! amounts fail early and arithmetic is exact. [`AmountValue`] scales amounts
+//! by a token's decimals in either mode.
+//!
+//! ```rust
+//! use goldrush_sdk::amount::{Amount, AmountValue};
+//!
+//! let balance: Amount = "1500000000000000000123".parse().unwrap();
+//! assert_eq!(balance.in_units(18).as_deref(), Some("1500.000000000000000123"));
+//! ```
+
+use std::borrow::Cow;
+
+/// Type of raw amount fields: `String`, or [`U256`] with the `bignum` feature.
+#[cfg(not(feature = "bignum"))]
+pub type Amount = String;
+
+/// Type of raw amount fields: `String`, or [`U256`] with the `bignum` feature.
+#[cfg(feature = "bignum")]
+pub type Amount = U256;
+
+#[cfg(feature = "bignum")]
+pub use ethnum;
+
+/// Unsigned 256-bit amount, (de)serialized as a base-10 string.
+#[cfg(feature = "bignum")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256(pub ethnum::U256);
+
+#[cfg(feature = "bignum")]
+mod u256 {
+    use super::U256;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use std::str::FromStr;
+
+    impl fmt::Display for U256 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl FromStr for U256 {
+        type Err = crate::Error;
+
+        fn from_str(s: &str) -> crate::Result<Self> {
+            let digits = s.trim();
+            let valid = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+            valid
+                .then(|| ethnum::U256::from_str_radix(digits, 10).ok())
+                .flatten()
+                .map(U256)
+                .ok_or_else(|| crate::Error::Config(format!("Invalid amount: '{}'", s)))
+        }
+    }
+
+    impl From<u128> for U256 {
+        fn from(value: u128) -> Self {
+            U256(ethnum::U256::new(value))
+        }
+    }
+
+    impl Serialize for U256 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for U256 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let raw = String::deserialize(deserializer)?;
+            raw.parse().map_err(de::Error::custom)
+        }
+    }
+}
+
+/// Scaled conversions of [`Amount`] values.
+pub trait AmountValue {
+    /// The amount as base-10 digits, or `None` if it isn't a non-negative integer.
+    fn digits(&self) -> Option<Cow<'_, str>>;
+
+    /// Whether the amount is a valid zero.
+    fn is_zero(&self) -> bool {
+        self.digits().is_some_and(|d| d.bytes().all(|b| b == b'0'))
+    }
+
+    /// Whether the amount is valid and above zero.
+    fn is_positive(&self) -> bool {
+        self.digits().is_some_and(|d| d.bytes().any(|b| b != b'0'))
+    }
+
+    /// The amount divided by `10^decimals`, exactly, as a decimal string.
+    fn in_units(&self, decimals: u32) -> Option<String> {
+        Some(crate::utils::format_digits(&self.digits()?, decimals))
+    }
+
+    /// The amount divided by `10^decimals`, as the nearest `f64`.
+    fn as_f64_units(&self, decimals: u32) -> Option<f64> {
+        let raw = self.digits()?.parse::<f64>().ok()?;
+        Some(raw / 10f64.powi(decimals as i32))
+    }
+}
+
+impl AmountValue for String {
+    fn digits(&self) -> Option<Cow<'_, str>> {
+        let digits = self.trim();
+        let valid = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+        valid.then_some(Cow::Borrowed(digits))
+    }
+}
+
+#[cfg(feature = "bignum")]
+impl AmountValue for U256 {
+    fn digits(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Owned(self.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::balances::BalancesData;
+
+    #[test]
+    fn test_amounts_scale_exactly_beyond_f64() {
+        let data: BalancesData = serde_json::from_value(serde_json::json!({
+            "items": [{ "contract_address": "0xa", "contract_decimals": 18, "balance": "123456789012345678901234567890" }],
+        }))
+        .unwrap();
+        let item = &data.items[0];
+        assert_eq!(item.balance_in_units().as_deref(), Some("123456789012.34567890123456789"));
+        assert!(item.has_balance());
+        assert_eq!(serde_json::to_value(item).unwrap()["balance"], "123456789012345678901234567890");
+        #[cfg(feature = "bignum")]
+        assert_eq!(item.balance.0, ethnum::U256::from_str_radix("123456789012345678901234567890", 10).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_amounts_have_no_units() {
+        assert_eq!("12a".to_string().in_units(2), None);
+        assert!("000".to_string().is_zero());
+        assert!(!"000".to_string().is_positive());
+        #[cfg(feature = "bignum")]
+        assert!("12a".parse::<U256>().is_err());
+    }
+}
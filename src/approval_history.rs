@@ -0,0 +1,243 @@
+//! When and in which transaction a wallet granted each approval.
+//!
+//! [`SecurityService::get_approval_history`](crate::SecurityService::get_approval_history)
+//! fetches the `Approval` and `ApprovalForAll` logs emitted for a wallet and
+//! ABI-decodes them into [`ApprovalEvent`]s, oldest first. Where the
+//! approvals endpoint shows the allowances standing today, the history shows
+//! how they came about, e.g. the transaction that granted a risky unlimited
+//! allowance.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{ApprovalHistoryOptions, Chain, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let options = ApprovalHistoryOptions::new().starting_block(18_000_000).ending_block(19_000_000);
+//! let history = client.security_service()
+//!     .get_approval_history(Chain::EthereumMainnet, "0x...", Some(options))
+//!     .await?;
+//! for event in history.standing().filter(|e| e.is_risky()) {
+//!     println!("{} may move {} since {:?} (tx {:?})", event.spender, event.token_address, event.block_signed_at, event.tx_hash);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::abi::{decode_hex, event_topic, AbiValue};
+use crate::address::{normalize_address, same_address};
+use crate::models::base::LogEventItem;
+use std::collections::HashMap;
+
+/// `Approval(address indexed owner, address indexed spender, uint256)`, as
+/// emitted by ERC-20 tokens; ERC-721 collections index the third parameter.
+pub const APPROVAL_EVENT: &str = "Approval(address,address,uint256)";
+
+/// `ApprovalForAll(address indexed owner, address indexed operator, bool approved)`.
+pub const APPROVAL_FOR_ALL_EVENT: &str = "ApprovalForAll(address,address,bool)";
+
+/// What an approval event granted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalGrant {
+    /// ERC-20 allowance in raw token units, as a decimal string.
+    Allowance(String),
+    /// ERC-721 approval of one token id.
+    Token(String),
+    /// Operator approval over all of the owner's tokens, or its revocation.
+    Operator(bool),
+}
+
+/// One decoded `Approval` or `ApprovalForAll` log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalEvent {
+    pub block_signed_at: Option<String>,
+    pub block_height: Option<u64>,
+    pub tx_hash: Option<String>,
+    pub tx_offset: Option<u64>,
+    pub log_offset: Option<u64>,
+    /// The token or collection contract that emitted the event.
+    pub token_address: String,
+    pub ticker_symbol: Option<String>,
+    pub owner: String,
+    /// Spender or operator; the zero address when an ERC-721 approval is cleared.
+    pub spender: String,
+    pub grant: ApprovalGrant,
+}
+
+impl ApprovalEvent {
+    /// Decode a log emitted for either approval event; `None` for other logs
+    /// or logs whose topics and data don't match.
+    pub fn from_log(log: &LogEventItem) -> Option<Self> {
+        let topics = log.raw_log_topics.as_deref()?;
+        let data = log.raw_log_data.as_deref().unwrap_or("0x");
+        let address = |topic: &String| match decode_hex(&["address"], topic)?.pop()? {
+            AbiValue::Address(address) => Some(address),
+            _ => None,
+        };
+        let (owner, spender) = (address(topics.get(1)?)?, address(topics.get(2)?)?);
+
+        let topic0 = topics.first()?.to_ascii_lowercase();
+        let grant = if topic0 == event_topic(APPROVAL_EVENT) {
+            match (topics.get(3), decode_hex(&["uint256"], data).and_then(|mut v| v.pop())) {
+                (Some(token_id), _) => match decode_hex(&["uint256"], token_id)?.pop()? {
+                    AbiValue::Uint(id) => ApprovalGrant::Token(id),
+                    _ => return None,
+                },
+                (None, Some(AbiValue::Uint(amount))) => ApprovalGrant::Allowance(amount),
+                _ => return None,
+            }
+        } else if topic0 == event_topic(APPROVAL_FOR_ALL_EVENT) {
+            match decode_hex(&["bool"], data)?.pop()? {
+                AbiValue::Bool(approved) => ApprovalGrant::Operator(approved),
+                _ => return None,
+            }
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            block_signed_at: log.block_signed_at.clone(),
+            block_height: log.block_height,
+            tx_hash: log.tx_hash.clone(),
+            tx_offset: log.tx_offset,
+            log_offset: log.log_offset,
+            token_address: normalize_address(log.sender_address.as_deref()?),
+            ticker_symbol: log.sender_contract_ticker_symbol.clone(),
+            owner,
+            spender,
+            grant,
+        })
+    }
+
+    /// Whether the event took an approval away rather than granting one.
+    pub fn is_revocation(&self) -> bool {
+        match &self.grant {
+            ApprovalGrant::Allowance(amount) => amount == "0",
+            ApprovalGrant::Token(_) => self.spender.bytes().skip(2).all(|b| b == b'0'),
+            ApprovalGrant::Operator(approved) => !approved,
+        }
+    }
+
+    /// Whether the allowance is effectively unlimited (2^128 raw units or more).
+    pub fn is_unlimited(&self) -> bool {
+        matches!(&self.grant, ApprovalGrant::Allowance(amount) if amount.parse::<u128>().is_err())
+    }
+
+    /// Whether the event granted an unlimited allowance or operator rights
+    /// over a whole collection.
+    pub fn is_risky(&self) -> bool {
+        self.is_unlimited() || self.grant == ApprovalGrant::Operator(true)
+    }
+
+    /// What the event replaces: the allowance of one spender on one token,
+    /// an ERC-721 token's single approval, or one operator's rights.
+    fn slot(&self) -> (String, String) {
+        match &self.grant {
+            ApprovalGrant::Allowance(_) => (self.token_address.clone(), self.spender.clone()),
+            ApprovalGrant::Token(id) => (self.token_address.clone(), format!("#{}", id)),
+            ApprovalGrant::Operator(_) => (self.token_address.clone(), format!("operator:{}", self.spender)),
+        }
+    }
+
+    fn position(&self) -> (u64, u64, u64) {
+        (self.block_height.unwrap_or(0), self.tx_offset.unwrap_or(0), self.log_offset.unwrap_or(0))
+    }
+}
+
+/// Every approval a wallet granted or revoked on one chain, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalHistory {
+    pub chain_name: String,
+    pub owner: String,
+    pub events: Vec<ApprovalEvent>,
+}
+
+impl ApprovalHistory {
+    /// Decode the approval logs among `logs` that `owner` emitted.
+    pub fn build<'a>(chain_name: impl Into<String>, owner: &str, logs: impl IntoIterator<Item = &'a LogEventItem>) -> Self {
+        let mut events: Vec<ApprovalEvent> = logs
+            .into_iter()
+            .filter_map(ApprovalEvent::from_log)
+            .filter(|event| same_address(&event.owner, owner))
+            .collect();
+        events.sort_by_key(ApprovalEvent::position);
+        Self { chain_name: chain_name.into(), owner: normalize_address(owner), events }
+    }
+
+    /// Events involving `spender`, oldest first.
+    pub fn for_spender<'a>(&'a self, spender: &'a str) -> impl Iterator<Item = &'a ApprovalEvent> + 'a {
+        self.events.iter().filter(move |e| same_address(&e.spender, spender))
+    }
+
+    /// Events on the token or collection at `token_address`, oldest first.
+    pub fn for_token<'a>(&'a self, token_address: &'a str) -> impl Iterator<Item = &'a ApprovalEvent> + 'a {
+        self.events.iter().filter(move |e| same_address(&e.token_address, token_address))
+    }
+
+    /// The latest event of each approval that hasn't been revoked since,
+    /// i.e. when each standing approval was granted, oldest first.
+    ///
+    /// Allowances spent down by transfers still count as standing; compare
+    /// with the approvals endpoint for current amounts.
+    pub fn standing(&self) -> impl Iterator<Item = &ApprovalEvent> {
+        let mut latest: HashMap<(String, String), usize> = HashMap::new();
+        for (i, event) in self.events.iter().enumerate() {
+            latest.insert(event.slot(), i);
+        }
+        let mut standing: Vec<usize> = latest.into_values().filter(|&i| !self.events[i].is_revocation()).collect();
+        standing.sort_unstable();
+        standing.into_iter().map(|i| &self.events[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn word(hex: &str) -> String {
+        format!("0x{:0>64}", hex)
+    }
+
+    fn log(height: u64, topics: Vec<String>, data: &str) -> LogEventItem {
+        serde_json::from_value(json!({
+            "block_height": height,
+            "tx_hash": format!("0xtx{}", height),
+            "sender_address": "0xTOKEN",
+            "raw_log_topics": topics,
+            "raw_log_data": data,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_event_topics() {
+        assert_eq!(event_topic(APPROVAL_EVENT), "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+        assert_eq!(event_topic(APPROVAL_FOR_ALL_EVENT), "0x17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31");
+    }
+
+    #[test]
+    fn test_history_decodes_and_tracks_standing_approvals() {
+        let (approval, for_all) = (event_topic(APPROVAL_EVENT), event_topic(APPROVAL_FOR_ALL_EVENT));
+        let (owner, dex, market, other) = (word("aa"), word("de"), word("cc"), word("bb"));
+        let logs = vec![
+            log(3, vec![approval.clone(), owner.clone(), dex.clone()], &word("0")),
+            log(1, vec![approval.clone(), owner.clone(), dex.clone()], &format!("0x{}", "f".repeat(64))),
+            log(2, vec![for_all.clone(), owner.clone(), market.clone()], &word("1")),
+            log(4, vec![approval.clone(), other, dex.clone()], &word("5")),
+            log(5, vec![approval.clone(), owner.clone(), market.clone(), word("7")], "0x"),
+            log(6, vec![approval.clone(), owner.clone()], "0x"),
+        ];
+
+        let history = ApprovalHistory::build("eth-mainnet", "0x00000000000000000000000000000000000000AA", &logs);
+        let heights: Vec<_> = history.events.iter().map(|e| e.block_height.unwrap()).collect();
+        assert_eq!(heights, [1, 2, 3, 5]);
+        assert!(history.events[0].is_unlimited() && history.events[0].is_risky());
+        assert_eq!(history.events[1].grant, ApprovalGrant::Operator(true));
+        assert!(history.events[2].is_revocation());
+        assert_eq!(history.events[3].grant, ApprovalGrant::Token("7".to_string()));
+        assert_eq!(history.events[3].token_address, "0xtoken");
+
+        let standing: Vec<_> = history.standing().map(|e| e.block_height.unwrap()).collect();
+        assert_eq!(standing, [2, 5]);
+        assert_eq!(history.for_spender("0x00000000000000000000000000000000000000de").count(), 2);
+    }
+}
@@ -0,0 +1,307 @@
+//! Canonical asset mapping for cross-chain portfolios.
+//!
+//! Bridged tokens show up once per chain (USDC on Ethereum, Base, Arbitrum, ...).
+//! An [`AssetRegistry`] maps each `(chain, contract)` deployment to one canonical
+//! asset so aggregated balances can be folded into a single line per asset while
+//! keeping the per-chain breakdown:
+//!
+//! ```rust
+//! use goldrush_sdk::assets::{AssetRegistry, CanonicalAsset};
+//!
+//! let registry = AssetRegistry::bundled()
+//!     .with_asset(CanonicalAsset::new("my-token", "MYT").deployment("base-mainnet", "0x1234"));
+//!
+//! assert_eq!(registry.canonical_for("eth-mainnet", "0xA0b86991c6218b36c1d19d4a2e9eB0cE3606eB48").unwrap().id, "usdc");
+//! ```
+
+use crate::address::normalize_address;
+use crate::models::all_chains::{MultiChainBalanceItem, MultiChainBalancesData};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One asset and the contracts representing it on each chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanonicalAsset {
+    /// Stable identifier, e.g. `usdc`.
+    pub id: String,
+    pub symbol: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    /// Contract address per chain slug.
+    #[serde(default)]
+    pub deployments: HashMap<String, String>,
+}
+
+impl CanonicalAsset {
+    pub fn new(id: impl Into<String>, symbol: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            symbol: symbol.into(),
+            name: None,
+            logo_url: None,
+            deployments: HashMap::new(),
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self { self.name = Some(name.into()); self }
+    pub fn logo_url(mut self, url: impl Into<String>) -> Self { self.logo_url = Some(url.into()); self }
+
+    /// Add the contract representing this asset on `chain`.
+    pub fn deployment(mut self, chain: impl Into<String>, address: impl Into<String>) -> Self {
+        self.deployments.insert(chain.into(), address.into());
+        self
+    }
+}
+
+/// `(id, symbol, name, [(chain, contract)])`
+type BundledAsset = (&'static str, &'static str, &'static str, &'static [(&'static str, &'static str)]);
+
+/// Widely bridged assets shipped with the SDK.
+const BUNDLED: &[BundledAsset] = &[
+    ("usdc", "USDC", "USD Coin", &[
+        ("eth-mainnet", "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+        ("base-mainnet", "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
+        ("arbitrum-mainnet", "0xaf88d065e77c8cc2239327c5edb3a432268e5831"),
+        ("optimism-mainnet", "0x0b2c639c533813f4aa9d7837caf62653d097ff85"),
+        ("matic-mainnet", "0x3c499c542cef5e3811e1192ce70d8cc03d5c3359"),
+        ("avalanche-mainnet", "0xb97ef9ef8734c71904d8002f8b6bc66dd9c48a6e"),
+        ("bsc-mainnet", "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d"),
+    ]),
+    ("usdt", "USDT", "Tether USD", &[
+        ("eth-mainnet", "0xdac17f958d2ee523a2206206994597c13d831ec7"),
+        ("arbitrum-mainnet", "0xfd086bc7cd5c481dcc9c85ebe478a1c0b69fcbb9"),
+        ("optimism-mainnet", "0x94b008aa00579c1307b0ef2c499ad98a8ce58e58"),
+        ("matic-mainnet", "0xc2132d05d31c914a87c6611c10748aeb04b58e8f"),
+        ("avalanche-mainnet", "0x9702230a8ea53601f5cd2dc00fdbc13d4df4a8c7"),
+        ("bsc-mainnet", "0x55d398326f99059ff775485246999027b3197955"),
+    ]),
+    ("dai", "DAI", "Dai Stablecoin", &[
+        ("eth-mainnet", "0x6b175474e89094c44da98b954eedeac495271d0f"),
+        ("arbitrum-mainnet", "0xda10009cbd5d07dd0cecc66161fc93d7c9000da1"),
+        ("optimism-mainnet", "0xda10009cbd5d07dd0cecc66161fc93d7c9000da1"),
+        ("matic-mainnet", "0x8f3cf7ad23cd3cadbd9735aff958023239c6a063"),
+    ]),
+    ("weth", "WETH", "Wrapped Ether", &[
+        ("eth-mainnet", "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+        ("base-mainnet", "0x4200000000000000000000000000000000000006"),
+        ("optimism-mainnet", "0x4200000000000000000000000000000000000006"),
+        ("arbitrum-mainnet", "0x82af49447d8a07e3bd95bd0d56f35241523fbab1"),
+        ("matic-mainnet", "0x7ceb23fd6bc0add59e62ac25578270cff1b9f619"),
+    ]),
+    ("wbtc", "WBTC", "Wrapped BTC", &[
+        ("eth-mainnet", "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599"),
+        ("arbitrum-mainnet", "0x2f2a2543b76a4166549f7aab2e75bef0aefc5b0f"),
+        ("matic-mainnet", "0x1bfd67037b42cf73acf2047067bd4f2c47d9bfd6"),
+    ]),
+];
+
+/// Lookup from `(chain, contract)` deployments to canonical assets.
+#[derive(Debug, Clone, Default)]
+pub struct AssetRegistry {
+    assets: HashMap<String, CanonicalAsset>,
+    by_deployment: HashMap<(String, String), String>,
+}
+
+impl AssetRegistry {
+    /// An empty registry; every holding stays on its own line.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry preloaded with major stablecoins and wrapped assets.
+    pub fn bundled() -> Self {
+        let mut registry = Self::new();
+        for (id, symbol, name, deployments) in BUNDLED {
+            let asset = deployments.iter().fold(
+                CanonicalAsset::new(*id, *symbol).name(*name),
+                |asset, (chain, address)| asset.deployment(*chain, *address),
+            );
+            registry.register(asset);
+        }
+        registry
+    }
+
+    /// Load assets from a JSON array of [`CanonicalAsset`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut registry = Self::new();
+        registry.extend_from_json(json)?;
+        Ok(registry)
+    }
+
+    /// Merge assets from a JSON array of [`CanonicalAsset`] into this registry.
+    pub fn extend_from_json(&mut self, json: &str) -> Result<()> {
+        let assets: Vec<CanonicalAsset> = serde_json::from_str(json)
+            .map_err(|e| Error::Config(format!("Invalid asset list: {}", e)))?;
+        for asset in assets {
+            self.register(asset);
+        }
+        Ok(())
+    }
+
+    pub fn with_asset(mut self, asset: CanonicalAsset) -> Self {
+        self.register(asset);
+        self
+    }
+
+    /// Add an asset. Deployments of an existing id are merged; a deployment
+    /// already mapped to another asset is remapped to this one.
+    pub fn register(&mut self, asset: CanonicalAsset) {
+        for (chain, address) in &asset.deployments {
+            self.by_deployment.insert(deployment_key(chain, address), asset.id.clone());
+        }
+        match self.assets.get_mut(&asset.id) {
+            Some(existing) => existing.deployments.extend(asset.deployments),
+            None => {
+                self.assets.insert(asset.id.clone(), asset);
+            }
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CanonicalAsset> {
+        self.assets.get(id)
+    }
+
+    /// The canonical asset deployed at `address` on `chain`.
+    pub fn canonical_for(&self, chain: &str, address: &str) -> Option<&CanonicalAsset> {
+        self.by_deployment
+            .get(&deployment_key(chain, address))
+            .and_then(|id| self.assets.get(id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Fold multi-chain balance items into one holding per canonical asset.
+    ///
+    /// Unmapped tokens get their own holding keyed by `chain:contract`.
+    /// Holdings are sorted by total quote, highest first.
+    pub fn consolidate(&self, items: &[MultiChainBalanceItem]) -> Vec<ConsolidatedHolding> {
+        let mut holdings: Vec<ConsolidatedHolding> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+
+        for item in items {
+            let chain = item.chain_name.as_deref().unwrap_or_default();
+            let address = item.contract_address.as_deref().unwrap_or_default();
+            let canonical = self.canonical_for(chain, address);
+            let key = canonical
+                .map(|a| a.id.clone())
+                .unwrap_or_else(|| format!("{}:{}", chain, normalize_address(address)));
+
+            let slot = *index.entry(key.clone()).or_insert_with(|| {
+                holdings.push(ConsolidatedHolding {
+                    asset_id: key,
+                    symbol: canonical.map(|a| a.symbol.clone()).or_else(|| item.contract_ticker_symbol.clone()),
+                    name: canonical.and_then(|a| a.name.clone()).or_else(|| item.contract_name.clone()),
+                    logo_url: canonical.and_then(|a| a.logo_url.clone()).or_else(|| item.logo_url.clone()),
+                    is_canonical: canonical.is_some(),
+                    amount: 0.0,
+                    quote: 0.0,
+                    breakdown: Vec::new(),
+                });
+                holdings.len() - 1
+            });
+
+            let holding = &mut holdings[slot];
+            holding.amount += scaled_amount(item).unwrap_or(0.0);
+            holding.quote += item.quote.unwrap_or(0.0);
+            holding.breakdown.push(item.clone());
+        }
+
+        holdings.sort_by(|a, b| b.quote.total_cmp(&a.quote));
+        holdings
+    }
+}
+
+fn deployment_key(chain: &str, address: &str) -> (String, String) {
+    (chain.to_ascii_lowercase(), normalize_address(address))
+}
+
+fn scaled_amount(item: &MultiChainBalanceItem) -> Option<f64> {
+    let raw = item.balance.as_deref()?.parse::<f64>().ok()?;
+    Some(raw / 10f64.powi(item.contract_decimals.unwrap_or(18) as i32))
+}
+
+/// A holding aggregated across every chain it appears on.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsolidatedHolding {
+    /// Canonical asset id, or `chain:contract` for unmapped tokens.
+    pub asset_id: String,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub logo_url: Option<String>,
+    /// Whether the holding was matched to a registry asset.
+    pub is_canonical: bool,
+    /// Decimal-adjusted amount summed across chains.
+    pub amount: f64,
+    /// Quote value summed across chains.
+    pub quote: f64,
+    /// The original per-chain balance items.
+    pub breakdown: Vec<MultiChainBalanceItem>,
+}
+
+impl ConsolidatedHolding {
+    /// Chains this holding is spread over.
+    pub fn chains(&self) -> impl Iterator<Item = &str> {
+        self.breakdown.iter().filter_map(|item| item.chain_name.as_deref())
+    }
+}
+
+impl MultiChainBalancesData {
+    /// Fold bridged representations into canonical holdings using `registry`.
+    pub fn consolidate(&self, registry: &AssetRegistry) -> Vec<ConsolidatedHolding> {
+        registry.consolidate(&self.items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(chain: &str, address: &str, symbol: &str, balance: &str, decimals: u32, quote: f64) -> MultiChainBalanceItem {
+        serde_json::from_value(serde_json::json!({
+            "chain_name": chain,
+            "contract_address": address,
+            "contract_ticker_symbol": symbol,
+            "contract_decimals": decimals,
+            "balance": balance,
+            "quote": quote,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_consolidates_bridged_usdc() {
+        let items = vec![
+            item("eth-mainnet", "0xA0b86991c6218b36c1d19d4a2e9eB0cE3606eB48", "USDC", "5000000", 6, 5.0),
+            item("base-mainnet", "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913", "USDbC", "2000000", 6, 2.0),
+            item("eth-mainnet", "0xfeed", "MEME", "10", 0, 0.5),
+        ];
+
+        let holdings = AssetRegistry::bundled().consolidate(&items);
+        assert_eq!(holdings.len(), 2);
+        assert_eq!(holdings[0].asset_id, "usdc");
+        assert_eq!(holdings[0].symbol.as_deref(), Some("USDC"));
+        assert_eq!(holdings[0].amount, 7.0);
+        assert_eq!(holdings[0].chains().collect::<Vec<_>>(), vec!["eth-mainnet", "base-mainnet"]);
+        assert_eq!(holdings[1].asset_id, "eth-mainnet:0xfeed");
+        assert!(!holdings[1].is_canonical);
+    }
+
+    #[test]
+    fn test_bring_your_own_list() {
+        let registry = AssetRegistry::from_json(
+            r#"[{ "id": "gho", "symbol": "GHO", "deployments": { "eth-mainnet": "0x40D16FC0246aD3160Ccc09B8D0D3A2cD28aE6C2f" } }]"#,
+        )
+        .unwrap();
+        assert_eq!(registry.canonical_for("ETH-MAINNET", "0x40d16fc0246ad3160ccc09b8d0d3a2cd28ae6c2f").unwrap().symbol, "GHO");
+        assert!(AssetRegistry::from_json("{}").is_err());
+    }
+}
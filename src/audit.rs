@@ -0,0 +1,130 @@
+//! Audit log of data fetched through the SDK.
+//!
+//! An [`AuditSink`] registered with
+//! [`ClientConfig::with_audit_sink`](crate::ClientConfig::with_audit_sink)
+//! receives one [`AuditEntry`] per REST call, successful or not, naming the
+//! endpoint, chain and wallet address it touched. Each audited call carries an
+//! `X-Request-ID` header (generated unless one was already set) so entries can
+//! be matched against server-side logs. Streaming subscriptions are not audited.
+//!
+//! ```rust
+//! use goldrush_sdk::{AuditLog, ClientConfig};
+//!
+//! let log = AuditLog::new();
+//! let config = ClientConfig::default().with_audit_sink(log.clone());
+//! // ... make calls with a client built from `config` ...
+//! for entry in log.for_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045") {
+//!     println!("{} {:?} {}", entry.endpoint, entry.chain_name, entry.request_id);
+//! }
+//! ```
+
+use crate::address::same_address;
+use crate::endpoint::Endpoint;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// One REST call made through the SDK.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub endpoint: Endpoint,
+    /// Chain named in the request path, if any.
+    pub chain_name: Option<String>,
+    /// Wallet address named in the request path, if any.
+    pub address: Option<String>,
+    /// When the call was made.
+    pub timestamp: SystemTime,
+    /// The `X-Request-ID` sent with the call.
+    pub request_id: String,
+    /// Request id assigned by the server, when it returned one.
+    pub server_request_id: Option<String>,
+    /// HTTP status of the final attempt; `None` when no response arrived.
+    pub status: Option<u16>,
+    pub succeeded: bool,
+}
+
+/// Receives an [`AuditEntry`] for every REST call.
+///
+/// Called inline once the call completes, so implementations should hand
+/// entries off rather than block.
+pub trait AuditSink: fmt::Debug + Send + Sync {
+    fn record(&self, entry: &AuditEntry);
+}
+
+/// In-memory [`AuditSink`]; clones share the same entries.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    entries: Arc<Mutex<Vec<AuditEntry>>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every entry so far, oldest first.
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Entries naming `address`, compared case-insensitively.
+    pub fn for_address(&self, address: &str) -> Vec<AuditEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.address.as_deref().is_some_and(|a| same_address(a, address)))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove and return every entry.
+    pub fn drain(&self) -> Vec<AuditEntry> {
+        std::mem::take(&mut *self.entries.lock().unwrap())
+    }
+}
+
+impl AuditSink for AuditLog {
+    fn record(&self, entry: &AuditEntry) {
+        self.entries.lock().unwrap().push(entry.clone());
+    }
+}
+
+/// The chain and wallet address named in a request path.
+pub(crate) fn scope(path: &str) -> (Option<String>, Option<String>) {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let Some(start) = segments.iter().position(|s| *s == "v1") else {
+        return (None, None);
+    };
+
+    match &segments[start + 1..] {
+        ["address", address, ..] | ["allchains", "address", address, ..] => (None, Some(address.to_string())),
+        ["pricing", _, chain, ..] => (Some(chain.to_string()), None),
+        [] | ["chains" | "graphql" | "allchains" | "pricing", ..] => (None, None),
+        [chain, "address", address, ..]
+        | [chain, "approvals", address]
+        | [chain, "nft", "approvals", address]
+        | [chain, "bulk", "transactions", address, ..]
+        | [chain, "events", "address", address] => (Some(chain.to_string()), Some(address.to_string())),
+        [chain, ..] => (Some(chain.to_string()), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_from_path() {
+        assert_eq!(
+            scope("/v1/eth-mainnet/address/0xabc/balances_v2/?quote-currency=USD"),
+            (Some("eth-mainnet".into()), Some("0xabc".into()))
+        );
+        assert_eq!(scope("/v1/allchains/address/0xabc/balances/"), (None, Some("0xabc".into())));
+        assert_eq!(scope("/v1/base-mainnet/nft/approvals/0xdef/"), (Some("base-mainnet".into()), Some("0xdef".into())));
+        assert_eq!(scope("/v1/eth-mainnet/tokens/0xtoken/token_holders_v2/"), (Some("eth-mainnet".into()), None));
+        assert_eq!(scope("/v1/chains/status/"), (None, None));
+        assert_eq!(scope("/health"), (None, None));
+    }
+}
@@ -0,0 +1,112 @@
+//! Historical quotes for transfers returned without one.
+//!
+//! The API often leaves `quote_rate` empty on transfers in older blocks.
+//! [`PricingService::backfill_transfer_quotes`](crate::PricingService::backfill_transfer_quotes)
+//! fetches the daily price of each such token once, over the range of dates
+//! its unpriced transfers span, and fills in `quote_rate`, `delta_quote` and
+//! `balance_quote` from the price on the day of each transfer.
+
+use crate::address::normalize_address;
+use crate::amount::{Amount, AmountValue};
+use crate::decimal::Quote;
+use crate::models::balances::{Erc20TransferItem, Erc20TransfersData};
+use std::collections::{BTreeMap, HashMap};
+
+/// Outcome of a quote backfill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QuoteBackfill {
+    /// Transfers that got a quote.
+    pub filled: usize,
+    /// Transfers still without one, because no price was found for their day.
+    pub unpriced: usize,
+    /// Price requests made, one per token.
+    pub requests: usize,
+}
+
+/// Dates to price for one token, as an inclusive `YYYY-MM-DD` range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PriceRange {
+    pub(crate) contract_address: String,
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+/// Contract address and day of a transfer missing its quote.
+fn unpriced(item: &Erc20TransferItem) -> Option<(String, &str)> {
+    if item.quote_rate.is_some() {
+        return None;
+    }
+    let day = item.block_signed_at.as_deref()?.get(..10)?;
+    Some((normalize_address(item.contract_address.as_deref()?), day))
+}
+
+/// One range per token covering all of its unpriced transfers.
+pub(crate) fn price_ranges(data: &Erc20TransfersData) -> Vec<PriceRange> {
+    let mut ranges: BTreeMap<String, (&str, &str)> = BTreeMap::new();
+    for (token, day) in data.items.iter().filter_map(unpriced) {
+        let range = ranges.entry(token).or_insert((day, day));
+        range.0 = range.0.min(day);
+        range.1 = range.1.max(day);
+    }
+    ranges
+        .into_iter()
+        .map(|(contract_address, (from, to))| PriceRange { contract_address, from: from.to_string(), to: to.to_string() })
+        .collect()
+}
+
+/// Fill unpriced transfers from `prices`, keyed by lowercase contract address and day.
+pub(crate) fn fill(data: &mut Erc20TransfersData, prices: &HashMap<(String, String), Quote>) -> QuoteBackfill {
+    let mut backfill = QuoteBackfill::default();
+    for item in &mut data.items {
+        let Some((token, day)) = unpriced(item) else { continue };
+        let Some(rate) = prices.get(&(token, day.to_string())).copied() else {
+            backfill.unpriced += 1;
+            continue;
+        };
+        let decimals = item.contract_decimals.unwrap_or(18);
+        let value = |raw: &Option<Amount>| Some(raw.as_ref()?.in_units(decimals)?.parse::<Quote>().ok()? * rate);
+        item.quote_rate = Some(rate);
+        item.delta_quote = item.delta_quote.or_else(|| value(&item.delta));
+        item.balance_quote = item.balance_quote.or_else(|| value(&item.balance));
+        backfill.filled += 1;
+    }
+    backfill
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::QuoteValue;
+    use serde_json::json;
+
+    #[test]
+    fn test_ranges_and_fill() {
+        let mut data: Erc20TransfersData = serde_json::from_value(json!({
+            "items": [
+                { "contract_address": "0xA", "block_signed_at": "2020-01-03T10:00:00Z", "delta": "2500000",
+                  "contract_decimals": 6 },
+                { "contract_address": "0xa", "block_signed_at": "2020-01-01T10:00:00Z", "delta": "1000000",
+                  "contract_decimals": 6 },
+                { "contract_address": "0xb", "block_signed_at": "2020-02-01T00:00:00Z", "delta": "1" },
+                { "contract_address": "0xc", "block_signed_at": "2020-02-01T00:00:00Z", "quote_rate": 3.0 },
+            ],
+        }))
+        .unwrap();
+
+        let ranges = price_ranges(&data);
+        assert_eq!(ranges, [
+            PriceRange { contract_address: "0xa".into(), from: "2020-01-01".into(), to: "2020-01-03".into() },
+            PriceRange { contract_address: "0xb".into(), from: "2020-02-01".into(), to: "2020-02-01".into() },
+        ]);
+
+        let prices = HashMap::from([
+            (("0xa".to_string(), "2020-01-03".to_string()), Quote::from_f64(2.0)),
+            (("0xa".to_string(), "2020-01-01".to_string()), Quote::from_f64(1.5)),
+        ]);
+        let backfill = fill(&mut data, &prices);
+        assert_eq!((backfill.filled, backfill.unpriced), (2, 1));
+        assert_eq!(data.items[0].delta_quote.map(|q| q.as_f64()), Some(5.0));
+        assert_eq!(data.items[1].quote_rate.map(|q| q.as_f64()), Some(1.5));
+        assert!(data.items[2].quote_rate.is_none());
+    }
+}
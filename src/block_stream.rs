@@ -0,0 +1,155 @@
+//! New-block subscription by polling, for chains without streaming support.
+//!
+//! [`BaseService::block_stream`](crate::BaseService::block_stream) polls the
+//! latest block of a chain and yields every block height once, in ascending
+//! order, as a [`BlockResponse`]. Blocks missed between polls are fetched
+//! individually, so nothing is skipped after a slow consumer or a long
+//! interval.
+//!
+//! The stream never goes backwards: a latest height lower than the last one
+//! yielded, as served by a lagging replica or after a reorg to a shorter
+//! chain, is ignored until the chain catches up. A block reorged out after it
+//! was yielded is not yielded again; set
+//! [`confirmations`](crate::BlockStreamOptions::confirmations) to the chain's
+//! reorg depth to only see blocks that are unlikely to change.
+
+use crate::clock::Clock;
+use crate::models::base::BlockResponse;
+use crate::services::base_service::BaseService;
+use crate::Result;
+use futures_core::stream::Stream;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Most blocks fetched per poll while catching up; the next poll follows without waiting.
+const MAX_CATCH_UP: u64 = 100;
+
+type PollFuture = Pin<Box<dyn Future<Output = (Poller, Result<Vec<BlockResponse>>)> + Send>>;
+
+/// Polling state, moved into each in-flight poll and handed back with its result.
+pub(crate) struct Poller {
+    service: BaseService,
+    chain_name: String,
+    interval: Duration,
+    confirmations: u64,
+    next_height: Option<u64>,
+    wait: bool,
+    clock: Arc<dyn Clock>,
+}
+
+impl Poller {
+    pub(crate) fn new(
+        service: BaseService, chain_name: String, interval: Duration, confirmations: u64, from_block: Option<u64>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self { service, chain_name, interval, confirmations, next_height: from_block, wait: false, clock }
+    }
+
+    async fn run(mut self) -> (Self, Result<Vec<BlockResponse>>) {
+        let result = self.next_blocks().await;
+        (self, result)
+    }
+
+    async fn next_blocks(&mut self) -> Result<Vec<BlockResponse>> {
+        loop {
+            if self.wait {
+                self.clock.sleep(self.interval).await;
+            }
+            self.wait = true;
+
+            let latest = self.service.get_block(&self.chain_name, "latest").await?;
+            let Some(head) = height(&latest) else { continue };
+            let target = head.saturating_sub(self.confirmations);
+            let next = *self.next_height.get_or_insert(target);
+            if next > target {
+                continue;
+            }
+
+            let last = target.min(next + MAX_CATCH_UP - 1);
+            let mut blocks = Vec::new();
+            for block_height in next..=last {
+                let block = if block_height == head {
+                    latest.clone()
+                } else {
+                    match self.service.get_block(&self.chain_name, &block_height.to_string()).await {
+                        Ok(block) => block,
+                        Err(e) if blocks.is_empty() => return Err(e),
+                        // Hand over what was fetched; the failure recurs on the next poll.
+                        Err(_) => break,
+                    }
+                };
+                // Not indexed yet; pick it up on the next poll.
+                if height(&block) != Some(block_height) {
+                    break;
+                }
+                blocks.push(block);
+                self.next_height = Some(block_height + 1);
+            }
+            // Go straight on with the next batch when behind.
+            let complete = blocks.len() as u64 == last - next + 1;
+            self.wait = !complete || last == target;
+            if !blocks.is_empty() {
+                return Ok(blocks);
+            }
+        }
+    }
+}
+
+fn height(block: &BlockResponse) -> Option<u64> {
+    block.data.as_ref()?.items.first()?.height
+}
+
+/// Stream of new blocks on one chain. See the [module docs](self).
+///
+/// A failed poll is yielded as an error and polling continues at the next
+/// interval; the stream only ends when dropped.
+pub struct BlockStream {
+    poller: Option<Poller>,
+    in_flight: Option<PollFuture>,
+    buffer: VecDeque<BlockResponse>,
+}
+
+impl BlockStream {
+    pub(crate) fn new(poller: Poller) -> Self {
+        Self { poller: Some(poller), in_flight: None, buffer: VecDeque::new() }
+    }
+
+    /// Height of the next block to be yielded, once known.
+    pub fn next_height(&self) -> Option<u64> {
+        let buffered = self.buffer.front().and_then(height);
+        buffered.or_else(|| self.poller.as_ref().and_then(|p| p.next_height))
+    }
+}
+
+impl Stream for BlockStream {
+    type Item = Result<BlockResponse>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(block) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(block)));
+            }
+
+            let poller = &mut this.poller;
+            let future = this.in_flight.get_or_insert_with(|| {
+                Box::pin(poller.take().expect("poller is present while no poll is in flight").run())
+            });
+            match future.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready((poller, result)) => {
+                    this.in_flight = None;
+                    this.poller = Some(poller);
+                    match result {
+                        Ok(blocks) => this.buffer.extend(blocks),
+                        Err(e) => return Poll::Ready(Some(Err(e))),
+                    }
+                }
+            }
+        }
+    }
+}
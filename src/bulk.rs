@@ -0,0 +1,356 @@
+use crate::clock::{default_clock, Clock};
+use crate::{CreditEstimator, Error, ProgressReporter, RateLimiter, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tracing::{debug, instrument, warn};
+
+/// Configuration for bulk request execution.
+#[derive(Debug, Clone)]
+pub struct BulkConfig {
+    /// Maximum number of tasks running at once.
+    pub max_concurrency: usize,
+    /// Retry attempts per task after the first failure.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each subsequent attempt.
+    pub retry_delay: Duration,
+}
+
+impl Default for BulkConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 8,
+            max_retries: 2,
+            retry_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Values paired with the index of the task that produced them.
+pub type Indexed<T> = Vec<(usize, T)>;
+
+/// Outcome of a bulk run, one entry per submitted task in submission order.
+#[derive(Debug)]
+pub struct BulkResult<T> {
+    pub outcomes: Vec<Result<T>>,
+}
+
+impl<T> BulkResult<T> {
+    /// Successful results with their task index.
+    pub fn successes(&self) -> Vec<(usize, &T)> {
+        self.outcomes.iter().enumerate()
+            .filter_map(|(i, r)| r.as_ref().ok().map(|v| (i, v)))
+            .collect()
+    }
+
+    /// Failed tasks with their task index.
+    pub fn errors(&self) -> Vec<(usize, &Error)> {
+        self.outcomes.iter().enumerate()
+            .filter_map(|(i, r)| r.as_ref().err().map(|e| (i, e)))
+            .collect()
+    }
+
+    /// Whether every task succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.outcomes.iter().all(|r| r.is_ok())
+    }
+
+    /// Split into indexed successes and indexed errors.
+    pub fn into_parts(self) -> (Indexed<T>, Indexed<Error>) {
+        let mut ok = Vec::new();
+        let mut err = Vec::new();
+        for (i, outcome) in self.outcomes.into_iter().enumerate() {
+            match outcome {
+                Ok(v) => ok.push((i, v)),
+                Err(e) => err.push((i, e)),
+            }
+        }
+        (ok, err)
+    }
+}
+
+/// Runs many independent requests with bounded concurrency.
+///
+/// Each task is retried on its own, so one failing request never cancels or
+/// delays the others; a panicking task is reported as an error for its slot.
+#[derive(Debug, Clone)]
+pub struct BulkExecutor {
+    config: BulkConfig,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    budget: Option<(Arc<CreditEstimator>, f64)>,
+    progress: Option<ProgressReporter>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for BulkExecutor {
+    fn default() -> Self {
+        Self::new(BulkConfig::default())
+    }
+}
+
+impl BulkExecutor {
+    pub fn new(config: BulkConfig) -> Self {
+        Self { config, rate_limiter: None, budget: None, progress: None, clock: default_clock() }
+    }
+
+    /// Wait between retries by `clock` instead of tokio's clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Acquire a token from `limiter` before every attempt.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Stop starting tasks once `credits` estimated credits have been charged
+    /// to `estimator` during the run; unstarted tasks fail with [`Error::Config`].
+    ///
+    /// Running tasks are left to finish, so the final spend can overshoot.
+    pub fn with_budget(mut self, estimator: Arc<CreditEstimator>, credits: f64) -> Self {
+        self.budget = Some((estimator, credits));
+        self
+    }
+
+    /// Report progress to `reporter` as each task finishes.
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
+    /// Run all tasks and collect partial results and per-task errors.
+    ///
+    /// Tasks are closures so they can be invoked again on retry.
+    #[instrument(skip(self, tasks), fields(tasks = tasks.len(), max_concurrency = self.config.max_concurrency))]
+    pub async fn run<T, F, Fut>(&self, tasks: Vec<F>) -> BulkResult<T>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let total = tasks.len();
+        let mut outcomes: Vec<Option<Result<T>>> = (0..total).map(|_| None).collect();
+        let mut pending = tasks.into_iter().enumerate();
+        let mut set = JoinSet::new();
+        let mut ids = HashMap::new();
+        let max_concurrency = self.config.max_concurrency.max(1);
+        let budget = self.budget.as_ref().map(|(estimator, credits)| (estimator, *credits, estimator.used()));
+        let mut over_budget = false;
+        let mut progress = self.progress.as_ref().map(|reporter| reporter.tracker(Some(total as u64)));
+
+        loop {
+            while set.len() < max_concurrency && !over_budget {
+                if let Some((estimator, budget, baseline)) = budget {
+                    let spent = estimator.used() - baseline;
+                    if spent > budget {
+                        warn!(spent = %spent, budget = %budget, "Credit budget exceeded, aborting bulk run");
+                        over_budget = true;
+                        break;
+                    }
+                }
+                let Some((index, task)) = pending.next() else { break };
+                let handle = set.spawn(run_task(
+                    task,
+                    self.rate_limiter.clone(),
+                    self.config.max_retries,
+                    self.config.retry_delay,
+                    Arc::clone(&self.clock),
+                ));
+                ids.insert(handle.id(), index);
+            }
+
+            let Some(joined) = set.join_next_with_id().await else { break };
+            let (id, outcome) = match joined {
+                Ok((id, outcome)) => (id, outcome),
+                Err(e) => (e.id(), Err(Error::Config(format!("Bulk task failed: {}", e)))),
+            };
+            if let Some(progress) = progress.as_mut() {
+                if let Err(e) = &outcome {
+                    progress.record_error(e);
+                }
+                progress.advance(1);
+            }
+            if let Some(index) = ids.remove(&id) {
+                outcomes[index] = Some(outcome);
+            }
+        }
+
+        let unstarted = || match budget {
+            Some((_, budget, _)) if over_budget => {
+                Error::Config(format!("Credit budget of {:.1} exceeded before the task started", budget))
+            }
+            _ => Error::Config("Bulk task did not complete".to_string()),
+        };
+        BulkResult {
+            outcomes: outcomes.into_iter()
+                .map(|o| o.unwrap_or_else(|| Err(unstarted())))
+                .collect(),
+        }
+    }
+}
+
+async fn run_task<T, F, Fut>(
+    task: F,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    max_retries: u32,
+    retry_delay: Duration,
+    clock: Arc<dyn Clock>,
+) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0u32;
+    let mut delay = retry_delay;
+
+    loop {
+        if let Some(limiter) = &rate_limiter {
+            limiter.acquire().await?;
+        }
+
+        match task().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_retries && is_transient(&e) => {
+                attempt += 1;
+                warn!(attempt = %attempt, error = %e, "Bulk task failed, retrying");
+                clock.sleep(delay).await;
+                delay = std::cmp::min(delay * 2, Duration::from_secs(30));
+            }
+            Err(e) => {
+                debug!(attempts = %(attempt + 1), "Bulk task gave up");
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Network failures, 5xx and 429 responses are worth retrying; everything
+/// else (bad input, decode errors, 4xx) will fail the same way again.
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+        Error::Api { status, .. } => *status >= 500 || *status == 429,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    type BoxedTask = Box<dyn Fn() -> std::pin::Pin<Box<dyn Future<Output = Result<u32>> + Send>> + Send + Sync>;
+
+    fn api_error(status: u16) -> Error {
+        Error::api(status, "error".to_string(), None)
+    }
+
+    fn fast_config() -> BulkConfig {
+        BulkConfig { max_concurrency: 2, max_retries: 2, retry_delay: Duration::from_millis(1) }
+    }
+
+    #[tokio::test]
+    async fn test_partial_results_keep_order() {
+        let tasks: Vec<_> = (0..5u32)
+            .map(|i| move || async move {
+                if i == 3 { Err(api_error(404)) } else { Ok(i * 10) }
+            })
+            .collect();
+
+        let result = BulkExecutor::new(fast_config()).run(tasks).await;
+        assert!(!result.is_complete());
+        assert_eq!(result.successes().iter().map(|(_, v)| **v).collect::<Vec<_>>(), vec![0, 10, 20, 40]);
+        assert_eq!(result.errors().len(), 1);
+        assert_eq!(result.errors()[0].0, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_are_isolated_per_task() {
+        let flaky_calls = Arc::new(AtomicU32::new(0));
+        let fatal_calls = Arc::new(AtomicU32::new(0));
+
+        let flaky = {
+            let calls = Arc::clone(&flaky_calls);
+            move || {
+                let calls = Arc::clone(&calls);
+                async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 { Err(api_error(503)) } else { Ok(1) }
+                }
+            }
+        };
+        let fatal = {
+            let calls = Arc::clone(&fatal_calls);
+            move || {
+                let calls = Arc::clone(&calls);
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Err(api_error(400))
+                }
+            }
+        };
+
+        let tasks: Vec<BoxedTask> = vec![
+            Box::new(move || Box::pin(flaky())),
+            Box::new(move || Box::pin(fatal())),
+        ];
+        let result = BulkExecutor::new(fast_config()).run(tasks).await;
+
+        assert!(result.outcomes[0].is_ok());
+        assert!(result.outcomes[1].is_err());
+        assert_eq!(flaky_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(fatal_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_delays_double_on_configured_clock() {
+        let clock = crate::MockClock::new();
+        let config = BulkConfig { retry_delay: Duration::from_secs(10), ..fast_config() };
+        let executor = BulkExecutor::new(config).with_clock(Arc::new(clock.clone()));
+
+        let result = executor.run(vec![|| async { Err::<u32, _>(api_error(503)) }]).await;
+        assert!(result.outcomes[0].is_err());
+        assert_eq!(clock.sleeps(), [Duration::from_secs(10), Duration::from_secs(20)]);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_is_reported() {
+        let tasks: Vec<_> = (0..3u32)
+            .map(|i| move || async move {
+                if i == 1 { panic!("task panicked") }
+                Ok(i)
+            })
+            .collect();
+
+        let (ok, err) = BulkExecutor::new(fast_config()).run(tasks).await.into_parts();
+        assert_eq!(ok, vec![(0, 0), (2, 2)]);
+        assert_eq!(err.len(), 1);
+        assert_eq!(err[0].0, 1);
+    }
+
+    #[tokio::test]
+    async fn test_budget_stops_starting_tasks() {
+        let estimator = Arc::new(crate::CreditEstimator::new());
+        estimator.set_cost(crate::Endpoint::TransactionsV3, 2.0);
+        let tasks: Vec<_> = (0..5u32)
+            .map(|i| {
+                let estimator = Arc::clone(&estimator);
+                move || {
+                    estimator.record(crate::Endpoint::TransactionsV3);
+                    async move {
+                        Ok(i)
+                    }
+                }
+            })
+            .collect();
+
+        let config = BulkConfig { max_concurrency: 1, ..fast_config() };
+        let (ok, err) = BulkExecutor::new(config).with_budget(Arc::clone(&estimator), 3.0).run(tasks).await.into_parts();
+        assert_eq!(ok, vec![(0, 0), (1, 1)]);
+        assert_eq!(err.len(), 3);
+        assert!(matches!(&err[0].1, Error::Config(msg) if msg.contains("budget")));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{Error, Result};
+use crate::clock::{default_clock, Clock};
+use crate::endpoint::Endpoint;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -14,8 +15,7 @@ pub struct CacheEntry<T> {
 }
 
 impl<T> CacheEntry<T> {
-    pub fn new(value: T, ttl: Duration) -> Self {
-        let now = Instant::now();
+    pub fn new(value: T, ttl: Duration, now: Instant) -> Self {
         Self {
             value,
             expires_at: now + ttl,
@@ -23,12 +23,12 @@ impl<T> CacheEntry<T> {
         }
     }
     
-    pub fn is_expired(&self) -> bool {
-        Instant::now() > self.expires_at
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now > self.expires_at
     }
     
-    pub fn age(&self) -> Duration {
-        Instant::now().duration_since(self.created_at)
+    pub fn age(&self, now: Instant) -> Duration {
+        now.duration_since(self.created_at)
     }
 }
 
@@ -38,6 +38,7 @@ pub struct MemoryCache<T> {
     store: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
     default_ttl: Duration,
     max_entries: usize,
+    clock: Arc<dyn Clock>,
 }
 
 impl<T: Clone + Send + Sync + 'static> MemoryCache<T> {
@@ -46,21 +47,29 @@ impl<T: Clone + Send + Sync + 'static> MemoryCache<T> {
             store: Arc::new(RwLock::new(HashMap::new())),
             default_ttl,
             max_entries,
+            clock: default_clock(),
         }
     }
 
+    /// Measure entry age and expiry with `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Get a value from the cache.
     #[instrument(skip(self), fields(key = %key))]
     pub async fn get(&self, key: &str) -> Option<T> {
         let mut store = self.store.write().await;
+        let now = self.clock.now();
         
         if let Some(entry) = store.get(key) {
-            if entry.is_expired() {
+            if entry.is_expired(now) {
                 debug!("Cache entry expired, removing");
                 store.remove(key);
                 None
             } else {
-                debug!(age_ms = %entry.age().as_millis(), "Cache hit");
+                debug!(age_ms = %entry.age(now).as_millis(), "Cache hit");
                 Some(entry.value.clone())
             }
         } else {
@@ -69,6 +78,16 @@ impl<T: Clone + Send + Sync + 'static> MemoryCache<T> {
         }
     }
 
+    /// Get a value and how long ago it was cached.
+    pub async fn get_with_age(&self, key: &str) -> Option<(T, Duration)> {
+        let store = self.store.read().await;
+        let now = self.clock.now();
+        store
+            .get(key)
+            .filter(|entry| !entry.is_expired(now))
+            .map(|entry| (entry.value.clone(), entry.age(now)))
+    }
+
     /// Set a value in the cache with default TTL.
     #[instrument(skip(self, value), fields(key = %key))]
     pub async fn set(&self, key: String, value: T) {
@@ -93,7 +112,7 @@ impl<T: Clone + Send + Sync + 'static> MemoryCache<T> {
             }
         }
         
-        let entry = CacheEntry::new(value, ttl);
+        let entry = CacheEntry::new(value, ttl, self.clock.now());
         store.insert(key, entry);
         debug!("Value cached successfully");
     }
@@ -121,7 +140,8 @@ impl<T: Clone + Send + Sync + 'static> MemoryCache<T> {
     pub async fn stats(&self) -> CacheStats {
         let store = self.store.read().await;
         let total_entries = store.len();
-        let expired_entries = store.values().filter(|entry| entry.is_expired()).count();
+        let now = self.clock.now();
+        let expired_entries = store.values().filter(|entry| entry.is_expired(now)).count();
         
         CacheStats {
             total_entries,
@@ -133,10 +153,11 @@ impl<T: Clone + Send + Sync + 'static> MemoryCache<T> {
 
     /// Remove all expired entries from the cache.
     async fn evict_expired_entries(&self, store: &mut HashMap<String, CacheEntry<T>>) {
+        let now = self.clock.now();
         let expired_keys: Vec<String> = store
             .iter()
             .filter_map(|(key, entry)| {
-                if entry.is_expired() {
+                if entry.is_expired(now) {
                     Some(key.clone())
                 } else {
                     None
@@ -173,6 +194,24 @@ pub struct CacheStats {
     pub max_entries: usize,
 }
 
+/// How a call uses cached responses.
+///
+/// Set it for a whole client with [`ClientConfig::with_cache_policy`](crate::ClientConfig::with_cache_policy)
+/// or per service handle with [`RequestOptions::cache_policy`](crate::RequestOptions::cache_policy).
+/// Only cached endpoints (see [`CacheConfig`]) are affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Serve unexpired cached entries, otherwise fetch and cache.
+    #[default]
+    Standard,
+    /// Always fetch, then cache the fresh response ("force refresh").
+    Bypass,
+    /// Serve cached entries younger than the given age, otherwise fetch and cache.
+    RefreshIfOlderThan(Duration),
+    /// Never touch the network; fail if nothing is cached.
+    CacheOnly,
+}
+
 /// Cache configuration for different endpoint types.
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
@@ -184,6 +223,8 @@ pub struct CacheConfig {
     pub nft_metadata_ttl: Duration,
     /// TTL for NFT collections (mostly static)
     pub nft_collection_ttl: Duration,
+    /// TTL for chain status and latest-block lookups (polled constantly)
+    pub status_ttl: Duration,
     /// Maximum number of cached entries
     pub max_entries: usize,
     /// Enable caching
@@ -197,29 +238,73 @@ impl Default for CacheConfig {
             transaction_ttl: Duration::from_secs(300), // 5 minutes for transactions
             nft_metadata_ttl: Duration::from_secs(3600), // 1 hour for NFT metadata
             nft_collection_ttl: Duration::from_secs(3600), // 1 hour for NFT collections
+            status_ttl: Duration::from_secs(2),          // 2 seconds for chain status / latest block
             max_entries: 1000,
             enabled: true,
         }
     }
 }
 
+impl CacheConfig {
+    /// TTL for responses from `endpoint`.
+    ///
+    /// Endpoints without a dedicated setting use `balance_ttl`, the shortest
+    /// of the data TTLs.
+    pub fn ttl_for(&self, endpoint: Endpoint) -> Duration {
+        match endpoint {
+            Endpoint::ChainStatus | Endpoint::Block => self.status_ttl,
+            Endpoint::Transaction
+            | Endpoint::TransactionsV2
+            | Endpoint::TransactionsV3
+            | Endpoint::BlockTransactions => {
+                self.transaction_ttl
+            }
+            Endpoint::NftMetadata | Endpoint::NftTraits | Endpoint::NftAttributes => self.nft_metadata_ttl,
+            Endpoint::NftCollections | Endpoint::NftTraitsSummary => self.nft_collection_ttl,
+            _ => self.balance_ttl,
+        }
+    }
+}
+
 /// Generate cache keys for different types of requests.
+#[allow(dead_code)]
 pub fn cache_key_for_balances(chain_name: &str, address: &str, options: &str) -> String {
-    format!("balances:{}:{}:{}", chain_name, address, options)
+    Endpoint::BalancesV2.cache_key(&[chain_name, address, options])
 }
 
+#[allow(dead_code)]
 pub fn cache_key_for_transactions(chain_name: &str, address: &str, options: &str) -> String {
-    format!("transactions:{}:{}:{}", chain_name, address, options)
+    Endpoint::TransactionsV3.cache_key(&[chain_name, address, options])
 }
 
+#[allow(dead_code)]
 pub fn cache_key_for_transaction(chain_name: &str, tx_hash: &str) -> String {
-    format!("transaction:{}:{}", chain_name, tx_hash)
+    Endpoint::Transaction.cache_key(&[chain_name, tx_hash])
 }
 
+#[allow(dead_code)]
 pub fn cache_key_for_nfts(chain_name: &str, address: &str, options: &str) -> String {
-    format!("nfts:{}:{}:{}", chain_name, address, options)
+    Endpoint::NftBalances.cache_key(&[chain_name, address, options])
 }
 
+#[allow(dead_code)]
 pub fn cache_key_for_nft_metadata(chain_name: &str, address: &str, token_id: &str) -> String {
-    format!("nft_metadata:{}:{}:{}", chain_name, address, token_id)
-}
\ No newline at end of file
+    Endpoint::NftMetadata.cache_key(&[chain_name, address, token_id])
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockClock;
+
+    #[tokio::test]
+    async fn test_entries_expire_by_configured_clock() {
+        let clock = MockClock::new();
+        let cache = MemoryCache::new(Duration::from_secs(60), 10).with_clock(Arc::new(clock.clone()));
+        cache.set("key".to_string(), 1u32).await;
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(cache.get_with_age("key").await, Some((1, Duration::from_secs(59))));
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(cache.get("key").await, None);
+    }
+}
@@ -0,0 +1,281 @@
+//! Runtime-extensible chain registry.
+//!
+//! The built-in [`Chain`] enum only covers networks known when the crate was
+//! released. [`ChainRegistry`] starts from those and lets callers register
+//! additional chains (private deployments, appchains) either through the
+//! builder API or, with the `chain-config` feature, from a `chains.toml` file:
+//!
+//! ```toml
+//! [[chains]]
+//! slug = "my-appchain-mainnet"
+//! chain_id = 424242
+//! display_name = "My Appchain"
+//! native_token = "MAC"
+//! ```
+
+use crate::chains::Chain;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Metadata describing a chain addressable through the GoldRush API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainInfo {
+    /// Chain slug used in API paths (e.g. `eth-mainnet`).
+    pub slug: String,
+    /// Numeric chain ID.
+    pub chain_id: u64,
+    /// Human-readable name.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Native gas token symbol.
+    #[serde(default)]
+    pub native_token: Option<String>,
+    /// Whether this is a test network.
+    #[serde(default)]
+    pub is_testnet: bool,
+    /// Whether the chain is EVM-compatible.
+    #[serde(default = "default_is_evm")]
+    pub is_evm: bool,
+}
+
+fn default_is_evm() -> bool {
+    true
+}
+
+impl ChainInfo {
+    /// Create metadata for a custom chain with the given slug and chain ID.
+    pub fn new(slug: impl Into<String>, chain_id: u64) -> Self {
+        Self {
+            slug: slug.into(),
+            chain_id,
+            display_name: None,
+            native_token: None,
+            is_testnet: false,
+            is_evm: true,
+        }
+    }
+
+    pub fn display_name(mut self, name: impl Into<String>) -> Self { self.display_name = Some(name.into()); self }
+    pub fn native_token(mut self, symbol: impl Into<String>) -> Self { self.native_token = Some(symbol.into()); self }
+    pub fn testnet(mut self, is_testnet: bool) -> Self { self.is_testnet = is_testnet; self }
+    pub fn evm(mut self, is_evm: bool) -> Self { self.is_evm = is_evm; self }
+
+    /// The built-in [`Chain`] variant for this slug, if any.
+    pub fn builtin(&self) -> Option<Chain> {
+        self.slug.parse().ok()
+    }
+}
+
+impl From<Chain> for ChainInfo {
+    fn from(chain: Chain) -> Self {
+        Self {
+            slug: chain.slug().to_string(),
+            chain_id: chain.chain_id(),
+            display_name: None,
+            native_token: None,
+            is_testnet: chain.is_testnet(),
+            is_evm: chain.is_evm(),
+        }
+    }
+}
+
+impl fmt::Display for ChainInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.slug)
+    }
+}
+
+impl AsRef<str> for ChainInfo {
+    fn as_ref(&self) -> &str {
+        &self.slug
+    }
+}
+
+/// On-disk layout of a `chains.toml` file.
+#[cfg(feature = "chain-config")]
+#[derive(Debug, Default, Deserialize)]
+struct ChainsFile {
+    #[serde(default)]
+    chains: Vec<ChainInfo>,
+}
+
+/// Registry of known chains: the built-in [`Chain`] variants merged with any
+/// custom chains registered at runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use goldrush_sdk::{ChainInfo, ChainRegistry};
+///
+/// let registry = ChainRegistry::builtin()
+///     .with_chain(ChainInfo::new("my-appchain-mainnet", 424242).native_token("MAC"));
+///
+/// assert!(registry.get("eth-mainnet").is_some());
+/// assert_eq!(registry.by_chain_id(424242).unwrap().slug, "my-appchain-mainnet");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChainRegistry {
+    chains: HashMap<String, ChainInfo>,
+}
+
+impl Default for ChainRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl ChainRegistry {
+    /// Create a registry containing only the built-in chains.
+    pub fn builtin() -> Self {
+        let chains = Chain::all()
+            .map(|chain| (chain.slug().to_string(), ChainInfo::from(chain)))
+            .collect();
+        Self { chains }
+    }
+
+    /// Create an empty registry.
+    pub fn empty() -> Self {
+        Self { chains: HashMap::new() }
+    }
+
+    /// Add a chain, replacing any existing entry with the same slug.
+    pub fn with_chain(mut self, info: ChainInfo) -> Self {
+        self.register(info);
+        self
+    }
+
+    /// Add a chain in place, returning the entry it replaced (if any).
+    pub fn register(&mut self, info: ChainInfo) -> Option<ChainInfo> {
+        self.chains.insert(info.slug.clone(), info)
+    }
+
+    /// Merge another registry into this one; entries from `other` win.
+    pub fn merge(mut self, other: ChainRegistry) -> Self {
+        self.chains.extend(other.chains);
+        self
+    }
+
+    /// Look up a chain by slug.
+    pub fn get(&self, slug: &str) -> Option<&ChainInfo> {
+        self.chains.get(slug.trim())
+    }
+
+    /// Look up a chain by numeric chain ID.
+    pub fn by_chain_id(&self, chain_id: u64) -> Option<&ChainInfo> {
+        self.chains.values().find(|info| info.chain_id == chain_id)
+    }
+
+    /// Whether a slug is known to this registry.
+    pub fn contains(&self, slug: &str) -> bool {
+        self.get(slug).is_some()
+    }
+
+    /// Resolve a slug, returning a configuration error if it is unknown.
+    pub fn resolve(&self, slug: &str) -> Result<&ChainInfo> {
+        self.get(slug)
+            .ok_or_else(|| Error::Config(format!("Unknown chain: {}", slug)))
+    }
+
+    /// Iterate over all registered chains (in no particular order).
+    pub fn iter(&self) -> impl Iterator<Item = &ChainInfo> {
+        self.chains.values()
+    }
+
+    /// Iterate over chains that are not part of the built-in [`Chain`] enum.
+    pub fn custom_chains(&self) -> impl Iterator<Item = &ChainInfo> {
+        self.chains.values().filter(|info| info.builtin().is_none())
+    }
+
+    /// Number of registered chains.
+    pub fn len(&self) -> usize {
+        self.chains.len()
+    }
+
+    /// Whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// Parse chains from TOML and merge them over the built-in list.
+    #[cfg(feature = "chain-config")]
+    pub fn from_toml_str(contents: &str) -> Result<Self> {
+        Ok(Self::builtin().merge(Self::parse_toml(contents)?))
+    }
+
+    /// Load chains from a `chains.toml` file and merge them over the built-in list.
+    #[cfg(feature = "chain-config")]
+    pub fn from_toml_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            Error::Config(format!("Failed to read chain config {}: {}", path.display(), e))
+        })?;
+        Self::from_toml_str(&contents)
+    }
+
+    #[cfg(feature = "chain-config")]
+    fn parse_toml(contents: &str) -> Result<Self> {
+        let file: ChainsFile = toml::from_str(contents)
+            .map_err(|e| Error::Config(format!("Invalid chain config: {}", e)))?;
+
+        let mut registry = Self::empty();
+        for info in file.chains {
+            if info.slug.trim().is_empty() {
+                return Err(Error::Config("Chain slug cannot be empty".to_string()));
+            }
+            if registry.register(info.clone()).is_some() {
+                return Err(Error::Config(format!("Duplicate chain slug: {}", info.slug)));
+            }
+        }
+        Ok(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = ChainRegistry::builtin();
+        assert_eq!(registry.len(), Chain::ALL.len());
+        assert_eq!(registry.get("eth-mainnet").unwrap().chain_id, 1);
+        assert_eq!(registry.by_chain_id(137).unwrap().builtin(), Some(Chain::PolygonMainnet));
+        assert_eq!(registry.custom_chains().count(), 0);
+        assert!(registry.resolve("nope-mainnet").is_err());
+    }
+
+    #[test]
+    fn test_register_custom_chain() {
+        let registry = ChainRegistry::builtin()
+            .with_chain(ChainInfo::new("my-appchain-mainnet", 424242).display_name("My Appchain"));
+
+        let info = registry.resolve("my-appchain-mainnet").unwrap();
+        assert_eq!(info.as_ref(), "my-appchain-mainnet");
+        assert_eq!(info.builtin(), None);
+        assert_eq!(registry.custom_chains().count(), 1);
+    }
+
+    #[cfg(feature = "chain-config")]
+    #[test]
+    fn test_from_toml_str() {
+        let registry = ChainRegistry::from_toml_str(r#"
+            [[chains]]
+            slug = "my-appchain-testnet"
+            chain_id = 424243
+            is_testnet = true
+
+            [[chains]]
+            slug = "eth-mainnet"
+            chain_id = 1
+            display_name = "Ethereum"
+        "#).unwrap();
+
+        let custom = registry.get("my-appchain-testnet").unwrap();
+        assert!(custom.is_testnet && custom.is_evm);
+        assert_eq!(registry.get("eth-mainnet").unwrap().display_name.as_deref(), Some("Ethereum"));
+
+        assert!(ChainRegistry::from_toml_str("[[chains]]\nslug = \"a\"\nchain_id = 1\n[[chains]]\nslug = \"a\"\nchain_id = 2\n").is_err());
+    }
+}
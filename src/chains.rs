@@ -217,6 +217,70 @@ pub enum Chain {
 }
 
 impl Chain {
+    /// Every chain known to the SDK, in declaration order.
+    pub const ALL: &[Chain] = &[
+        Chain::EthereumMainnet,
+        Chain::EthereumSepolia,
+        Chain::EthereumHolesky,
+        Chain::PolygonMainnet,
+        Chain::PolygonAmoyTestnet,
+        Chain::BscMainnet,
+        Chain::BscTestnet,
+        Chain::AvalancheMainnet,
+        Chain::AvalancheTestnet,
+        Chain::ArbitrumMainnet,
+        Chain::ArbitrumSepolia,
+        Chain::ArbitrumNovaMainnet,
+        Chain::OptimismMainnet,
+        Chain::OptimismSepolia,
+        Chain::BaseMainnet,
+        Chain::BaseSepolia,
+        Chain::FantomMainnet,
+        Chain::FantomTestnet,
+        Chain::GnosisMainnet,
+        Chain::GnosisTestnet,
+        Chain::CronosMainnet,
+        Chain::MoonbeamMainnet,
+        Chain::MoonbeamMoonriver,
+        Chain::MoonbeamMoonbaseAlpha,
+        Chain::CeloMainnet,
+        Chain::CeloAlfajores,
+        Chain::HarmonyMainnet,
+        Chain::AuroraMainnet,
+        Chain::AuroraTestnet,
+        Chain::LineaMainnet,
+        Chain::LineaTestnet,
+        Chain::ScrollMainnet,
+        Chain::ScrollSepoliaTestnet,
+        Chain::ZksyncMainnet,
+        Chain::ZksyncTestnet,
+        Chain::MantleMainnet,
+        Chain::MantleTestnet,
+        Chain::PolygonZkevmMainnet,
+        Chain::PolygonZkevmTestnet,
+        Chain::ZoraMainnet,
+        Chain::BlastMainnet,
+        Chain::BlastSepolia,
+        Chain::ModeMainnet,
+        Chain::ModeTestnet,
+        Chain::LiskMainnet,
+        Chain::MerlinMainnet,
+        Chain::BobMainnet,
+        Chain::BtcMainnet,
+        Chain::SolanaMainnet,
+        Chain::SeiMainnet,
+        Chain::TaikoMainnet,
+        Chain::WorldchainMainnet,
+        Chain::BerachainBartio,
+        Chain::ImmutableZkevmMainnet,
+        Chain::ApechainMainnet,
+        Chain::InkMainnet,
+        Chain::SoneiumMainnet,
+        Chain::AbstractMainnet,
+        Chain::UnichainMainnet,
+        Chain::SonicMainnet,
+    ];
+
     /// Returns the chain slug used in API paths.
     pub fn slug(&self) -> &'static str {
         match self {
@@ -386,6 +450,74 @@ impl Chain {
             Chain::SonicMainnet => 146,
         }
     }
+
+    /// Iterate over every supported chain.
+    pub fn all() -> impl Iterator<Item = Chain> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Iterate over all production networks.
+    pub fn mainnets() -> impl Iterator<Item = Chain> {
+        Self::all().filter(|c| !c.is_testnet())
+    }
+
+    /// Iterate over all test networks.
+    pub fn testnets() -> impl Iterator<Item = Chain> {
+        Self::all().filter(|c| c.is_testnet())
+    }
+
+    /// Iterate over all EVM-compatible chains.
+    pub fn evm_chains() -> impl Iterator<Item = Chain> {
+        Self::all().filter(|c| c.is_evm())
+    }
+
+    /// Returns `true` if this is a test network.
+    pub fn is_testnet(&self) -> bool {
+        matches!(
+            self,
+            Chain::EthereumSepolia
+                | Chain::EthereumHolesky
+                | Chain::PolygonAmoyTestnet
+                | Chain::BscTestnet
+                | Chain::AvalancheTestnet
+                | Chain::ArbitrumSepolia
+                | Chain::OptimismSepolia
+                | Chain::BaseSepolia
+                | Chain::FantomTestnet
+                | Chain::GnosisTestnet
+                | Chain::MoonbeamMoonbaseAlpha
+                | Chain::CeloAlfajores
+                | Chain::AuroraTestnet
+                | Chain::LineaTestnet
+                | Chain::ScrollSepoliaTestnet
+                | Chain::ZksyncTestnet
+                | Chain::MantleTestnet
+                | Chain::PolygonZkevmTestnet
+                | Chain::BlastSepolia
+                | Chain::ModeTestnet
+                | Chain::BerachainBartio
+        )
+    }
+
+    /// Returns `true` if this chain uses EVM-style accounts and transactions.
+    pub fn is_evm(&self) -> bool {
+        !matches!(self, Chain::BtcMainnet | Chain::SolanaMainnet)
+    }
+
+    /// The matching streaming API chain, if this chain can be streamed.
+    #[cfg(feature = "streaming")]
+    pub fn streaming(&self) -> Option<crate::models::streaming::StreamingChain> {
+        use crate::models::streaming::StreamingChain;
+        match self {
+            Chain::BaseMainnet => Some(StreamingChain::BaseMainnet),
+            Chain::SolanaMainnet => Some(StreamingChain::SolanaMainnet),
+            Chain::SonicMainnet => Some(StreamingChain::SonicMainnet),
+            Chain::EthereumMainnet => Some(StreamingChain::EthMainnet),
+            Chain::BscMainnet => Some(StreamingChain::BscMainnet),
+            Chain::PolygonMainnet => Some(StreamingChain::PolygonMainnet),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Chain {
@@ -518,4 +650,26 @@ mod tests {
         let deserialized: Chain = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, Chain::EthereumMainnet);
     }
+
+    #[test]
+    fn test_chain_all_round_trips() {
+        let mut seen = std::collections::HashSet::new();
+        for chain in Chain::all() {
+            assert!(seen.insert(chain), "duplicate chain {:?}", chain);
+            assert_eq!(chain.slug().parse::<Chain>().unwrap(), chain);
+        }
+        assert_eq!(seen.len(), Chain::ALL.len());
+    }
+
+    #[test]
+    fn test_chain_grouping() {
+        assert!(Chain::mainnets().any(|c| c == Chain::EthereumMainnet));
+        assert!(Chain::mainnets().all(|c| !c.is_testnet()));
+        assert!(Chain::testnets().any(|c| c == Chain::BaseSepolia));
+        assert_eq!(
+            Chain::mainnets().count() + Chain::testnets().count(),
+            Chain::all().count()
+        );
+        assert!(!Chain::evm_chains().any(|c| c == Chain::BtcMainnet || c == Chain::SolanaMainnet));
+    }
 }
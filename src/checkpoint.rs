@@ -0,0 +1,297 @@
+//! Persisted progress for long-running sync jobs.
+//!
+//! A [`CheckpointStore`] records the last fully processed block per
+//! (chain, address), with EVM addresses lowercased. The resumable walkers,
+//! [`TransactionService::resume_transactions_stream`](crate::TransactionService::resume_transactions_stream),
+//! [`BaseService::resume_log_events_stream`](crate::BaseService::resume_log_events_stream)
+//! and, with the `streaming` feature, `StreamingService::resume_wallet_activity`,
+//! start from the block after the stored checkpoint and advance it as items
+//! are consumed, so a restarted job picks up where it stopped instead of
+//! refetching from genesis.
+//!
+//! A block is checkpointed once the consumer asks for an item past it, so
+//! everything up to the checkpoint has been handed over. Items of a block that
+//! was only partly consumed are fetched again after a restart.
+//!
+//! ```rust,no_run
+//! use futures_util::StreamExt;
+//! use goldrush_sdk::{CheckpointStore, FileCheckpointStore, GoldRushClient};
+//! use std::sync::Arc;
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let store: Arc<dyn CheckpointStore> = Arc::new(FileCheckpointStore::open("checkpoints.json")?);
+//! let mut txs = client.transaction_service()
+//!     .resume_transactions_stream("eth-mainnet", "0x...", None, store)?;
+//! while let Some(tx) = txs.next().await {
+//!     println!("{}", tx?.tx_hash);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::normalize_address;
+use crate::models::base::LogEventItem;
+use crate::models::transactions::TransactionItem;
+use crate::Result;
+use futures_core::stream::{FusedStream, Stream};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// Records the last fully processed block per (chain, address).
+pub trait CheckpointStore: fmt::Debug + Send + Sync {
+    /// Last block processed for `address` on `chain_name`, if any.
+    fn last_block(&self, chain_name: &str, address: &str) -> Result<Option<u64>>;
+
+    /// Record `block` as processed; a checkpoint never moves backwards.
+    fn set_last_block(&self, chain_name: &str, address: &str, block: u64) -> Result<()>;
+}
+
+/// In-memory [`CheckpointStore`]; clones share the same checkpoints.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCheckpointStore {
+    blocks: Arc<Mutex<HashMap<(String, String), u64>>>,
+}
+
+impl MemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    fn last_block(&self, chain_name: &str, address: &str) -> Result<Option<u64>> {
+        Ok(self.blocks.lock().unwrap().get(&(chain_name.to_string(), address.to_string())).copied())
+    }
+
+    fn set_last_block(&self, chain_name: &str, address: &str, block: u64) -> Result<()> {
+        let mut blocks = self.blocks.lock().unwrap();
+        let last = blocks.entry((chain_name.to_string(), address.to_string())).or_insert(block);
+        *last = (*last).max(block);
+        Ok(())
+    }
+}
+
+/// [`CheckpointStore`] kept in a JSON file.
+///
+/// The whole file is rewritten through a temporary file and a rename on each
+/// update, so a crash leaves either the old or the new checkpoints.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+    blocks: Mutex<BTreeMap<String, u64>>,
+}
+
+impl FileCheckpointStore {
+    /// Open the checkpoint file at `path`, starting empty if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let blocks = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, blocks: Mutex::new(blocks) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn key(chain_name: &str, address: &str) -> String {
+        format!("{}/{}", chain_name, address)
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn last_block(&self, chain_name: &str, address: &str) -> Result<Option<u64>> {
+        Ok(self.blocks.lock().unwrap().get(&Self::key(chain_name, address)).copied())
+    }
+
+    fn set_last_block(&self, chain_name: &str, address: &str, block: u64) -> Result<()> {
+        let mut blocks = self.blocks.lock().unwrap();
+        let last = blocks.entry(Self::key(chain_name, address)).or_insert(block);
+        if *last > block {
+            return Ok(());
+        }
+        *last = block;
+
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&*blocks)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Items that belong to a block, in block order within a walk.
+pub trait BlockItem {
+    fn block_height(&self) -> Option<u64>;
+}
+
+impl BlockItem for TransactionItem {
+    fn block_height(&self) -> Option<u64> {
+        self.block_height
+    }
+}
+
+impl BlockItem for LogEventItem {
+    fn block_height(&self) -> Option<u64> {
+        self.block_height
+    }
+}
+
+#[cfg(feature = "streaming")]
+impl BlockItem for crate::models::streaming::WalletActivityEvent {
+    fn block_height(&self) -> Option<u64> {
+        crate::models::streaming::WalletActivityEvent::block_height(self)
+    }
+}
+
+/// Stream that advances a [`CheckpointStore`] as its items are consumed.
+///
+/// Items must arrive in ascending block order. Ends after the first error,
+/// including a failure to save a checkpoint.
+pub struct CheckpointedStream<S> {
+    inner: S,
+    store: Arc<dyn CheckpointStore>,
+    chain_name: String,
+    addresses: Vec<String>,
+    current: Option<u64>,
+    done: bool,
+}
+
+impl<S> CheckpointedStream<S> {
+    /// Checkpoint `inner` for every one of `addresses` on `chain_name`,
+    /// saved under their [`normalize_address`] form.
+    pub fn new(
+        inner: S, store: Arc<dyn CheckpointStore>, chain_name: impl Into<String>, addresses: Vec<String>,
+    ) -> Self {
+        let addresses = addresses.iter().map(|address| normalize_address(address)).collect();
+        Self { inner, store, chain_name: chain_name.into(), addresses, current: None, done: false }
+    }
+
+    /// The block currently being consumed, which is checkpointed once it is complete.
+    pub fn current_block(&self) -> Option<u64> {
+        self.current
+    }
+
+    fn save(&self, block: u64) -> Result<()> {
+        self.addresses
+            .iter()
+            .try_for_each(|address| self.store.set_last_block(&self.chain_name, address, block))
+    }
+}
+
+impl<S, T> Stream for CheckpointedStream<S>
+where
+    S: Stream<Item = Result<T>> + Unpin,
+    T: BlockItem,
+{
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        match Pin::new(&mut this.inner).poll_next(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Some(Ok(item))) => {
+                if let Some(height) = item.block_height() {
+                    match this.current {
+                        // Everything in `current` has been handed over.
+                        Some(current) if height > current => {
+                            if let Err(e) = this.save(current) {
+                                this.done = true;
+                                return Poll::Ready(Some(Err(e)));
+                            }
+                            this.current = Some(height);
+                        }
+                        Some(_) => {}
+                        None => this.current = Some(height),
+                    }
+                }
+                Poll::Ready(Some(Ok(item)))
+            }
+            Poll::Ready(Some(Err(e))) => {
+                this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Ready(None) => {
+                this.done = true;
+                match this.current.map(|block| this.save(block)) {
+                    Some(Err(e)) => Poll::Ready(Some(Err(e))),
+                    _ => Poll::Ready(None),
+                }
+            }
+        }
+    }
+}
+
+impl<S, T> FusedStream for CheckpointedStream<S>
+where
+    S: Stream<Item = Result<T>> + Unpin,
+    T: BlockItem,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[derive(Debug)]
+    struct Item(u64);
+
+    impl BlockItem for Item {
+        fn block_height(&self) -> Option<u64> {
+            Some(self.0)
+        }
+    }
+
+    fn items(blocks: &[u64]) -> impl Stream<Item = Result<Item>> + Unpin {
+        futures_util::stream::iter(blocks.iter().map(|b| Ok(Item(*b))).collect::<Vec<_>>())
+    }
+
+    #[tokio::test]
+    async fn test_checkpoints_completed_blocks() {
+        let store = MemoryCheckpointStore::new();
+        let shared: Arc<dyn CheckpointStore> = Arc::new(store.clone());
+        let mut stream =
+            CheckpointedStream::new(items(&[10, 10, 12, 15]), shared, "eth-mainnet", vec!["0xa".into(), "0xb".into()]);
+
+        stream.next().await.unwrap().unwrap();
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xa").unwrap(), None);
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xa").unwrap(), Some(10));
+        assert_eq!(store.last_block("eth-mainnet", "0xb").unwrap(), Some(10));
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xa").unwrap(), Some(12));
+        assert!(stream.next().await.is_none());
+        assert_eq!(store.last_block("eth-mainnet", "0xa").unwrap(), Some(15));
+
+        store.set_last_block("eth-mainnet", "0xa", 3).unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xa").unwrap(), Some(15));
+    }
+
+    #[test]
+    fn test_file_store_persists() {
+        let path = std::env::temp_dir().join(format!("goldrush-checkpoints-{}.json", uuid::Uuid::new_v4()));
+        let store = FileCheckpointStore::open(&path).unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xa").unwrap(), None);
+        store.set_last_block("eth-mainnet", "0xa", 100).unwrap();
+        store.set_last_block("eth-mainnet", "0xa", 90).unwrap();
+
+        let reopened = FileCheckpointStore::open(&path).unwrap();
+        assert_eq!(reopened.last_block("eth-mainnet", "0xa").unwrap(), Some(100));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
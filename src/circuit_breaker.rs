@@ -1,5 +1,7 @@
-use crate::{Error, Result};
-use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use crate::endpoint::Endpoint;
+use crate::Error;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -187,6 +189,14 @@ impl CircuitBreaker {
         self.recent_failures.write().await.clear();
     }
     
+    /// Time until an open circuit lets a trial request through; zero otherwise.
+    pub async fn retry_after(&self) -> Duration {
+        if *self.state.read().await != CircuitState::Open {
+            return Duration::ZERO;
+        }
+        self.config.timeout.saturating_sub(self.last_state_change.read().await.elapsed())
+    }
+
     /// Check if enough time has passed to attempt reset from open state.
     async fn should_attempt_reset(&self) -> bool {
         let last_state_change = *self.last_state_change.read().await;
@@ -270,6 +280,107 @@ impl CircuitBreakerStats {
     }
 }
 
+/// Error from [`CircuitBreakerExecutor::execute`]: either a rejection by the
+/// open circuit or the operation's own error, unchanged.
+#[derive(Debug)]
+pub enum CircuitError<E> {
+    /// The circuit is open; the operation was not run.
+    Open { retry_after: Duration },
+    /// The operation ran and failed.
+    Failed(E),
+}
+
+impl<E> CircuitError<E> {
+    pub fn is_open(&self) -> bool {
+        matches!(self, CircuitError::Open { .. })
+    }
+
+    /// The operation's error, if it ran.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            CircuitError::Open { .. } => None,
+            CircuitError::Failed(e) => Some(e),
+        }
+    }
+
+    /// Convert the operation's error, keeping rejections.
+    pub fn map<F, U>(self, f: F) -> CircuitError<U>
+    where
+        F: FnOnce(E) -> U,
+    {
+        match self {
+            CircuitError::Open { retry_after } => CircuitError::Open { retry_after },
+            CircuitError::Failed(e) => CircuitError::Failed(f(e)),
+        }
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for CircuitError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Open { retry_after } => write!(f, "circuit breaker open, retry in {:?}", retry_after),
+            CircuitError::Failed(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for CircuitError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CircuitError::Open { .. } => None,
+            CircuitError::Failed(e) => Some(e),
+        }
+    }
+}
+
+/// SDK errors pass through unchanged; rejections become [`Error::CircuitOpen`].
+impl From<CircuitError<Error>> for Error {
+    fn from(error: CircuitError<Error>) -> Self {
+        match error {
+            CircuitError::Open { retry_after } => Error::CircuitOpen { retry_after },
+            CircuitError::Failed(e) => e,
+        }
+    }
+}
+
+/// Future returned by [`FallbackProvider::fallback`].
+pub type FallbackFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = Option<T>> + Send + 'a>>;
+
+/// Alternative data source consulted when the circuit breaker rejects a request.
+///
+/// Implementations might serve last-known cached data or query a
+/// user-supplied RPC node. Returning `None` surfaces the original rejection.
+pub trait FallbackProvider<T>: Send + Sync {
+    /// Produce a value in place of the rejected request.
+    fn fallback<'a>(&'a self, rejection: &'a Error) -> FallbackFuture<'a, T>;
+
+    /// Observe a successful result, e.g. to remember it for later fallbacks.
+    fn record(&self, _value: &T) {}
+}
+
+/// Fallback that serves the most recent successful result.
+#[derive(Debug, Default)]
+pub struct LastKnownGood<T> {
+    value: std::sync::Mutex<Option<T>>,
+}
+
+impl<T> LastKnownGood<T> {
+    pub fn new() -> Self {
+        Self { value: std::sync::Mutex::new(None) }
+    }
+}
+
+impl<T: Clone + Send> FallbackProvider<T> for LastKnownGood<T> {
+    fn fallback<'a>(&'a self, _rejection: &'a Error) -> FallbackFuture<'a, T> {
+        let value = self.value.lock().unwrap().clone();
+        Box::pin(async move { value })
+    }
+
+    fn record(&self, value: &T) {
+        *self.value.lock().unwrap() = Some(value.clone());
+    }
+}
+
 /// Wrapper for executing operations through a circuit breaker.
 pub struct CircuitBreakerExecutor {
     circuit_breaker: Arc<CircuitBreaker>,
@@ -283,18 +394,20 @@ impl CircuitBreakerExecutor {
     }
     
     /// Execute an operation through the circuit breaker.
+    ///
+    /// The operation's error is returned unchanged in [`CircuitError::Failed`];
+    /// with SDK operations, `?` converts the result back into [`Error`], with
+    /// rejections surfacing as [`Error::CircuitOpen`].
     #[instrument(skip(self, operation))]
-    pub async fn execute<F, T, E>(&self, operation: F) -> Result<T>
+    pub async fn execute<F, T, E>(&self, operation: F) -> std::result::Result<T, CircuitError<E>>
     where
         F: std::future::Future<Output = std::result::Result<T, E>>,
-        E: std::fmt::Display + std::error::Error + Send + Sync + 'static,
+        E: std::fmt::Display,
     {
-        // Check if request can proceed
         if !self.circuit_breaker.can_proceed().await {
-            return Err(Error::Config("Circuit breaker is open, request rejected".to_string()));
+            return Err(self.rejection().await);
         }
-        
-        // Execute the operation
+
         match operation.await {
             Ok(result) => {
                 self.circuit_breaker.record_success().await;
@@ -302,11 +415,52 @@ impl CircuitBreakerExecutor {
             }
             Err(error) => {
                 self.circuit_breaker.record_failure(&error).await;
-                Err(Error::Config(format!("Operation failed: {}", error)))
+                Err(CircuitError::Failed(error))
             }
         }
     }
     
+    /// Execute an operation, consulting `fallback` if the circuit is open.
+    ///
+    /// Successful results are passed to [`FallbackProvider::record`]. Failures
+    /// of the operation itself are returned as-is; only rejections by the open
+    /// circuit are routed to the fallback.
+    #[instrument(skip(self, operation, fallback))]
+    pub async fn execute_with_fallback<F, T, E, P>(
+        &self,
+        operation: F,
+        fallback: &P,
+    ) -> std::result::Result<T, CircuitError<E>>
+    where
+        F: std::future::Future<Output = std::result::Result<T, E>>,
+        E: std::fmt::Display,
+        P: FallbackProvider<T> + ?Sized,
+    {
+        if !self.circuit_breaker.can_proceed().await {
+            let retry_after = self.circuit_breaker.retry_after().await;
+            return match fallback.fallback(&Error::CircuitOpen { retry_after }).await {
+                Some(value) => {
+                    info!("Circuit open, served request from fallback");
+                    Ok(value)
+                }
+                None => Err(CircuitError::Open { retry_after }),
+            };
+        }
+
+        let result = self.execute(operation).await?;
+        fallback.record(&result);
+        Ok(result)
+    }
+
+    /// The breaker itself, for callers that record outcomes by hand.
+    pub(crate) fn breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+
+    async fn rejection<E>(&self) -> CircuitError<E> {
+        CircuitError::Open { retry_after: self.circuit_breaker.retry_after().await }
+    }
+
     /// Get circuit breaker statistics.
     pub async fn stats(&self) -> CircuitBreakerStats {
         self.circuit_breaker.stats().await
@@ -318,9 +472,54 @@ impl CircuitBreakerExecutor {
     }
 }
 
+/// One circuit breaker per [`Endpoint`], created on first use.
+///
+/// Failures of one endpoint (say, a degraded NFT backend) open only that
+/// endpoint's circuit; the rest of the API stays reachable.
+pub struct EndpointCircuitBreakers {
+    config: CircuitBreakerConfig,
+    breakers: std::sync::Mutex<HashMap<Endpoint, Arc<CircuitBreakerExecutor>>>,
+}
+
+impl EndpointCircuitBreakers {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The breaker guarding `endpoint`.
+    pub fn for_endpoint(&self, endpoint: Endpoint) -> Arc<CircuitBreakerExecutor> {
+        let mut breakers = self.breakers.lock().unwrap();
+        Arc::clone(
+            breakers
+                .entry(endpoint)
+                .or_insert_with(|| Arc::new(CircuitBreakerExecutor::new(self.config.clone()))),
+        )
+    }
+
+    /// Statistics for every endpoint that has a breaker.
+    pub async fn stats(&self) -> HashMap<Endpoint, CircuitBreakerStats> {
+        let breakers: Vec<_> = self
+            .breakers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(endpoint, breaker)| (*endpoint, Arc::clone(breaker)))
+            .collect();
+        let mut stats = HashMap::new();
+        for (endpoint, breaker) in breakers {
+            stats.insert(endpoint, breaker.stats().await);
+        }
+        stats
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Result;
     use tokio::time::{sleep, Duration};
     
     #[tokio::test]
@@ -355,4 +554,75 @@ mod tests {
         let stats = cb.stats().await;
         assert_eq!(stats.state, CircuitState::Closed);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_fallback_serves_last_known_good_when_open() {
+        let executor = CircuitBreakerExecutor::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout: Duration::from_secs(60),
+            ..Default::default()
+        });
+        let fallback = LastKnownGood::new();
+
+        let fresh = executor
+            .execute_with_fallback(async { Ok::<_, std::io::Error>(42) }, &fallback)
+            .await
+            .unwrap();
+        assert_eq!(fresh, 42);
+
+        let failed = executor
+            .execute_with_fallback(async { Err::<u32, _>(std::io::Error::other("down")) }, &fallback)
+            .await;
+        assert_eq!(failed.unwrap_err().into_inner().unwrap().to_string(), "down");
+
+        // Circuit is now open: the operation is not run and the last good value is served.
+        let served = executor
+            .execute_with_fallback(async { Ok::<_, std::io::Error>(0) }, &fallback)
+            .await
+            .unwrap();
+        assert_eq!(served, 42);
+
+        let empty: LastKnownGood<u32> = LastKnownGood::new();
+        assert!(executor.execute_with_fallback(async { Ok::<_, std::io::Error>(1) }, &empty).await.unwrap_err().is_open());
+    }
+
+    #[tokio::test]
+    async fn test_execute_preserves_sdk_errors() {
+        let executor = CircuitBreakerExecutor::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout: Duration::from_secs(60),
+            ..Default::default()
+        });
+
+        async fn call(executor: &CircuitBreakerExecutor) -> Result<u32> {
+            let value = executor
+                .execute(async { Err::<u32, _>(Error::api(503, "down".to_string(), None)) })
+                .await?;
+            Ok(value)
+        }
+
+        assert!(matches!(call(&executor).await, Err(Error::Api { status: 503, .. })));
+        match call(&executor).await {
+            Err(Error::CircuitOpen { retry_after }) => {
+                assert!(retry_after > Duration::from_secs(59) && retry_after <= Duration::from_secs(60));
+            }
+            other => panic!("expected CircuitOpen, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_breakers_are_independent() {
+        let breakers = EndpointCircuitBreakers::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout: Duration::from_secs(60),
+            ..Default::default()
+        });
+
+        let nft = breakers.for_endpoint(Endpoint::NftMetadata);
+        let _ = nft.execute(async { Err::<(), _>(std::io::Error::other("down")) }).await;
+
+        assert!(breakers.for_endpoint(Endpoint::NftMetadata).execute(async { Ok::<_, std::io::Error>(()) }).await.is_err());
+        assert!(breakers.for_endpoint(Endpoint::BalancesV2).execute(async { Ok::<_, std::io::Error>(()) }).await.is_ok());
+        assert_eq!(breakers.stats().await[&Endpoint::NftMetadata].state, CircuitState::Open);
+    }
+}
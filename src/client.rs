@@ -1,9 +1,21 @@
-use crate::services::{ServiceContext, BalanceService, TransactionService, NftService, BaseService, PricingService, SecurityService, BitcoinService, AllChainsService};
-use crate::{Error, RateLimitConfig, CacheConfig, MetricsCollector, validation::Validator};
+use crate::services::base_service::StatusCache;
+use crate::services::{ServiceContext, BalanceService, TransactionService, NftService, BaseService, PricingService, SecurityService, BitcoinService, AllChainsService, SolanaService};
+use crate::clock::{default_clock, Clock};
+use crate::{ApiVersion, AuditSink, ScreeningProvider, WarmUpConfig, WarmUpReport, CancellationToken, ChainRegistry, MoneyHook, Error, SecurityConfig, SecurityManager, Endpoint, EndpointRateLimit, RateLimitConfig, CacheConfig, CircuitBreakerConfig, CircuitBreakerStats, EndpointCircuitBreakers, CachePolicy, MetricsCollector, CreditEstimator, validation::Validator};
 use reqwest::Client as HttpClient;
+use crate::http::request::{to_header_map, RequestOptions};
+use crate::lifecycle::{Lifecycle, ShutdownReport};
+use crate::rate_limit::RequestLimiter;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Default SDK user agent, `goldrush-sdk-rs/<version>`.
+pub(crate) fn sdk_user_agent() -> String {
+    format!("goldrush-sdk-rs/{}", env!("CARGO_PKG_VERSION"))
+}
+
 /// Configuration options for the GoldRush client.
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
@@ -19,6 +31,9 @@ pub struct ClientConfig {
     /// User agent string for requests.
     pub user_agent: String,
 
+    /// Optional application identifier (`name/version`) appended to the user agent.
+    pub app_info: Option<String>,
+
     /// Rate limiting configuration.
     pub rate_limit: RateLimitConfig,
 
@@ -36,6 +51,45 @@ pub struct ClientConfig {
 
     /// Keep-alive timeout for connections.
     pub keep_alive_timeout: Duration,
+
+    /// Known chains, including any custom chains registered at runtime.
+    pub chains: ChainRegistry,
+
+    /// Extra headers sent with every request; they override SDK defaults.
+    pub default_headers: HashMap<String, String>,
+
+    /// Security settings (request size limits, header generation).
+    pub security: SecurityConfig,
+
+    /// Keep the exact response body on each response's `ResponseMeta`.
+    pub keep_raw_body: bool,
+
+    /// Version used for endpoints available in both v2 and v3 flavors.
+    pub api_version: ApiVersion,
+
+    /// How cached endpoints use the cache unless a call overrides it.
+    pub cache_policy: CachePolicy,
+
+    /// Conversion applied to monetary fields before responses are deserialized.
+    pub money_hook: Option<Arc<dyn MoneyHook>>,
+
+    /// Receives an audit entry for every REST call.
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+
+    /// Screens the wallet address of every REST call before it is sent.
+    pub screening: Option<Arc<dyn ScreeningProvider>>,
+
+    /// What [`GoldRushClient::warm_up`] does.
+    pub warm_up: WarmUpConfig,
+
+    /// Time source for retry backoff, cache expiry and polling.
+    pub clock: Arc<dyn Clock>,
+
+    /// Fail decoding on enum values the SDK doesn't know instead of keeping them as `Unknown`.
+    pub strict_enums: bool,
+
+    /// Per-endpoint circuit breakers guarding REST calls; off when `None`.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl Default for ClientConfig {
@@ -44,13 +98,27 @@ impl Default for ClientConfig {
             base_url: "https://api.covalenthq.com".to_string(),
             timeout: Duration::from_secs(30),
             max_retries: 3,
-            user_agent: format!("goldrush-sdk-rs/{}", env!("CARGO_PKG_VERSION")),
+            user_agent: sdk_user_agent(),
+            app_info: None,
             rate_limit: RateLimitConfig::default(),
             cache: CacheConfig::default(),
             enable_logging: true,
             enable_metrics: true,
             connection_pool_size: 10,
             keep_alive_timeout: Duration::from_secs(90),
+            chains: ChainRegistry::builtin(),
+            default_headers: HashMap::new(),
+            security: SecurityConfig::default(),
+            keep_raw_body: false,
+            api_version: ApiVersion::default(),
+            cache_policy: CachePolicy::default(),
+            money_hook: None,
+            audit_sink: None,
+            screening: None,
+            warm_up: WarmUpConfig::default(),
+            clock: default_clock(),
+            strict_enums: false,
+            circuit_breaker: None,
         }
     }
 }
@@ -81,6 +149,122 @@ impl ClientConfig {
         self.user_agent = user_agent.into();
         self
     }
+
+    /// Tag requests with an application name and version, sent as
+    /// `goldrush-sdk-rs/<sdk version> <name>/<version>`.
+    pub fn with_app_info(mut self, name: impl AsRef<str>, version: impl AsRef<str>) -> Self {
+        self.app_info = Some(format!("{}/{}", name.as_ref().trim(), version.as_ref().trim()));
+        self
+    }
+
+    /// The full user agent sent with each request, including any app identifier.
+    pub fn full_user_agent(&self) -> String {
+        match &self.app_info {
+            Some(app) => format!("{} {}", self.user_agent, app),
+            None => self.user_agent.clone(),
+        }
+    }
+
+    /// Keep the exact JSON body returned by the API alongside the parsed model,
+    /// for archival or audit pipelines. Off by default since it doubles memory use.
+    pub fn with_raw_body(mut self, keep: bool) -> Self {
+        self.keep_raw_body = keep;
+        self
+    }
+
+    /// Select the version of endpoints that exist as both v2 and v3.
+    ///
+    /// Override it for individual calls with [`RequestOptions::api_version`](crate::RequestOptions::api_version).
+    pub fn with_api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+        self
+    }
+
+    /// Set the default cache policy.
+    ///
+    /// Override it for individual calls with [`RequestOptions::cache_policy`](crate::RequestOptions::cache_policy).
+    pub fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+
+    /// Run every monetary field of REST responses through `hook`
+    /// (e.g. a [`MoneyPolicy`](crate::MoneyPolicy)) before deserialization.
+    pub fn with_money_hook(mut self, hook: impl MoneyHook + 'static) -> Self {
+        self.money_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Report every REST call to `sink` (e.g. an [`AuditLog`](crate::AuditLog)),
+    /// with the endpoint, chain and address it fetched.
+    pub fn with_audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Consult `provider` before every REST call about a wallet address; it
+    /// may block the call or flag it. See [`screening`](crate::screening).
+    pub fn with_screening(mut self, provider: impl ScreeningProvider + 'static) -> Self {
+        self.screening = Some(Arc::new(provider));
+        self
+    }
+
+    /// Configure DNS resolution and keepalive for [`GoldRushClient::warm_up`].
+    pub fn with_warm_up(mut self, warm_up: WarmUpConfig) -> Self {
+        self.warm_up = warm_up;
+        self
+    }
+
+    /// Measure and wait for time with `clock`, e.g. a [`MockClock`](crate::MockClock) in tests.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Limit the rate of requests this client sends.
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Limit requests to `endpoint` to `limit`, on top of the client-wide limit.
+    pub fn with_endpoint_rate_limit(mut self, endpoint: Endpoint, limit: EndpointRateLimit) -> Self {
+        self.rate_limit.endpoint_limits.insert(endpoint, limit);
+        self
+    }
+
+    /// Guard each endpoint with a circuit breaker. After repeated transport
+    /// errors or 5xx responses, calls to the endpoint fail at once with
+    /// [`Error::CircuitOpen`] until the breaker's timeout passes.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Reject responses carrying an enum value the SDK doesn't know, such as
+    /// a new `transfer_type`, instead of decoding it as `Unknown(..)`.
+    pub fn with_strict_enums(mut self, strict: bool) -> Self {
+        self.strict_enums = strict;
+        self
+    }
+
+    /// Add a header sent with every request.
+    pub fn with_default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Replace the chain registry (e.g. one loaded from `chains.toml`).
+    pub fn with_chain_registry(mut self, chains: ChainRegistry) -> Self {
+        self.chains = chains;
+        self
+    }
+
+    /// Register an additional custom chain.
+    pub fn with_chain(mut self, chain: crate::ChainInfo) -> Self {
+        self.chains.register(chain);
+        self
+    }
 }
 
 /// The main GoldRush client for interacting with the API.
@@ -105,6 +289,7 @@ impl ClientConfig {
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct GoldRushClient {
     ctx: Arc<ServiceContext>,
 }
@@ -116,26 +301,48 @@ impl GoldRushClient {
 
         Validator::validate_api_key(&api_key)?;
         Validator::validate_url(&config.base_url)?;
+        if let Some(app) = &config.app_info {
+            Validator::validate_app_info(app)?;
+        }
+        config.rate_limit.validate()?;
 
         let http = HttpClient::builder()
-            .user_agent(&config.user_agent)
+            .user_agent(config.full_user_agent())
             .timeout(config.timeout)
             .pool_max_idle_per_host(config.connection_pool_size)
             .pool_idle_timeout(config.keep_alive_timeout)
             .tcp_keepalive(Some(Duration::from_secs(60)))
             .build()?;
 
+        let headers = to_header_map(
+            config.default_headers.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+        )?;
+
+        let config_security = config.security.clone();
+
         let metrics = if config.enable_metrics {
             Some(Arc::new(MetricsCollector::new()))
         } else {
             None
         };
 
+        let status_cache = Arc::new(StatusCache::new(&config.cache, &config.clock));
+        let rate_limiter = Arc::new(RequestLimiter::new(&config.rate_limit, &config.clock));
+        let circuit_breakers = config.circuit_breaker.clone().map(|c| Arc::new(EndpointCircuitBreakers::new(c)));
         let ctx = Arc::new(ServiceContext {
             http,
             api_key,
             config,
             metrics,
+            headers,
+            security: Arc::new(SecurityManager::new(config_security)),
+            status_cache,
+            cancellation: None,
+            lifecycle: Arc::new(Lifecycle::new()),
+            credits: Arc::new(CreditEstimator::new()),
+            keepalive: Arc::new(AtomicBool::new(false)),
+            rate_limiter,
+            circuit_breakers,
         });
 
         Ok(Self { ctx })
@@ -146,11 +353,88 @@ impl GoldRushClient {
         Self::new(api_key, ClientConfig::default())
     }
 
+    /// A client whose every service applies `options`, e.g. a
+    /// [`CancellationToken`](crate::CancellationToken) tied to an incoming request.
+    ///
+    /// Shares the connection pool, metrics and caches of this client.
+    pub fn with_request_options(&self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
+    /// Stop accepting requests, close streaming connections and wait up to
+    /// `timeout` for in-flight requests and background tasks to finish.
+    ///
+    /// Anything still running at the timeout is aborted. Applies to every
+    /// clone made with [`with_request_options`](Self::with_request_options)
+    /// and to all service handles, which fail with [`Error::ShuttingDown`]
+    /// afterwards.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.ctx.lifecycle.shutdown(timeout).await
+    }
+
+    /// Resolve DNS and open a connection to the base URL ahead of the first
+    /// real call, then start keepalive requests if
+    /// [`WarmUpConfig::keepalive_interval`] is set.
+    ///
+    /// ```rust,no_run
+    /// use goldrush_sdk::{ClientConfig, GoldRushClient, WarmUpConfig};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), goldrush_sdk::Error> {
+    /// let config = ClientConfig::default()
+    ///     .with_warm_up(WarmUpConfig::new().keepalive_interval(Duration::from_secs(45)));
+    /// let client = GoldRushClient::new("cqt_your_api_key_here", config)?;
+    /// let report = client.warm_up().await?;
+    /// println!("connected in {:?}", report.request);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_up(&self) -> Result<WarmUpReport, Error> {
+        crate::warm_up::warm_up(&self.ctx).await
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.ctx.lifecycle.is_closing()
+    }
+
+    /// Run a background task (reporter, watcher, refresher, ...) that
+    /// [`shutdown`](Self::shutdown) waits for.
+    ///
+    /// The task receives a token that is cancelled when shutdown starts and
+    /// should return promptly once it is.
+    pub fn spawn_background<F, Fut>(&self, task: F) -> Result<(), Error>
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.ctx.lifecycle.spawn(task)
+    }
+
     /// Get access to the metrics collector (if enabled).
     pub fn metrics(&self) -> Option<&Arc<MetricsCollector>> {
         self.ctx.metrics.as_ref()
     }
 
+    /// State of each endpoint's circuit breaker; empty unless
+    /// [`ClientConfig::with_circuit_breaker`] is set or before any call.
+    pub async fn circuit_breaker_stats(&self) -> HashMap<Endpoint, CircuitBreakerStats> {
+        match &self.ctx.circuit_breakers {
+            Some(breakers) => breakers.stats().await,
+            None => HashMap::new(),
+        }
+    }
+
+    /// Estimated credit usage of this client and every handle derived from it.
+    pub fn credits(&self) -> &Arc<CreditEstimator> {
+        &self.ctx.credits
+    }
+
+    /// Chains known to this client, including custom registered chains.
+    pub fn chains(&self) -> &ChainRegistry {
+        &self.ctx.config.chains
+    }
+
     /// Access balance-related endpoints.
     pub fn balance_service(&self) -> BalanceService {
         BalanceService::new(Arc::clone(&self.ctx))
@@ -186,6 +470,11 @@ impl GoldRushClient {
         BitcoinService::new(Arc::clone(&self.ctx))
     }
 
+    /// Access Solana-specific endpoints.
+    pub fn solana_service(&self) -> SolanaService {
+        SolanaService::new(Arc::clone(&self.ctx))
+    }
+
     /// Access cross-chain endpoints.
     pub fn all_chains_service(&self) -> AllChainsService {
         AllChainsService::new(Arc::clone(&self.ctx))
@@ -214,10 +503,7 @@ impl GoldRushClient {
     /// ```
     #[cfg(feature = "streaming")]
     pub fn streaming_service(&self) -> crate::services::StreamingService {
-        crate::services::StreamingService::new(
-            self.ctx.api_key.clone(),
-            crate::streaming::StreamingConfig::default(),
-        )
+        self.streaming_service_with_config(crate::streaming::StreamingConfig::default())
     }
 
     /// Access streaming endpoints with custom configuration.
@@ -244,6 +530,9 @@ impl GoldRushClient {
         &self,
         config: crate::streaming::StreamingConfig,
     ) -> crate::services::StreamingService {
-        crate::services::StreamingService::new(self.ctx.api_key.clone(), config)
+        let service = crate::services::StreamingService::new(self.ctx.api_key.clone(), config)
+            .with_transactions(self.transaction_service());
+        self.ctx.lifecycle.track_streaming(service.connection_slot());
+        service
     }
 }
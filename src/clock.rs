@@ -0,0 +1,108 @@
+//! Time source for retry backoff, cache expiry, rate limiting and polling.
+//!
+//! Everything in the SDK that waits or checks elapsed time goes through a
+//! [`Clock`], set with [`ClientConfig::with_clock`](crate::ClientConfig::with_clock).
+//! The default [`TokioClock`] reads tokio's clock, so tests can also use
+//! `tokio::time::pause`. [`MockClock`] makes waiting free: each sleep moves
+//! the clock forward and returns at once, so retries and TTLs can be tested
+//! deterministically and without delay.
+//!
+//! ```rust
+//! use goldrush_sdk::{Clock, MockClock};
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let clock = MockClock::new();
+//! let start = clock.now();
+//! clock.sleep(Duration::from_secs(30)).await;
+//! assert_eq!(clock.now() - start, Duration::from_secs(30));
+//! assert_eq!(clock.sleeps(), [Duration::from_secs(30)]);
+//! # }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Future returned by [`Clock::sleep`].
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Source of the current time and of delays.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// The current instant.
+    fn now(&self) -> Instant;
+
+    /// Wait for `duration`.
+    fn sleep(&self, duration: Duration) -> Sleep;
+}
+
+/// The tokio clock, which follows `tokio::time::pause` and `advance`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: Instant,
+    sleeps: Vec<Duration>,
+}
+
+/// A clock that only moves when told to or when slept on.
+///
+/// Sleeping advances the clock by the requested duration and completes
+/// immediately. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self { state: Arc::new(Mutex::new(MockState { now: Instant::now(), sleeps: Vec::new() })) }
+    }
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.state.lock().unwrap().now += duration;
+    }
+
+    /// Every duration slept so far, in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.state.lock().unwrap().sleeps.clone()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let mut state = self.state.lock().unwrap();
+        state.now += duration;
+        state.sleeps.push(duration);
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// The clock used when none is configured.
+pub(crate) fn default_clock() -> Arc<dyn Clock> {
+    Arc::new(TokioClock)
+}
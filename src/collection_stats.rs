@@ -0,0 +1,180 @@
+//! Period-over-period statistics for an NFT collection.
+//!
+//! Rolls the daily floor price, volume and sales count series of a collection
+//! into two windows of `days` each: the current one, ending on the most recent
+//! date any series reports, and the one before it. Each metric carries its
+//! value in both windows and the change between them.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{Chain, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let stats = client.nft_service().collection_stats(Chain::EthereumMainnet, "0x...", 7).await?;
+//! println!("7d volume {:.0} ({:+.1?}%)", stats.volume.current, stats.volume.change_pct());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::activity::CalendarDate;
+use crate::models::nfts::{FloorPriceItem, FloorPricesData, SalesCountData, VolumeData};
+
+/// One metric in the current and previous window.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MetricChange {
+    pub current: f64,
+    /// `None` when the series has no data in the previous window.
+    pub previous: Option<f64>,
+}
+
+impl MetricChange {
+    /// `current - previous`.
+    pub fn change(&self) -> Option<f64> {
+        self.previous.map(|previous| self.current - previous)
+    }
+
+    /// Change relative to the previous window, in percent; `None` when that was zero.
+    pub fn change_pct(&self) -> Option<f64> {
+        self.previous.filter(|p| *p != 0.0).map(|previous| (self.current - previous) / previous * 100.0)
+    }
+}
+
+/// Floor price, volume and sales of a collection over the last `days`,
+/// compared with the `days` before.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionStats {
+    pub chain_name: Option<String>,
+    pub contract_address: String,
+    pub days: u32,
+    /// Last day of the current window; `None` when no series had dated items.
+    pub as_of: Option<CalendarDate>,
+    /// Floor price on the last reported day of each window.
+    pub floor_price: MetricChange,
+    /// Floor price in the chain's native token, on the last reported day of each window.
+    pub floor_price_native: MetricChange,
+    /// Total volume traded in each window.
+    pub volume: MetricChange,
+    /// Total sales in each window.
+    pub sales: MetricChange,
+}
+
+/// Which window a date falls in.
+#[derive(Clone, Copy, PartialEq)]
+enum Window {
+    Current,
+    Previous,
+}
+
+impl CollectionStats {
+    /// Roll up daily series covering at least `2 * days`.
+    pub fn build(
+        contract_address: impl Into<String>,
+        days: u32,
+        floor: &FloorPricesData,
+        volume: &VolumeData,
+        sales: &SalesCountData,
+    ) -> Self {
+        let dates = floor.items.iter().map(|i| &i.date)
+            .chain(volume.items.iter().map(|i| &i.date))
+            .chain(sales.items.iter().map(|i| &i.date));
+        let as_of = dates.filter_map(|d| d.as_deref().and_then(CalendarDate::parse)).max();
+
+        let window = |date: &Option<String>| {
+            let (as_of, date) = (as_of?, CalendarDate::parse(date.as_deref()?)?);
+            let current_start = as_of.add_days(1 - days as i64);
+            let previous_start = current_start.add_days(-(days as i64));
+            if date > as_of || date < previous_start {
+                None
+            } else if date >= current_start {
+                Some(Window::Current)
+            } else {
+                Some(Window::Previous)
+            }
+        };
+
+        let floor_series = |value: fn(&FloorPriceItem) -> Option<f64>| {
+            latest(floor.items.iter().filter_map(|i| {
+                Some((CalendarDate::parse(i.date.as_deref()?)?, window(&i.date)?, value(i)?))
+            }))
+        };
+
+        Self {
+            chain_name: floor.chain_name.clone()
+                .or_else(|| volume.chain_name.clone())
+                .or_else(|| sales.chain_name.clone()),
+            contract_address: contract_address.into(),
+            days,
+            as_of,
+            floor_price: floor_series(|i| i.floor_price_quote),
+            floor_price_native: floor_series(|i| i.floor_price_native_quote),
+            volume: total(volume.items.iter().filter_map(|i| Some((window(&i.date)?, i.volume_quote?)))),
+            sales: total(sales.items.iter().filter_map(|i| Some((window(&i.date)?, i.sale_count? as f64)))),
+        }
+    }
+}
+
+/// Value on the latest date of each window.
+fn latest(values: impl Iterator<Item = (CalendarDate, Window, f64)>) -> MetricChange {
+    let mut current: Option<(CalendarDate, f64)> = None;
+    let mut previous: Option<(CalendarDate, f64)> = None;
+    for (date, window, value) in values {
+        let slot = if window == Window::Current { &mut current } else { &mut previous };
+        if slot.map_or(true, |(d, _)| date > d) {
+            *slot = Some((date, value));
+        }
+    }
+    MetricChange { current: current.map_or(0.0, |(_, v)| v), previous: previous.map(|(_, v)| v) }
+}
+
+/// Sum over each window.
+fn total(values: impl Iterator<Item = (Window, f64)>) -> MetricChange {
+    let mut change = MetricChange::default();
+    for (window, value) in values {
+        match window {
+            Window::Current => change.current += value,
+            Window::Previous => *change.previous.get_or_insert(0.0) += value,
+        }
+    }
+    change
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_splits_windows() {
+        let floor: FloorPricesData = serde_json::from_value(json!({
+            "chain_name": "eth-mainnet",
+            "items": [
+                { "date": "2024-01-04", "floor_price_quote": 12.0, "floor_price_native_quote": 0.006 },
+                { "date": "2024-01-03", "floor_price_quote": 11.0 },
+                { "date": "2024-01-02", "floor_price_quote": 10.0, "floor_price_native_quote": 0.005 },
+                { "date": "2024-01-01", "floor_price_quote": 9.0 },
+            ],
+        })).unwrap();
+        let volume: VolumeData = serde_json::from_value(json!({
+            "items": [
+                { "date": "2024-01-04", "volume_quote": 100.0 },
+                { "date": "2024-01-03", "volume_quote": 50.0 },
+                { "date": "2024-01-02", "volume_quote": 75.0 },
+                { "date": "2024-01-01", "volume_quote": 25.0 },
+                { "date": "2023-12-31", "volume_quote": 1000.0 },
+            ],
+        })).unwrap();
+        let sales: SalesCountData = serde_json::from_value(json!({
+            "items": [{ "date": "2024-01-04", "sale_count": 3 }, { "date": "2024-01-03", "sale_count": 2 }],
+        })).unwrap();
+
+        let stats = CollectionStats::build("0xabc", 2, &floor, &volume, &sales);
+        assert_eq!(stats.chain_name.as_deref(), Some("eth-mainnet"));
+        assert_eq!(stats.as_of, CalendarDate::from_ymd(2024, 1, 4));
+        assert_eq!(stats.floor_price, MetricChange { current: 12.0, previous: Some(10.0) });
+        assert_eq!(stats.floor_price.change_pct(), Some(20.0));
+        assert_eq!(stats.floor_price_native, MetricChange { current: 0.006, previous: Some(0.005) });
+        assert_eq!(stats.volume, MetricChange { current: 150.0, previous: Some(100.0) });
+        assert_eq!(stats.volume.change(), Some(50.0));
+        assert_eq!(stats.sales, MetricChange { current: 5.0, previous: None });
+        assert_eq!(stats.sales.change_pct(), None);
+    }
+}
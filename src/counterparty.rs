@@ -0,0 +1,192 @@
+//! Counterparties of a wallet, ranked by how often it dealt with them.
+//!
+//! [`TransactionService::counterparty_report`](crate::TransactionService::counterparty_report)
+//! folds a wallet's whole transaction history into one [`Counterparty`] per
+//! address it sent to or received from, one page at a time, so memory grows
+//! with the number of counterparties rather than transactions.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{Chain, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let report = client.transaction_service()
+//!     .counterparty_report(Chain::EthereumMainnet, "0x...", None)
+//!     .await?;
+//! for c in report.counterparties.iter().take(10) {
+//!     println!("{} x{}: in {:.2} out {:.2}", c.name(), c.tx_count, c.value_in_quote, c.value_out_quote);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::{normalize_address, same_address};
+use crate::models::transactions::TransactionItem;
+use std::collections::HashMap;
+
+/// Everything a wallet exchanged with one address.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Counterparty {
+    pub address: String,
+    /// Label reported by the API, e.g. a protocol or exchange name.
+    pub label: Option<String>,
+    pub tx_count: u64,
+    /// Transactions sent by the counterparty to the wallet.
+    pub received_count: u64,
+    /// Transactions sent by the wallet to the counterparty.
+    pub sent_count: u64,
+    /// Native value received from the counterparty, in wei.
+    pub value_in: u128,
+    /// Native value sent to the counterparty, in wei.
+    pub value_out: u128,
+    pub value_in_quote: f64,
+    pub value_out_quote: f64,
+    pub first_interaction: Option<String>,
+    pub last_interaction: Option<String>,
+}
+
+impl Counterparty {
+    /// The label, or the address when there is none.
+    pub fn name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.address)
+    }
+
+    /// Quote value moved in both directions.
+    pub fn total_value_quote(&self) -> f64 {
+        self.value_in_quote + self.value_out_quote
+    }
+
+    fn record(&mut self, tx: &TransactionItem, incoming: bool) {
+        let value: u128 = tx.value.trim().parse().unwrap_or(0);
+        let quote = tx.value_quote.unwrap_or(0.0);
+        self.tx_count += 1;
+        if incoming {
+            self.received_count += 1;
+            self.value_in = self.value_in.saturating_add(value);
+            self.value_in_quote += quote;
+        } else {
+            self.sent_count += 1;
+            self.value_out = self.value_out.saturating_add(value);
+            self.value_out_quote += quote;
+        }
+        if let Some(at) = &tx.block_signed_at {
+            if self.first_interaction.as_ref().map_or(true, |first| at < first) {
+                self.first_interaction = Some(at.clone());
+            }
+            if self.last_interaction.as_ref().map_or(true, |last| at > last) {
+                self.last_interaction = Some(at.clone());
+            }
+        }
+    }
+}
+
+/// A wallet's counterparties on one chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterpartyReport {
+    pub address: String,
+    pub chain_name: String,
+    /// Transactions scanned, including ones without a counterparty.
+    pub transactions: u64,
+    /// Ranked by transaction count, then by total quote value.
+    pub counterparties: Vec<Counterparty>,
+}
+
+impl CounterpartyReport {
+    /// Rank the counterparties of `address` in `transactions`.
+    pub fn build<'a>(
+        chain_name: impl Into<String>,
+        address: impl Into<String>,
+        transactions: impl IntoIterator<Item = &'a TransactionItem>,
+    ) -> Self {
+        let mut counterparties = Counterparties::new(chain_name, address);
+        transactions.into_iter().for_each(|tx| counterparties.record(tx));
+        counterparties.into_report()
+    }
+}
+
+/// Running tally behind a [`CounterpartyReport`].
+pub(crate) struct Counterparties {
+    chain_name: String,
+    address: String,
+    transactions: u64,
+    by_address: HashMap<String, Counterparty>,
+}
+
+impl Counterparties {
+    pub(crate) fn new(chain_name: impl Into<String>, address: impl Into<String>) -> Self {
+        Self { chain_name: chain_name.into(), address: address.into(), transactions: 0, by_address: HashMap::new() }
+    }
+
+    pub(crate) fn record(&mut self, tx: &TransactionItem) {
+        self.transactions += 1;
+        let outgoing = same_address(&tx.from_address, &self.address);
+        let (other, label) = if outgoing {
+            match &tx.to_address {
+                Some(to) => (to, &tx.to_address_label),
+                // Contract creation.
+                None => return,
+            }
+        } else {
+            (&tx.from_address, &tx.from_address_label)
+        };
+        if same_address(other, &self.address) {
+            return;
+        }
+
+        let key = normalize_address(other);
+        let counterparty = self.by_address.entry(key.clone()).or_insert_with(|| Counterparty {
+            address: key,
+            ..Counterparty::default()
+        });
+        if counterparty.label.is_none() {
+            counterparty.label = label.clone();
+        }
+        counterparty.record(tx, !outgoing);
+    }
+
+    pub(crate) fn into_report(self) -> CounterpartyReport {
+        let mut counterparties: Vec<Counterparty> = self.by_address.into_values().collect();
+        counterparties.sort_by(|a, b| {
+            b.tx_count
+                .cmp(&a.tx_count)
+                .then(b.total_value_quote().total_cmp(&a.total_value_quote()))
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        CounterpartyReport {
+            address: self.address,
+            chain_name: self.chain_name,
+            transactions: self.transactions,
+            counterparties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_ranks_counterparties() {
+        let txs: Vec<TransactionItem> = serde_json::from_value(json!([
+            { "tx_hash": "0x1", "from_address": "0xabc", "to_address": "0xDEX", "to_address_label": "DEX",
+              "value": "100", "value_quote": 1.0, "block_signed_at": "2024-01-02T00:00:00Z" },
+            { "tx_hash": "0x2", "from_address": "0xdex", "to_address": "0xABC",
+              "value": "40", "value_quote": 0.4, "block_signed_at": "2024-01-01T00:00:00Z" },
+            { "tx_hash": "0x3", "from_address": "0xfriend", "to_address": "0xabc", "value": "5", "value_quote": 9.0 },
+            { "tx_hash": "0x4", "from_address": "0xabc", "value": "0" },
+            { "tx_hash": "0x5", "from_address": "0xabc", "to_address": "0xabc", "value": "0" },
+        ]))
+        .unwrap();
+
+        let report = CounterpartyReport::build("eth-mainnet", "0xabc", &txs);
+        assert_eq!(report.transactions, 5);
+        assert_eq!(report.counterparties.len(), 2);
+        let dex = &report.counterparties[0];
+        assert_eq!((dex.address.as_str(), dex.name()), ("0xdex", "DEX"));
+        assert_eq!((dex.tx_count, dex.sent_count, dex.received_count), (2, 1, 1));
+        assert_eq!((dex.value_out, dex.value_in), (100, 40));
+        assert_eq!(dex.first_interaction.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(dex.last_interaction.as_deref(), Some("2024-01-02T00:00:00Z"));
+        assert_eq!(report.counterparties[1].value_in_quote, 9.0);
+    }
+}
@@ -0,0 +1,108 @@
+//! Estimated API credit usage.
+//!
+//! Every successful REST call is charged to the client's [`CreditEstimator`]
+//! at its endpoint's approximate cost, and reported in
+//! [`MetricsSummary::estimated_credits`](crate::MetricsSummary::estimated_credits).
+//! Costs default to [`Endpoint::credit_cost`] and can be overridden to match
+//! your plan. A [`BulkExecutor`](crate::BulkExecutor) given a budget stops
+//! starting tasks once the estimated spend passes it.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{Endpoint, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! client.credits().set_cost(Endpoint::BalancesV2, 1.5);
+//! client.balance_service().get_token_balances_for_wallet_address("eth-mainnet", "0x...", None).await?;
+//! println!("about {:.1} credits so far", client.credits().used());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::endpoint::Endpoint;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// Estimated credits charged so far.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreditUsage {
+    pub total: f64,
+    /// Calls charged, including free ones.
+    pub requests: u64,
+    pub per_endpoint: BTreeMap<Endpoint, f64>,
+}
+
+/// Approximate credit cost per endpoint and running usage for one client.
+#[derive(Debug, Default)]
+pub struct CreditEstimator {
+    overrides: Mutex<HashMap<Endpoint, f64>>,
+    usage: Mutex<CreditUsage>,
+}
+
+impl CreditEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Estimated credits for one call to `endpoint`.
+    pub fn cost(&self, endpoint: Endpoint) -> f64 {
+        self.overrides
+            .lock()
+            .unwrap()
+            .get(&endpoint)
+            .copied()
+            .unwrap_or_else(|| endpoint.credit_cost())
+    }
+
+    /// Charge `credits` per call to `endpoint` from now on.
+    pub fn set_cost(&self, endpoint: Endpoint, credits: f64) {
+        self.overrides.lock().unwrap().insert(endpoint, credits);
+    }
+
+    /// Charge one call to `endpoint`, returning its cost.
+    pub fn record(&self, endpoint: Endpoint) -> f64 {
+        let cost = self.cost(endpoint);
+        let mut usage = self.usage.lock().unwrap();
+        usage.total += cost;
+        usage.requests += 1;
+        *usage.per_endpoint.entry(endpoint).or_insert(0.0) += cost;
+        cost
+    }
+
+    /// Total estimated credits charged so far.
+    pub fn used(&self) -> f64 {
+        self.usage.lock().unwrap().total
+    }
+
+    pub fn usage(&self) -> CreditUsage {
+        self.usage.lock().unwrap().clone()
+    }
+
+    /// Clear usage, keeping cost overrides.
+    pub fn reset(&self) {
+        *self.usage.lock().unwrap() = CreditUsage::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_usage_with_overrides() {
+        let credits = CreditEstimator::new();
+        credits.record(Endpoint::TransactionsV3);
+        credits.set_cost(Endpoint::BalancesV2, 0.5);
+        credits.record(Endpoint::BalancesV2);
+        credits.record(Endpoint::BalancesV2);
+        credits.record(Endpoint::Chains);
+
+        let usage = credits.usage();
+        assert_eq!(usage.total, Endpoint::TransactionsV3.credit_cost() + 1.0);
+        assert_eq!(usage.requests, 4);
+        assert_eq!(usage.per_endpoint[&Endpoint::BalancesV2], 1.0);
+
+        credits.reset();
+        assert_eq!(credits.used(), 0.0);
+        assert_eq!(credits.cost(Endpoint::BalancesV2), 0.5);
+    }
+}
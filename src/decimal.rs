@@ -0,0 +1,84 @@
+//! Numeric type of monetary quote fields.
+//!
+//! Quote fields of the balance, pricing and streaming models (`quote`,
+//! `quote_rate`, `price`, `close`, `quote_usd`, ...) are typed as [`Quote`].
+//! By default that is `f64`. With the `decimal` feature it is
+//! [`rust_decimal::Decimal`], parsed from the shortest representation of each
+//! JSON number, so sums and differences don't pick up binary floating-point
+//! error. The SDK's own aggregations go through [`QuoteValue`] and work in
+//! either mode.
+//!
+//! ```rust
+//! use goldrush_sdk::decimal::{Quote, QuoteValue};
+//!
+//! let quotes: Vec<Quote> = vec![Quote::from_f64(0.1), Quote::from_f64(0.2)];
+//! let total = quotes.iter().fold(Quote::from_f64(0.0), |sum, q| sum + *q);
+//! assert!((total.as_f64() - 0.3).abs() < 1e-9);
+//! ```
+
+/// Type of monetary quote fields: `f64`, or `Decimal` with the `decimal` feature.
+#[cfg(not(feature = "decimal"))]
+pub type Quote = f64;
+
+/// Type of monetary quote fields: `f64`, or `Decimal` with the `decimal` feature.
+#[cfg(feature = "decimal")]
+pub type Quote = rust_decimal::Decimal;
+
+/// Conversions between [`Quote`] and `f64`.
+pub trait QuoteValue: Copy {
+    /// The nearest `f64`.
+    fn as_f64(&self) -> f64;
+
+    /// `value` as a quote; NaN and infinities become zero under `decimal`.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl QuoteValue for f64 {
+    fn as_f64(&self) -> f64 {
+        *self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl QuoteValue for rust_decimal::Decimal {
+    fn as_f64(&self) -> f64 {
+        use rust_decimal::prelude::ToPrimitive;
+        self.to_f64().unwrap_or_default()
+    }
+
+    fn from_f64(value: f64) -> Self {
+        use std::str::FromStr;
+        // Via the shortest representation, so 0.1 becomes exactly 0.1.
+        rust_decimal::Decimal::from_str(&value.to_string()).unwrap_or_default()
+    }
+}
+
+/// `quote` as `f64`, for internal aggregations.
+pub(crate) fn to_f64(quote: Quote) -> f64 {
+    quote.as_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::balances::BalancesData;
+
+    #[test]
+    fn test_quotes_round_trip_through_json() {
+        let data: BalancesData = serde_json::from_value(serde_json::json!({
+            "items": [
+                { "contract_address": "0xa", "balance": "1", "quote": 0.1 },
+                { "contract_address": "0xb", "balance": "1", "quote": 0.2 },
+            ],
+        }))
+        .unwrap();
+        assert!((data.total_value().as_f64() - 0.3).abs() < 1e-9);
+        #[cfg(feature = "decimal")]
+        assert_eq!(data.total_value(), Quote::from_f64(0.3));
+        assert_eq!(serde_json::to_value(&data.items[0]).unwrap()["quote"], 0.1);
+    }
+}
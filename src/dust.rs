@@ -0,0 +1,187 @@
+//! Dust balances and whether they are worth sweeping.
+//!
+//! [`AllChainsService::dust_report`](crate::AllChainsService::dust_report)
+//! lists the tokens of a wallet worth less than a threshold on each chain,
+//! next to what a transfer of that token currently costs in gas there. A token
+//! is worth sweeping when its value exceeds that cost.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{DustReportOptions, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let options = DustReportOptions::new()
+//!     .chains(vec!["eth-mainnet".into(), "base-mainnet".into()])
+//!     .threshold_quote(5.0);
+//! let report = client.all_chains_service().dust_report("0x...", options).await?;
+//! for token in report.worth_sweeping() {
+//!     println!("{} on {}: {:.2} for {:.2?} gas", token.name(), token.chain_name, token.quote, token.sweep_cost_quote);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::normalize_address;
+use crate::models::all_chains::{ChainFailure, PartitionedBalances};
+use crate::models::base::GasPricesData;
+use std::collections::HashMap;
+
+/// Placeholder address the API reports native tokens under.
+const NATIVE_TOKEN_ADDRESS: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+
+/// Current cost of a transfer on one chain, in the quote currency.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransferCost {
+    pub erc20: Option<f64>,
+    pub native: Option<f64>,
+}
+
+impl TransferCost {
+    /// Cheapest interval of a gas prices response; sweeping is rarely urgent.
+    pub fn cheapest(gas_prices: &GasPricesData) -> Option<f64> {
+        gas_prices.items.iter().filter_map(|item| item.gas_quote).min_by(f64::total_cmp)
+    }
+}
+
+/// A token balance below the dust threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DustToken {
+    pub chain_name: String,
+    pub contract_address: String,
+    pub ticker_symbol: Option<String>,
+    pub native: bool,
+    /// Raw balance in the token's smallest unit.
+    pub balance: Option<String>,
+    pub quote: f64,
+    /// Gas cost of transferring the token; `None` when gas prices were unavailable.
+    pub sweep_cost_quote: Option<f64>,
+}
+
+impl DustToken {
+    /// The ticker, or the contract address when there is none.
+    pub fn name(&self) -> &str {
+        self.ticker_symbol.as_deref().unwrap_or(&self.contract_address)
+    }
+
+    /// Value left after paying for the transfer.
+    pub fn net_sweep_quote(&self) -> Option<f64> {
+        self.sweep_cost_quote.map(|cost| self.quote - cost)
+    }
+
+    /// Whether the token is worth more than moving it costs; `None` when the cost is unknown.
+    pub fn worth_sweeping(&self) -> Option<bool> {
+        self.net_sweep_quote().map(|net| net > 0.0)
+    }
+}
+
+/// Dust across the chains of one wallet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DustReport {
+    pub address: Option<String>,
+    pub threshold_quote: f64,
+    /// Sorted by net sweep value, highest first; tokens with unknown cost last.
+    pub tokens: Vec<DustToken>,
+    /// Chains whose balances could not be fetched.
+    pub failed_chains: Vec<ChainFailure>,
+}
+
+impl DustReport {
+    /// Pick the priced balances worth less than `threshold_quote` and attach
+    /// the transfer cost of their chain from `costs`, keyed by chain name.
+    pub fn build(balances: &PartitionedBalances, threshold_quote: f64, costs: &HashMap<String, TransferCost>) -> Self {
+        let mut tokens: Vec<DustToken> = balances
+            .items
+            .iter()
+            .filter_map(|item| {
+                let quote = item.quote.filter(|q| *q > 0.0 && *q < threshold_quote)?;
+                let chain_name = item.chain_name.clone()?;
+                let contract_address = normalize_address(item.contract_address.as_deref()?);
+                let native = contract_address == NATIVE_TOKEN_ADDRESS;
+                let cost = costs.get(&chain_name).copied().unwrap_or_default();
+                Some(DustToken {
+                    sweep_cost_quote: if native { cost.native } else { cost.erc20 },
+                    chain_name,
+                    contract_address,
+                    ticker_symbol: item.contract_ticker_symbol.clone(),
+                    native,
+                    balance: item.balance.clone(),
+                    quote,
+                })
+            })
+            .collect();
+        tokens.sort_by(|a, b| {
+            let net = |t: &DustToken| t.net_sweep_quote().unwrap_or(f64::NEG_INFINITY);
+            net(b).total_cmp(&net(a))
+        });
+        Self {
+            address: balances.address.clone(),
+            threshold_quote,
+            tokens,
+            failed_chains: balances.failed_chains.clone(),
+        }
+    }
+
+    /// Tokens worth more than the gas to move them.
+    pub fn worth_sweeping(&self) -> impl Iterator<Item = &DustToken> {
+        self.tokens.iter().filter(|t| t.worth_sweeping() == Some(true))
+    }
+
+    /// Total value of all dust.
+    pub fn total_quote(&self) -> f64 {
+        self.tokens.iter().map(|t| t.quote).sum()
+    }
+
+    /// Value recovered by sweeping every token worth sweeping, after gas.
+    pub fn net_sweep_quote(&self) -> f64 {
+        self.worth_sweeping().filter_map(DustToken::net_sweep_quote).sum()
+    }
+
+    /// Chains holding dust, for fetching their transfer costs.
+    pub(crate) fn chains(balances: &PartitionedBalances, threshold_quote: f64) -> Vec<String> {
+        let mut chains: Vec<String> = balances
+            .items
+            .iter()
+            .filter(|item| item.quote.is_some_and(|q| q > 0.0 && q < threshold_quote))
+            .filter_map(|item| item.chain_name.clone())
+            .collect();
+        chains.sort();
+        chains.dedup();
+        chains
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_prices_sweeps() {
+        let balances: PartitionedBalances = serde_json::from_value(json!({
+            "address": "0xabc",
+            "items": [
+                { "chain_name": "eth-mainnet", "contract_address": "0xA", "contract_ticker_symbol": "AAA", "quote": 3.0 },
+                { "chain_name": "eth-mainnet", "contract_address": NATIVE_TOKEN_ADDRESS, "quote": 0.5 },
+                { "chain_name": "eth-mainnet", "contract_address": "0xb", "quote": 500.0 },
+                { "chain_name": "base-mainnet", "contract_address": "0xc", "quote": 0.2 },
+                { "chain_name": "eth-mainnet", "contract_address": "0xd", "quote": 0.0 },
+                { "chain_name": "matic-mainnet", "contract_address": "0xe", "quote": 1.0 },
+            ],
+            "failed_chains": [],
+        }))
+        .unwrap();
+        let costs = HashMap::from([
+            ("eth-mainnet".to_string(), TransferCost { erc20: Some(2.0), native: Some(1.0) }),
+            ("base-mainnet".to_string(), TransferCost { erc20: Some(0.01), native: Some(0.005) }),
+        ]);
+
+        assert_eq!(DustReport::chains(&balances, 5.0), ["base-mainnet", "eth-mainnet", "matic-mainnet"]);
+        let report = DustReport::build(&balances, 5.0, &costs);
+        let names: Vec<&str> = report.tokens.iter().map(DustToken::name).collect();
+        assert_eq!(names, ["AAA", "0xc", NATIVE_TOKEN_ADDRESS, "0xe"]);
+        assert_eq!(report.tokens[2].worth_sweeping(), Some(false));
+        assert_eq!(report.tokens[3].worth_sweeping(), None);
+        assert_eq!(report.worth_sweeping().count(), 2);
+        assert!((report.net_sweep_quote() - 1.19).abs() < 1e-9);
+        assert!((report.total_quote() - 4.7).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,253 @@
+//! Typed API endpoint families.
+//!
+//! Metrics, cache keys, rate-limit partitions and circuit breakers are grouped
+//! by [`Endpoint`] rather than by raw request path, so calls to the same
+//! endpoint for different chains, addresses or pages land in one bucket.
+//!
+//! ```rust
+//! use goldrush_sdk::Endpoint;
+//!
+//! let endpoint = Endpoint::from_path("/v1/eth-mainnet/address/0xabc/transactions_v3/page/2/");
+//! assert_eq!(endpoint, Endpoint::TransactionsV3);
+//! assert_eq!(endpoint.label(), "transactions_v3");
+//! ```
+
+use std::fmt;
+
+/// A GoldRush REST endpoint, independent of chain, address and paging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Endpoint {
+    BalancesV2,
+    NativeBalance,
+    HistoricalBalances,
+    HistoricalPortfolio,
+    Erc20Transfers,
+    TokenHolders,
+    TokenMetadata,
+    TransactionsV2,
+    TransactionsV3,
+    Transaction,
+    TransactionSummary,
+    BlockTransactions,
+    BulkTransactions,
+    NftBalances,
+    NftMetadata,
+    NftTokenIds,
+    NftTokenOwners,
+    NftTransactions,
+    NftCollections,
+    NftTraits,
+    NftAttributes,
+    NftTraitsSummary,
+    NftFloorPrice,
+    NftVolume,
+    NftSaleCount,
+    NftOwnershipCheck,
+    Approvals,
+    NftApprovals,
+    Block,
+    BlockHeights,
+    LogEventsByAddress,
+    LogEventsByTopic,
+    ResolveAddress,
+    AddressActivity,
+    GasPrices,
+    Chains,
+    ChainStatus,
+    HistoricalPrices,
+    PoolSpotPrices,
+    HdWallets,
+    AllChainsBalances,
+    AllChainsTransactions,
+    GraphQl,
+    /// A path the SDK does not recognise.
+    Other,
+}
+
+impl Endpoint {
+    /// Stable snake_case name used in metrics, cache keys and rate-limit partitions.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Endpoint::BalancesV2 => "balances_v2",
+            Endpoint::NativeBalance => "balances_native",
+            Endpoint::HistoricalBalances => "historical_balances",
+            Endpoint::HistoricalPortfolio => "portfolio_v2",
+            Endpoint::Erc20Transfers => "transfers_v2",
+            Endpoint::TokenHolders => "token_holders_v2",
+            Endpoint::TokenMetadata => "token_metadata",
+            Endpoint::TransactionsV2 => "transactions_v2",
+            Endpoint::TransactionsV3 => "transactions_v3",
+            Endpoint::Transaction => "transaction_v2",
+            Endpoint::TransactionSummary => "transactions_summary",
+            Endpoint::BlockTransactions => "block_transactions",
+            Endpoint::BulkTransactions => "bulk_transactions",
+            Endpoint::NftBalances => "balances_nft",
+            Endpoint::NftMetadata => "nft_metadata",
+            Endpoint::NftTokenIds => "nft_token_ids",
+            Endpoint::NftTokenOwners => "nft_token_owners",
+            Endpoint::NftTransactions => "nft_transactions",
+            Endpoint::NftCollections => "nft_collections",
+            Endpoint::NftTraits => "nft_traits",
+            Endpoint::NftAttributes => "nft_attributes",
+            Endpoint::NftTraitsSummary => "nft_traits_summary",
+            Endpoint::NftFloorPrice => "nft_floor_price",
+            Endpoint::NftVolume => "nft_volume",
+            Endpoint::NftSaleCount => "nft_sale_count",
+            Endpoint::NftOwnershipCheck => "nft_ownership_check",
+            Endpoint::Approvals => "approvals",
+            Endpoint::NftApprovals => "nft_approvals",
+            Endpoint::Block => "block_v2",
+            Endpoint::BlockHeights => "block_heights",
+            Endpoint::LogEventsByAddress => "events_by_address",
+            Endpoint::LogEventsByTopic => "events_by_topic",
+            Endpoint::ResolveAddress => "resolve_address",
+            Endpoint::AddressActivity => "address_activity",
+            Endpoint::GasPrices => "gas_prices",
+            Endpoint::Chains => "chains",
+            Endpoint::ChainStatus => "chain_status",
+            Endpoint::HistoricalPrices => "historical_prices",
+            Endpoint::PoolSpotPrices => "pool_spot_prices",
+            Endpoint::HdWallets => "hd_wallets",
+            Endpoint::AllChainsBalances => "allchains_balances",
+            Endpoint::AllChainsTransactions => "allchains_transactions",
+            Endpoint::GraphQl => "graphql",
+            Endpoint::Other => "other",
+        }
+    }
+
+    /// Rough credits charged per call, for budgeting and prioritisation.
+    ///
+    /// These are hints, not billing figures; consult the GoldRush pricing
+    /// page for current rates.
+    pub fn credit_cost(&self) -> f64 {
+        match self {
+            Endpoint::Chains | Endpoint::ChainStatus => 0.0,
+            Endpoint::Block
+            | Endpoint::BlockHeights
+            | Endpoint::ResolveAddress
+            | Endpoint::Transaction
+            | Endpoint::GasPrices => 0.1,
+            Endpoint::HistoricalPortfolio
+            | Endpoint::Approvals
+            | Endpoint::NftApprovals
+            | Endpoint::AllChainsBalances
+            | Endpoint::AllChainsTransactions => 2.5,
+            Endpoint::TransactionsV2
+            | Endpoint::TransactionsV3
+            | Endpoint::BlockTransactions
+            | Endpoint::BulkTransactions => 5.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Classify a request path such as `/v1/eth-mainnet/address/0xabc/balances_v2/`.
+    ///
+    /// Query strings and base URL prefixes are ignored.
+    pub fn from_path(path: &str) -> Self {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let Some(start) = segments.iter().position(|s| *s == "v1") else {
+            return Endpoint::Other;
+        };
+
+        match &segments[start + 1..] {
+            ["chains"] => Endpoint::Chains,
+            ["chains", "status"] => Endpoint::ChainStatus,
+            ["graphql"] => Endpoint::GraphQl,
+            ["address", _, "activity"] => Endpoint::AddressActivity,
+            ["allchains", "address", _, "balances"] => Endpoint::AllChainsBalances,
+            ["allchains", "transactions"] => Endpoint::AllChainsTransactions,
+            ["pricing", "historical_by_addresses_v2", ..] => Endpoint::HistoricalPrices,
+            ["pricing", "spot_prices", ..] => Endpoint::PoolSpotPrices,
+            [_, "address", _, "balances_v2"] => Endpoint::BalancesV2,
+            [_, "address", _, "balances_native"] => Endpoint::NativeBalance,
+            [_, "address", _, "balances_nft"] => Endpoint::NftBalances,
+            [_, "address", _, "historical_balances"] => Endpoint::HistoricalBalances,
+            [_, "address", _, "portfolio_v2"] => Endpoint::HistoricalPortfolio,
+            [_, "address", _, "transfers_v2"] => Endpoint::Erc20Transfers,
+            [_, "address", _, "transactions_v2"] => Endpoint::TransactionsV2,
+            [_, "address", _, "transactions_v3", ..] => Endpoint::TransactionsV3,
+            [_, "address", _, "transactions_summary"] => Endpoint::TransactionSummary,
+            [_, "address", _, "resolve_address"] => Endpoint::ResolveAddress,
+            [_, "address", _, "hd_wallets"] => Endpoint::HdWallets,
+            [_, "address", _, "collection", ..] => Endpoint::NftOwnershipCheck,
+            [_, "approvals", _] => Endpoint::Approvals,
+            [_, "nft", "approvals", _] => Endpoint::NftApprovals,
+            [_, "nft", "collections"] => Endpoint::NftCollections,
+            [_, "nft", _, "metadata"] => Endpoint::NftMetadata,
+            [_, "nft", _, "traits"] => Endpoint::NftTraits,
+            [_, "nft", _, "traits", _, "attributes"] => Endpoint::NftAttributes,
+            [_, "nft", _, "traits_summary"] => Endpoint::NftTraitsSummary,
+            [_, "nft_market", _, "floor_price"] => Endpoint::NftFloorPrice,
+            [_, "nft_market", _, "volume"] => Endpoint::NftVolume,
+            [_, "nft_market", _, "sale_count"] => Endpoint::NftSaleCount,
+            [_, "tokens", _, "nft_metadata", _] => Endpoint::NftMetadata,
+            [_, "tokens", _, "nft_token_ids"] => Endpoint::NftTokenIds,
+            [_, "tokens", _, "nft_token_owners"] => Endpoint::NftTokenOwners,
+            [_, "tokens", _, "nft_transactions", _] => Endpoint::NftTransactions,
+            [_, "tokens", _, "token_holders_v2"] => Endpoint::TokenHolders,
+            [_, "tokens", _, "contract_metadata"] => Endpoint::TokenMetadata,
+            [_, "transaction_v2", _] => Endpoint::Transaction,
+            [_, "block", _, "transactions_v2" | "transactions_v3", ..]
+            | [_, "block_hash", _, "transactions_v3"] => {
+                Endpoint::BlockTransactions
+            }
+            [_, "bulk", "transactions", ..] => Endpoint::BulkTransactions,
+            [_, "block_v2", _] => Endpoint::Block,
+            [_, "block_v2", _, _] => Endpoint::BlockHeights,
+            [_, "event", _, "gas_prices"] => Endpoint::GasPrices,
+            [_, "events", "address", _] => Endpoint::LogEventsByAddress,
+            [_, "events", "topics", _] => Endpoint::LogEventsByTopic,
+            _ => Endpoint::Other,
+        }
+    }
+
+    /// Cache key namespaced by this endpoint: `label:part:part…`.
+    pub fn cache_key(&self, parts: &[&str]) -> String {
+        let mut key = self.label().to_string();
+        for part in parts {
+            key.push(':');
+            key.push_str(part);
+        }
+        key
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path_groups_by_endpoint() {
+        let cases = [
+            ("/v1/eth-mainnet/address/0xabc/balances_v2/", Endpoint::BalancesV2),
+            ("https://api.covalenthq.com/v1/base-mainnet/address/0xabc/balances_v2/?quote-currency=EUR", Endpoint::BalancesV2),
+            ("/v1/eth-mainnet/address/0xabc/transactions_v3/", Endpoint::TransactionsV3),
+            ("/v1/eth-mainnet/address/0xabc/transactions_v3/page/3/", Endpoint::TransactionsV3),
+            ("/v1/eth-mainnet/address/0xabc/transactions_v2/", Endpoint::TransactionsV2),
+            ("/v1/eth-mainnet/tokens/0xabc/nft_metadata/1/", Endpoint::NftMetadata),
+            ("/v1/eth-mainnet/nft/0xabc/traits/color/attributes/", Endpoint::NftAttributes),
+            ("/v1/eth-mainnet/block_v2/2024-01-01/latest/", Endpoint::BlockHeights),
+            ("/v1/eth-mainnet/tokens/0xabc/contract_metadata/", Endpoint::TokenMetadata),
+            ("/v1/chains/status/", Endpoint::ChainStatus),
+            ("/v1/allchains/transactions/", Endpoint::AllChainsTransactions),
+            ("/v2/something/", Endpoint::Other),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(Endpoint::from_path(path), expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(Endpoint::BalancesV2.cache_key(&["eth-mainnet", "0xabc"]), "balances_v2:eth-mainnet:0xabc");
+        assert_eq!(Endpoint::Chains.to_string(), "chains");
+    }
+}
@@ -24,12 +24,49 @@ pub enum Error {
         message: String,
         /// Optional error code from the API
         code: Option<u32>,
+        /// What went wrong, classified from the status, code and message
+        kind: ApiErrorCode,
+        /// Wait requested by the response's `Retry-After` header
+        retry_after: Option<std::time::Duration>,
+        /// The response body as received, when there was a response
+        body: Option<String>,
     },
 
+    /// I/O errors from writers and sinks (e.g. NDJSON export).
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Invalid configuration provided.
     #[error("configuration error: {0}")]
     Config(String),
 
+    /// The request was cancelled through its [`CancellationToken`](crate::CancellationToken).
+    #[error("request cancelled")]
+    Cancelled,
+
+    /// A circuit breaker is open and rejected the call without running it.
+    #[error("circuit breaker open, retry in {retry_after:?}")]
+    CircuitOpen {
+        /// Time until the circuit lets a trial request through.
+        retry_after: std::time::Duration,
+    },
+
+    /// The [`ScreeningProvider`](crate::ScreeningProvider) blocked the address a call was about.
+    #[error("address {address} blocked by screening: {reason}")]
+    Screened {
+        address: String,
+        reason: String,
+    },
+
+    /// The client is shutting down and no longer accepts requests.
+    #[error("client is shutting down")]
+    ShuttingDown,
+
+    /// SQLite sink errors.
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
     /// Streaming-related errors.
     #[cfg(feature = "streaming")]
     #[error("streaming error: {0}")]
@@ -44,10 +81,208 @@ pub enum Error {
     #[cfg(feature = "streaming")]
     #[error("GraphQL error: {0}")]
     GraphQL(String),
+
+    /// The streaming connection could not be established or initialised.
+    #[cfg(feature = "streaming")]
+    #[error("streaming connection failed: {reason}")]
+    ConnectionFailed {
+        reason: String,
+    },
+
+    /// The streaming server rejected a subscription or query.
+    #[cfg(feature = "streaming")]
+    #[error("subscription rejected: {message}")]
+    SubscriptionRejected {
+        /// `extensions.code` of the first GraphQL error, if the server sent one
+        code: Option<String>,
+        message: String,
+    },
+
+    /// A streaming payload did not have the expected shape.
+    #[cfg(feature = "streaming")]
+    #[error("failed to deserialize `{field}`: {message}")]
+    Deserialization {
+        /// GraphQL field that was being read
+        field: String,
+        message: String,
+        /// The payload as received
+        payload: serde_json::Value,
+    },
+
+    /// The server ended a subscription the client had not unsubscribed from.
+    #[cfg(feature = "streaming")]
+    #[error("subscription {subscription_id} completed by the server")]
+    ServerComplete {
+        subscription_id: String,
+    },
+}
+
+/// Documented GoldRush API failures, attached to [`Error::Api`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ApiErrorCode {
+    /// The chain name or ID is unknown or not supported by the endpoint.
+    InvalidChain,
+    /// The address or domain could not be parsed.
+    MalformedAddress,
+    /// The address has no data on the chain.
+    AddressNotFound,
+    /// Another parameter was missing or malformed (HTTP 400).
+    InvalidParameter,
+    /// The API key is missing, invalid or revoked (HTTP 401).
+    InvalidApiKey,
+    /// The account is out of credits or the plan doesn't cover the endpoint (HTTP 402).
+    PaymentRequired,
+    /// The requested resource does not exist (HTTP 404).
+    NotFound,
+    /// Too many requests (HTTP 429).
+    RateLimited,
+    /// The API failed to handle the request (HTTP 5xx).
+    ServerError,
+    /// Anything not covered above.
+    Other,
+}
+
+impl ApiErrorCode {
+    /// Classify an API error response.
+    pub fn classify(status: u16, message: &str) -> Self {
+        let message = message.to_ascii_lowercase();
+        let says = |words: &[&str]| words.iter().any(|w| message.contains(w));
+        let about_chain = message.contains("chain") && says(&["invalid", "not supported", "unsupported", "unknown", "not found"]);
+        let about_address = says(&["address", "domain"]);
+
+        match status {
+            404 if about_address => ApiErrorCode::AddressNotFound,
+            400 | 404 | 501 if about_chain => ApiErrorCode::InvalidChain,
+            400 if about_address && says(&["malformed", "invalid", "not a valid"]) => ApiErrorCode::MalformedAddress,
+            400 => ApiErrorCode::InvalidParameter,
+            401 | 403 => ApiErrorCode::InvalidApiKey,
+            402 => ApiErrorCode::PaymentRequired,
+            404 => ApiErrorCode::NotFound,
+            429 => ApiErrorCode::RateLimited,
+            500..=599 => ApiErrorCode::ServerError,
+            _ => ApiErrorCode::Other,
+        }
+    }
+}
+
+/// How a streaming consumer should respond to an [`Error`].
+#[cfg(feature = "streaming")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingRecovery {
+    /// The connection is gone; reconnect, then subscribe again.
+    Reconnect,
+    /// The connection is fine; subscribing again may succeed.
+    Resubscribe,
+    /// Retrying the same subscription will fail the same way.
+    GiveUp,
+}
+
+impl Error {
+    /// An [`Error::Api`] with its [`ApiErrorCode`] classified from `status` and `message`.
+    pub(crate) fn api(status: u16, message: String, code: Option<u32>) -> Self {
+        // The API's `error_code` mirrors an HTTP status when it has one.
+        let effective = code.and_then(|c| u16::try_from(c).ok()).filter(|c| (400..600).contains(c)).unwrap_or(status);
+        let kind = ApiErrorCode::classify(effective, &message);
+        Error::Api { status, message, code, kind, retry_after: None, body: None }
+    }
+
+    /// Attach the `Retry-After` wait and raw body of the response behind an [`Error::Api`].
+    pub(crate) fn with_response(mut self, wait: Option<std::time::Duration>, raw: String) -> Self {
+        if let Error::Api { retry_after, body, .. } = &mut self {
+            *retry_after = wait;
+            *body = Some(raw);
+        }
+        self
+    }
+
+    /// The classified API failure, if this is an [`Error::Api`].
+    pub fn api_error_code(&self) -> Option<ApiErrorCode> {
+        match self {
+            Error::Api { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// Whether the same call may succeed if made again later: transport
+    /// failures, rate limiting, 5xx responses and open circuits. Calls the
+    /// SDK gives up on have already been retried `max_retries` times.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            Error::Api { kind, .. } => matches!(kind, ApiErrorCode::RateLimited | ApiErrorCode::ServerError),
+            Error::CircuitOpen { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// How long to wait before calling again, when the server or an open
+    /// circuit breaker said so.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            Error::Api { retry_after, .. } => *retry_after,
+            Error::CircuitOpen { retry_after } => Some(*retry_after),
+            _ => None,
+        }
+    }
+
+    /// The body of the error response, as received.
+    pub fn response_body(&self) -> Option<&str> {
+        match self {
+            Error::Api { body, .. } => body.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// How a streaming consumer should respond to this error.
+    #[cfg(feature = "streaming")]
+    pub fn streaming_recovery(&self) -> StreamingRecovery {
+        match self {
+            Error::ConnectionFailed { .. } | Error::WebSocket(_) => StreamingRecovery::Reconnect,
+            Error::ServerComplete { .. } | Error::Streaming(_) => StreamingRecovery::Resubscribe,
+            _ => StreamingRecovery::GiveUp,
+        }
+    }
 }
 
 /// Result type alias for GoldRush SDK operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Convenience alias for GoldRushError
-pub type GoldRushError = Error;
\ No newline at end of file
+#[allow(dead_code)]
+pub type GoldRushError = Error;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_codes() {
+        let kind = |status, message: &str| Error::api(status, message.to_string(), None).api_error_code();
+        assert_eq!(kind(400, "Invalid chain name: eth-mainet"), Some(ApiErrorCode::InvalidChain));
+        assert_eq!(kind(501, "Chain not supported for this endpoint"), Some(ApiErrorCode::InvalidChain));
+        assert_eq!(kind(400, "Malformed address provided: 0x12"), Some(ApiErrorCode::MalformedAddress));
+        assert_eq!(kind(400, "Invalid page-size"), Some(ApiErrorCode::InvalidParameter));
+        assert_eq!(kind(401, "Invalid or missing API key"), Some(ApiErrorCode::InvalidApiKey));
+        assert_eq!(kind(402, "Payment required"), Some(ApiErrorCode::PaymentRequired));
+        assert_eq!(kind(404, "Address not found on chain"), Some(ApiErrorCode::AddressNotFound));
+        assert_eq!(kind(404, "Not found"), Some(ApiErrorCode::NotFound));
+        assert_eq!(kind(429, ""), Some(ApiErrorCode::RateLimited));
+        assert_eq!(kind(503, ""), Some(ApiErrorCode::ServerError));
+        assert_eq!(Error::Cancelled.api_error_code(), None);
+    }
+
+    #[test]
+    fn test_retryability() {
+        let wait = std::time::Duration::from_secs(2);
+        let limited = Error::api(429, "Too many requests".to_string(), None).with_response(Some(wait), "{\"error\":true}".to_string());
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(wait));
+        assert_eq!(limited.response_body(), Some("{\"error\":true}"));
+        assert!(Error::api(502, String::new(), None).is_retryable());
+        assert!(!Error::api(401, "Invalid or missing API key".to_string(), None).is_retryable());
+        assert!(!Error::api(404, "Not found".to_string(), None).is_retryable());
+        assert!(Error::CircuitOpen { retry_after: wait }.is_retryable());
+        assert!(!Error::MissingApiKey.is_retryable());
+    }
+}
@@ -0,0 +1,309 @@
+//! Deposits to and withdrawals from known exchanges and bridges.
+//!
+//! An [`ExchangeAddressSet`] maps addresses to the exchange or bridge that
+//! controls them. [`ExchangeFlowSummary`] uses it to tag a wallet's ERC-20
+//! transfers and native transactions as deposits (wallet → exchange) or
+//! withdrawals (exchange → wallet) and totals them per entity.
+//!
+//! The bundled set only covers a few well-known Ethereum hot wallets and
+//! bridge contracts; load a fuller dataset with
+//! [`ExchangeAddressSet::extend_from_json`].
+//!
+//! ```rust
+//! use goldrush_sdk::{EntityKind, ExchangeAddressSet, ExchangeFlowSummary, LabeledAddress};
+//!
+//! let set = ExchangeAddressSet::bundled()
+//!     .with(LabeledAddress::new("0x1111111111111111111111111111111111111111", "Acme Exchange", EntityKind::Exchange));
+//!
+//! let mut summary = ExchangeFlowSummary::new("0xabc");
+//! # let transfers: goldrush_sdk::models::balances::Erc20TransfersData =
+//! #     serde_json::from_str(r#"{"chain_name":"eth-mainnet","items":[]}"#).unwrap();
+//! summary.add_transfers(&set, &transfers);
+//! for entity in &summary.entities {
+//!     println!("{}: in {:.2} out {:.2}", entity.entity, entity.inflow_quote, entity.outflow_quote);
+//! }
+//! ```
+
+use crate::address::{normalize_address, same_address};
+use crate::decimal::to_f64;
+use crate::models::balances::Erc20TransfersData;
+use crate::models::transactions::TransactionItem;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What kind of entity controls an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Exchange,
+    Bridge,
+}
+
+/// An address controlled by an exchange or bridge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabeledAddress {
+    pub address: String,
+    /// Name of the exchange or bridge, e.g. `Binance`.
+    pub entity: String,
+    pub kind: EntityKind,
+    /// Chain the address belongs to; `None` matches it on every chain.
+    #[serde(default)]
+    pub chain: Option<String>,
+}
+
+impl LabeledAddress {
+    pub fn new(address: impl Into<String>, entity: impl Into<String>, kind: EntityKind) -> Self {
+        Self { address: address.into(), entity: entity.into(), kind, chain: None }
+    }
+
+    pub fn chain(mut self, chain: impl Into<String>) -> Self { self.chain = Some(chain.into()); self }
+}
+
+const BUNDLED: &[(&str, &str, EntityKind)] = &[
+    ("0x28c6c06298d514db089934071355e5743bf21d60", "Binance", EntityKind::Exchange),
+    ("0xbe0eb53f46cd790cd13851d5eff43d12404d33e8", "Binance", EntityKind::Exchange),
+    ("0x71660c4005ba85c37ccec55d0c4493e66fe775d3", "Coinbase", EntityKind::Exchange),
+    ("0xa9d1e08c7793af67e9d92fe308d5697fb81d3e43", "Coinbase", EntityKind::Exchange),
+    ("0x2910543af39aba0cd09dbb2d50200b3e800a63d2", "Kraken", EntityKind::Exchange),
+    ("0x6cc5f688a315f3dc28a7781717a9a798a59fda7b", "OKX", EntityKind::Exchange),
+    ("0x8315177ab297ba92a06054ce80a67ed4dbd7ed3a", "Arbitrum Bridge", EntityKind::Bridge),
+    ("0x99c9fc46f92e8a1c0dec1b1747d010903e884be1", "Optimism Gateway", EntityKind::Bridge),
+    ("0x3154cf16ccdb4c6d922629664174b904d80f2c35", "Base Bridge", EntityKind::Bridge),
+    ("0x40ec5b33f54e0e8a33a975908c5ba1c14e5bbbdf", "Polygon Bridge", EntityKind::Bridge),
+];
+
+/// Lookup from addresses to the exchanges and bridges controlling them.
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeAddressSet {
+    /// Keyed by lowercase address; chain-specific entries are checked first.
+    by_address: HashMap<String, Vec<LabeledAddress>>,
+}
+
+impl ExchangeAddressSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A set preloaded with a few well-known Ethereum exchange wallets and bridges.
+    pub fn bundled() -> Self {
+        BUNDLED.iter().fold(Self::new(), |set, (address, entity, kind)| {
+            set.with(LabeledAddress::new(*address, *entity, *kind).chain("eth-mainnet"))
+        })
+    }
+
+    /// Load addresses from a JSON array of [`LabeledAddress`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut set = Self::new();
+        set.extend_from_json(json)?;
+        Ok(set)
+    }
+
+    /// Merge addresses from a JSON array of [`LabeledAddress`] into this set.
+    pub fn extend_from_json(&mut self, json: &str) -> Result<()> {
+        let addresses: Vec<LabeledAddress> = serde_json::from_str(json)
+            .map_err(|e| Error::Config(format!("Invalid exchange address set: {}", e)))?;
+        addresses.into_iter().for_each(|address| self.insert(address));
+        Ok(())
+    }
+
+    pub fn with(mut self, address: LabeledAddress) -> Self {
+        self.insert(address);
+        self
+    }
+
+    /// Add an address, replacing any entry for the same address and chain.
+    pub fn insert(&mut self, address: LabeledAddress) {
+        let entries = self.by_address.entry(normalize_address(&address.address)).or_default();
+        entries.retain(|e| e.chain != address.chain);
+        entries.push(address);
+    }
+
+    /// The entity controlling `address` on `chain_name`.
+    pub fn lookup(&self, chain_name: &str, address: &str) -> Option<&LabeledAddress> {
+        let entries = self.by_address.get(&normalize_address(address))?;
+        entries
+            .iter()
+            .find(|e| e.chain.as_deref() == Some(chain_name))
+            .or_else(|| entries.iter().find(|e| e.chain.is_none()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_address.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_address.is_empty()
+    }
+}
+
+/// Direction of a flow, from the wallet's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowDirection {
+    /// The wallet sent funds to the entity.
+    Deposit,
+    /// The entity sent funds to the wallet.
+    Withdrawal,
+}
+
+/// A wallet's flows with one exchange or bridge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityFlow {
+    pub entity: String,
+    pub kind: EntityKind,
+    pub deposits: u64,
+    pub withdrawals: u64,
+    /// Value withdrawn from the entity into the wallet.
+    pub inflow_quote: f64,
+    /// Value deposited by the wallet into the entity.
+    pub outflow_quote: f64,
+}
+
+impl EntityFlow {
+    /// Inflow minus outflow.
+    pub fn net_quote(&self) -> f64 {
+        self.inflow_quote - self.outflow_quote
+    }
+}
+
+/// Per-entity deposits and withdrawals of one wallet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeFlowSummary {
+    pub address: String,
+    /// Largest total flow first.
+    pub entities: Vec<EntityFlow>,
+}
+
+impl ExchangeFlowSummary {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self { address: address.into(), entities: Vec::new() }
+    }
+
+    /// Whether a transfer from `from` to `to` is a deposit or withdrawal of
+    /// this wallet, and with which entity.
+    pub fn tag<'a>(
+        &self, set: &'a ExchangeAddressSet, chain_name: &str, from: &str, to: &str,
+    ) -> Option<(FlowDirection, &'a LabeledAddress)> {
+        if same_address(from, &self.address) {
+            set.lookup(chain_name, to).map(|entity| (FlowDirection::Deposit, entity))
+        } else if same_address(to, &self.address) {
+            set.lookup(chain_name, from).map(|entity| (FlowDirection::Withdrawal, entity))
+        } else {
+            None
+        }
+    }
+
+    /// Count the wallet's ERC-20 transfers with known entities.
+    pub fn add_transfers(&mut self, set: &ExchangeAddressSet, data: &Erc20TransfersData) {
+        let chain_name = data.chain_name.as_deref().unwrap_or_default();
+        for item in &data.items {
+            let (Some(from), Some(to)) = (item.from_address.as_deref(), item.to_address.as_deref()) else {
+                continue;
+            };
+            if let Some((direction, entity)) = self.tag(set, chain_name, from, to) {
+                let quote = item.delta_quote.map(to_f64).unwrap_or(0.0);
+                self.record(direction, entity, quote.abs());
+            }
+        }
+    }
+
+    /// Count the wallet's native-value transactions with known entities.
+    pub fn add_transactions(&mut self, set: &ExchangeAddressSet, chain_name: &str, transactions: &[TransactionItem]) {
+        for tx in transactions.iter().filter(|tx| tx.successful != Some(false) && tx.has_native_value()) {
+            let Some(to) = tx.to_address.as_deref() else { continue };
+            if let Some((direction, entity)) = self.tag(set, chain_name, &tx.from_address, to) {
+                self.record(direction, entity, tx.value_quote.unwrap_or(0.0));
+            }
+        }
+    }
+
+    /// Total value withdrawn from all entities.
+    pub fn total_inflow_quote(&self) -> f64 {
+        self.entities.iter().map(|e| e.inflow_quote).sum()
+    }
+
+    /// Total value deposited into all entities.
+    pub fn total_outflow_quote(&self) -> f64 {
+        self.entities.iter().map(|e| e.outflow_quote).sum()
+    }
+
+    fn record(&mut self, direction: FlowDirection, entity: &LabeledAddress, quote: f64) {
+        let index = match self.entities.iter().position(|e| e.entity == entity.entity && e.kind == entity.kind) {
+            Some(index) => index,
+            None => {
+                self.entities.push(EntityFlow {
+                    entity: entity.entity.clone(),
+                    kind: entity.kind,
+                    deposits: 0,
+                    withdrawals: 0,
+                    inflow_quote: 0.0,
+                    outflow_quote: 0.0,
+                });
+                self.entities.len() - 1
+            }
+        };
+        let flow = &mut self.entities[index];
+        match direction {
+            FlowDirection::Deposit => {
+                flow.deposits += 1;
+                flow.outflow_quote += quote;
+            }
+            FlowDirection::Withdrawal => {
+                flow.withdrawals += 1;
+                flow.inflow_quote += quote;
+            }
+        }
+        let total = |e: &EntityFlow| e.inflow_quote + e.outflow_quote;
+        self.entities.sort_by(|a, b| total(b).total_cmp(&total(a)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const BINANCE: &str = "0x28C6c06298d514Db089934071355E5743bf21d60";
+
+    #[test]
+    fn test_summarizes_flows() {
+        let set = ExchangeAddressSet::bundled();
+        let transfers: Erc20TransfersData = serde_json::from_value(json!({
+            "chain_name": "eth-mainnet",
+            "items": [
+                { "from_address": "0xabc", "to_address": BINANCE, "delta_quote": 100.0 },
+                { "from_address": BINANCE, "to_address": "0xABC", "delta_quote": 30.0 },
+                { "from_address": "0xabc", "to_address": "0xfriend", "delta_quote": 5.0 },
+            ],
+        }))
+        .unwrap();
+        let txs: Vec<TransactionItem> = serde_json::from_value(json!([
+            { "tx_hash": "0x1", "from_address": "0xabc", "to_address": "0x8315177ab297ba92a06054ce80a67ed4dbd7ed3a",
+              "value": "1000", "value_quote": 250.0 },
+            { "tx_hash": "0x2", "from_address": "0xabc", "to_address": BINANCE, "value": "0", "value_quote": 0.0 },
+        ]))
+        .unwrap();
+
+        let mut summary = ExchangeFlowSummary::new("0xabc");
+        summary.add_transfers(&set, &transfers);
+        summary.add_transactions(&set, "eth-mainnet", &txs);
+        let entities: Vec<(&str, u64, u64)> =
+            summary.entities.iter().map(|e| (e.entity.as_str(), e.deposits, e.withdrawals)).collect();
+        assert_eq!(entities, [("Arbitrum Bridge", 1, 0), ("Binance", 1, 1)]);
+        assert_eq!(summary.entities[1].net_quote(), -70.0);
+        assert_eq!(summary.total_outflow_quote(), 350.0);
+        assert!(summary.tag(&set, "base-mainnet", "0xabc", BINANCE).is_none());
+    }
+
+    #[test]
+    fn test_chain_specific_entries_win() {
+        let set = ExchangeAddressSet::from_json(&json!([
+            { "address": "0xAA", "entity": "Anywhere", "kind": "exchange" },
+            { "address": "0xaa", "entity": "Base Only", "kind": "bridge", "chain": "base-mainnet" },
+        ]).to_string())
+        .unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.lookup("base-mainnet", "0xaa").unwrap().entity, "Base Only");
+        assert_eq!(set.lookup("eth-mainnet", "0xAA").unwrap().kind, EntityKind::Exchange);
+    }
+}
@@ -0,0 +1,227 @@
+//! Newline-delimited JSON export of model streams.
+//!
+//! [`NdjsonWriter`] pulls items from any stream of results (e.g. a
+//! [`PageStream`](crate::PageStream)) and writes one JSON document per line
+//! to an [`AsyncWrite`]. The next item is only requested once the previous one
+//! has been written, so a slow sink naturally slows down fetching.
+
+use crate::{Error, Result};
+use futures_core::stream::Stream;
+use serde::{Deserialize, Serialize};
+use std::future::poll_fn;
+use std::pin::pin;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::debug;
+
+/// Flush policy for [`NdjsonWriter`].
+#[derive(Debug, Clone)]
+pub struct NdjsonConfig {
+    /// Flush after this many items.
+    pub flush_every: usize,
+    /// Flush when this much time has passed since the last flush.
+    pub flush_interval: Duration,
+}
+
+impl Default for NdjsonConfig {
+    fn default() -> Self {
+        Self {
+            flush_every: 1000,
+            flush_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Progress of an export, recorded at each flush.
+///
+/// Persist it to resume an interrupted export: reopen the output in append
+/// mode and pass the checkpoint to [`NdjsonWriter::resume_from`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportCheckpoint {
+    /// Items durably written (flushed) so far.
+    pub items_written: u64,
+    /// Bytes durably written (flushed) so far.
+    pub bytes_written: u64,
+}
+
+/// Writes models as newline-delimited JSON.
+pub struct NdjsonWriter<W> {
+    writer: W,
+    config: NdjsonConfig,
+    /// Progress including unflushed items.
+    pending: ExportCheckpoint,
+    /// Progress as of the last flush.
+    flushed: ExportCheckpoint,
+    unflushed_items: usize,
+    last_flush: Instant,
+    skip: u64,
+}
+
+impl<W: AsyncWrite + Unpin> NdjsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            config: NdjsonConfig::default(),
+            pending: ExportCheckpoint::default(),
+            flushed: ExportCheckpoint::default(),
+            unflushed_items: 0,
+            last_flush: Instant::now(),
+            skip: 0,
+        }
+    }
+
+    pub fn with_config(mut self, config: NdjsonConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Continue an interrupted export: the first `items_written` items of the
+    /// next [`write_stream`](Self::write_stream) call are skipped.
+    pub fn resume_from(mut self, checkpoint: ExportCheckpoint) -> Self {
+        self.skip = checkpoint.items_written;
+        self.pending = checkpoint;
+        self.flushed = checkpoint;
+        self
+    }
+
+    /// Progress as of the last flush.
+    pub fn checkpoint(&self) -> ExportCheckpoint {
+        self.flushed
+    }
+
+    /// Write a single item as one line.
+    pub async fn write_item<T: Serialize>(&mut self, item: &T) -> Result<()> {
+        let mut line = serde_json::to_vec(item)?;
+        line.push(b'\n');
+        self.writer.write_all(&line).await?;
+
+        self.pending.items_written += 1;
+        self.pending.bytes_written += line.len() as u64;
+        self.unflushed_items += 1;
+        Ok(())
+    }
+
+    /// Flush buffered output and advance the checkpoint.
+    pub async fn flush(&mut self) -> Result<ExportCheckpoint> {
+        self.writer.flush().await?;
+        self.flushed = self.pending;
+        self.unflushed_items = 0;
+        self.last_flush = Instant::now();
+        debug!(items = %self.flushed.items_written, bytes = %self.flushed.bytes_written, "NDJSON export flushed");
+        Ok(self.flushed)
+    }
+
+    /// Drain `stream` into the writer, calling `on_checkpoint` after every flush.
+    ///
+    /// Stops at the first stream or write error; everything up to
+    /// [`checkpoint`](Self::checkpoint) has been flushed and can be resumed from.
+    pub async fn write_stream<S, T, F>(&mut self, stream: S, mut on_checkpoint: F) -> Result<ExportCheckpoint>
+    where
+        S: Stream<Item = Result<T>>,
+        T: Serialize,
+        F: FnMut(ExportCheckpoint),
+    {
+        let mut stream = pin!(stream);
+
+        while let Some(item) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            let item = match item {
+                Ok(item) => item,
+                Err(e) => return Err(self.flush_then(e).await),
+            };
+            if self.skip > 0 {
+                self.skip -= 1;
+                continue;
+            }
+            if let Err(e) = self.write_item(&item).await {
+                return Err(self.flush_then(e).await);
+            }
+            if self.unflushed_items >= self.config.flush_every.max(1)
+                || self.last_flush.elapsed() >= self.config.flush_interval
+            {
+                on_checkpoint(self.flush().await?);
+            }
+        }
+
+        let checkpoint = self.flush().await?;
+        on_checkpoint(checkpoint);
+        Ok(checkpoint)
+    }
+
+    /// Flush and return the underlying writer.
+    pub async fn finish(mut self) -> Result<W> {
+        self.flush().await?;
+        Ok(self.writer)
+    }
+
+    /// Best-effort flush before surfacing `error`, so the checkpoint covers
+    /// everything written before the failure.
+    async fn flush_then(&mut self, error: Error) -> Error {
+        let _ = self.flush().await;
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pagination::{Page, PageStream};
+
+    fn numbers(total: u32) -> PageStream<serde_json::Value> {
+        PageStream::new(0, move |page| async move {
+            let start = page * 2;
+            let end = (start + 2).min(total);
+            Ok(Page {
+                items: (start..end).map(|n| serde_json::json!({ "n": n })).collect(),
+                has_more: end < total,
+                total_count: Some(total as u64),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_writes_lines_and_checkpoints() {
+        let mut writer = NdjsonWriter::new(Vec::new())
+            .with_config(NdjsonConfig { flush_every: 2, ..Default::default() });
+        let mut checkpoints = Vec::new();
+
+        let done = writer.write_stream(numbers(5), |c| checkpoints.push(c.items_written)).await.unwrap();
+        assert_eq!(done.items_written, 5);
+        assert_eq!(checkpoints, vec![2, 4, 5]);
+
+        let output = String::from_utf8(writer.finish().await.unwrap()).unwrap();
+        assert_eq!(output.lines().count(), 5);
+        assert_eq!(output.lines().next(), Some(r#"{"n":0}"#));
+        assert_eq!(done.bytes_written, output.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_written_items() {
+        let checkpoint = ExportCheckpoint { items_written: 3, bytes_written: 24 };
+        let mut writer = NdjsonWriter::new(Vec::new()).resume_from(checkpoint);
+
+        let done = writer.write_stream(numbers(5), |_| {}).await.unwrap();
+        assert_eq!(done.items_written, 5);
+
+        let output = String::from_utf8(writer.finish().await.unwrap()).unwrap();
+        assert_eq!(output, "{\"n\":3}\n{\"n\":4}\n");
+    }
+
+    #[tokio::test]
+    async fn test_model_round_trips_through_ndjson() {
+        let item: crate::BalanceItem = serde_json::from_value(serde_json::json!({
+            "contract_address": "0xa0b8",
+            "contract_ticker_symbol": "USDC",
+            "balance": "1000000",
+            "type": "stablecoin",
+            "unknown_field": 7,
+        })).unwrap();
+
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.write_item(&item).await.unwrap();
+        let output = writer.finish().await.unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(parsed["type"], "stablecoin");
+        assert_eq!(parsed["unknown_field"], 7);
+    }
+}
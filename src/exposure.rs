@@ -0,0 +1,238 @@
+//! Wallet value exposed to approved spenders.
+//!
+//! Joins token balances with ERC-20 approvals to show, per spender, how much
+//! of the wallet could be moved by that spender today. A token's value at risk
+//! is the lesser of its balance and the spender's allowance, both in the quote
+//! currency of the balances response.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{Chain, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let report = client.security_service().get_exposure_report(Chain::EthereumMainnet, "0x...").await?;
+//! for spender in report.spenders.iter().take(5) {
+//!     println!("{}: {:.2} at risk", spender.name(), spender.value_at_risk_quote);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::normalize_address;
+use crate::decimal::to_f64;
+use crate::models::approvals::{ApprovalItem, ApprovalsData};
+use crate::models::balances::{BalanceItem, BalancesData};
+use std::collections::HashMap;
+
+/// One token a spender may move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenExposure {
+    pub token_address: String,
+    pub ticker_symbol: Option<String>,
+    /// Raw allowance, or `None` when unlimited.
+    pub allowance: Option<String>,
+    pub unlimited: bool,
+    pub balance_quote: f64,
+    pub value_at_risk_quote: f64,
+    pub risk_factor: Option<String>,
+}
+
+/// Everything one spender may move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpenderExposure {
+    pub spender_address: String,
+    /// Protocol or contract name reported by the API.
+    pub label: Option<String>,
+    pub value_at_risk_quote: f64,
+    /// Whether any allowance is unlimited.
+    pub unlimited: bool,
+    /// Tokens sorted by value at risk, highest first.
+    pub tokens: Vec<TokenExposure>,
+}
+
+impl SpenderExposure {
+    /// The label, or the spender address when there is none.
+    pub fn name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.spender_address)
+    }
+}
+
+/// Exposure of one wallet, by spender.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureReport {
+    pub address: Option<String>,
+    pub chain_name: Option<String>,
+    pub quote_currency: Option<String>,
+    pub total_balance_quote: f64,
+    /// Value any spender could move, counting each token once.
+    pub total_at_risk_quote: f64,
+    /// Spenders sorted by value at risk, highest first.
+    pub spenders: Vec<SpenderExposure>,
+}
+
+impl ExposureReport {
+    /// Join a balances response with an approvals response for the same wallet.
+    pub fn build(balances: &BalancesData, approvals: &ApprovalsData) -> Self {
+        let by_token: HashMap<String, &BalanceItem> = balances
+            .items
+            .iter()
+            .map(|item| (normalize_address(&item.contract_address), item))
+            .collect();
+
+        let mut spenders: HashMap<String, SpenderExposure> = HashMap::new();
+        let mut token_at_risk: HashMap<String, f64> = HashMap::new();
+        for approval in &approvals.items {
+            let Some(token_address) = approval.token_address.as_deref().map(str::to_ascii_lowercase) else {
+                continue;
+            };
+            let balance = by_token.get(&token_address).copied();
+            let balance_quote = balance.and_then(|b| b.quote).map(to_f64).or(approval.balance_quote).unwrap_or(0.0);
+            let quote_rate = balance.and_then(|b| b.quote_rate).map(to_f64).or(approval.quote_rate);
+            let decimals = balance.and_then(|b| b.contract_decimals).or(approval.contract_decimals).unwrap_or(18);
+
+            for spender in approval.spenders.iter().flatten() {
+                let Some(spender_address) = spender.spender_address.as_deref().map(str::to_ascii_lowercase) else {
+                    continue;
+                };
+                let unlimited = is_unlimited(spender.allowance.as_deref());
+                let value_at_risk_quote = if unlimited {
+                    balance_quote
+                } else {
+                    let allowance_quote = allowance_quote(spender.allowance.as_deref(), decimals, quote_rate);
+                    allowance_quote.map_or(balance_quote, |allowance| allowance.min(balance_quote))
+                };
+                let at_risk = token_at_risk.entry(token_address.clone()).or_default();
+                *at_risk = at_risk.max(value_at_risk_quote);
+
+                let entry = spenders.entry(spender_address.clone()).or_insert_with(|| SpenderExposure {
+                    spender_address,
+                    label: None,
+                    value_at_risk_quote: 0.0,
+                    unlimited: false,
+                    tokens: Vec::new(),
+                });
+                if entry.label.is_none() {
+                    entry.label = spender.spender_address_label.clone();
+                }
+                entry.value_at_risk_quote += value_at_risk_quote;
+                entry.unlimited |= unlimited;
+                entry.tokens.push(TokenExposure {
+                    token_address: token_address.clone(),
+                    ticker_symbol: ticker(approval, balance),
+                    allowance: if unlimited { None } else { spender.allowance.clone() },
+                    unlimited,
+                    balance_quote,
+                    value_at_risk_quote,
+                    risk_factor: spender.risk_factor.clone(),
+                });
+            }
+        }
+
+        let mut spenders: Vec<SpenderExposure> = spenders.into_values().collect();
+        for spender in &mut spenders {
+            spender.tokens.sort_by(|a, b| b.value_at_risk_quote.total_cmp(&a.value_at_risk_quote));
+        }
+        spenders.sort_by(|a, b| {
+            b.value_at_risk_quote
+                .total_cmp(&a.value_at_risk_quote)
+                .then_with(|| a.spender_address.cmp(&b.spender_address))
+        });
+
+        Self {
+            address: balances.address.clone().or_else(|| approvals.address.clone()),
+            chain_name: balances.chain_name.clone().or_else(|| approvals.chain_name.clone()),
+            quote_currency: balances.quote_currency.clone(),
+            total_balance_quote: to_f64(balances.total_value()),
+            total_at_risk_quote: token_at_risk.values().sum(),
+            spenders,
+        }
+    }
+
+    /// Share of the wallet's value exposed to at least one spender, from 0 to 1.
+    pub fn share_at_risk(&self) -> f64 {
+        if self.total_balance_quote > 0.0 {
+            (self.total_at_risk_quote / self.total_balance_quote).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Spenders exposing at least `min_quote`.
+    pub fn spenders_above(&self, min_quote: f64) -> impl Iterator<Item = &SpenderExposure> {
+        self.spenders.iter().filter(move |s| s.value_at_risk_quote >= min_quote)
+    }
+}
+
+fn is_unlimited(allowance: Option<&str>) -> bool {
+    allowance.is_some_and(|a| a.trim().eq_ignore_ascii_case("unlimited"))
+}
+
+/// Allowance converted to the quote currency, if it can be priced.
+fn allowance_quote(allowance: Option<&str>, decimals: u32, quote_rate: Option<f64>) -> Option<f64> {
+    let raw: f64 = allowance?.trim().parse().ok()?;
+    Some(raw / 10f64.powi(decimals as i32) * quote_rate?)
+}
+
+fn ticker(approval: &ApprovalItem, balance: Option<&BalanceItem>) -> Option<String> {
+    approval
+        .ticker_symbol
+        .clone()
+        .or_else(|| balance.and_then(|b| b.contract_ticker_symbol.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_exposure_ranked_by_value_at_risk() {
+        let balances: BalancesData = serde_json::from_value(json!({
+            "address": "0xwallet",
+            "chain_name": "eth-mainnet",
+            "quote_currency": "USD",
+            "items": [
+                { "contract_address": "0xUSDC", "balance": "1000000000", "contract_decimals": 6, "quote_rate": 1.0, "quote": 1000.0 },
+                { "contract_address": "0xweth", "balance": "1000000000000000000", "contract_decimals": 18, "quote_rate": 3000.0, "quote": 3000.0 },
+                { "contract_address": "0xdai", "balance": "0", "contract_decimals": 18, "quote_rate": 1.0, "quote": 500.0 },
+            ],
+        }))
+        .unwrap();
+        let approvals: ApprovalsData = serde_json::from_value(json!({
+            "items": [
+                {
+                    "token_address": "0xusdc",
+                    "ticker_symbol": "USDC",
+                    "spenders": [
+                        { "spender_address": "0xRouter", "spender_address_label": "Uniswap", "allowance": "UNLIMITED" },
+                        { "spender_address": "0xold", "allowance": "250000000" },
+                    ],
+                },
+                {
+                    "token_address": "0xweth",
+                    "ticker_symbol": "WETH",
+                    "spenders": [{ "spender_address": "0xrouter", "allowance": "500000000000000000" }],
+                },
+            ],
+        }))
+        .unwrap();
+
+        let report = ExposureReport::build(&balances, &approvals);
+        assert_eq!(report.spenders.len(), 2);
+
+        let router = &report.spenders[0];
+        assert_eq!(router.name(), "Uniswap");
+        assert!(router.unlimited);
+        assert_eq!(router.value_at_risk_quote, 2500.0);
+        assert_eq!(router.tokens[0].ticker_symbol.as_deref(), Some("WETH"));
+
+        let old = &report.spenders[1];
+        assert_eq!(old.name(), "0xold");
+        assert_eq!(old.value_at_risk_quote, 250.0);
+
+        // USDC counted once at its full balance, WETH at the partial allowance.
+        assert_eq!(report.total_at_risk_quote, 2500.0);
+        assert_eq!(report.total_balance_quote, 4500.0);
+        assert!((report.share_at_risk() - 2500.0 / 4500.0).abs() < 1e-12);
+        assert_eq!(report.spenders_above(1000.0).count(), 1);
+    }
+}
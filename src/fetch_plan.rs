@@ -0,0 +1,485 @@
+use crate::bulk::{BulkConfig, BulkExecutor};
+use crate::models::balances::{BalancesResponse, Erc20TransferItem};
+use crate::models::nfts::NftsResponse;
+use crate::models::transactions::TransactionsResponse;
+use crate::{
+    BalancesOptions, Endpoint, Erc20TransfersOptions, Error, GoldRushClient, NftOptions, ProgressReporter, RateLimiter,
+    Result, TxOptions,
+};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, instrument};
+
+/// Relative priority of a step; higher-priority steps are started first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// One request (or paged series of requests) in a [`FetchPlan`].
+#[derive(Debug, Clone)]
+pub enum FetchStep {
+    /// Token balances for an address on one chain.
+    Balances { chain: String, address: String, options: Option<BalancesOptions> },
+    /// Every ERC-20 transfer of one token, optionally limited to the last `since_days` days.
+    Transfers { chain: String, address: String, contract: String, since_days: Option<u32> },
+    /// One page of transactions for an address.
+    Transactions { chain: String, address: String, options: Option<TxOptions> },
+    /// NFTs held by an address on one chain.
+    NftHoldings { chain: String, address: String, options: Option<NftOptions> },
+}
+
+impl FetchStep {
+    /// Short human-readable description, e.g. `balances eth-mainnet 0xabc…`.
+    pub fn describe(&self) -> String {
+        match self {
+            FetchStep::Balances { chain, address, .. } => format!("balances {} {}", chain, address),
+            FetchStep::Transfers { chain, address, contract, .. } => {
+                format!("transfers {} {} {}", chain, address, contract)
+            }
+            FetchStep::Transactions { chain, address, .. } => format!("transactions {} {}", chain, address),
+            FetchStep::NftHoldings { chain, address, .. } => format!("nfts {} {}", chain, address),
+        }
+    }
+
+    /// Endpoints this step calls at least once.
+    fn endpoints(&self) -> Vec<Endpoint> {
+        match self {
+            FetchStep::Balances { .. } => vec![Endpoint::BalancesV2],
+            FetchStep::Transfers { since_days: Some(_), .. } => vec![Endpoint::BlockHeights, Endpoint::Erc20Transfers],
+            FetchStep::Transfers { since_days: None, .. } => vec![Endpoint::Erc20Transfers],
+            FetchStep::Transactions { .. } => vec![Endpoint::TransactionsV3],
+            FetchStep::NftHoldings { .. } => vec![Endpoint::NftBalances],
+        }
+    }
+}
+
+/// Data returned by a step.
+#[derive(Debug, Clone)]
+pub enum FetchOutput {
+    Balances(BalancesResponse),
+    Transfers(Vec<Erc20TransferItem>),
+    Transactions(TransactionsResponse),
+    NftHoldings(NftsResponse),
+}
+
+/// Upfront credit estimate for a plan, from [`Endpoint::credit_cost`].
+#[derive(Debug, Clone, Default)]
+pub struct CreditEstimate {
+    /// Estimated credits, assuming one request per call.
+    pub total: f64,
+    pub per_endpoint: BTreeMap<Endpoint, f64>,
+    /// Whether some steps page through results, so the real cost may be higher.
+    pub open_ended: bool,
+}
+
+/// Progress of a running plan, reported after each step finishes.
+#[derive(Debug, Clone)]
+pub struct FetchProgress {
+    pub completed: usize,
+    pub total: usize,
+    /// Index of the finished step in the plan.
+    pub index: usize,
+    pub description: String,
+    pub succeeded: bool,
+}
+
+/// Callback receiving [`FetchProgress`] updates.
+pub type ProgressCallback = Arc<dyn Fn(&FetchProgress) + Send + Sync>;
+
+/// Result of one step.
+#[derive(Debug)]
+pub struct FetchResult {
+    pub step: FetchStep,
+    pub outcome: Result<FetchOutput>,
+}
+
+/// Outcome of a plan, one entry per step in plan order.
+#[derive(Debug)]
+pub struct FetchReport {
+    pub estimate: CreditEstimate,
+    pub results: Vec<FetchResult>,
+}
+
+impl FetchReport {
+    /// Whether every step succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(|r| r.outcome.is_ok())
+    }
+
+    /// Successful steps with their output.
+    pub fn successes(&self) -> impl Iterator<Item = (&FetchStep, &FetchOutput)> {
+        self.results.iter().filter_map(|r| r.outcome.as_ref().ok().map(|o| (&r.step, o)))
+    }
+
+    /// Failed steps with their error.
+    pub fn errors(&self) -> impl Iterator<Item = (&FetchStep, &Error)> {
+        self.results.iter().filter_map(|r| r.outcome.as_ref().err().map(|e| (&r.step, e)))
+    }
+}
+
+/// A declarative set of fetches executed together.
+///
+/// Steps share one concurrency limit and optional rate limiter, start in
+/// priority order, and fail independently.
+///
+/// ```rust,no_run
+/// use goldrush_sdk::{FetchPlan, GoldRushClient, Priority};
+///
+/// # async fn example(client: GoldRushClient) -> goldrush_sdk::Result<()> {
+/// let wallet = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+/// let plan = FetchPlan::new()
+///     .balances(["eth-mainnet", "base-mainnet", "arbitrum-mainnet"], wallet)
+///     .transfers("eth-mainnet", wallet, "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", Some(90))
+///     .step(goldrush_sdk::FetchStep::NftHoldings {
+///         chain: "eth-mainnet".into(),
+///         address: wallet.into(),
+///         options: None,
+///     }, Priority::Low)
+///     .budget(50.0)
+///     .on_progress(|p| println!("{}/{} {}", p.completed, p.total, p.description));
+///
+/// println!("about {} credits", plan.estimate().total);
+/// let report = plan.execute(&client).await?;
+/// for (step, error) in report.errors() {
+///     eprintln!("{} failed: {}", step.describe(), error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct FetchPlan {
+    steps: Vec<(FetchStep, Priority)>,
+    max_concurrency: Option<usize>,
+    budget: Option<f64>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    on_progress: Option<ProgressCallback>,
+    progress: Option<ProgressReporter>,
+}
+
+impl FetchPlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a step.
+    pub fn step(mut self, step: FetchStep, priority: Priority) -> Self {
+        self.steps.push((step, priority));
+        self
+    }
+
+    /// Token balances for `address` on each chain, at high priority.
+    pub fn balances<I, S>(mut self, chains: I, address: &str) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for chain in chains {
+            self = self.step(
+                FetchStep::Balances { chain: chain.as_ref().to_string(), address: address.to_string(), options: None },
+                Priority::High,
+            );
+        }
+        self
+    }
+
+    /// All transfers of `contract` for `address`, optionally over the last `since_days` days.
+    pub fn transfers(self, chain: &str, address: &str, contract: &str, since_days: Option<u32>) -> Self {
+        self.step(
+            FetchStep::Transfers {
+                chain: chain.to_string(),
+                address: address.to_string(),
+                contract: contract.to_string(),
+                since_days,
+            },
+            Priority::Normal,
+        )
+    }
+
+    /// NFTs held by `address` on `chain`.
+    pub fn nft_holdings(self, chain: &str, address: &str) -> Self {
+        self.step(
+            FetchStep::NftHoldings { chain: chain.to_string(), address: address.to_string(), options: None },
+            Priority::Normal,
+        )
+    }
+
+    /// Limit how many steps run at once (default 8).
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.max_concurrency = Some(max);
+        self
+    }
+
+    /// Refuse to run if the upfront estimate exceeds `credits`.
+    pub fn budget(mut self, credits: f64) -> Self {
+        self.budget = Some(credits);
+        self
+    }
+
+    /// Acquire a token from `limiter` before each step.
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    pub fn on_progress(mut self, f: impl Fn(&FetchProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Report uniform [`Progress`](crate::Progress) updates as steps finish.
+    pub fn progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Estimate the credits the plan will use.
+    pub fn estimate(&self) -> CreditEstimate {
+        let mut estimate = CreditEstimate::default();
+        for (step, _) in &self.steps {
+            for endpoint in step.endpoints() {
+                let cost = endpoint.credit_cost();
+                estimate.total += cost;
+                *estimate.per_endpoint.entry(endpoint).or_insert(0.0) += cost;
+            }
+            estimate.open_ended |= matches!(step, FetchStep::Transfers { .. });
+        }
+        estimate
+    }
+
+    /// Run every step and collect per-step results.
+    ///
+    /// Fails before sending anything if the estimate exceeds the budget.
+    #[instrument(skip(self, client), fields(steps = self.steps.len()))]
+    pub async fn execute(self, client: &GoldRushClient) -> Result<FetchReport> {
+        let estimate = self.estimate();
+        if let Some(budget) = self.budget.filter(|b| estimate.total > *b) {
+            return Err(Error::Config(format!(
+                "Fetch plan needs an estimated {:.1} credits, over the budget of {:.1}",
+                estimate.total, budget
+            )));
+        }
+        info!(credits = %estimate.total, "Executing fetch plan");
+
+        // Stable sort keeps plan order within a priority.
+        let mut order: Vec<usize> = (0..self.steps.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.steps[i].1));
+
+        let client = Arc::new(PlanClient::new(client));
+        let total = self.steps.len();
+        let completed = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = order
+            .iter()
+            .map(|&index| {
+                let step = self.steps[index].0.clone();
+                let client = Arc::clone(&client);
+                let completed = Arc::clone(&completed);
+                let on_progress = self.on_progress.clone();
+                move || {
+                    let step = step.clone();
+                    let client = Arc::clone(&client);
+                    let completed = Arc::clone(&completed);
+                    let on_progress = on_progress.clone();
+                    async move {
+                        let outcome = client.run(&step).await;
+                        let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                        if let Some(callback) = on_progress {
+                            callback(&FetchProgress {
+                                completed: done,
+                                total,
+                                index,
+                                description: step.describe(),
+                                succeeded: outcome.is_ok(),
+                            });
+                        }
+                        outcome
+                    }
+                }
+            })
+            .collect();
+
+        // Requests already retry inside the client; retrying whole steps here
+        // would repeat paged fetches and double-report progress.
+        let config = BulkConfig {
+            max_concurrency: self.max_concurrency.unwrap_or(BulkConfig::default().max_concurrency),
+            max_retries: 0,
+            ..Default::default()
+        };
+        let mut executor = BulkExecutor::new(config);
+        if let Some(limiter) = self.rate_limiter {
+            executor = executor.with_rate_limiter(limiter);
+        }
+        if let Some(reporter) = self.progress {
+            executor = executor.with_progress(reporter);
+        }
+        let outcomes = executor.run(tasks).await.outcomes;
+
+        let mut slots: Vec<Option<Result<FetchOutput>>> = (0..total).map(|_| None).collect();
+        for (outcome, &index) in outcomes.into_iter().zip(&order) {
+            slots[index] = Some(outcome);
+        }
+        let results = self
+            .steps
+            .into_iter()
+            .zip(slots)
+            .map(|((step, _), outcome)| FetchResult {
+                step,
+                outcome: outcome.unwrap_or_else(|| Err(Error::Config("Fetch step did not run".to_string()))),
+            })
+            .collect();
+
+        Ok(FetchReport { estimate, results })
+    }
+}
+
+impl std::fmt::Debug for FetchPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FetchPlan")
+            .field("steps", &self.steps)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("budget", &self.budget)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Service handles shared by every task of a plan.
+struct PlanClient {
+    balances: crate::BalanceService,
+    transactions: crate::TransactionService,
+    nfts: crate::NftService,
+    base: crate::BaseService,
+}
+
+impl PlanClient {
+    fn new(client: &GoldRushClient) -> Self {
+        Self {
+            balances: client.balance_service(),
+            transactions: client.transaction_service(),
+            nfts: client.nft_service(),
+            base: client.base_service(),
+        }
+    }
+
+    async fn run(&self, step: &FetchStep) -> Result<FetchOutput> {
+        match step {
+            FetchStep::Balances { chain, address, options } => self
+                .balances
+                .get_token_balances_for_wallet_address(chain, address.as_str(), options.clone())
+                .await
+                .map(FetchOutput::Balances),
+            FetchStep::Transfers { chain, address, contract, since_days } => {
+                self.transfers(chain, address, contract, *since_days).await.map(FetchOutput::Transfers)
+            }
+            FetchStep::Transactions { chain, address, options } => self
+                .transactions
+                .get_all_transactions_for_address(chain, address.as_str(), options.clone())
+                .await
+                .map(FetchOutput::Transactions),
+            FetchStep::NftHoldings { chain, address, options } => self
+                .nfts
+                .get_nfts_for_address(chain, address.as_str(), options.clone())
+                .await
+                .map(FetchOutput::NftHoldings),
+        }
+    }
+
+    async fn transfers(
+        &self,
+        chain: &str,
+        address: &str,
+        contract: &str,
+        since_days: Option<u32>,
+    ) -> Result<Vec<Erc20TransferItem>> {
+        let mut options = Erc20TransfersOptions::new().contract_address(contract);
+        if let Some(days) = since_days {
+            let start = date_days_ago(days);
+            let heights = self.base.get_block_heights(chain, &start, "latest", None).await?;
+            if let Some(height) = heights.data.and_then(|d| d.items.into_iter().find_map(|b| b.height)) {
+                options = options.starting_block(height);
+            }
+        }
+
+        let mut items = Vec::new();
+        for page in 0.. {
+            let response = self
+                .balances
+                .get_erc20_transfers_for_wallet_address(chain, address, Some(options.clone().page_number(page)))
+                .await?;
+            let has_more = response.pagination.as_ref().and_then(|p| p.has_more).unwrap_or(false);
+            let batch = response.data.map(|d| d.items).unwrap_or_default();
+            let empty = batch.is_empty();
+            items.extend(batch);
+            if !has_more || empty {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// UTC date `days` days ago as `YYYY-MM-DD`.
+fn date_days_ago(days: u32) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    civil_date((now / 86_400) as i64 - days as i64)
+}
+
+/// Format days since 1970-01-01 as a proleptic Gregorian `YYYY-MM-DD`.
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(19_782), "2024-02-29");
+        assert_eq!(civil_date(-1), "1969-12-31");
+    }
+
+    #[test]
+    fn test_estimate_and_budget() {
+        let plan = FetchPlan::new()
+            .balances(["eth-mainnet", "base-mainnet", "arbitrum-mainnet"], "0xabc")
+            .transfers("eth-mainnet", "0xabc", "0xtoken", Some(90))
+            .nft_holdings("eth-mainnet", "0xabc");
+
+        let estimate = plan.estimate();
+        assert_eq!(estimate.per_endpoint[&Endpoint::BalancesV2], 3.0);
+        assert!(estimate.per_endpoint.contains_key(&Endpoint::BlockHeights));
+        assert!(estimate.open_ended);
+        assert_eq!(estimate.total, 3.0 + 0.1 + 1.0 + 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_over_budget_plan_sends_nothing() {
+        let client = GoldRushClient::new("cqt_rQ4mW8kP2vB7nX3jL9sT5yH1gF", crate::ClientConfig::new("http://127.0.0.1:9")).unwrap();
+        let plan = FetchPlan::new().balances(["eth-mainnet", "base-mainnet"], "0xabc").budget(1.0);
+        let err = plan.execute(&client).await.unwrap_err();
+        assert!(matches!(err, Error::Config(msg) if msg.contains("budget")));
+    }
+}
@@ -0,0 +1,157 @@
+//! Wallet first-seen and funding-source tracing.
+//!
+//! [`AllChainsService::trace_funding`](crate::AllChainsService::trace_funding)
+//! finds the chain a wallet was first active on, takes the sender of its
+//! earliest incoming transaction carrying native value as the funder, and can
+//! repeat that for the funder, hop by hop, across chains.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{FundingTraceOptions, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let trace = client.all_chains_service()
+//!     .trace_funding("0x...", FundingTraceOptions::new().max_hops(3))
+//!     .await?;
+//! for hop in &trace.hops {
+//!     println!("{} funded by {:?}", hop.address, hop.funding.as_ref().map(|f| &f.funder));
+//! }
+//! println!("stopped: {:?}", trace.stop);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::{normalize_address, same_address};
+use crate::models::base::AddressActivityItem;
+use crate::models::transactions::TransactionItem;
+
+/// The transaction that first sent native value to a wallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingTransaction {
+    pub chain_name: String,
+    pub tx_hash: String,
+    pub funder: String,
+    /// Label of the funder, e.g. an exchange hot wallet, if known.
+    pub funder_label: Option<String>,
+    /// Value in the chain's smallest unit.
+    pub value: String,
+    pub block_height: Option<u64>,
+    pub block_signed_at: Option<String>,
+}
+
+impl FundingTransaction {
+    /// The earliest successful transaction in `transactions` that sent native
+    /// value to `address`.
+    pub fn find(chain_name: &str, address: &str, transactions: &[TransactionItem]) -> Option<Self> {
+        transactions
+            .iter()
+            .filter(|tx| tx.successful != Some(false) && tx.has_native_value())
+            .filter(|tx| tx.to_address.as_deref().is_some_and(|to| same_address(to, address)))
+            .min_by_key(|tx| (tx.block_height.is_none(), tx.block_height, tx.block_signed_at.clone()))
+            .map(|tx| Self {
+                chain_name: chain_name.to_string(),
+                tx_hash: tx.tx_hash.clone(),
+                funder: normalize_address(&tx.from_address),
+                funder_label: tx.from_address_label.clone(),
+                value: tx.value.clone(),
+                block_height: tx.block_height,
+                block_signed_at: tx.block_signed_at.clone(),
+            })
+    }
+}
+
+/// One wallet along a funding trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingHop {
+    pub address: String,
+    /// Chain the wallet was active on first.
+    pub first_seen_chain: Option<String>,
+    pub first_seen_at: Option<String>,
+    /// `None` when none of the wallet's earliest transactions funded it.
+    pub funding: Option<FundingTransaction>,
+}
+
+/// Why a funding trace ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FundingStop {
+    /// `max_hops` funders were followed.
+    MaxHops,
+    /// The last wallet has no activity on the traced chains.
+    NoActivity,
+    /// The last wallet's earliest transactions include no incoming value transfer,
+    /// e.g. because it was funded by an internal transaction or a token transfer.
+    FunderNotFound,
+    /// The last funder already appears earlier in the trace.
+    Cycle,
+}
+
+/// A wallet and the chain of wallets that funded it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingTrace {
+    pub address: String,
+    /// The traced wallet first, then each funder in turn.
+    pub hops: Vec<FundingHop>,
+    pub stop: FundingStop,
+}
+
+impl FundingTrace {
+    /// Funders in order, nearest first.
+    pub fn funders(&self) -> impl Iterator<Item = &str> {
+        self.hops.iter().filter_map(|hop| hop.funding.as_ref().map(|f| f.funder.as_str()))
+    }
+
+    /// The furthest funder reached.
+    pub fn origin(&self) -> Option<&str> {
+        self.funders().last()
+    }
+}
+
+/// Chains an address was active on, first seen first, with the first-seen time.
+pub(crate) fn first_seen_order(
+    items: Vec<AddressActivityItem>, chains: Option<&[String]>, include_testnets: bool,
+) -> Vec<(String, Option<String>)> {
+    let mut chains: Vec<_> = items
+        .into_iter()
+        .filter(|item| include_testnets || item.is_testnet != Some(true))
+        .filter_map(|item| Some((item.chain_name?, item.first_seen_at)))
+        .filter(|(name, _)| chains.map_or(true, |chains| chains.iter().any(|c| c == name)))
+        .collect();
+    chains.sort_by(|a, b| (a.1.is_none(), &a.1).cmp(&(b.1.is_none(), &b.1)));
+    chains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_finds_earliest_incoming_value() {
+        let txs: Vec<TransactionItem> = serde_json::from_value(json!([
+            { "tx_hash": "0x3", "from_address": "0xB", "to_address": "0xabc", "value": "5", "block_height": 30 },
+            { "tx_hash": "0x1", "from_address": "0xabc", "to_address": "0xdef", "value": "1", "block_height": 10 },
+            { "tx_hash": "0x2", "from_address": "0xC", "to_address": "0xABC", "value": "0", "block_height": 20 },
+            { "tx_hash": "0x4", "from_address": "0xD", "to_address": "0xAbC", "value": "7", "block_height": 25,
+              "successful": false },
+        ]))
+        .unwrap();
+        let funding = FundingTransaction::find("eth-mainnet", "0xabc", &txs).unwrap();
+        assert_eq!(funding.tx_hash, "0x3");
+        assert_eq!(funding.funder, "0xb");
+        assert!(FundingTransaction::find("eth-mainnet", "0xdef", &txs[2..]).is_none());
+    }
+
+    #[test]
+    fn test_first_seen_order() {
+        let items: Vec<AddressActivityItem> = serde_json::from_value(json!([
+            { "chain_name": "base-mainnet", "first_seen_at": "2023-08-01T00:00:00Z" },
+            { "chain_name": "eth-sepolia", "first_seen_at": "2019-01-01T00:00:00Z", "is_testnet": true },
+            { "chain_name": "matic-mainnet" },
+            { "chain_name": "eth-mainnet", "first_seen_at": "2021-05-01T00:00:00Z" },
+        ]))
+        .unwrap();
+        let names = |order: Vec<(String, Option<String>)>| order.into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+        assert_eq!(names(first_seen_order(items.clone(), None, false)), ["eth-mainnet", "base-mainnet", "matic-mainnet"]);
+        let only = ["base-mainnet".to_string()];
+        assert_eq!(names(first_seen_order(items, Some(&only), false)), ["base-mainnet"]);
+    }
+}
@@ -0,0 +1,234 @@
+//! Quote-currency normalization across responses.
+//!
+//! Responses fetched with different `quote-currency` settings (or none, which
+//! the API treats as USD) can't be summed directly. A [`QuoteNormalizer`]
+//! holds one [`FxRate`] per source currency and rewrites every quote field of
+//! a response (`quote`, `quote_rate`, `gas_quote`, ...) into its target
+//! currency, returning the rates it applied so their timestamps can be kept
+//! alongside the converted figures.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{GoldRushClient, QuoteCurrency};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let usd = client.balance_service().get_token_balances_for_wallet_address("eth-mainnet", "0x...", None).await?;
+//! let eur = client.balance_service().get_token_balances_for_wallet_address("base-mainnet", "0x...", None).await?;
+//!
+//! let normalized = client.pricing_service().normalize_quotes(QuoteCurrency::GBP, &[usd, eur]).await?;
+//! for response in &normalized {
+//!     for rate in &response.applied {
+//!         println!("{} -> {} at {} (as of {:?})", rate.from, rate.to, rate.rate, rate.as_of);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::money::{classify, MoneyKind};
+use crate::{Error, QuoteCurrency, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// Rate converting quotes in `from` into `to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxRate {
+    pub from: QuoteCurrency,
+    pub to: QuoteCurrency,
+    /// Multiply a `from` quote by this to get a `to` quote.
+    pub rate: f64,
+    /// Date of the prices the rate was derived from, as reported by the API.
+    pub as_of: Option<String>,
+    /// When the rate was fetched.
+    pub fetched_at: SystemTime,
+}
+
+impl FxRate {
+    pub fn new(from: QuoteCurrency, to: QuoteCurrency, rate: f64) -> Self {
+        Self { from, to, rate, as_of: None, fetched_at: SystemTime::now() }
+    }
+
+    pub fn as_of(mut self, date: impl Into<String>) -> Self {
+        self.as_of = Some(date.into());
+        self
+    }
+}
+
+/// A response with every quote converted to one currency.
+#[derive(Debug, Clone)]
+pub struct Normalized<T> {
+    pub value: T,
+    pub currency: QuoteCurrency,
+    /// Rates applied, one per source currency found; empty if nothing needed converting.
+    pub applied: Vec<FxRate>,
+}
+
+/// Converts quote fields into a target currency using known rates.
+#[derive(Debug, Clone)]
+pub struct QuoteNormalizer {
+    target: QuoteCurrency,
+    rates: HashMap<QuoteCurrency, FxRate>,
+}
+
+impl QuoteNormalizer {
+    pub fn new(target: QuoteCurrency) -> Self {
+        Self { target, rates: HashMap::new() }
+    }
+
+    pub fn target(&self) -> QuoteCurrency {
+        self.target
+    }
+
+    /// Add a rate into the target currency, replacing any for the same source.
+    pub fn with_rate(mut self, rate: FxRate) -> Result<Self> {
+        if rate.to != self.target {
+            return Err(Error::Config(format!(
+                "Rate converts into {}, but the normalizer targets {}",
+                rate.to, self.target
+            )));
+        }
+        self.rates.insert(rate.from, rate);
+        Ok(self)
+    }
+
+    pub fn rate(&self, from: QuoteCurrency) -> Option<&FxRate> {
+        self.rates.get(&from)
+    }
+
+    /// Convert one quote, or `None` without a rate for `from`.
+    pub fn convert(&self, quote: f64, from: QuoteCurrency) -> Option<f64> {
+        if from == self.target {
+            return Some(quote);
+        }
+        self.rates.get(&from).map(|rate| quote * rate.rate)
+    }
+
+    /// Convert every quote field of `value` into the target currency.
+    ///
+    /// Fields are read in the currency named by the nearest enclosing
+    /// `quote_currency`, or USD when there is none. `quote_currency` fields are
+    /// rewritten and `pretty_*` quote strings cleared, since they would no
+    /// longer match. Fails if a currency has no rate.
+    pub fn normalize<T: Serialize + DeserializeOwned>(&self, value: &T) -> Result<Normalized<T>> {
+        let mut json = serde_json::to_value(value)?;
+        let mut applied = Vec::new();
+        self.walk(&mut json, QuoteCurrency::default(), &mut applied)?;
+        Ok(Normalized {
+            value: serde_json::from_value(json)?,
+            currency: self.target,
+            applied: applied.iter().filter_map(|from| self.rates.get(from).cloned()).collect(),
+        })
+    }
+
+    fn walk(&self, value: &mut Value, currency: QuoteCurrency, applied: &mut Vec<QuoteCurrency>) -> Result<()> {
+        match value {
+            Value::Object(map) => {
+                let currency = match map.get("quote_currency").and_then(Value::as_str) {
+                    Some(code) => parse(code)?,
+                    None => currency,
+                };
+                let needs_rate = currency != self.target;
+                if let Some(field) = map.get_mut("quote_currency").filter(|f| f.is_string()) {
+                    *field = Value::from(self.target.code());
+                }
+                for (key, field) in map.iter_mut() {
+                    match (classify(key), field.as_f64()) {
+                        (Some(MoneyKind::Quote), Some(quote)) if needs_rate => {
+                            let converted = self.convert(quote, currency).ok_or_else(|| {
+                                Error::Config(format!("No {} to {} rate to normalize '{}'", currency, self.target, key))
+                            })?;
+                            *field = Value::from(converted);
+                            if !applied.contains(&currency) {
+                                applied.push(currency);
+                            }
+                        }
+                        (Some(MoneyKind::Quote), _) | (Some(MoneyKind::Amount), _) => {}
+                        _ if needs_rate && key.starts_with("pretty_") && is_quote(&key["pretty_".len()..]) => {
+                            *field = Value::Null;
+                        }
+                        _ => self.walk(field, currency, applied)?,
+                    }
+                }
+                Ok(())
+            }
+            Value::Array(items) => items.iter_mut().try_for_each(|item| self.walk(item, currency, applied)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Currencies the quote fields of `value` are in, as [`QuoteNormalizer::normalize`] reads them.
+pub(crate) fn currencies_in<T: Serialize>(value: &T) -> Result<Vec<QuoteCurrency>> {
+    fn walk(value: &Value, currency: QuoteCurrency, found: &mut Vec<QuoteCurrency>) -> Result<()> {
+        match value {
+            Value::Object(map) => {
+                let currency = match map.get("quote_currency").and_then(Value::as_str) {
+                    Some(code) => parse(code)?,
+                    None => currency,
+                };
+                if map.keys().any(|k| is_quote(k)) && !found.contains(&currency) {
+                    found.push(currency);
+                }
+                map.values().try_for_each(|v| walk(v, currency, found))
+            }
+            Value::Array(items) => items.iter().try_for_each(|v| walk(v, currency, found)),
+            _ => Ok(()),
+        }
+    }
+    let mut found = Vec::new();
+    walk(&serde_json::to_value(value)?, QuoteCurrency::default(), &mut found)?;
+    Ok(found)
+}
+
+fn parse(code: &str) -> Result<QuoteCurrency> {
+    code.parse().map_err(Error::Config)
+}
+
+fn is_quote(field: &str) -> bool {
+    classify(field) == Some(MoneyKind::Quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::{Quote, QuoteValue};
+    use crate::models::balances::BalancesData;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_mixed_currencies() {
+        let eur: BalancesData = serde_json::from_value(json!({
+            "quote_currency": "EUR",
+            "items": [{ "contract_address": "0xa", "balance": "5", "quote_rate": 2.0, "quote": 10.0, "pretty_quote": "€10.00" }],
+        }))
+        .unwrap();
+        let usd: BalancesData = serde_json::from_value(json!({
+            "items": [{ "contract_address": "0xb", "balance": "5", "quote": 5.0, "pretty_quote": "$5.00" }],
+        }))
+        .unwrap();
+
+        let normalizer = QuoteNormalizer::new(QuoteCurrency::USD)
+            .with_rate(FxRate::new(QuoteCurrency::EUR, QuoteCurrency::USD, 1.1).as_of("2024-06-01"))
+            .unwrap();
+        assert!(normalizer.clone().with_rate(FxRate::new(QuoteCurrency::EUR, QuoteCurrency::GBP, 0.9)).is_err());
+
+        let converted = normalizer.normalize(&eur).unwrap();
+        assert_eq!(converted.value.quote_currency.as_deref(), Some("USD"));
+        assert!((converted.value.items[0].quote.unwrap().as_f64() - 11.0).abs() < 1e-9);
+        assert!((converted.value.items[0].quote_rate.unwrap().as_f64() - 2.2).abs() < 1e-9);
+        let pretty = |data: &BalancesData| data.items[0].metadata.as_ref().unwrap()["pretty_quote"].clone();
+        assert_eq!(pretty(&converted.value), Value::Null);
+        assert_eq!(converted.applied[0].as_of.as_deref(), Some("2024-06-01"));
+
+        let untouched = normalizer.normalize(&usd).unwrap();
+        assert_eq!(untouched.value.items[0].quote, Some(Quote::from_f64(5.0)));
+        assert_eq!(pretty(&untouched.value), json!("$5.00"));
+        assert!(untouched.applied.is_empty());
+
+        assert_eq!(currencies_in(&eur).unwrap(), vec![QuoteCurrency::EUR]);
+        assert_eq!(currencies_in(&usd).unwrap(), vec![QuoteCurrency::USD]);
+        assert!(QuoteNormalizer::new(QuoteCurrency::GBP).normalize(&eur).is_err());
+    }
+}
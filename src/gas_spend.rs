@@ -0,0 +1,234 @@
+//! Gas fees a wallet paid, by chain and by contract called.
+//!
+//! [`TransactionService::gas_spend_report`](crate::TransactionService::gas_spend_report)
+//! walks the wallet's transactions on each chain newest first, stopping once
+//! it passes the start of the date range, and sums the fees of every
+//! transaction the wallet sent, failed ones included.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{CalendarDate, GasSpendOptions, GoldRushClient};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let options = GasSpendOptions::new()
+//!     .from(CalendarDate::from_ymd(2024, 1, 1).unwrap())
+//!     .to(CalendarDate::from_ymd(2024, 3, 31).unwrap());
+//! let report = client.transaction_service()
+//!     .gas_spend_report(&["eth-mainnet", "base-mainnet"], "0x...", Some(options))
+//!     .await?;
+//! println!("total {:.2}", report.total_quote());
+//! for chain in &report.chains {
+//!     println!("{}: {} native over {} txs", chain.chain_name, chain.spend.fee_native(), chain.spend.tx_count);
+//!     for contract in chain.contracts.iter().take(5) {
+//!         println!("  {}: {:.2}", contract.name(), contract.spend.fee_quote);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::activity::CalendarDate;
+use crate::address::{normalize_address, same_address};
+use crate::models::transactions::TransactionItem;
+use crate::utils::format_ether;
+use std::collections::HashMap;
+
+/// Gas fees summed over some transactions.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GasSpend {
+    pub tx_count: u64,
+    /// Fees in the chain's native token, in wei.
+    pub fee_wei: u128,
+    /// Fees in the quote currency, over the transactions that had a quote.
+    pub fee_quote: f64,
+    /// Transactions whose fee had no quote value.
+    pub unpriced: u64,
+}
+
+impl GasSpend {
+    /// Fees in whole native tokens.
+    pub fn fee_native(&self) -> String {
+        format_ether(self.fee_wei)
+    }
+
+    fn record(&mut self, tx: &TransactionItem) {
+        self.tx_count += 1;
+        self.fee_wei = self.fee_wei.saturating_add(tx.total_fee_wei().unwrap_or(0));
+        match tx.total_fee_quote() {
+            Some(quote) => self.fee_quote += quote,
+            None => self.unpriced += 1,
+        }
+    }
+}
+
+/// Gas paid calling one contract or address.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContractGasSpend {
+    /// The called address; `None` for contract deployments.
+    pub address: Option<String>,
+    /// Label reported by the API, e.g. a protocol name.
+    pub label: Option<String>,
+    pub spend: GasSpend,
+}
+
+impl ContractGasSpend {
+    /// The label, or the address, or `"contract creation"`.
+    pub fn name(&self) -> &str {
+        self.label.as_deref().or(self.address.as_deref()).unwrap_or("contract creation")
+    }
+}
+
+/// Gas paid on one chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainGasSpend {
+    pub chain_name: String,
+    pub spend: GasSpend,
+    /// Ranked by quote value, then by transaction count.
+    pub contracts: Vec<ContractGasSpend>,
+}
+
+/// Gas a wallet paid across chains within a date range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasSpendReport {
+    pub address: String,
+    /// First day included, if bounded.
+    pub from: Option<CalendarDate>,
+    /// Last day included, if bounded.
+    pub to: Option<CalendarDate>,
+    /// Ranked by quote value.
+    pub chains: Vec<ChainGasSpend>,
+}
+
+impl GasSpendReport {
+    pub(crate) fn new(address: String, from: Option<CalendarDate>, to: Option<CalendarDate>, mut chains: Vec<ChainGasSpend>) -> Self {
+        chains.sort_by(|a, b| b.spend.fee_quote.total_cmp(&a.spend.fee_quote).then_with(|| a.chain_name.cmp(&b.chain_name)));
+        Self { address, from, to, chains }
+    }
+
+    /// Quote value of all fees, across chains.
+    pub fn total_quote(&self) -> f64 {
+        self.chains.iter().map(|c| c.spend.fee_quote).sum()
+    }
+
+    /// Transactions paid for, across chains.
+    pub fn tx_count(&self) -> u64 {
+        self.chains.iter().map(|c| c.spend.tx_count).sum()
+    }
+
+    /// The chain named `chain_name`, if it was in the report.
+    pub fn chain(&self, chain_name: &str) -> Option<&ChainGasSpend> {
+        self.chains.iter().find(|c| c.chain_name == chain_name)
+    }
+}
+
+/// Running tally behind a [`ChainGasSpend`].
+pub(crate) struct GasTally {
+    chain_name: String,
+    address: String,
+    from: Option<CalendarDate>,
+    to: Option<CalendarDate>,
+    spend: GasSpend,
+    by_contract: HashMap<Option<String>, ContractGasSpend>,
+}
+
+impl GasTally {
+    pub(crate) fn new(chain_name: impl Into<String>, address: impl Into<String>, from: Option<CalendarDate>, to: Option<CalendarDate>) -> Self {
+        Self {
+            chain_name: chain_name.into(),
+            address: address.into(),
+            from,
+            to,
+            spend: GasSpend::default(),
+            by_contract: HashMap::new(),
+        }
+    }
+
+    /// Whether `tx` is older than the range, and so is everything after it in a newest-first walk.
+    pub(crate) fn is_before_range(&self, tx: &TransactionItem) -> bool {
+        let (Some(from), Some(day)) = (self.from, signed_day(tx)) else { return false };
+        day < from
+    }
+
+    /// Count `tx` if the wallet sent it within the range.
+    pub(crate) fn record(&mut self, tx: &TransactionItem) {
+        if !same_address(&tx.from_address, &self.address) || !self.in_range(tx) {
+            return;
+        }
+        self.spend.record(tx);
+        let address = tx.to_address.as_deref().map(normalize_address);
+        let contract = self.by_contract.entry(address.clone()).or_insert_with(|| ContractGasSpend {
+            address,
+            ..ContractGasSpend::default()
+        });
+        if contract.label.is_none() {
+            contract.label = tx.to_address_label.clone();
+        }
+        contract.spend.record(tx);
+    }
+
+    fn in_range(&self, tx: &TransactionItem) -> bool {
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        let Some(day) = signed_day(tx) else { return false };
+        self.from.map_or(true, |from| day >= from) && self.to.map_or(true, |to| day <= to)
+    }
+
+    pub(crate) fn into_chain(self) -> ChainGasSpend {
+        let mut contracts: Vec<ContractGasSpend> = self.by_contract.into_values().collect();
+        contracts.sort_by(|a, b| {
+            b.spend
+                .fee_quote
+                .total_cmp(&a.spend.fee_quote)
+                .then(b.spend.tx_count.cmp(&a.spend.tx_count))
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        ChainGasSpend { chain_name: self.chain_name, spend: self.spend, contracts }
+    }
+}
+
+/// UTC date of the transaction's block.
+fn signed_day(tx: &TransactionItem) -> Option<CalendarDate> {
+    CalendarDate::parse(tx.block_signed_at.as_deref()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sums_fees_sent_within_range_by_contract() {
+        let txs: Vec<TransactionItem> = serde_json::from_value(json!([
+            { "tx_hash": "0x1", "from_address": "0xABC", "to_address": "0xdex", "to_address_label": "DEX", "value": "0",
+              "fees_paid": "2000000000000000", "gas_quote": 5.0, "block_signed_at": "2024-03-02T00:00:00Z" },
+            { "tx_hash": "0x2", "from_address": "0xabc", "to_address": "0xDEX", "value": "0", "successful": false,
+              "fees_paid": "1000000000000000", "block_signed_at": "2024-03-01T12:00:00Z" },
+            { "tx_hash": "0x3", "from_address": "0xfriend", "to_address": "0xabc", "value": "1",
+              "fees_paid": "9", "gas_quote": 1.0, "block_signed_at": "2024-03-01T00:00:00Z" },
+            { "tx_hash": "0x4", "from_address": "0xabc", "value": "0",
+              "fees_paid": "3000000000000000", "gas_quote": 7.0, "block_signed_at": "2024-02-20T00:00:00Z" },
+            { "tx_hash": "0x5", "from_address": "0xabc", "to_address": "0xdex", "value": "0",
+              "fees_paid": "1", "gas_quote": 1.0, "block_signed_at": "2024-01-31T00:00:00Z" },
+        ]))
+        .unwrap();
+
+        let from = CalendarDate::from_ymd(2024, 2, 1);
+        let mut tally = GasTally::new("eth-mainnet", "0xabc", from, None);
+        for tx in &txs {
+            if tally.is_before_range(tx) {
+                break;
+            }
+            tally.record(tx);
+        }
+        let chain = tally.into_chain();
+        assert_eq!((chain.spend.tx_count, chain.spend.unpriced), (3, 1));
+        assert_eq!(chain.spend.fee_native(), "0.006");
+        assert_eq!(chain.spend.fee_quote, 12.0);
+
+        let names: Vec<_> = chain.contracts.iter().map(|c| (c.name(), c.spend.tx_count)).collect();
+        assert_eq!(names, [("contract creation", 1), ("DEX", 2)]);
+
+        let report = GasSpendReport::new("0xabc".to_string(), from, None, vec![chain]);
+        assert_eq!((report.tx_count(), report.total_quote()), (3, 12.0));
+    }
+}
@@ -1,10 +1,186 @@
-//! Query parameter trait for eliminating repetitive Option handling.
+//! Query parameter traits shared by all endpoint option structs.
 
-use reqwest::RequestBuilder;
+use crate::validation::Validator;
+use crate::{Error, Result};
+use reqwest::{RequestBuilder, Url};
 
-/// Trait for applying query parameters to a request builder.
+/// Common behavior for endpoint option structs.
+///
+/// Implementors only describe their query parameters; applying them to a
+/// request, validation and cache-key derivation are shared.
+pub trait EndpointOptions {
+    /// Query parameters as `(name, value)` pairs, in declaration order.
+    fn query_pairs(&self) -> Vec<(&'static str, String)>;
+
+    /// Validate option values before a request is sent.
+    ///
+    /// The default checks `page-size` bounds and that `starting-block` does not
+    /// exceed `ending-block`.
+    fn validate(&self) -> Result<()> {
+        let pairs = self.query_pairs();
+        let get = |name: &str| pairs.iter().find(|(k, _)| *k == name).map(|(_, v)| v.as_str());
+
+        if let Some(size) = get("page-size") {
+            let size = size.parse()
+                .map_err(|_| Error::Config(format!("Invalid page size: {}", size)))?;
+            Validator::validate_page_size(size)?;
+        }
+
+        let block = |name: &str| get(name).and_then(|v| v.parse::<u64>().ok());
+        if let (Some(start), Some(end)) = (block("starting-block"), block("ending-block")) {
+            if start > end {
+                return Err(Error::Config(format!(
+                    "Starting block {} is after ending block {}", start, end
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deterministic key fragment identifying these options, independent of
+    /// the order in which builder methods were called.
+    ///
+    /// This is the exact query string sent with the request; see [`canonical_query`].
+    fn cache_key(&self) -> String {
+        canonical_query(self.query_pairs().iter().map(|(k, v)| (*k, v.as_str())))
+    }
+}
+
+/// Encode query parameters sorted by name, then value.
 ///
-/// Implement this on Options structs to eliminate repetitive `if let Some(x)` boilerplate.
+/// Every SDK request sends its options in this form, so equal options always
+/// produce the same query string.
+pub fn canonical_query<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let mut pairs: Vec<_> = pairs.into_iter().collect();
+    pairs.sort();
+    let mut url = Url::parse("http://localhost/").expect("static URL");
+    if !pairs.is_empty() {
+        url.query_pairs_mut().extend_pairs(pairs);
+    }
+    url.query().unwrap_or_default().to_string()
+}
+
+/// Key for a request URL that ignores the host and query parameter order:
+/// the path, then `?` and the [`canonical_query`] if there is one.
+///
+/// Available on responses as [`ResponseMeta::request_key`](crate::ResponseMeta::request_key).
+pub fn canonical_request_key(url: &Url) -> String {
+    let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    let query = canonical_query(pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+    if query.is_empty() {
+        url.path().to_string()
+    } else {
+        format!("{}?{}", url.path(), query)
+    }
+}
+
+/// Options for endpoints paginated by `page-number`.
+///
+/// Used by [`Pagination::next_options`](crate::Pagination::next_options) to
+/// derive the request for the following page.
+pub trait PagedOptions: EndpointOptions + Clone {
+    /// The requested page number, if set.
+    fn current_page(&self) -> Option<u32>;
+
+    /// A copy of these options requesting `page`.
+    fn at_page(&self, page: u32) -> Self;
+}
+
+/// Implement [`PagedOptions`] for option structs with a `page_number` field.
+macro_rules! impl_paged_options {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $crate::http::query::PagedOptions for $ty {
+                fn current_page(&self) -> Option<u32> {
+                    self.page_number
+                }
+
+                fn at_page(&self, page: u32) -> Self {
+                    Self { page_number: Some(page), ..self.clone() }
+                }
+            }
+        )*
+    };
+}
+pub(crate) use impl_paged_options;
+
+/// Trait for applying query parameters to a request builder.
 pub(crate) trait QueryParams {
     fn apply_to(self, builder: RequestBuilder) -> RequestBuilder;
 }
+
+impl<T: EndpointOptions> QueryParams for T {
+    fn apply_to(self, builder: RequestBuilder) -> RequestBuilder {
+        let mut pairs = self.query_pairs();
+        if pairs.is_empty() {
+            builder
+        } else {
+            pairs.sort();
+            builder.query(&pairs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::balance_service::BalancesOptions;
+    use crate::services::base_service::LogEventsByAddressOptions;
+    use crate::services::transaction_service::TxOptions;
+    use crate::types::QuoteCurrency;
+
+    #[test]
+    fn test_query_pairs_and_cache_key() {
+        let a = TxOptions::new().page_size(10).quote_currency(QuoteCurrency::EUR);
+        let b = TxOptions::new().quote_currency(QuoteCurrency::EUR).page_size(10);
+        assert_eq!(a.cache_key(), b.cache_key());
+        assert_eq!(a.cache_key(), "page-size=10&quote-currency=EUR");
+        assert!(BalancesOptions::new().query_pairs().is_empty());
+    }
+
+    #[test]
+    fn test_canonical_query_and_request_key() {
+        assert_eq!(canonical_query([("b", "x y"), ("a", "1&2"), ("a", "0")]), "a=0&a=1%262&b=x+y");
+        assert_eq!(canonical_query([]), "");
+
+        let url = Url::parse("https://api.covalenthq.com/v1/eth-mainnet/address/0xabc/balances_v2/?quote-currency=EUR&no-spam=true").unwrap();
+        let reordered = Url::parse("http://127.0.0.1:9/v1/eth-mainnet/address/0xabc/balances_v2/?no-spam=true&quote-currency=EUR").unwrap();
+        assert_eq!(canonical_request_key(&url), canonical_request_key(&reordered));
+        assert_eq!(canonical_request_key(&url), "/v1/eth-mainnet/address/0xabc/balances_v2/?no-spam=true&quote-currency=EUR");
+
+        let options = TxOptions::new().quote_currency(QuoteCurrency::EUR).page_size(10);
+        let request = options.clone().apply_to(reqwest::Client::new().get("http://localhost/")).build().unwrap();
+        assert_eq!(request.url().query(), Some(options.cache_key().as_str()));
+    }
+
+    #[test]
+    fn test_default_validation() {
+        assert!(TxOptions::new().page_size(100).validate().is_ok());
+        assert!(TxOptions::new().page_size(0).validate().is_err());
+        assert!(BalancesOptions::new().page_size(5000).validate().is_err());
+
+        let range = LogEventsByAddressOptions::new().starting_block(10).ending_block(5);
+        assert!(range.validate().is_err());
+    }
+
+    #[test]
+    fn test_next_options() {
+        let pagination = |has_more, page_number| crate::Pagination {
+            has_more: Some(has_more),
+            page_number,
+            page_size: Some(10),
+            total_count: None,
+        };
+        let options = TxOptions::new().page_size(10).quote_currency(QuoteCurrency::EUR);
+
+        let next = pagination(true, Some(2)).next_options(&options).unwrap();
+        assert_eq!(next.page_number, Some(3));
+        assert_eq!(next.page_size, Some(10));
+        assert_eq!(next.quote_currency, Some(QuoteCurrency::EUR));
+
+        let from_request = pagination(true, None).next_options(&next).unwrap();
+        assert_eq!(from_request.page_number, Some(4));
+        assert!(pagination(false, Some(2)).next_options(&options).is_none());
+    }
+}
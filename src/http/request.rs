@@ -0,0 +1,75 @@
+//! Per-request options applied on top of the client configuration.
+
+use crate::{ApiVersion, CachePolicy, Error, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use tokio_util::sync::CancellationToken;
+
+/// Options applied to every request made through a service handle.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use goldrush_sdk::{GoldRushClient, RequestOptions, Chain};
+///
+/// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+/// let balances = client.balance_service()
+///     .with_request_options(RequestOptions::new().header("X-Partner-Id", "acme"))?
+///     .get_token_balances_for_wallet_address(Chain::EthereumMainnet, "0x...", None)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    pub headers: Vec<(String, String)>,
+    /// Overrides [`ClientConfig::api_version`](crate::ClientConfig::api_version).
+    pub api_version: Option<ApiVersion>,
+    /// Overrides [`ClientConfig::cache_policy`](crate::ClientConfig::cache_policy).
+    pub cache_policy: Option<CachePolicy>,
+    /// Abort requests, retries and paginated fetches once this token is cancelled.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self { self.headers.push((name.into(), value.into())); self }
+    pub fn api_version(mut self, version: ApiVersion) -> Self { self.api_version = Some(version); self }
+    pub fn cache_policy(mut self, policy: CachePolicy) -> Self { self.cache_policy = Some(policy); self }
+    pub fn cancellation(mut self, token: CancellationToken) -> Self { self.cancellation = Some(token); self }
+
+    /// Parse the configured headers, failing on invalid names or values.
+    pub fn header_map(&self) -> Result<HeaderMap> {
+        to_header_map(self.headers.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+    }
+}
+
+/// Build a header map from string pairs; later entries override earlier ones.
+pub(crate) fn to_header_map<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in pairs {
+        let header_name: HeaderName = name.parse()
+            .map_err(|e| Error::Config(format!("Invalid header name '{}': {}", name, e)))?;
+        let header_value = HeaderValue::from_str(value)
+            .map_err(|e| Error::Config(format!("Invalid value for header '{}': {}", name, e)))?;
+        map.insert(header_name, header_value);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_map() {
+        let options = RequestOptions::new()
+            .header("X-Partner-Id", "acme")
+            .header("x-partner-id", "globex");
+        let map = options.header_map().unwrap();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map["x-partner-id"], "globex");
+
+        assert!(RequestOptions::new().header("bad header", "v").header_map().is_err());
+        assert!(RequestOptions::new().header("X-Ok", "line\nbreak").header_map().is_err());
+    }
+}
@@ -30,9 +30,96 @@ pub mod services;
 /// Comprehensive Chain enum for all GoldRush-supported blockchain networks.
 pub mod chains;
 
+/// Runtime-extensible registry of chains, merging custom chains with the built-in enum.
+pub mod chain_registry;
+
 /// Shared types (QuoteCurrency, GasEventType, re-exports Chain).
 pub mod types;
 
+/// Method selector lookup and ABI decoding of transaction input data.
+pub mod abi;
+
+/// Auto-paginating streams over page-numbered endpoints.
+pub mod pagination;
+
+/// Newline-delimited JSON export of model streams.
+pub mod export;
+
+/// Typed address newtypes (EVM, Solana, Bitcoin) accepted by service methods.
+pub mod address;
+
+/// Canonical asset registry folding bridged tokens into one holding.
+pub mod assets;
+
+/// Uniswap-style token lists for metadata normalization and impersonation checks.
+pub mod token_list;
+
+/// Address labels merged from API responses and user input.
+pub mod address_book;
+
+/// EIP-55 checksums, hex normalization, unit conversions and address formatting.
+pub mod utils;
+
+/// Conversion hooks applied to monetary fields of every REST response.
+pub mod money;
+
+/// `f64` or `Decimal` type of monetary quote fields, chosen by the `decimal` feature.
+pub mod decimal;
+
+/// `String` or `U256` type of raw token amount fields, chosen by the `bignum` feature.
+pub mod amount;
+
+/// Audit hook recording the endpoint, chain and address of every REST call.
+pub mod audit;
+
+/// Screening hook consulted for the wallet address of every REST call.
+pub mod screening;
+
+/// Conversion of quotes from mixed-currency responses into one currency.
+pub mod fx;
+
+/// Wallet value exposed to approved spenders, from balances joined with approvals.
+pub mod exposure;
+
+/// Per-chain transaction calendars for activity heatmaps.
+pub mod activity;
+
+/// Period-over-period floor price, volume and sales rollups for NFT collections.
+pub mod collection_stats;
+
+/// First-seen and funding-source tracing for wallets, hop by hop across chains.
+pub mod funding;
+
+/// Ranked counterparties of a wallet, folded from its full transaction history.
+pub mod counterparty;
+
+/// Gas fees paid by a wallet over a date range, by chain and contract.
+pub mod gas_spend;
+
+/// Approval history of a wallet, decoded from its `Approval` and `ApprovalForAll` logs.
+pub mod approval_history;
+
+/// Dust balances across chains and whether sweeping them pays for the gas.
+pub mod dust;
+
+/// Stablecoin exposure by issuer and depeg alerts.
+pub mod stablecoin;
+
+/// Deposits to and withdrawals from known exchanges and bridges, per wallet.
+pub mod exchange_flow;
+
+/// `axum`/`tower` layer and extractors sharing a client with request handlers.
+#[cfg(feature = "axum")]
+pub mod web;
+
+/// Builder-style factories for model instances in downstream unit tests.
+#[cfg(feature = "test-util")]
+pub mod testing;
+
+/// SQLite sink and incremental sync job for fetched data.
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
 /// Streaming module for WebSocket-based real-time data subscriptions.
 #[cfg(feature = "streaming")]
 pub mod streaming;
@@ -44,44 +131,100 @@ mod cache;
 mod validation;
 mod metrics;
 mod circuit_breaker;
+mod endpoint;
 mod security;
+mod bulk;
+mod credits;
+mod checkpoint;
+mod warm_up;
+mod progress;
+mod clock;
+mod backfill;
+mod block_stream;
+mod multichain_stream;
+mod fetch_plan;
+mod lifecycle;
+mod replay;
 
 // Core exports
 pub use client::{GoldRushClient, ClientConfig};
-pub use error::{Error, Result};
+pub use error::{ApiErrorCode, Error, Result};
+#[cfg(feature = "streaming")]
+pub use error::StreamingRecovery;
 pub use chains::Chain;
-pub use types::{QuoteCurrency, GasEventType};
+pub use chain_registry::{ChainInfo, ChainRegistry};
+pub use types::{ApiVersion, QuoteCurrency, GasEventType};
+pub use http::query::{canonical_query, canonical_request_key, EndpointOptions, PagedOptions};
+pub use http::request::RequestOptions;
+pub use tokio_util::sync::CancellationToken;
+pub use pagination::{CursorPage, CursorStream, CursorStreamOptions, Page, PageStream, Paginator, PaginatorStream};
+pub use export::{ExportCheckpoint, NdjsonConfig, NdjsonWriter};
+pub use abi::{AbiValue, DecodedArg, DecodedCall, MethodSignature, SelectorRegistry};
+pub use assets::{AssetRegistry, CanonicalAsset, ConsolidatedHolding};
+pub use token_list::{TokenList, TokenListEntry, TokenListRegistry, TokenListStatus};
+pub use address_book::{AddressBook, AddressLabel, LabelSource};
+pub use money::{MoneyHook, MoneyKind, MoneyPolicy, Rounding};
+pub use audit::{AuditEntry, AuditLog, AuditSink};
+pub use screening::{RiskFlag, RiskSeverity, ScreeningDecision, ScreeningList, ScreeningProvider, ScreeningRequest};
+pub use fx::{FxRate, Normalized, QuoteNormalizer};
+pub use exposure::{ExposureReport, SpenderExposure, TokenExposure};
+pub use approval_history::{ApprovalEvent, ApprovalGrant, ApprovalHistory};
+pub use activity::{ActivityCalendar, CalendarDate, CalendarGranularity};
+pub use collection_stats::{CollectionStats, MetricChange};
+pub use funding::{FundingHop, FundingStop, FundingTrace, FundingTransaction};
+pub use counterparty::{Counterparty, CounterpartyReport};
+pub use gas_spend::{ChainGasSpend, ContractGasSpend, GasSpend, GasSpendReport};
+pub use dust::{DustReport, DustToken, TransferCost};
+pub use exchange_flow::{EntityFlow, EntityKind, ExchangeAddressSet, ExchangeFlowSummary, FlowDirection, LabeledAddress};
+pub use stablecoin::{DepegAlert, DepegConfig, IssuerExposure, Stablecoin, StablecoinClassifier, StablecoinExposure, StablecoinHolding};
+pub use address::{display_address, normalize_address, same_address, AddressFormat, AddressParam, BtcAddress, EvmAddress, SolanaAddress};
 
 // Service exports
 pub use services::balance_service::{BalancesOptions, PortfolioOptions, Erc20TransfersOptions, TokenHoldersOptions, HistoricalBalancesOptions, NativeBalanceOptions};
-pub use services::transaction_service::{TxOptions, SingleTxOptions, TransactionSummaryOptions, TimeBucketOptions};
-pub use services::nft_service::NftOptions;
-pub use services::base_service::{BlockHeightsOptions, LogEventsByAddressOptions, LogEventsByTopicOptions};
-pub use services::pricing_service::PricingOptions;
-pub use services::all_chains_service::{MultiChainTxOptions, MultiChainBalancesOptions};
-pub use services::{BalanceService, TransactionService, NftService, BaseService, PricingService, SecurityService, BitcoinService, AllChainsService};
+pub use services::transaction_service::{TxOptions, SingleTxOptions, TransactionSummaryOptions, TimeBucketOptions, GasSpendOptions};
+pub use services::nft_service::{NftOptions, NftTransactionsOptions};
+pub use services::base_service::{BlockHeightsOptions, BlockStreamOptions, LogEventsByAddressOptions, LogEventsByTopicOptions};
+pub use services::pricing_service::{PricingOptions, MAX_PRICE_CONTRACTS_PER_CALL};
+pub use services::security_service::ApprovalHistoryOptions;
+pub use services::all_chains_service::{MultiChainTxOptions, MultiChainBalancesOptions, ActivityCalendarOptions, FundingTraceOptions, DustReportOptions, MAX_CHAINS_PER_CALL};
+pub use services::solana_service::{SolanaBalancesOptions, SplTransfersOptions, SolanaTxOptions};
+pub use services::{BalanceService, TransactionService, NftService, BaseService, PricingService, SecurityService, BitcoinService, AllChainsService, SolanaService};
 
 #[cfg(feature = "streaming")]
 pub use services::StreamingService;
 
 // Production readiness exports
 pub use tracing::{RequestId, TracingContext};
-pub use rate_limit::{RateLimitConfig, RateLimiter};
-pub use cache::{CacheConfig, CacheStats, MemoryCache};
+pub use rate_limit::{EndpointRateLimit, RateLimitConfig, RateLimiter};
+pub use cache::{CacheConfig, CachePolicy, CacheStats, MemoryCache};
 pub use validation::{Validator, Sanitizer};
+pub use endpoint::Endpoint;
 pub use metrics::{MetricsCollector, MetricsSummary, EndpointStats, Timer};
-pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitBreakerExecutor, CircuitState};
-pub use security::{SecurityConfig, SecurityManager, SecurityContext};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitBreakerExecutor, CircuitError, CircuitState, EndpointCircuitBreakers, FallbackProvider, LastKnownGood};
+pub use security::{ResponseScanConfig, ResponseScanMode, ScanFinding, ScanRule, SecurityConfig, SecurityManager, SecurityContext, SignedRequest};
+pub use bulk::{BulkConfig, BulkExecutor, BulkResult};
+pub use credits::{CreditEstimator, CreditUsage};
+pub use progress::{Progress, ProgressReporter};
+pub use clock::{Clock, MockClock, Sleep, TokioClock};
+pub use backfill::QuoteBackfill;
+pub use block_stream::BlockStream;
+pub use multichain_stream::{ChainProgress, MultiChainTxStream};
+pub use warm_up::{WarmUpConfig, WarmUpReport};
+pub use checkpoint::{BlockItem, CheckpointStore, CheckpointedStream, FileCheckpointStore, MemoryCheckpointStore};
+pub use fetch_plan::{CreditEstimate, FetchOutput, FetchPlan, FetchProgress, FetchReport, FetchResult, FetchStep, Priority, ProgressCallback};
+pub use replay::{PendingPoll, ReplayQueue};
+pub use lifecycle::ShutdownReport;
 
 // Model exports
 pub use models::{
-    ApiResponse, Pagination, PaginationLinks,
-    balances::{BalanceItem, BalancesData, BalancesResponse, Erc20TransferItem, Erc20TransfersData, Erc20TransfersResponse, TokenHolderItem, TokenHoldersData, TokenHoldersResponse, HistoricalBalanceItem, HistoricalBalancesData, HistoricalBalancesResponse, NativeTokenBalanceData, NativeTokenBalanceResponse},
-    transactions::{TransactionItem, TransactionsData, TransactionsResponse, TransactionResponse, TransactionSummaryData, TransactionSummaryResponse, TimeBucketData, TimeBucketResponse},
-    nfts::{NftItem, NftsData, NftsResponse, NftMetadataItem, NftMetadataResponse, ChainCollectionsResponse, NftTransactionsResponse, TraitsResponse, AttributesResponse, TraitsSummaryResponse, FloorPricesResponse, VolumeResponse, SalesCountResponse, OwnershipCheckResponse},
-    base::{BlockResponse, ResolvedAddressResponse, BlockHeightsResponse, LogsResponse, AllChainsResponse, AllChainStatusResponse, AddressActivityResponse, GasPricesResponse},
+    ApiResponse, ErcStandard, Pagination, PaginationLinks, ResponseMeta, TransferType,
+    balances::{BalanceItem, BalancesData, BalancesResponse, Erc20TransferItem, Erc20TransfersData, Erc20TransfersResponse, TokenHolderItem, TokenHoldersData, TokenHoldersResponse, HistoricalBalanceItem, HistoricalBalancesData, HistoricalBalancesResponse, NativeTokenBalanceData, NativeTokenBalanceResponse, HoldingValue, HoldingPoint, PortfolioItem, PortfolioData, PortfolioResponse},
+    transactions::{TransactionItem, TransactionKind, InternalTransfer, StateChange, StorageChange, BalanceChange, TransactionsData, TransactionsResponse, TransactionResponse, TransactionSummaryData, TransactionSummaryResponse, TimeBucketData, TimeBucketResponse},
+    nfts::{NftItem, NftsData, NftsResponse, NftMetadataItem, NftMetadataResponse, ChainCollectionsResponse, NftTransactionsResponse, NftTransactionItem, NftActivityCheckpoint, TraitsResponse, AttributesResponse, TraitsSummaryResponse, FloorPricesResponse, VolumeResponse, SalesCountResponse, OwnershipCheckResponse},
+    base::{BlockResponse, ResolvedAddressResponse, TokenMetadataResponse, BlockHeightsResponse, LogsResponse, AllChainsResponse, AllChainStatusResponse, AddressActivityResponse, GasPricesResponse},
     pricing::{TokenPricesResponse, PoolSpotPricesResponse},
     approvals::{ApprovalsResponse, NftApprovalsResponse},
     bitcoin::{BtcHdWalletResponse, BtcTransactionsResponse},
-    all_chains::{MultiChainTransactionsResponse, MultiChainBalancesResponse},
+    all_chains::{MultiChainTransactionsResponse, MultiChainBalancesResponse, PartitionedBalances, ChainFailure},
+    solana::{SplTokenBalanceItem, SolanaBalancesResponse, SplTransferItem, SplTransfersResponse, SolanaTransactionItem, SolanaTransactionsResponse},
 };
@@ -0,0 +1,245 @@
+//! Graceful client shutdown.
+//!
+//! Every REST call holds an in-flight guard for its duration, background
+//! tasks started with [`GoldRushClient::spawn_background`](crate::GoldRushClient::spawn_background)
+//! are tracked, and streaming services created by the client are remembered.
+//! [`GoldRushClient::shutdown`](crate::GoldRushClient::shutdown) stops new
+//! calls, closes streaming connections, then waits for the rest to finish.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::GoldRushClient;
+//! use std::time::Duration;
+//!
+//! # async fn example(client: GoldRushClient) {
+//! client.spawn_background(|shutdown| async move {
+//!     shutdown.cancelled().await;
+//!     // flush and exit
+//! }).unwrap();
+//!
+//! let report = client.shutdown(Duration::from_secs(10)).await;
+//! assert!(report.drained);
+//! # }
+//! ```
+
+use crate::{Error, Result};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "streaming")]
+type StreamingSlot = std::sync::Weak<tokio::sync::Mutex<Option<crate::streaming::WebSocketClient>>>;
+
+/// Outcome of [`GoldRushClient::shutdown`](crate::GoldRushClient::shutdown).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Whether every request and background task finished before the timeout.
+    pub drained: bool,
+    /// Requests still running at the timeout; they are aborted with [`Error::ShuttingDown`].
+    pub requests_aborted: usize,
+    /// Background tasks still running at the timeout; they are aborted.
+    pub tasks_aborted: usize,
+    /// Streaming connections that were closed.
+    pub streams_closed: usize,
+    pub elapsed: Duration,
+}
+
+/// Shutdown state shared by a client and every service handle derived from it.
+#[derive(Default)]
+pub(crate) struct Lifecycle {
+    closing: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    /// Cancelled when shutdown starts; handed to background tasks.
+    signal: CancellationToken,
+    /// Cancelled when the shutdown timeout passes; aborts in-flight requests.
+    abort: CancellationToken,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+    #[cfg(feature = "streaming")]
+    streams: Mutex<Vec<StreamingSlot>>,
+}
+
+/// Marks a request as in flight until dropped.
+pub(crate) struct InFlight<'a> {
+    lifecycle: &'a Lifecycle,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
+impl Lifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_closing(&self) -> bool {
+        self.closing.load(Ordering::SeqCst)
+    }
+
+    /// Register a request, failing once shutdown has started.
+    pub fn enter(&self) -> Result<InFlight<'_>> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight { lifecycle: self };
+        if self.is_closing() {
+            return Err(Error::ShuttingDown);
+        }
+        Ok(guard)
+    }
+
+    /// Cancelled once the shutdown timeout passes.
+    pub fn abort_token(&self) -> &CancellationToken {
+        &self.abort
+    }
+
+    pub fn spawn<F, Fut>(&self, task: F) -> Result<()>
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.is_closing() {
+            return Err(Error::ShuttingDown);
+        }
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(tokio::spawn(task(self.signal.clone())));
+        Ok(())
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn track_streaming(&self, slot: StreamingSlot) {
+        let mut streams = self.streams.lock().unwrap();
+        streams.retain(|slot| slot.strong_count() > 0);
+        streams.push(slot);
+    }
+
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        let started = Instant::now();
+        let deadline = tokio::time::Instant::now() + timeout;
+        self.closing.store(true, Ordering::SeqCst);
+        self.signal.cancel();
+
+        let streams_closed = self.close_streams().await;
+        let idle = tokio::time::timeout_at(deadline, self.wait_idle()).await.is_ok();
+        let requests_aborted = self.in_flight.load(Ordering::SeqCst);
+        if !idle {
+            self.abort.cancel();
+        }
+
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        let mut tasks_aborted = 0;
+        for mut task in tasks {
+            if tokio::time::timeout_at(deadline, &mut task).await.is_err() {
+                task.abort();
+                tasks_aborted += 1;
+            }
+        }
+
+        ShutdownReport {
+            drained: idle && tasks_aborted == 0,
+            requests_aborted: if idle { 0 } else { requests_aborted },
+            tasks_aborted,
+            streams_closed,
+            elapsed: started.elapsed(),
+        }
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    #[cfg(feature = "streaming")]
+    async fn close_streams(&self) -> usize {
+        let slots = std::mem::take(&mut *self.streams.lock().unwrap());
+        let mut closed = 0;
+        for slot in slots.iter().filter_map(|slot| slot.upgrade()) {
+            if let Some(client) = slot.lock().await.take() {
+                if client.disconnect().await.is_ok() {
+                    closed += 1;
+                }
+            }
+        }
+        closed
+    }
+
+    #[cfg(not(feature = "streaming"))]
+    async fn close_streams(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_requests() {
+        let lifecycle = Arc::new(Lifecycle::new());
+        let request = {
+            let lifecycle = Arc::clone(&lifecycle);
+            tokio::spawn(async move {
+                let _guard = lifecycle.enter().unwrap();
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let report = lifecycle.shutdown(Duration::from_secs(2)).await;
+        assert!(report.drained);
+        assert!(report.elapsed >= Duration::from_millis(30));
+        request.await.unwrap();
+        assert!(matches!(lifecycle.enter(), Err(Error::ShuttingDown)));
+        assert!(!lifecycle.abort_token().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_timeout_aborts_stragglers() {
+        let lifecycle = Arc::new(Lifecycle::new());
+        lifecycle.spawn(|signal| async move {
+            signal.cancelled().await;
+        }).unwrap();
+        lifecycle.spawn(|_| async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }).unwrap();
+        let _stuck = lifecycle.enter().unwrap();
+
+        let report = lifecycle.shutdown(Duration::from_millis(50)).await;
+        assert!(!report.drained);
+        assert_eq!(report.requests_aborted, 1);
+        assert_eq!(report.tasks_aborted, 1);
+        assert!(lifecycle.abort_token().is_cancelled());
+        assert!(matches!(lifecycle.spawn(|_| async {}), Err(Error::ShuttingDown)));
+    }
+
+    #[tokio::test]
+    async fn test_client_refuses_requests_after_shutdown() {
+        let client = crate::GoldRushClient::new(
+            "cqt_rQ4mW8kP2vB7nX3jL9sT5yH1gF",
+            crate::ClientConfig::new("http://127.0.0.1:9"),
+        )
+        .unwrap();
+        let scoped = client.with_request_options(crate::RequestOptions::new()).unwrap();
+
+        let report = client.shutdown(Duration::from_secs(1)).await;
+        assert!(report.drained);
+        assert!(scoped.is_shutting_down());
+        let result = scoped.base_service().get_all_chains().await;
+        assert!(matches!(result, Err(Error::ShuttingDown)));
+    }
+}
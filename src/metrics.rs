@@ -1,3 +1,4 @@
+use crate::endpoint::Endpoint;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -20,12 +21,18 @@ pub struct MetricsCollector {
     cache_misses: AtomicU64,
     /// Rate limit hit count.
     rate_limit_hits: AtomicU64,
+    /// Requests that succeeded only after retrying.
+    retried_success_count: AtomicU64,
+    /// Retries made by requests that eventually succeeded.
+    retry_count: AtomicU64,
+    /// Backoff time and retried statuses for eventually successful requests.
+    retry_stats: Arc<RwLock<RetryStats>>,
     /// Response time tracking.
     response_times: Arc<RwLock<ResponseTimeTracker>>,
     /// Error breakdown by type.
     error_breakdown: Arc<RwLock<HashMap<String, u64>>>,
     /// Request breakdown by endpoint.
-    endpoint_stats: Arc<RwLock<HashMap<String, EndpointStats>>>,
+    endpoint_stats: Arc<RwLock<HashMap<Endpoint, EndpointStats>>>,
 }
 
 impl Default for MetricsCollector {
@@ -43,6 +50,9 @@ impl MetricsCollector {
             cache_hits: AtomicU64::new(0),
             cache_misses: AtomicU64::new(0),
             rate_limit_hits: AtomicU64::new(0),
+            retried_success_count: AtomicU64::new(0),
+            retry_count: AtomicU64::new(0),
+            retry_stats: Arc::new(RwLock::new(RetryStats::default())),
             response_times: Arc::new(RwLock::new(ResponseTimeTracker::new())),
             error_breakdown: Arc::new(RwLock::new(HashMap::new())),
             endpoint_stats: Arc::new(RwLock::new(HashMap::new())),
@@ -51,7 +61,7 @@ impl MetricsCollector {
     
     /// Record a successful request.
     #[instrument(skip(self), fields(endpoint = %endpoint, response_time_ms = %response_time.as_millis()))]
-    pub async fn record_success(&self, endpoint: &str, response_time: Duration) {
+    pub async fn record_success(&self, endpoint: Endpoint, response_time: Duration) {
         self.request_count.fetch_add(1, Ordering::Relaxed);
         self.success_count.fetch_add(1, Ordering::Relaxed);
         
@@ -60,7 +70,7 @@ impl MetricsCollector {
         
         // Update endpoint stats
         let mut endpoint_stats = self.endpoint_stats.write().await;
-        let stats = endpoint_stats.entry(endpoint.to_string()).or_insert_with(EndpointStats::new);
+        let stats = endpoint_stats.entry(endpoint).or_insert_with(EndpointStats::new);
         stats.request_count += 1;
         stats.success_count += 1;
         stats.total_response_time += response_time;
@@ -70,7 +80,7 @@ impl MetricsCollector {
     
     /// Record a failed request.
     #[instrument(skip(self), fields(endpoint = %endpoint, error_type = %error_type))]
-    pub async fn record_error(&self, endpoint: &str, error_type: &str) {
+    pub async fn record_error(&self, endpoint: Endpoint, error_type: &str) {
         self.request_count.fetch_add(1, Ordering::Relaxed);
         self.error_count.fetch_add(1, Ordering::Relaxed);
         
@@ -80,13 +90,51 @@ impl MetricsCollector {
         
         // Update endpoint stats
         let mut endpoint_stats = self.endpoint_stats.write().await;
-        let stats = endpoint_stats.entry(endpoint.to_string()).or_insert_with(EndpointStats::new);
+        let stats = endpoint_stats.entry(endpoint).or_insert_with(EndpointStats::new);
         stats.request_count += 1;
         stats.error_count += 1;
         
         debug!("Error metrics recorded");
     }
     
+    /// Record the retries a request needed before it succeeded.
+    ///
+    /// `retried_statuses` lists the HTTP status of each retried attempt; transport
+    /// errors that were retried have no status and are only counted in `retries`.
+    #[instrument(skip(self, retried_statuses), fields(endpoint = %endpoint, retries = %retries))]
+    pub async fn record_retried_success(
+        &self,
+        endpoint: Endpoint,
+        retries: u32,
+        backoff: Duration,
+        retried_statuses: &[u16],
+    ) {
+        if retries == 0 {
+            return;
+        }
+        self.retried_success_count.fetch_add(1, Ordering::Relaxed);
+        self.retry_count.fetch_add(retries as u64, Ordering::Relaxed);
+
+        let mut retry_stats = self.retry_stats.write().await;
+        retry_stats.total_backoff += backoff;
+        for status in retried_statuses {
+            *retry_stats.statuses.entry(*status).or_insert(0) += 1;
+        }
+        drop(retry_stats);
+
+        let mut endpoint_stats = self.endpoint_stats.write().await;
+        let stats = endpoint_stats.entry(endpoint).or_insert_with(EndpointStats::new);
+        stats.retry_count += retries as u64;
+
+        debug!("Retry metrics recorded");
+    }
+
+    /// Record the estimated credits charged for a successful request.
+    pub async fn record_credits(&self, endpoint: Endpoint, credits: f64) {
+        let mut endpoint_stats = self.endpoint_stats.write().await;
+        endpoint_stats.entry(endpoint).or_insert_with(EndpointStats::new).estimated_credits += credits;
+    }
+
     /// Record a cache hit.
     pub fn record_cache_hit(&self) {
         self.cache_hits.fetch_add(1, Ordering::Relaxed);
@@ -110,6 +158,8 @@ impl MetricsCollector {
         let response_times = self.response_times.read().await;
         let error_breakdown = self.error_breakdown.read().await.clone();
         let endpoint_stats = self.endpoint_stats.read().await.clone();
+        let retry_stats = self.retry_stats.read().await;
+        let estimated_credits = endpoint_stats.values().map(|stats| stats.estimated_credits).sum();
         
         MetricsSummary {
             request_count: self.request_count.load(Ordering::Relaxed),
@@ -118,9 +168,14 @@ impl MetricsCollector {
             cache_hits: self.cache_hits.load(Ordering::Relaxed),
             cache_misses: self.cache_misses.load(Ordering::Relaxed),
             rate_limit_hits: self.rate_limit_hits.load(Ordering::Relaxed),
+            retried_success_count: self.retried_success_count.load(Ordering::Relaxed),
+            retry_count: self.retry_count.load(Ordering::Relaxed),
+            retry_backoff: retry_stats.total_backoff,
+            retried_statuses: retry_stats.statuses.clone(),
             avg_response_time: response_times.average(),
             p95_response_time: response_times.p95(),
             p99_response_time: response_times.p99(),
+            estimated_credits,
             error_breakdown,
             endpoint_stats,
         }
@@ -134,7 +189,10 @@ impl MetricsCollector {
         self.cache_hits.store(0, Ordering::Relaxed);
         self.cache_misses.store(0, Ordering::Relaxed);
         self.rate_limit_hits.store(0, Ordering::Relaxed);
+        self.retried_success_count.store(0, Ordering::Relaxed);
+        self.retry_count.store(0, Ordering::Relaxed);
         
+        *self.retry_stats.write().await = RetryStats::default();
         self.response_times.write().await.reset();
         self.error_breakdown.write().await.clear();
         self.endpoint_stats.write().await.clear();
@@ -143,6 +201,13 @@ impl MetricsCollector {
     }
 }
 
+/// Aggregated retry telemetry.
+#[derive(Debug, Default)]
+struct RetryStats {
+    total_backoff: Duration,
+    statuses: HashMap<u16, u64>,
+}
+
 /// Track response times with percentile calculations.
 #[derive(Debug)]
 struct ResponseTimeTracker {
@@ -208,6 +273,10 @@ pub struct EndpointStats {
     pub success_count: u64,
     pub error_count: u64,
     pub total_response_time: Duration,
+    /// Retries made by requests that eventually succeeded.
+    pub retry_count: u64,
+    /// Estimated credits charged, from the client's [`CreditEstimator`](crate::CreditEstimator).
+    pub estimated_credits: f64,
 }
 
 impl EndpointStats {
@@ -217,6 +286,8 @@ impl EndpointStats {
             success_count: 0,
             error_count: 0,
             total_response_time: Duration::ZERO,
+            retry_count: 0,
+            estimated_credits: 0.0,
         }
     }
     
@@ -246,11 +317,21 @@ pub struct MetricsSummary {
     pub cache_hits: u64,
     pub cache_misses: u64,
     pub rate_limit_hits: u64,
+    /// Requests that succeeded only after retrying.
+    pub retried_success_count: u64,
+    /// Retries made by requests that eventually succeeded.
+    pub retry_count: u64,
+    /// Total backoff slept by requests that eventually succeeded.
+    pub retry_backoff: Duration,
+    /// How often each HTTP status was retried before a success.
+    pub retried_statuses: HashMap<u16, u64>,
     pub avg_response_time: Duration,
     pub p95_response_time: Duration,
     pub p99_response_time: Duration,
+    /// Estimated credits charged across all endpoints.
+    pub estimated_credits: f64,
     pub error_breakdown: HashMap<String, u64>,
-    pub endpoint_stats: HashMap<String, EndpointStats>,
+    pub endpoint_stats: HashMap<Endpoint, EndpointStats>,
 }
 
 impl MetricsSummary {
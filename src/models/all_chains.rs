@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiChainTransactionItem {
     pub chain_id: Option<u64>,
     pub chain_name: Option<String>,
@@ -19,15 +19,19 @@ pub struct MultiChainTransactionItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiChainTransactionsData {
     pub updated_at: Option<String>,
+    /// Cursor for the page of older transactions, passed as `before`.
+    pub cursor_before: Option<String>,
+    /// Cursor for the page of newer transactions, passed as `after`.
+    pub cursor_after: Option<String>,
     pub items: Vec<MultiChainTransactionItem>,
 }
 
 pub type MultiChainTransactionsResponse = crate::models::ApiResponse<MultiChainTransactionsData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiChainBalanceItem {
     pub chain_id: Option<u64>,
     pub chain_name: Option<String>,
@@ -43,7 +47,7 @@ pub struct MultiChainBalanceItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiChainBalancesData {
     pub updated_at: Option<String>,
     pub address: Option<String>,
@@ -51,3 +55,33 @@ pub struct MultiChainBalancesData {
 }
 
 pub type MultiChainBalancesResponse = crate::models::ApiResponse<MultiChainBalancesData>;
+
+/// A chain whose balances could not be fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainFailure {
+    pub chain_name: String,
+    pub error: String,
+}
+
+/// Balances merged from one all-chains call per group of chains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionedBalances {
+    pub address: Option<String>,
+    /// Oldest `updated_at` among the calls that succeeded.
+    pub updated_at: Option<String>,
+    pub items: Vec<MultiChainBalanceItem>,
+    /// Chains whose call failed; their balances are missing from `items`.
+    pub failed_chains: Vec<ChainFailure>,
+}
+
+impl PartitionedBalances {
+    /// Whether every requested chain was fetched.
+    pub fn is_complete(&self) -> bool {
+        self.failed_chains.is_empty()
+    }
+
+    /// Total quote value of the fetched balances.
+    pub fn total_quote(&self) -> f64 {
+        self.items.iter().filter_map(|item| item.quote).sum()
+    }
+}
@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalItem {
     pub token_address: Option<String>,
     pub token_address_label: Option<String>,
@@ -19,7 +19,7 @@ pub struct ApprovalItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpenderItem {
     pub block_height: Option<u64>,
     pub tx_hash: Option<String>,
@@ -34,7 +34,7 @@ pub struct SpenderItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalsData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -45,7 +45,7 @@ pub struct ApprovalsData {
 
 pub type ApprovalsResponse = crate::models::ApiResponse<ApprovalsData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftApprovalItem {
     pub contract_address: Option<String>,
     pub contract_address_label: Option<String>,
@@ -59,7 +59,7 @@ pub struct NftApprovalItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftSpenderItem {
     pub block_height: Option<u64>,
     pub tx_hash: Option<String>,
@@ -71,7 +71,7 @@ pub struct NftSpenderItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftApprovalsData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -1,7 +1,9 @@
-use serde::Deserialize;
+use crate::amount::{Amount, AmountValue};
+use crate::decimal::Quote;
+use serde::{Deserialize, Serialize};
 
 /// Represents a token balance item returned by the API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceItem {
     /// The contract address of the token.
     pub contract_address: String,
@@ -14,18 +16,18 @@ pub struct BalanceItem {
     #[serde(rename = "contract_name")]
     pub contract_name: Option<String>,
 
-    /// The raw balance amount as a string (to handle large numbers).
-    pub balance: String,
+    /// The raw balance in the token's smallest unit.
+    pub balance: Amount,
 
     /// The number of decimal places for this token.
     #[serde(rename = "contract_decimals")]
     pub contract_decimals: Option<u32>,
 
     /// The current quote rate for the token.
-    pub quote_rate: Option<f64>,
+    pub quote_rate: Option<Quote>,
 
     /// The quote value (balance * quote_rate).
-    pub quote: Option<f64>,
+    pub quote: Option<Quote>,
 
     /// The type of token (e.g., "cryptocurrency", "stablecoin", etc.).
     #[serde(rename = "type")]
@@ -63,19 +65,22 @@ impl BalanceItem {
 
     /// Parse the balance as a floating point number, accounting for decimals.
     pub fn balance_as_float(&self) -> Option<f64> {
-        let balance = self.balance.parse::<f64>().ok()?;
-        let decimals = self.contract_decimals.unwrap_or(18);
-        Some(balance / 10f64.powi(decimals as i32))
+        self.balance.as_f64_units(self.contract_decimals.unwrap_or(18))
+    }
+
+    /// The balance in whole tokens, exactly, as a decimal string.
+    pub fn balance_in_units(&self) -> Option<String> {
+        self.balance.in_units(self.contract_decimals.unwrap_or(18))
     }
 
     /// Check if this token has a non-zero balance.
     pub fn has_balance(&self) -> bool {
-        self.balance.parse::<f64>().unwrap_or(0.0) > 0.0
+        self.balance.is_positive()
     }
 
     /// Check if this token has quote value information.
     pub fn has_quote_value(&self) -> bool {
-        self.quote.unwrap_or(0.0) > 0.0
+        self.quote.unwrap_or_default() > Quote::default()
     }
 
     /// Check if this token is marked as spam.
@@ -85,7 +90,7 @@ impl BalanceItem {
 }
 
 /// Container for balance items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalancesData {
     /// The address these balances belong to.
     pub address: Option<String>,
@@ -103,12 +108,12 @@ pub struct BalancesData {
     pub quote_currency: Option<String>,
 
     /// Total quote value across all tokens.
-    pub total_quote: Option<f64>,
+    pub total_quote: Option<Quote>,
 }
 
 impl BalancesData {
     /// Calculate the total portfolio value from all balance items.
-    pub fn total_value(&self) -> f64 {
+    pub fn total_value(&self) -> Quote {
         self.items.iter()
             .filter_map(|item| item.quote)
             .sum()
@@ -117,7 +122,7 @@ impl BalancesData {
     /// Get tokens with non-zero quote value, sorted by value (highest first).
     pub fn tokens_by_value(&self) -> Vec<&BalanceItem> {
         let mut tokens: Vec<_> = self.items.iter()
-            .filter(|item| item.quote.unwrap_or(0.0) > 0.0)
+            .filter(|item| item.has_quote_value())
             .collect();
         tokens.sort_by(|a, b|
             b.quote.partial_cmp(&a.quote).unwrap_or(std::cmp::Ordering::Equal)
@@ -126,9 +131,9 @@ impl BalancesData {
     }
 
     /// Filter tokens by minimum quote value.
-    pub fn tokens_above_value(&self, min_value: f64) -> Vec<&BalanceItem> {
+    pub fn tokens_above_value(&self, min_value: Quote) -> Vec<&BalanceItem> {
         self.items.iter()
-            .filter(|item| item.quote.unwrap_or(0.0) >= min_value)
+            .filter(|item| item.quote.unwrap_or_default() >= min_value)
             .collect()
     }
 
@@ -146,7 +151,7 @@ pub type BalancesResponse = crate::models::ApiResponse<BalancesData>;
 // --- Extended models for additional balance endpoints ---
 
 /// Represents an ERC20 token transfer item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Erc20TransferItem {
     pub block_signed_at: Option<String>,
     pub block_height: Option<u64>,
@@ -160,18 +165,25 @@ pub struct Erc20TransferItem {
     pub contract_ticker_symbol: Option<String>,
     pub contract_address: Option<String>,
     pub logo_url: Option<String>,
-    pub transfer_type: Option<String>,
-    pub delta: Option<String>,
-    pub balance: Option<String>,
-    pub quote_rate: Option<f64>,
-    pub delta_quote: Option<f64>,
-    pub balance_quote: Option<f64>,
+    pub transfer_type: Option<crate::models::TransferType>,
+    pub delta: Option<Amount>,
+    pub balance: Option<Amount>,
+    pub quote_rate: Option<Quote>,
+    pub delta_quote: Option<Quote>,
+    pub balance_quote: Option<Quote>,
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
+impl Erc20TransferItem {
+    /// The transferred amount in whole tokens, exactly, as a decimal string.
+    pub fn delta_in_units(&self) -> Option<String> {
+        self.delta.as_ref()?.in_units(self.contract_decimals.unwrap_or(18))
+    }
+}
+
 /// Container for ERC20 transfer items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Erc20TransfersData {
     pub address: Option<String>,
     pub chain_id: Option<u64>,
@@ -184,18 +196,18 @@ pub struct Erc20TransfersData {
 pub type Erc20TransfersResponse = crate::models::ApiResponse<Erc20TransfersData>;
 
 /// Represents a token holder item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenHolderItem {
     pub address: Option<String>,
-    pub balance: Option<String>,
-    pub total_supply: Option<String>,
+    pub balance: Option<Amount>,
+    pub total_supply: Option<Amount>,
     pub block_height: Option<u64>,
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
 /// Container for token holder items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenHoldersData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -207,23 +219,30 @@ pub struct TokenHoldersData {
 pub type TokenHoldersResponse = crate::models::ApiResponse<TokenHoldersData>;
 
 /// Represents a historical balance item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoricalBalanceItem {
     pub contract_address: Option<String>,
     pub contract_name: Option<String>,
     pub contract_ticker_symbol: Option<String>,
     pub contract_decimals: Option<u32>,
     pub logo_url: Option<String>,
-    pub balance: Option<String>,
-    pub quote: Option<f64>,
-    pub quote_rate: Option<f64>,
+    pub balance: Option<Amount>,
+    pub quote: Option<Quote>,
+    pub quote_rate: Option<Quote>,
     pub block_height: Option<u64>,
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
+impl HistoricalBalanceItem {
+    /// The balance in whole tokens, exactly, as a decimal string.
+    pub fn balance_in_units(&self) -> Option<String> {
+        self.balance.as_ref()?.in_units(self.contract_decimals.unwrap_or(18))
+    }
+}
+
 /// Container for historical balance items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoricalBalancesData {
     pub address: Option<String>,
     pub chain_id: Option<u64>,
@@ -235,8 +254,91 @@ pub struct HistoricalBalancesData {
 /// Response structure for historical balance queries.
 pub type HistoricalBalancesResponse = crate::models::ApiResponse<HistoricalBalancesData>;
 
+/// One side (open, high, low or close) of a daily holding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingValue {
+    /// Raw balance in the token's smallest unit.
+    pub balance: Option<Amount>,
+    pub quote: Option<Quote>,
+    pub pretty_quote: Option<String>,
+}
+
+/// A token holding at one point of the portfolio timeseries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingPoint {
+    pub timestamp: Option<String>,
+    pub quote_rate: Option<Quote>,
+    pub open: Option<HoldingValue>,
+    pub high: Option<HoldingValue>,
+    pub low: Option<HoldingValue>,
+    pub close: Option<HoldingValue>,
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+impl HoldingPoint {
+    /// Quote value at the close of this point.
+    pub fn close_quote(&self) -> Option<Quote> {
+        self.close.as_ref()?.quote
+    }
+}
+
+/// Holdings timeseries of a single token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioItem {
+    pub contract_address: Option<String>,
+    pub contract_name: Option<String>,
+    pub contract_ticker_symbol: Option<String>,
+    pub contract_decimals: Option<u32>,
+    pub logo_url: Option<String>,
+    pub supports_erc: Option<Vec<crate::models::ErcStandard>>,
+    /// Time points, most recent first.
+    #[serde(default)]
+    pub holdings: Vec<HoldingPoint>,
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+impl PortfolioItem {
+    /// The most recent time point.
+    pub fn latest(&self) -> Option<&HoldingPoint> {
+        self.holdings
+            .iter()
+            .filter(|point| point.timestamp.is_some())
+            .max_by(|a, b| a.timestamp.cmp(&b.timestamp))
+    }
+}
+
+/// Container for the historical portfolio of an address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioData {
+    pub address: Option<String>,
+    pub updated_at: Option<String>,
+    pub next_update_at: Option<String>,
+    pub quote_currency: Option<String>,
+    pub chain_id: Option<u64>,
+    pub chain_name: Option<String>,
+    pub items: Vec<PortfolioItem>,
+}
+
+impl PortfolioData {
+    /// Total closing quote across all tokens per timestamp, oldest first.
+    pub fn total_quote_series(&self) -> Vec<(String, Quote)> {
+        let mut totals: std::collections::BTreeMap<&str, Quote> = std::collections::BTreeMap::new();
+        for point in self.items.iter().flat_map(|item| &item.holdings) {
+            if let Some(timestamp) = point.timestamp.as_deref() {
+                *totals.entry(timestamp).or_default() += point.close_quote().unwrap_or_default();
+            }
+        }
+        totals.into_iter().map(|(timestamp, quote)| (timestamp.to_string(), quote)).collect()
+    }
+}
+
+/// Response structure for historical portfolio queries.
+pub type PortfolioResponse = crate::models::ApiResponse<PortfolioData>;
+
 /// Container for native token balance data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NativeTokenBalanceData {
     pub address: Option<String>,
     pub chain_id: Option<u64>,
@@ -247,3 +349,43 @@ pub struct NativeTokenBalanceData {
 
 /// Response structure for native token balance queries.
 pub type NativeTokenBalanceResponse = crate::models::ApiResponse<NativeTokenBalanceData>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::QuoteValue;
+
+    #[test]
+    fn test_portfolio_holdings_timeseries() {
+        let point = |timestamp: &str, quote: f64| serde_json::json!({
+            "timestamp": timestamp,
+            "quote_rate": 1.0,
+            "open": { "balance": "1", "quote": quote, "pretty_quote": null },
+            "close": { "balance": "1", "quote": quote, "pretty_quote": null },
+        });
+        let data: PortfolioData = serde_json::from_value(serde_json::json!({
+            "address": "0xabc",
+            "chain_id": 1,
+            "items": [
+                {
+                    "contract_ticker_symbol": "USDC",
+                    "holdings": [point("2024-01-02T00:00:00Z", 10.0), point("2024-01-01T00:00:00Z", 8.0)],
+                },
+                {
+                    "contract_ticker_symbol": "WETH",
+                    "holdings": [point("2024-01-02T00:00:00Z", 5.0)],
+                },
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(data.items[0].latest().unwrap().close_quote(), Some(Quote::from_f64(10.0)));
+        assert_eq!(
+            data.total_quote_series(),
+            vec![
+                ("2024-01-01T00:00:00Z".to_string(), Quote::from_f64(8.0)),
+                ("2024-01-02T00:00:00Z".to_string(), Quote::from_f64(15.0)),
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use serde::Deserialize;
+use crate::types::GasEventType;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -8,7 +9,7 @@ pub struct BlockData {
     pub items: Vec<BlockItem>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockItem {
     pub signed_at: Option<String>,
     pub height: Option<u64>,
@@ -23,7 +24,7 @@ pub struct BlockItem {
 
 pub type BlockResponse = crate::models::ApiResponse<BlockData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResolvedAddressData {
     pub address: Option<String>,
     pub name: Option<String>,
@@ -31,7 +32,7 @@ pub struct ResolvedAddressData {
 
 pub type ResolvedAddressResponse = crate::models::ApiResponse<ResolvedAddressData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockHeightsData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -39,7 +40,7 @@ pub struct BlockHeightsData {
     pub items: Vec<BlockHeightItem>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockHeightItem {
     pub signed_at: Option<String>,
     pub height: Option<u64>,
@@ -47,7 +48,7 @@ pub struct BlockHeightItem {
 
 pub type BlockHeightsResponse = crate::models::ApiResponse<BlockHeightsData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogsData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -55,7 +56,7 @@ pub struct LogsData {
     pub items: Vec<LogEventItem>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEventItem {
     pub block_signed_at: Option<String>,
     pub block_height: Option<u64>,
@@ -77,7 +78,7 @@ pub struct LogEventItem {
 
 pub type LogsResponse = crate::models::ApiResponse<LogsData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainItem {
     pub name: Option<String>,
     pub chain_id: Option<String>,
@@ -97,13 +98,13 @@ pub struct ChainItem {
 
 pub type AllChainsResponse = crate::models::ApiResponse<AllChainsData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllChainsData {
     pub updated_at: Option<String>,
     pub items: Vec<ChainItem>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainStatusItem {
     pub name: Option<String>,
     pub chain_id: Option<String>,
@@ -115,7 +116,7 @@ pub struct ChainStatusItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllChainStatusData {
     pub updated_at: Option<String>,
     pub items: Vec<ChainStatusItem>,
@@ -123,7 +124,7 @@ pub struct AllChainStatusData {
 
 pub type AllChainStatusResponse = crate::models::ApiResponse<AllChainStatusData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddressActivityItem {
     pub chain_id: Option<String>,
     pub chain_name: Option<String>,
@@ -134,7 +135,7 @@ pub struct AddressActivityItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddressActivityData {
     pub updated_at: Option<String>,
     pub address: Option<String>,
@@ -143,12 +144,20 @@ pub struct AddressActivityData {
 
 pub type AddressActivityResponse = crate::models::ApiResponse<AddressActivityData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GasPriceItem {
-    pub event_type: Option<String>,
+    pub event_type: Option<GasEventType>,
+    /// Gas price in wei for this interval.
+    pub gas_price: Option<String>,
+    /// Median gas units spent by this event type.
+    pub gas_spent: Option<String>,
+    /// Cost of `gas_spent` at `gas_price`, in the quote currency.
+    pub gas_quote: Option<f64>,
     pub gas_quote_rate: Option<f64>,
     pub gas_price_gwei: Option<f64>,
     pub gas_price_wei: Option<String>,
+    /// Total fee in wei including base and priority fees.
+    pub total_gas_fee: Option<String>,
     pub interval: Option<String>,
     pub pretty_total_gas_quote: Option<String>,
     pub total_gas_quote: Option<f64>,
@@ -156,12 +165,48 @@ pub struct GasPriceItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GasPricesData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
     pub chain_name: Option<String>,
+    pub event_type: Option<GasEventType>,
+    pub gas_quote_rate: Option<f64>,
+    pub quote_currency: Option<String>,
+    /// Current base fee in wei.
+    pub base_fee: Option<String>,
     pub items: Vec<GasPriceItem>,
 }
 
 pub type GasPricesResponse = crate::models::ApiResponse<GasPricesData>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMetadataItem {
+    pub contract_name: Option<String>,
+    pub contract_ticker_symbol: Option<String>,
+    pub contract_address: Option<String>,
+    pub contract_decimals: Option<u32>,
+    pub logo_url: Option<String>,
+    /// Total supply in the token's smallest unit.
+    pub total_supply: Option<String>,
+    pub supports_erc: Option<Vec<crate::models::ErcStandard>>,
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMetadataData {
+    pub updated_at: Option<String>,
+    pub chain_id: Option<u64>,
+    pub chain_name: Option<String>,
+    pub items: Vec<TokenMetadataItem>,
+}
+
+impl TokenMetadataData {
+    /// The metadata of the requested contract.
+    pub fn token(&self) -> Option<&TokenMetadataItem> {
+        self.items.first()
+    }
+}
+
+pub type TokenMetadataResponse = crate::models::ApiResponse<TokenMetadataData>;
@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BtcHdWalletBalance {
     pub total_balance: Option<String>,
     pub total_receive: Option<String>,
@@ -12,7 +12,7 @@ pub struct BtcHdWalletBalance {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BtcHdWalletData {
     pub updated_at: Option<String>,
     pub address: Option<String>,
@@ -21,7 +21,7 @@ pub struct BtcHdWalletData {
 
 pub type BtcHdWalletResponse = crate::models::ApiResponse<BtcHdWalletData>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BtcTransactionItem {
     pub block_signed_at: Option<String>,
     pub block_height: Option<u64>,
@@ -37,7 +37,7 @@ pub struct BtcTransactionItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BtcTxInput {
     pub prev_hash: Option<String>,
     pub output_index: Option<u64>,
@@ -49,7 +49,7 @@ pub struct BtcTxInput {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BtcTxOutput {
     pub value: Option<u64>,
     pub script: Option<String>,
@@ -60,7 +60,7 @@ pub struct BtcTxOutput {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BtcTransactionsData {
     pub updated_at: Option<String>,
     pub address: Option<String>,
@@ -6,14 +6,22 @@ pub mod pricing;
 pub mod approvals;
 pub mod bitcoin;
 pub mod all_chains;
+pub mod solana;
 
 #[cfg(feature = "streaming")]
 pub mod streaming;
 
-use serde::Deserialize;
+mod open_enum;
+
+pub(crate) use open_enum::{decode_with_strictness, open_enum, strict_enums};
+
+use crate::http::query::PagedOptions;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::time::Duration;
 
 /// Pagination information returned by the API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pagination {
     /// Whether there are more pages available.
     pub has_more: Option<bool>,
@@ -28,8 +36,40 @@ pub struct Pagination {
     pub total_count: Option<u64>,
 }
 
+impl Pagination {
+    /// Options for the page after this one, or `None` when there are no more pages.
+    ///
+    /// Page numbers are zero-based; the current page is taken from the response,
+    /// falling back to the page requested in `current`.
+    pub fn next_options<O: PagedOptions>(&self, current: &O) -> Option<O> {
+        if self.has_more != Some(true) {
+            return None;
+        }
+        let page = self.page_number.or_else(|| current.current_page()).unwrap_or(0);
+        Some(current.at_page(page + 1))
+    }
+}
+
+open_enum! {
+    /// Direction of a token transfer relative to the queried wallet.
+    pub enum TransferType {
+        In = "IN",
+        Out = "OUT",
+    }
+}
+
+open_enum! {
+    /// Token standard listed in a contract's `supports_erc`.
+    pub enum ErcStandard {
+        Erc20 = "erc20",
+        Erc165 = "erc165",
+        Erc721 = "erc721",
+        Erc1155 = "erc1155",
+    }
+}
+
 /// Cursor-based pagination links returned by v3 endpoints.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginationLinks {
     /// Previous page cursor URL.
     pub prev: Option<String>,
@@ -39,7 +79,7 @@ pub struct PaginationLinks {
 }
 
 /// Error information returned by the API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiError {
     /// Error code from the API.
     pub code: Option<u32>,
@@ -52,7 +92,7 @@ pub struct ApiError {
 }
 
 /// Error response envelope from the API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiErrorEnvelope {
     /// Error information.
     pub error: Option<ApiError>,
@@ -62,12 +102,13 @@ pub struct ApiErrorEnvelope {
 }
 
 /// Standard response wrapper for successful API responses.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     /// The main data payload.
     pub data: Option<T>,
 
     /// Error information (usually null for successful responses).
+    #[serde(default, deserialize_with = "error_or_flag")]
     pub error: Option<ApiError>,
 
     /// Pagination information for paginated endpoints.
@@ -78,4 +119,126 @@ pub struct ApiResponse<T> {
 
     /// Additional metadata.
     pub meta: Option<serde_json::Value>,
+
+    /// HTTP-level metadata for the call that produced this response.
+    #[serde(skip)]
+    pub response_meta: Option<ResponseMeta>,
 }
+
+/// The API marks bodies with `"error": false` (or `true` next to top-level
+/// `error_message`/`error_code`) rather than an error object.
+fn error_or_flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<ApiError>, D::Error> {
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Bool(_)) => Ok(None),
+        Some(error) => ApiError::deserialize(error).map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+/// HTTP-level metadata captured for each call.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status code of the final attempt.
+    pub status: u16,
+
+    /// Server-assigned request id, when the API returns one.
+    pub request_id: Option<String>,
+
+    /// Request quota for the current rate-limit window.
+    pub rate_limit_limit: Option<u64>,
+
+    /// Requests remaining in the current rate-limit window.
+    pub rate_limit_remaining: Option<u64>,
+
+    /// Seconds until the rate-limit window resets.
+    pub rate_limit_reset: Option<u64>,
+
+    /// Time from the first attempt to the final response, including retries.
+    pub elapsed: Duration,
+
+    /// Number of attempts made, including the successful one.
+    pub attempts: u32,
+
+    /// Total time spent sleeping between retries.
+    pub backoff: Duration,
+
+    /// HTTP status of each retried attempt, in order. Retried transport
+    /// errors have no status and only show up in `attempts`.
+    pub retried_statuses: Vec<u16>,
+
+    /// All response headers of the final attempt.
+    pub headers: HeaderMap,
+
+    /// The exact response body, when `ClientConfig::keep_raw_body` is set.
+    pub raw_body: Option<String>,
+
+    /// Security scan matches, when scanning runs in report-only mode.
+    pub scan_findings: Vec<crate::ScanFinding>,
+
+    /// Host-independent key for the request, with query parameters in
+    /// canonical order; see [`canonical_request_key`](crate::canonical_request_key).
+    pub request_key: String,
+
+    /// Flags the [`ScreeningProvider`](crate::ScreeningProvider) raised for the address the call was about.
+    pub risk_flags: Vec<crate::RiskFlag>,
+}
+
+impl ResponseMeta {
+    pub(crate) fn new(status: u16, headers: &HeaderMap, elapsed: Duration, attempts: u32) -> Self {
+        let text = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let number = |name: &str| text(name).and_then(|v| v.trim().parse().ok());
+
+        Self {
+            status,
+            request_id: server_request_id(headers),
+            rate_limit_limit: number("x-ratelimit-limit"),
+            rate_limit_remaining: number("x-ratelimit-remaining"),
+            rate_limit_reset: number("x-ratelimit-reset"),
+            elapsed,
+            attempts,
+            backoff: Duration::ZERO,
+            retried_statuses: Vec::new(),
+            headers: headers.clone(),
+            raw_body: None,
+            scan_findings: Vec::new(),
+            request_key: String::new(),
+            risk_flags: Vec::new(),
+        }
+    }
+
+    /// Whether the call needed more than one attempt.
+    pub fn was_retried(&self) -> bool {
+        self.attempts > 1
+    }
+
+    /// Look up any response header, e.g. credit-usage headers.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+}
+
+impl<T> ApiResponse<T> {
+    /// The exact JSON body returned by the API, when `ClientConfig::keep_raw_body` is set.
+    pub fn raw_body(&self) -> Option<&str> {
+        self.response_meta.as_ref().and_then(|m| m.raw_body.as_deref())
+    }
+}
+
+/// Response types that can carry [`ResponseMeta`] after a call completes.
+/// The request id a response was tagged with, if any.
+pub(crate) fn server_request_id(headers: &HeaderMap) -> Option<String> {
+    ["x-request-id", "cf-ray"]
+        .iter()
+        .find_map(|name| headers.get(*name).and_then(|v| v.to_str().ok()).map(str::to_string))
+}
+
+pub(crate) trait WithResponseMeta {
+    fn set_response_meta(&mut self, _meta: ResponseMeta) {}
+}
+
+impl<T> WithResponseMeta for ApiResponse<T> {
+    fn set_response_meta(&mut self, meta: ResponseMeta) {
+        self.response_meta = Some(meta);
+    }
+}
+
+impl WithResponseMeta for serde_json::Value {}
@@ -1,7 +1,8 @@
-use serde::Deserialize;
+use crate::amount::Amount;
+use serde::{Deserialize, Serialize};
 
 /// Represents an NFT item returned by the API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftItem {
     /// The contract address of the NFT collection.
     pub contract_address: String,
@@ -10,7 +11,7 @@ pub struct NftItem {
     pub token_id: String,
 
     /// The owner's balance of this NFT.
-    pub token_balance: Option<String>,
+    pub token_balance: Option<Amount>,
 
     /// URL for the token metadata.
     pub token_url: Option<String>,
@@ -22,14 +23,14 @@ pub struct NftItem {
     pub contract_ticker_symbol: Option<String>,
 
     /// Whether this is an ERC-721 or ERC-1155 token.
-    pub supports_erc: Option<Vec<String>>,
+    pub supports_erc: Option<Vec<crate::models::ErcStandard>>,
 
     /// External metadata for the NFT.
     pub nft_data: Option<NftMetadata>,
 }
 
 /// Metadata for an NFT token.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftMetadata {
     pub token_uri: Option<String>,
     pub external_data: Option<ExternalNftData>,
@@ -42,7 +43,7 @@ pub struct NftMetadata {
 }
 
 /// External NFT metadata loaded from token URI.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExternalNftData {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -53,7 +54,7 @@ pub struct ExternalNftData {
 }
 
 /// An attribute/trait of an NFT.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftAttribute {
     pub trait_type: Option<String>,
     pub value: Option<serde_json::Value>,
@@ -61,7 +62,7 @@ pub struct NftAttribute {
 }
 
 /// Container for NFT items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftsData {
     pub address: Option<String>,
     pub chain_id: Option<u64>,
@@ -73,7 +74,7 @@ pub struct NftsData {
 pub type NftsResponse = crate::models::ApiResponse<NftsData>;
 
 /// Represents detailed NFT metadata for a specific token.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftMetadataItem {
     pub contract_address: String,
     pub token_id: String,
@@ -92,12 +93,12 @@ pub type NftMetadataResponse = crate::models::ApiResponse<Vec<NftMetadataItem>>;
 // --- Extended models for additional NFT endpoints ---
 
 /// Represents an NFT collection item in chain collection listings.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainCollectionItem {
     pub contract_address: Option<String>,
     pub contract_name: Option<String>,
     pub contract_ticker_symbol: Option<String>,
-    pub token_total_supply: Option<String>,
+    pub token_total_supply: Option<Amount>,
     pub floor_price_quote: Option<f64>,
     pub floor_price_native_quote: Option<f64>,
     pub market_cap_quote: Option<f64>,
@@ -106,7 +107,7 @@ pub struct ChainCollectionItem {
 }
 
 /// Container for chain collection items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainCollectionsData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -118,7 +119,7 @@ pub struct ChainCollectionsData {
 pub type ChainCollectionsResponse = crate::models::ApiResponse<ChainCollectionsData>;
 
 /// Represents an NFT transaction item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftTransactionItem {
     pub block_signed_at: Option<String>,
     pub block_height: Option<u64>,
@@ -131,8 +132,31 @@ pub struct NftTransactionItem {
     pub extra: Option<serde_json::Value>,
 }
 
+/// Position in a collection activity feed, ordered by block, then transaction, then token id.
+///
+/// Persist the checkpoint of the last item processed and pass it to
+/// [`NftService::resume_collection_activity`](crate::NftService::resume_collection_activity)
+/// to continue after it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NftActivityCheckpoint {
+    pub block_height: u64,
+    pub tx_hash: String,
+    pub token_id: String,
+}
+
+impl NftActivityCheckpoint {
+    /// Checkpoint for an item, or `None` if it has no block height.
+    pub fn from_item(item: &NftTransactionItem) -> Option<Self> {
+        Some(Self {
+            block_height: item.block_height?,
+            tx_hash: item.tx_hash.clone().unwrap_or_default(),
+            token_id: item.token_id.clone().unwrap_or_default(),
+        })
+    }
+}
+
 /// Container for NFT transaction items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NftTransactionsData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -144,7 +168,7 @@ pub struct NftTransactionsData {
 pub type NftTransactionsResponse = crate::models::ApiResponse<NftTransactionsData>;
 
 /// Represents a trait item for a collection.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitItem {
     pub name: Option<String>,
     #[serde(flatten)]
@@ -152,7 +176,7 @@ pub struct TraitItem {
 }
 
 /// Container for traits data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitsData {
     pub items: Vec<TraitItem>,
 }
@@ -161,7 +185,7 @@ pub struct TraitsData {
 pub type TraitsResponse = crate::models::ApiResponse<TraitsData>;
 
 /// Represents an attribute item for a trait.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttributeItem {
     pub trait_type: Option<String>,
     pub values: Option<Vec<AttributeValue>>,
@@ -170,7 +194,7 @@ pub struct AttributeItem {
 }
 
 /// An attribute value with count information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttributeValue {
     pub value: Option<serde_json::Value>,
     pub count: Option<u64>,
@@ -179,7 +203,7 @@ pub struct AttributeValue {
 }
 
 /// Container for attributes data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttributesData {
     pub items: Vec<AttributeItem>,
 }
@@ -188,7 +212,7 @@ pub struct AttributesData {
 pub type AttributesResponse = crate::models::ApiResponse<AttributesData>;
 
 /// Represents a traits summary item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitsSummaryItem {
     pub name: Option<String>,
     pub value_count: Option<u64>,
@@ -198,7 +222,7 @@ pub struct TraitsSummaryItem {
 }
 
 /// Container for traits summary data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitsSummaryData {
     pub items: Vec<TraitsSummaryItem>,
 }
@@ -207,7 +231,7 @@ pub struct TraitsSummaryData {
 pub type TraitsSummaryResponse = crate::models::ApiResponse<TraitsSummaryData>;
 
 /// Represents a floor price item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FloorPriceItem {
     pub date: Option<String>,
     pub floor_price_quote: Option<f64>,
@@ -218,7 +242,7 @@ pub struct FloorPriceItem {
 }
 
 /// Container for floor prices data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FloorPricesData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -230,7 +254,7 @@ pub struct FloorPricesData {
 pub type FloorPricesResponse = crate::models::ApiResponse<FloorPricesData>;
 
 /// Represents a volume item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeItem {
     pub date: Option<String>,
     pub volume_quote: Option<f64>,
@@ -241,7 +265,7 @@ pub struct VolumeItem {
 }
 
 /// Container for volume data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -253,7 +277,7 @@ pub struct VolumeData {
 pub type VolumeResponse = crate::models::ApiResponse<VolumeData>;
 
 /// Represents a sales count item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SalesCountItem {
     pub date: Option<String>,
     pub sale_count: Option<u64>,
@@ -263,7 +287,7 @@ pub struct SalesCountItem {
 }
 
 /// Container for sales count data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SalesCountData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -275,10 +299,10 @@ pub struct SalesCountData {
 pub type SalesCountResponse = crate::models::ApiResponse<SalesCountData>;
 
 /// Represents an ownership check item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OwnershipCheckItem {
     pub token_id: Option<String>,
-    pub token_balance: Option<String>,
+    pub token_balance: Option<Amount>,
     pub contract_address: Option<String>,
     pub contract_name: Option<String>,
     pub contract_ticker_symbol: Option<String>,
@@ -287,7 +311,7 @@ pub struct OwnershipCheckItem {
 }
 
 /// Container for ownership check data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OwnershipCheckData {
     pub address: Option<String>,
     pub is_owner: Option<bool>,
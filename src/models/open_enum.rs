@@ -0,0 +1,126 @@
+//! Response enums that tolerate values added to the API after this release.
+//!
+//! Enums declared with [`open_enum!`] keep any value they don't recognize in
+//! an `Unknown(String)` variant, so a new `transfer_type` or chain slug
+//! doesn't fail the whole response. With
+//! [`ClientConfig::with_strict_enums`](crate::ClientConfig::with_strict_enums)
+//! decoding fails on such values instead.
+
+use std::cell::Cell;
+
+thread_local! {
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether enums decoded on this thread reject unknown values.
+pub(crate) fn strict_enums() -> bool {
+    STRICT.with(Cell::get)
+}
+
+/// Run `decode` with unknown enum values rejected when `strict` is set.
+pub(crate) fn decode_with_strictness<R>(strict: bool, decode: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            STRICT.with(|s| s.set(self.0));
+        }
+    }
+
+    let _restore = Restore(STRICT.with(|s| s.replace(strict)));
+    decode()
+}
+
+/// Declare an enum of known wire values plus `Unknown(String)`.
+///
+/// Generates `as_str`, `is_unknown`, `From<&str>`, `Display`, `AsRef<str>`
+/// and string (de)serialization.
+macro_rules! open_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $wire:literal),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A value this version of the SDK doesn't know, as received.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value as sent on the wire.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $wire,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+
+            /// Whether this is a value the SDK doesn't know.
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, $name::Unknown(_))
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($wire => $name::$variant,)*
+                    other => $name::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+                match $name::from(value.as_str()) {
+                    $name::Unknown(_) if $crate::models::strict_enums() => {
+                        Err(serde::de::Error::unknown_variant(&value, &[$($wire),*]))
+                    }
+                    known => Ok(known),
+                }
+            }
+        }
+    };
+}
+pub(crate) use open_enum;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TransferType;
+
+    #[test]
+    fn test_unknown_values_are_kept_unless_strict() {
+        let decode = || serde_json::from_str::<TransferType>("\"SELF\"");
+        assert_eq!(decode().unwrap(), TransferType::Unknown("SELF".to_string()));
+        assert_eq!(serde_json::to_string(&decode().unwrap()).unwrap(), "\"SELF\"");
+
+        let err = decode_with_strictness(true, decode).unwrap_err();
+        assert!(err.to_string().contains("unknown variant `SELF`"), "{}", err);
+        assert_eq!(decode_with_strictness(true, || serde_json::from_str::<TransferType>("\"IN\"")).unwrap(), TransferType::In);
+        assert!(!strict_enums());
+    }
+}
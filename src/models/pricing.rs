@@ -1,12 +1,13 @@
-use serde::Deserialize;
+use crate::decimal::Quote;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenPriceItem {
     pub contract_decimals: Option<u32>,
     pub contract_name: Option<String>,
     pub contract_ticker_symbol: Option<String>,
     pub contract_address: Option<String>,
-    pub supports_erc: Option<Vec<String>>,
+    pub supports_erc: Option<Vec<crate::models::ErcStandard>>,
     pub logo_url: Option<String>,
     pub quote_currency: Option<String>,
     pub prices: Option<Vec<PricePoint>>,
@@ -14,16 +15,16 @@ pub struct TokenPriceItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PricePoint {
     pub date: Option<String>,
-    pub price: Option<f64>,
+    pub price: Option<Quote>,
     pub pretty_price: Option<String>,
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenPricesData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -33,13 +34,13 @@ pub struct TokenPricesData {
 
 pub type TokenPricesResponse = crate::models::ApiResponse<Vec<TokenPriceItem>>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolSpotPriceItem {
     pub exchange: Option<String>,
     pub swap_count_24h: Option<u64>,
-    pub total_liquidity_quote: Option<f64>,
-    pub volume_24h_quote: Option<f64>,
-    pub fee_24h_quote: Option<f64>,
+    pub total_liquidity_quote: Option<Quote>,
+    pub volume_24h_quote: Option<Quote>,
+    pub fee_24h_quote: Option<Quote>,
     pub token_0: Option<PoolToken>,
     pub token_1: Option<PoolToken>,
     pub quote_currency: Option<String>,
@@ -47,20 +48,20 @@ pub struct PoolSpotPriceItem {
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolToken {
     pub contract_address: Option<String>,
     pub contract_name: Option<String>,
     pub contract_ticker_symbol: Option<String>,
     pub contract_decimals: Option<u32>,
     pub logo_url: Option<String>,
-    pub quote_rate: Option<f64>,
+    pub quote_rate: Option<Quote>,
     pub reserve: Option<String>,
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolSpotPricesData {
     pub updated_at: Option<String>,
     pub chain_id: Option<u64>,
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents an SPL token (or native SOL) balance held by a Solana account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplTokenBalanceItem {
+    /// The mint address of the token (base58).
+    pub contract_address: Option<String>,
+
+    /// The token symbol/ticker.
+    pub contract_ticker_symbol: Option<String>,
+
+    /// The token name.
+    pub contract_name: Option<String>,
+
+    /// The number of decimal places for this token.
+    pub contract_decimals: Option<u32>,
+
+    /// The raw balance amount as a string.
+    pub balance: Option<String>,
+
+    /// The current quote rate for the token.
+    pub quote_rate: Option<f64>,
+
+    /// The quote value (balance * quote_rate).
+    pub quote: Option<f64>,
+
+    /// The logo URL for the token.
+    pub logo_url: Option<String>,
+
+    /// Whether this is native SOL rather than an SPL token.
+    pub native_token: Option<bool>,
+
+    /// Whether this token is spam.
+    pub is_spam: Option<bool>,
+
+    /// Last transferred timestamp.
+    pub last_transferred_at: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+impl SplTokenBalanceItem {
+    /// Parse the balance as a floating point number, accounting for decimals.
+    pub fn balance_as_float(&self) -> Option<f64> {
+        let balance = self.balance.as_deref()?.parse::<f64>().ok()?;
+        let decimals = self.contract_decimals.unwrap_or(9);
+        Some(balance / 10f64.powi(decimals as i32))
+    }
+}
+
+/// Container for Solana token balances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolanaBalancesData {
+    pub address: Option<String>,
+    pub chain_name: Option<String>,
+    pub updated_at: Option<String>,
+    pub items: Vec<SplTokenBalanceItem>,
+    pub quote_currency: Option<String>,
+}
+
+/// Response structure for Solana token balance queries.
+pub type SolanaBalancesResponse = crate::models::ApiResponse<SolanaBalancesData>;
+
+/// Represents a single SPL token transfer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplTransferItem {
+    pub block_signed_at: Option<String>,
+    pub slot: Option<u64>,
+    pub tx_hash: Option<String>,
+    pub from_address: Option<String>,
+    pub to_address: Option<String>,
+    pub mint_address: Option<String>,
+    pub contract_ticker_symbol: Option<String>,
+    pub contract_decimals: Option<u32>,
+    pub transfer_type: Option<crate::models::TransferType>,
+    pub delta: Option<String>,
+    pub quote_rate: Option<f64>,
+    pub delta_quote: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+/// Container for SPL token transfers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplTransfersData {
+    pub address: Option<String>,
+    pub chain_name: Option<String>,
+    pub updated_at: Option<String>,
+    pub items: Vec<SplTransferItem>,
+    pub quote_currency: Option<String>,
+}
+
+/// Response structure for SPL token transfer queries.
+pub type SplTransfersResponse = crate::models::ApiResponse<SplTransfersData>;
+
+/// A program invoked by a Solana transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramInteraction {
+    pub program_id: Option<String>,
+    pub program_name: Option<String>,
+    pub instruction_type: Option<String>,
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+/// Represents a Solana transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolanaTransactionItem {
+    pub block_signed_at: Option<String>,
+    pub slot: Option<u64>,
+    /// The transaction signature (base58).
+    pub tx_hash: Option<String>,
+    pub signer: Option<String>,
+    pub successful: Option<bool>,
+    /// Fee paid in lamports.
+    pub fees_paid: Option<String>,
+    pub fee_quote: Option<f64>,
+    pub programs: Option<Vec<ProgramInteraction>>,
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+/// Container for Solana transactions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolanaTransactionsData {
+    pub address: Option<String>,
+    pub chain_name: Option<String>,
+    pub updated_at: Option<String>,
+    pub items: Vec<SolanaTransactionItem>,
+}
+
+/// Response structure for Solana transaction queries.
+pub type SolanaTransactionsResponse = crate::models::ApiResponse<SolanaTransactionsData>;
@@ -2,83 +2,181 @@
 //!
 //! Request and response types for all streaming endpoints, matching TypeScript SDK exactly.
 
+use crate::decimal::Quote;
+use crate::models::open_enum;
+use crate::models::transactions::TransactionItem;
+use crate::validation::Validator;
+use crate::{Chain, Error, Result};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+open_enum! {
+    /// Supported blockchain networks for streaming
+    pub enum StreamingChain {
+        BaseMainnet = "BASE_MAINNET",
+        SolanaMainnet = "SOLANA_MAINNET",
+        SonicMainnet = "SONIC_MAINNET",
+        EthMainnet = "ETH_MAINNET",
+        BscMainnet = "BSC_MAINNET",
+        HypercoreMainnet = "HYPERCORE_MAINNET",
+        HyperevmMainnet = "HYPEREVM_MAINNET",
+        MonadMainnet = "MONAD_MAINNET",
+        PolygonMainnet = "POLYGON_MAINNET",
+        MegaethMainnet = "MEGAETH_MAINNET",
+    }
+}
+
+impl StreamingChain {
+    /// Whether addresses on this chain are base58 rather than `0x` hex.
+    pub fn uses_base58_addresses(&self) -> bool {
+        matches!(self, StreamingChain::SolanaMainnet)
+    }
+
+    /// Check that `address` is well formed for this chain.
+    pub fn validate_address(&self, address: &str) -> Result<()> {
+        let checked = if self.uses_base58_addresses() {
+            Validator::validate_solana_address(address)
+        } else {
+            Validator::validate_address(address)
+        };
+        checked.map_err(|e| match e {
+            Error::Config(reason) => Error::Config(format!("{:?}: {} ('{}')", self, reason, address)),
+            other => other,
+        })
+    }
+
+    /// The matching REST chain, if the REST API serves it.
+    pub fn rest(&self) -> Option<Chain> {
+        match self {
+            StreamingChain::BaseMainnet => Some(Chain::BaseMainnet),
+            StreamingChain::SolanaMainnet => Some(Chain::SolanaMainnet),
+            StreamingChain::SonicMainnet => Some(Chain::SonicMainnet),
+            StreamingChain::EthMainnet => Some(Chain::EthereumMainnet),
+            StreamingChain::BscMainnet => Some(Chain::BscMainnet),
+            StreamingChain::PolygonMainnet => Some(Chain::PolygonMainnet),
+            StreamingChain::HypercoreMainnet
+            | StreamingChain::HyperevmMainnet
+            | StreamingChain::MonadMainnet
+            | StreamingChain::MegaethMainnet
+            | StreamingChain::Unknown(_) => None,
+        }
+    }
+}
+
+impl TryFrom<StreamingChain> for Chain {
+    type Error = Error;
+
+    fn try_from(chain: StreamingChain) -> Result<Self> {
+        chain
+            .rest()
+            .ok_or_else(|| Error::Config(format!("{:?} has no REST API chain", chain)))
+    }
+}
+
+impl TryFrom<Chain> for StreamingChain {
+    type Error = Error;
+
+    fn try_from(chain: Chain) -> Result<Self> {
+        chain
+            .streaming()
+            .ok_or_else(|| Error::Config(format!("{} is not available on the streaming API", chain)))
+    }
+}
 
-/// Supported blockchain networks for streaming
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum StreamingChain {
-    BaseMainnet,
-    SolanaMainnet,
-    SonicMainnet,
-    EthMainnet,
-    BscMainnet,
-    HypercoreMainnet,
-    HyperevmMainnet,
-    MonadMainnet,
-    PolygonMainnet,
-    MegaethMainnet,
-}
-
-/// Time intervals for OHLCV data
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum StreamingInterval {
-    OneSecond,
-    FiveSeconds,
-    FifteenSeconds,
-    OneMinute,
-    FiveMinutes,
-    FifteenMinutes,
-    OneHour,
-    FourHours,
-    OneDay,
-}
-
-/// Timeframe windows for aggregation
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum StreamingTimeframe {
-    OneMinute,
-    FiveMinutes,
-    FifteenMinutes,
-    OneHour,
-    FourHours,
-    OneDay,
-    SevenDays,
-}
-
-/// DEX protocols supported
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum StreamingProtocol {
-    UniswapV2,
-    UniswapV3,
-    VirtualsV2,
-    #[serde(rename = "CLANKER")]
-    ClankerV3,
-    RaydiumAmm,
-    RaydiumClmm,
-    RaydiumCpmm,
-    PumpDotFun,
-    PumpFunAmm,
-    Moonshot,
-    RaydiumLaunchLab,
-    MeteoraDamm,
-    MeteoraDlmm,
-    MeteoraDbc,
-    PancakeswapV2,
-    PancakeswapV3,
-    ShadowV2,
-    ShadowV3,
-    OctoswapV2,
-    OctoswapV3,
-    QuickswapV2,
-    QuickswapV3,
-    SushiswapV2,
-    ProjectX,
-    KumbayaV1,
-    JoeV2,
+open_enum! {
+    /// Time intervals for OHLCV data
+    pub enum StreamingInterval {
+        OneSecond = "ONE_SECOND",
+        FiveSeconds = "FIVE_SECONDS",
+        FifteenSeconds = "FIFTEEN_SECONDS",
+        OneMinute = "ONE_MINUTE",
+        FiveMinutes = "FIVE_MINUTES",
+        FifteenMinutes = "FIFTEEN_MINUTES",
+        OneHour = "ONE_HOUR",
+        FourHours = "FOUR_HOURS",
+        OneDay = "ONE_DAY",
+    }
+}
+
+impl StreamingInterval {
+    /// Length of one candle (`None` for an unknown value).
+    pub fn duration(&self) -> Option<Duration> {
+        let secs = match self {
+            StreamingInterval::OneSecond => 1,
+            StreamingInterval::FiveSeconds => 5,
+            StreamingInterval::FifteenSeconds => 15,
+            StreamingInterval::OneMinute => 60,
+            StreamingInterval::FiveMinutes => 300,
+            StreamingInterval::FifteenMinutes => 900,
+            StreamingInterval::OneHour => 3_600,
+            StreamingInterval::FourHours => 14_400,
+            StreamingInterval::OneDay => 86_400,
+            StreamingInterval::Unknown(_) => return None,
+        };
+        Some(Duration::from_secs(secs))
+    }
+}
+
+open_enum! {
+    /// Timeframe windows for aggregation
+    pub enum StreamingTimeframe {
+        OneMinute = "ONE_MINUTE",
+        FiveMinutes = "FIVE_MINUTES",
+        FifteenMinutes = "FIFTEEN_MINUTES",
+        OneHour = "ONE_HOUR",
+        FourHours = "FOUR_HOURS",
+        OneDay = "ONE_DAY",
+        SevenDays = "SEVEN_DAYS",
+    }
+}
+
+impl StreamingTimeframe {
+    /// Length of the window (`None` for an unknown value).
+    pub fn duration(&self) -> Option<Duration> {
+        let secs = match self {
+            StreamingTimeframe::OneMinute => 60,
+            StreamingTimeframe::FiveMinutes => 300,
+            StreamingTimeframe::FifteenMinutes => 900,
+            StreamingTimeframe::OneHour => 3_600,
+            StreamingTimeframe::FourHours => 14_400,
+            StreamingTimeframe::OneDay => 86_400,
+            StreamingTimeframe::SevenDays => 604_800,
+            StreamingTimeframe::Unknown(_) => return None,
+        };
+        Some(Duration::from_secs(secs))
+    }
+}
+
+open_enum! {
+    /// DEX protocols supported
+    pub enum StreamingProtocol {
+        UniswapV2 = "UNISWAP_V2",
+        UniswapV3 = "UNISWAP_V3",
+        VirtualsV2 = "VIRTUALS_V2",
+        ClankerV3 = "CLANKER",
+        RaydiumAmm = "RAYDIUM_AMM",
+        RaydiumClmm = "RAYDIUM_CLMM",
+        RaydiumCpmm = "RAYDIUM_CPMM",
+        PumpDotFun = "PUMP_DOT_FUN",
+        PumpFunAmm = "PUMP_FUN_AMM",
+        Moonshot = "MOONSHOT",
+        RaydiumLaunchLab = "RAYDIUM_LAUNCH_LAB",
+        MeteoraDamm = "METEORA_DAMM",
+        MeteoraDlmm = "METEORA_DLMM",
+        MeteoraDbc = "METEORA_DBC",
+        PancakeswapV2 = "PANCAKESWAP_V2",
+        PancakeswapV3 = "PANCAKESWAP_V3",
+        ShadowV2 = "SHADOW_V2",
+        ShadowV3 = "SHADOW_V3",
+        OctoswapV2 = "OCTOSWAP_V2",
+        OctoswapV3 = "OCTOSWAP_V3",
+        QuickswapV2 = "QUICKSWAP_V2",
+        QuickswapV3 = "QUICKSWAP_V3",
+        SushiswapV2 = "SUSHISWAP_V2",
+        ProjectX = "PROJECT_X",
+        KumbayaV1 = "KUMBAYA_V1",
+        JoeV2 = "JOE_V2",
+    }
 }
 
 /// Contract/Token metadata
@@ -89,7 +187,7 @@ pub struct ContractMetadata {
     pub contract_ticker_symbol: Option<String>,
     pub contract_address: String,
     #[serde(default)]
-    pub supports_erc: Vec<String>,
+    pub supports_erc: Vec<crate::models::ErcStandard>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logo_url: Option<String>,
 }
@@ -117,14 +215,14 @@ pub struct OhlcvPairsResponse {
     pub interval: StreamingInterval,
     pub timeframe: StreamingTimeframe,
     pub timestamp: String,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
+    pub open: Quote,
+    pub high: Quote,
+    pub low: Quote,
+    pub close: Quote,
     pub volume: f64,
-    pub volume_usd: f64,
-    pub quote_rate: f64,
-    pub quote_rate_usd: f64,
+    pub volume_usd: Quote,
+    pub quote_rate: Quote,
+    pub quote_rate_usd: Quote,
     pub base_token: ContractMetadata,
     pub quote_token: ContractMetadata,
 }
@@ -152,18 +250,26 @@ pub struct OhlcvTokensResponse {
     pub interval: StreamingInterval,
     pub timeframe: StreamingTimeframe,
     pub timestamp: String,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
+    pub open: Quote,
+    pub high: Quote,
+    pub low: Quote,
+    pub close: Quote,
     pub volume: f64,
-    pub volume_usd: f64,
-    pub quote_rate: f64,
-    pub quote_rate_usd: f64,
+    pub volume_usd: Quote,
+    pub quote_rate: Quote,
+    pub quote_rate_usd: Quote,
     pub base_token: ContractMetadata,
     pub quote_token: ContractMetadata,
 }
 
+/// Candles from an OHLCV subscription with history preloaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcvBatch<T> {
+    pub candles: Vec<T>,
+    /// Whether these candles were replayed from history rather than received live
+    pub historical: bool,
+}
+
 // =============================================================================
 // New DEX Pairs Stream
 // =============================================================================
@@ -203,12 +309,12 @@ pub struct NewPairsResponse {
     pub deployer_address: String,
     pub tx_hash: String,
     pub block_signed_at: String,
-    pub liquidity: f64,
+    pub liquidity: Quote,
     pub supply: f64,
-    pub market_cap: f64,
+    pub market_cap: Quote,
     pub event_name: String,
-    pub quote_rate: f64,
-    pub quote_rate_usd: f64,
+    pub quote_rate: Quote,
+    pub quote_rate_usd: Quote,
     pub base_token: ContractMetadata,
     pub quote_token: ContractMetadata,
     pub pair: ContractMetadata,
@@ -231,12 +337,12 @@ pub struct UpdatePairsResponse {
     pub chain_name: String,
     pub pair_address: String,
     pub timestamp: String,
-    pub quote_rate: f64,
-    pub quote_rate_usd: f64,
+    pub quote_rate: Quote,
+    pub quote_rate_usd: Quote,
     pub volume: f64,
-    pub volume_usd: f64,
-    pub market_cap: f64,
-    pub liquidity: f64,
+    pub volume_usd: Quote,
+    pub market_cap: Quote,
+    pub liquidity: Quote,
     pub base_token: ContractMetadata,
     pub quote_token: ContractMetadata,
     pub price_deltas: PriceMetrics,
@@ -269,8 +375,8 @@ pub struct TransferTransaction {
     pub from: String,
     pub to: String,
     pub amount: String,
-    pub quote_usd: f64,
-    pub quote_rate_usd: f64,
+    pub quote_usd: Quote,
+    pub quote_rate_usd: Quote,
     pub contract_metadata: ContractMetadata,
 }
 
@@ -293,8 +399,8 @@ pub struct BridgeTransaction {
     pub from: String,
     pub to: String,
     pub amount: String,
-    pub quote_usd: f64,
-    pub quote_rate_usd: f64,
+    pub quote_usd: Quote,
+    pub quote_rate_usd: Quote,
     pub contract_metadata: ContractMetadata,
 }
 
@@ -304,8 +410,8 @@ pub struct DepositTransaction {
     pub from: String,
     pub to: String,
     pub amount: String,
-    pub quote_usd: f64,
-    pub quote_rate_usd: f64,
+    pub quote_usd: Quote,
+    pub quote_rate_usd: Quote,
     pub contract_metadata: ContractMetadata,
 }
 
@@ -315,8 +421,8 @@ pub struct WithdrawTransaction {
     pub from: String,
     pub to: String,
     pub amount: String,
-    pub quote_usd: f64,
-    pub quote_rate_usd: f64,
+    pub quote_usd: Quote,
+    pub quote_rate_usd: Quote,
     pub contract_metadata: ContractMetadata,
 }
 
@@ -325,8 +431,8 @@ pub struct WithdrawTransaction {
 pub struct ApproveTransaction {
     pub spender: String,
     pub amount: String,
-    pub quote_usd: f64,
-    pub quote_rate_usd: f64,
+    pub quote_usd: Quote,
+    pub quote_rate_usd: Quote,
     pub contract_metadata: ContractMetadata,
 }
 
@@ -370,6 +476,36 @@ pub struct WalletActivityResponse {
     pub logs: Vec<WalletActivityLogItem>,
 }
 
+/// One transaction from a wallet activity subscription with backfill
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "source", content = "transaction", rename_all = "snake_case")]
+pub enum WalletActivityEvent {
+    /// Fetched from the REST API for the blocks before the subscription started
+    Historical(Box<TransactionItem>),
+    /// Received on the live subscription
+    Live(Box<WalletActivityResponse>),
+}
+
+impl WalletActivityEvent {
+    pub fn tx_hash(&self) -> &str {
+        match self {
+            WalletActivityEvent::Historical(tx) => &tx.tx_hash,
+            WalletActivityEvent::Live(tx) => &tx.tx_hash,
+        }
+    }
+
+    pub fn block_height(&self) -> Option<u64> {
+        match self {
+            WalletActivityEvent::Historical(tx) => tx.block_height,
+            WalletActivityEvent::Live(tx) => Some(tx.block_height),
+        }
+    }
+
+    pub fn is_historical(&self) -> bool {
+        matches!(self, WalletActivityEvent::Historical(_))
+    }
+}
+
 // =============================================================================
 // Token Search Query
 // =============================================================================
@@ -385,11 +521,11 @@ pub struct TokenSearchParams {
 pub struct TokenSearchResponse {
     pub pair_address: String,
     pub chain_name: String,
-    pub quote_rate: f64,
-    pub quote_rate_usd: f64,
+    pub quote_rate: Quote,
+    pub quote_rate_usd: Quote,
     pub volume: f64,
-    pub volume_usd: f64,
-    pub market_cap: f64,
+    pub volume_usd: Quote,
+    pub market_cap: Quote,
     pub base_token: ContractMetadata,
     pub quote_token: ContractMetadata,
 }
@@ -412,10 +548,10 @@ pub struct UpnlForTokenResponse {
     pub wallet_address: String,
     pub volume: String,
     pub transactions_count: u32,
-    pub pnl_realized_usd: f64,
+    pub pnl_realized_usd: Quote,
     pub balance: String,
     pub balance_pretty: String,
-    pub pnl_unrealized_usd: f64,
+    pub pnl_unrealized_usd: Quote,
     pub contract_metadata: ContractMetadata,
 }
 
@@ -434,15 +570,103 @@ pub struct UpnlForWalletParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpnlForWalletResponse {
     pub token_address: String,
-    pub cost_basis: f64,
-    pub current_price: f64,
-    pub pnl_realized_usd: Option<f64>,
-    pub pnl_unrealized_usd: f64,
+    pub cost_basis: Quote,
+    pub current_price: Quote,
+    pub pnl_realized_usd: Option<Quote>,
+    pub pnl_unrealized_usd: Quote,
     pub net_balance_change: String,
     pub marketcap_usd: String,
     pub contract_metadata: ContractMetadata,
 }
 
+// =============================================================================
+// Local validation
+// =============================================================================
+//
+// The streaming API reports bad variables as opaque GraphQL errors, often
+// only after the subscription has been accepted. These checks run before
+// anything is sent.
+
+fn validate_addresses(chain: &StreamingChain, field: &str, addresses: &[String]) -> Result<()> {
+    if addresses.is_empty() {
+        return Err(Error::Config(format!("{} cannot be empty", field)));
+    }
+    addresses.iter().try_for_each(|address| chain.validate_address(address))
+}
+
+fn validate_candles(interval: &StreamingInterval, timeframe: &StreamingTimeframe, limit: Option<u32>) -> Result<()> {
+    // Values newer than the SDK are left for the server to check.
+    let finer = match (interval.duration(), timeframe.duration()) {
+        (Some(interval_len), Some(timeframe_len)) => interval_len < timeframe_len,
+        _ => true,
+    };
+    if !finer {
+        return Err(Error::Config(format!(
+            "interval {:?} must be finer than timeframe {:?}", interval, timeframe
+        )));
+    }
+    if limit == Some(0) {
+        return Err(Error::Config("limit must be at least 1".to_string()));
+    }
+    Ok(())
+}
+
+impl OhlcvPairsParams {
+    pub fn validate(&self) -> Result<()> {
+        validate_addresses(&self.chain_name, "pair_addresses", &self.pair_addresses)?;
+        validate_candles(&self.interval, &self.timeframe, self.limit)
+    }
+}
+
+impl OhlcvTokensParams {
+    pub fn validate(&self) -> Result<()> {
+        validate_addresses(&self.chain_name, "token_addresses", &self.token_addresses)?;
+        validate_candles(&self.interval, &self.timeframe, self.limit)
+    }
+}
+
+impl NewPairsParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.protocols.is_empty() {
+            return Err(Error::Config("protocols cannot be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl UpdatePairsParams {
+    pub fn validate(&self) -> Result<()> {
+        validate_addresses(&self.chain_name, "pair_addresses", &self.pair_addresses)
+    }
+}
+
+impl WalletActivityParams {
+    pub fn validate(&self) -> Result<()> {
+        validate_addresses(&self.chain_name, "wallet_addresses", &self.wallet_addresses)
+    }
+}
+
+impl TokenSearchParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.query.trim().is_empty() {
+            return Err(Error::Config("search query cannot be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl UpnlForTokenParams {
+    pub fn validate(&self) -> Result<()> {
+        self.chain_name.validate_address(&self.token_address)
+    }
+}
+
+impl UpnlForWalletParams {
+    pub fn validate(&self) -> Result<()> {
+        self.chain_name.validate_address(&self.wallet_address)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -451,7 +675,7 @@ mod tests {
     fn test_enum_serialization() {
         let chain = StreamingChain::BaseMainnet;
         let json = serde_json::to_string(&chain).unwrap();
-        assert_eq!(json, r#""BASE_MAINNET"#);
+        assert_eq!(json, r#""BASE_MAINNET""#);
     }
 
     #[test]
@@ -466,4 +690,51 @@ mod tests {
         let json = serde_json::to_value(&params).unwrap();
         assert_eq!(json["chain_name"], "BASE_MAINNET");
     }
+
+    #[test]
+    fn test_chain_conversion() {
+        assert_eq!(Chain::try_from(StreamingChain::EthMainnet).unwrap(), Chain::EthereumMainnet);
+        assert_eq!(StreamingChain::try_from(Chain::PolygonMainnet).unwrap(), StreamingChain::PolygonMainnet);
+        assert!(Chain::try_from(StreamingChain::MonadMainnet).is_err());
+        assert!(StreamingChain::try_from(Chain::ArbitrumMainnet).is_err());
+        for chain in Chain::all() {
+            if let Some(streaming) = chain.streaming() {
+                assert_eq!(streaming.rest(), Some(chain));
+            }
+        }
+    }
+
+    #[test]
+    fn test_params_validation() {
+        let mut params = OhlcvPairsParams {
+            chain_name: StreamingChain::BaseMainnet,
+            pair_addresses: vec!["0x9c087Eb773291e50CF6c6a90ef0F4500e349B903".to_string()],
+            interval: StreamingInterval::OneMinute,
+            timeframe: StreamingTimeframe::OneHour,
+            limit: Some(10),
+        };
+        assert!(params.validate().is_ok());
+
+        params.interval = StreamingInterval::OneHour;
+        let err = params.validate().unwrap_err().to_string();
+        assert!(err.contains("must be finer than timeframe"), "{}", err);
+
+        params.interval = StreamingInterval::OneMinute;
+        params.pair_addresses.clear();
+        assert!(params.validate().unwrap_err().to_string().contains("pair_addresses cannot be empty"));
+
+        let wallet = WalletActivityParams {
+            chain_name: StreamingChain::SolanaMainnet,
+            wallet_addresses: vec!["0x4200000000000000000000000000000000000006".to_string()],
+        };
+        let err = wallet.validate().unwrap_err().to_string();
+        assert!(err.contains("SolanaMainnet"), "{}", err);
+
+        let wallet = WalletActivityParams {
+            chain_name: StreamingChain::SolanaMainnet,
+            wallet_addresses: vec!["9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string()],
+        };
+        assert!(wallet.validate().is_ok());
+        assert!(TokenSearchParams { query: "  ".to_string() }.validate().is_err());
+    }
 }
@@ -1,7 +1,10 @@
-use serde::Deserialize;
+use crate::address::same_address;
+use crate::amount::{Amount, AmountValue};
+use crate::abi::{DecodedCall, SelectorRegistry};
+use serde::{Deserialize, Serialize};
 
 /// Represents a transaction item returned by the API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionItem {
     /// The transaction hash.
     pub tx_hash: String,
@@ -9,12 +12,21 @@ pub struct TransactionItem {
     /// The sender address.
     pub from_address: String,
 
+    /// Label for the sender address, if known.
+    pub from_address_label: Option<String>,
+
     /// The recipient address.
     pub to_address: Option<String>,
 
+    /// Label for the recipient address, if known.
+    pub to_address_label: Option<String>,
+
     /// The transaction value as a string.
     pub value: String,
 
+    /// Raw call data, returned when `with_input_data` is set.
+    pub input: Option<String>,
+
     /// Whether the transaction was successful.
     pub successful: Option<bool>,
 
@@ -36,6 +48,18 @@ pub struct TransactionItem {
     /// Gas used by the transaction.
     pub gas_used: Option<u64>,
 
+    /// Gas units actually spent (the API's name for `gas_used`).
+    pub gas_spent: Option<u64>,
+
+    /// EIP-1559 base fee per gas of the including block, in wei.
+    pub base_fee_per_gas: Option<u64>,
+
+    /// EIP-1559 max fee per gas, in wei.
+    pub max_fee_per_gas: Option<u64>,
+
+    /// EIP-1559 max priority fee per gas, in wei.
+    pub max_priority_fee_per_gas: Option<u64>,
+
     /// Transaction fee paid.
     pub fees_paid: Option<String>,
 
@@ -50,10 +74,270 @@ pub struct TransactionItem {
 
     /// Log events associated with this transaction.
     pub log_events: Option<Vec<LogEvent>>,
+
+    /// Internal (trace-level) value transfers, returned when `with_internal` is set.
+    pub internal_transfers: Option<Vec<InternalTransfer>>,
+
+    /// Per-account state diffs, returned when `with_state` is set.
+    pub state_changes: Option<Vec<StateChange>>,
+}
+
+/// State diff for a single account touched by a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateChange {
+    /// The account whose state changed.
+    pub address: Option<String>,
+
+    /// Native balance before the transaction, in wei.
+    pub balance_before: Option<Amount>,
+
+    /// Native balance after the transaction, in wei.
+    pub balance_after: Option<Amount>,
+
+    /// Account nonce before the transaction.
+    pub nonce_before: Option<u64>,
+
+    /// Account nonce after the transaction.
+    pub nonce_after: Option<u64>,
+
+    /// Storage slots written by the transaction.
+    pub storage_changes: Option<Vec<StorageChange>>,
+
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+/// A single storage slot write.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageChange {
+    /// The storage slot (32-byte hex).
+    #[serde(alias = "storage_key")]
+    pub slot: Option<String>,
+
+    /// Slot value before the transaction.
+    #[serde(alias = "from")]
+    pub before: Option<String>,
+
+    /// Slot value after the transaction.
+    #[serde(alias = "to")]
+    pub after: Option<String>,
+}
+
+/// Native balance delta for an account, derived from a [`StateChange`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub address: String,
+    /// Balance before, in wei.
+    pub before: u128,
+    /// Balance after, in wei.
+    pub after: u128,
+}
+
+impl BalanceChange {
+    /// Signed change in wei (positive when the account gained value).
+    pub fn delta(&self) -> i128 {
+        self.after as i128 - self.before as i128
+    }
+}
+
+impl StateChange {
+    /// The native balance delta, if both balances are present and parseable.
+    pub fn balance_change(&self) -> Option<BalanceChange> {
+        let before = self.balance_before.as_ref()?.digits()?.parse().ok()?;
+        let after = self.balance_after.as_ref()?.digits()?.parse().ok()?;
+        Some(BalanceChange { address: self.address.clone()?, before, after })
+    }
+}
+
+/// A value transfer made by a nested call within a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InternalTransfer {
+    /// The calling address.
+    pub from_address: Option<String>,
+
+    /// Label for the calling address, if known.
+    pub from_address_label: Option<String>,
+
+    /// The receiving address.
+    pub to_address: Option<String>,
+
+    /// Label for the receiving address, if known.
+    pub to_address_label: Option<String>,
+
+    /// Native value transferred, in wei.
+    pub value: Option<String>,
+
+    /// Quote value of the transfer.
+    pub value_quote: Option<f64>,
+
+    /// Call type (`call`, `delegatecall`, `staticcall`, `create`, ...).
+    pub call_type: Option<String>,
+
+    /// Depth of the call in the trace (the top-level call is depth 0).
+    pub depth: Option<u32>,
+
+    #[serde(flatten)]
+    pub extra: Option<serde_json::Value>,
+}
+
+impl InternalTransfer {
+    /// Transferred value in wei, if present and parseable.
+    pub fn value_wei(&self) -> Option<u128> {
+        self.value.as_deref()?.trim().parse().ok()
+    }
+
+    /// Whether this transfer moved a non-zero native value.
+    pub fn has_value(&self) -> bool {
+        self.value_wei().is_some_and(|v| v > 0)
+    }
+
+    /// Whether this transfer is a `delegatecall` (executes in the caller's context).
+    pub fn is_delegate_call(&self) -> bool {
+        self.call_type.as_deref().is_some_and(|t| t.eq_ignore_ascii_case("delegatecall"))
+    }
+}
+
+/// Native token decimals assumed when converting wei fees.
+const NATIVE_DECIMALS: i32 = 18;
+
+impl TransactionItem {
+    /// Gas units consumed, preferring `gas_spent` over `gas_used`.
+    pub fn gas_units(&self) -> Option<u64> {
+        self.gas_spent.or(self.gas_used)
+    }
+
+    /// Price actually paid per unit of gas, in wei.
+    ///
+    /// Derived from `fees_paid / gas units` when both are present; otherwise
+    /// computed from the EIP-1559 fields as `min(max_fee, base_fee + priority_fee)`,
+    /// falling back to the legacy `gas_price`.
+    pub fn effective_gas_price(&self) -> Option<u128> {
+        if let (Some(fees), Some(units)) = (self.fees_paid_wei(), self.gas_units()) {
+            if units > 0 {
+                return Some(fees / units as u128);
+            }
+        }
+
+        if let (Some(base), Some(max_fee)) = (self.base_fee_per_gas, self.max_fee_per_gas) {
+            let priority = self.max_priority_fee_per_gas.unwrap_or(0);
+            return Some(max_fee.min(base.saturating_add(priority)) as u128);
+        }
+
+        self.gas_price.map(u128::from)
+    }
+
+    /// Total fee paid, in wei.
+    pub fn total_fee_wei(&self) -> Option<u128> {
+        self.fees_paid_wei().or_else(|| {
+            let units = self.gas_units()? as u128;
+            Some(self.effective_gas_price()? * units)
+        })
+    }
+
+    /// Total fee paid, in native token units (assuming 18 decimals).
+    pub fn total_fee_native(&self) -> Option<f64> {
+        self.total_fee_wei()
+            .map(|wei| wei as f64 / 10f64.powi(NATIVE_DECIMALS))
+    }
+
+    /// Total fee paid, in the quote currency.
+    ///
+    /// Uses `gas_quote` when present, else `total_fee_native() * gas_quote_rate`.
+    pub fn total_fee_quote(&self) -> Option<f64> {
+        self.gas_quote
+            .or_else(|| Some(self.total_fee_native()? * self.gas_quote_rate?))
+    }
+
+    fn fees_paid_wei(&self) -> Option<u128> {
+        self.fees_paid.as_deref()?.trim().parse().ok()
+    }
+
+    /// Whether the transaction moved a non-zero amount of the native token.
+    pub fn has_native_value(&self) -> bool {
+        let value = self.value.trim();
+        !value.is_empty() && value.chars().any(|c| c != '0')
+    }
+
+    /// Whether the given address is the sender or recipient (case-insensitive).
+    pub fn involves(&self, address: &str) -> bool {
+        same_address(&self.from_address, address)
+            || self.to_address.as_deref().is_some_and(|to| same_address(to, address))
+    }
+
+    /// Log events that decode as ERC-20 `Transfer` events.
+    pub fn erc20_transfer_events(&self) -> impl Iterator<Item = &LogEvent> {
+        self.log_events.iter().flatten().filter(|log| log.is_erc20_transfer())
+    }
+
+    /// Internal transfers that moved non-zero native value.
+    pub fn internal_value_transfers(&self) -> impl Iterator<Item = &InternalTransfer> {
+        self.internal_transfers.iter().flatten().filter(|t| t.has_value())
+    }
+
+    /// Native balance changes for every account whose balance moved.
+    pub fn balance_changes(&self) -> Vec<BalanceChange> {
+        self.state_changes.iter().flatten()
+            .filter_map(StateChange::balance_change)
+            .filter(|change| change.before != change.after)
+            .collect()
+    }
+
+    /// Storage writes made to the given contract address.
+    pub fn storage_changes_for(&self, address: &str) -> Vec<&StorageChange> {
+        self.state_changes.iter().flatten()
+            .filter(|change| change.address.as_deref().is_some_and(|a| same_address(a, address)))
+            .flat_map(|change| change.storage_changes.iter().flatten())
+            .collect()
+    }
+
+    /// The `0x`-prefixed 4-byte method selector from `input`, if any.
+    pub fn method_selector(&self) -> Option<&str> {
+        let input = self.input.as_deref()?.trim();
+        let selector = input.get(..10)?;
+        (selector.starts_with("0x") && selector[2..].chars().all(|c| c.is_ascii_hexdigit()))
+            .then_some(selector)
+    }
+
+    /// Decode `input` into a method name and arguments using `registry`.
+    pub fn decode_input(&self, registry: &SelectorRegistry) -> Option<DecodedCall> {
+        registry.decode(self.input.as_deref()?)
+    }
+
+    /// Classify the transaction from its recipient, call data and log events.
+    pub fn kind(&self) -> TransactionKind {
+        if self.to_address.as_deref().map_or(true, |to| to.trim().is_empty()) {
+            return TransactionKind::ContractDeploy;
+        }
+        if self.erc20_transfer_events().next().is_some() {
+            return TransactionKind::Erc20Transfer;
+        }
+
+        let has_call_data = self.input.as_deref()
+            .is_some_and(|input| !matches!(input.trim(), "" | "0x"));
+        let has_logs = self.log_events.as_ref().is_some_and(|logs| !logs.is_empty());
+        if !has_call_data && !has_logs {
+            TransactionKind::NativeTransfer
+        } else {
+            TransactionKind::ContractCall
+        }
+    }
+}
+
+/// Coarse classification of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionKind {
+    /// Plain native-token transfer with no call data or logs.
+    NativeTransfer,
+    /// Transaction emitting at least one ERC-20 `Transfer` event.
+    Erc20Transfer,
+    /// Contract creation (no recipient).
+    ContractDeploy,
+    /// Any other contract interaction.
+    ContractCall,
 }
 
 /// Represents a log event in a transaction.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEvent {
     /// The contract address that emitted this log.
     pub sender_contract_address: String,
@@ -68,8 +352,23 @@ pub struct LogEvent {
     pub decoded: Option<serde_json::Value>,
 }
 
+impl LogEvent {
+    /// Whether this log decodes as an ERC-20 `Transfer(from, to, value)` event.
+    ///
+    /// ERC-721 transfers share the event name but carry `tokenId` instead of `value`.
+    pub fn is_erc20_transfer(&self) -> bool {
+        let Some(decoded) = self.decoded.as_ref() else { return false };
+        if decoded.get("name").and_then(|n| n.as_str()) != Some("Transfer") {
+            return false;
+        }
+        decoded.get("params")
+            .and_then(|p| p.as_array())
+            .is_some_and(|params| params.iter().any(|p| p.get("name").and_then(|n| n.as_str()) == Some("value")))
+    }
+}
+
 /// Container for transaction items.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionsData {
     /// The address these transactions belong to.
     pub address: Option<String>,
@@ -87,6 +386,43 @@ pub struct TransactionsData {
     pub quote_currency: Option<String>,
 }
 
+impl TransactionsData {
+    /// Transactions sent to or received from the given address.
+    pub fn filter_by_counterparty(&self, address: &str) -> Vec<&TransactionItem> {
+        self.items.iter()
+            .filter(|tx| tx.involves(address))
+            .collect()
+    }
+
+    /// Transactions that executed successfully.
+    pub fn successful_only(&self) -> Vec<&TransactionItem> {
+        self.items.iter()
+            .filter(|tx| tx.successful.unwrap_or(false))
+            .collect()
+    }
+
+    /// Transactions that moved native value or emitted ERC-20 transfers.
+    pub fn value_transfers(&self) -> Vec<&TransactionItem> {
+        self.items.iter()
+            .filter(|tx| tx.has_native_value() || tx.erc20_transfer_events().next().is_some())
+            .collect()
+    }
+
+    /// Pair every transaction with its [`TransactionKind`].
+    pub fn classify(&self) -> Vec<(&TransactionItem, TransactionKind)> {
+        self.items.iter()
+            .map(|tx| (tx, tx.kind()))
+            .collect()
+    }
+
+    /// Transactions of a single [`TransactionKind`].
+    pub fn of_kind(&self, kind: TransactionKind) -> Vec<&TransactionItem> {
+        self.items.iter()
+            .filter(|tx| tx.kind() == kind)
+            .collect()
+    }
+}
+
 /// Response structure for transaction list queries.
 pub type TransactionsResponse = crate::models::ApiResponse<TransactionsData>;
 
@@ -96,7 +432,7 @@ pub type TransactionResponse = crate::models::ApiResponse<TransactionItem>;
 // --- Extended models for additional transaction endpoints ---
 
 /// Transaction summary data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionSummaryItem {
     pub total_count: Option<u64>,
     pub earliest_transaction: Option<TransactionTimestamp>,
@@ -106,7 +442,7 @@ pub struct TransactionSummaryItem {
 }
 
 /// Timestamp info for a transaction.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionTimestamp {
     pub block_signed_at: Option<String>,
     pub tx_hash: Option<String>,
@@ -114,7 +450,7 @@ pub struct TransactionTimestamp {
 }
 
 /// Container for transaction summary data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionSummaryData {
     pub address: Option<String>,
     pub chain_id: Option<u64>,
@@ -126,7 +462,7 @@ pub struct TransactionSummaryData {
 pub type TransactionSummaryResponse = crate::models::ApiResponse<TransactionSummaryData>;
 
 /// Represents a time bucket transaction item.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeBucketTransactionItem {
     pub date: Option<String>,
     pub block_height: Option<u64>,
@@ -143,7 +479,7 @@ pub struct TimeBucketTransactionItem {
 }
 
 /// Container for time bucket data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeBucketData {
     pub address: Option<String>,
     pub chain_id: Option<u64>,
@@ -156,7 +492,7 @@ pub struct TimeBucketData {
 pub type TimeBucketResponse = crate::models::ApiResponse<TimeBucketData>;
 
 /// Container for block transactions data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockTransactionsData {
     pub chain_id: Option<u64>,
     pub chain_name: Option<String>,
@@ -167,3 +503,135 @@ pub struct BlockTransactionsData {
 
 /// Response structure for block transaction queries.
 pub type BlockTransactionsResponse = crate::models::ApiResponse<BlockTransactionsData>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(fields: serde_json::Value) -> TransactionItem {
+        let mut base = serde_json::json!({
+            "tx_hash": "0xabc",
+            "from_address": "0x1",
+            "value": "0",
+        });
+        base.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(base).unwrap()
+    }
+
+    #[test]
+    fn test_fee_helpers_prefer_fees_paid() {
+        let item = tx(serde_json::json!({
+            "gas_spent": 21000,
+            "gas_price": 1,
+            "fees_paid": "420000000000000",
+            "gas_quote_rate": 2000.0,
+        }));
+        assert_eq!(item.effective_gas_price(), Some(20_000_000_000));
+        assert_eq!(item.total_fee_wei(), Some(420_000_000_000_000));
+        assert!((item.total_fee_quote().unwrap() - 0.84).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fee_helpers_eip1559_and_legacy() {
+        let item = tx(serde_json::json!({
+            "gas_used": 100,
+            "base_fee_per_gas": 10,
+            "max_fee_per_gas": 50,
+            "max_priority_fee_per_gas": 2,
+            "gas_quote": 1.5,
+        }));
+        assert_eq!(item.effective_gas_price(), Some(12));
+        assert_eq!(item.total_fee_wei(), Some(1200));
+        assert_eq!(item.total_fee_quote(), Some(1.5));
+
+        let legacy = tx(serde_json::json!({ "gas_used": 10, "gas_price": 7 }));
+        assert_eq!(legacy.total_fee_wei(), Some(70));
+        assert_eq!(legacy.total_fee_quote(), None);
+    }
+
+    #[test]
+    fn test_classification() {
+        let native = tx(serde_json::json!({ "to_address": "0x2", "value": "1000", "successful": true }));
+        let deploy = tx(serde_json::json!({ "to_address": null, "input": "0x6080" }));
+        let call = tx(serde_json::json!({ "to_address": "0x3", "input": "0xa9059cbb" }));
+        let erc20 = tx(serde_json::json!({
+            "to_address": "0x4",
+            "log_events": [{
+                "sender_contract_address": "0x4",
+                "decoded": {
+                    "name": "Transfer",
+                    "params": [{ "name": "from" }, { "name": "to" }, { "name": "value" }]
+                }
+            }]
+        }));
+
+        assert_eq!(native.kind(), TransactionKind::NativeTransfer);
+        assert_eq!(deploy.kind(), TransactionKind::ContractDeploy);
+        assert_eq!(call.kind(), TransactionKind::ContractCall);
+        assert_eq!(erc20.kind(), TransactionKind::Erc20Transfer);
+
+        let data = TransactionsData {
+            address: Some("0x1".to_string()),
+            chain_id: Some(1),
+            chain_name: None,
+            items: vec![native, deploy, call, erc20],
+            quote_currency: None,
+        };
+        assert_eq!(data.filter_by_counterparty("0X2").len(), 1);
+        assert_eq!(data.successful_only().len(), 1);
+        assert_eq!(data.value_transfers().len(), 2);
+        assert_eq!(data.of_kind(TransactionKind::ContractCall).len(), 1);
+    }
+
+    #[test]
+    fn test_internal_transfers() {
+        let item = tx(serde_json::json!({
+            "internal_transfers": [
+                { "from_address": "0xa", "to_address": "0xb", "value": "500", "call_type": "call", "depth": 1 },
+                { "from_address": "0xb", "to_address": "0xc", "value": "0", "call_type": "DELEGATECALL", "depth": 2 },
+            ]
+        }));
+        let transfers = item.internal_transfers.as_ref().unwrap();
+        assert_eq!(transfers[0].value_wei(), Some(500));
+        assert!(transfers[1].is_delegate_call());
+        assert_eq!(item.internal_value_transfers().count(), 1);
+    }
+
+    #[test]
+    fn test_state_changes() {
+        let item = tx(serde_json::json!({
+            "state_changes": [
+                { "address": "0xA", "balance_before": "1000", "balance_after": "400", "nonce_before": 1, "nonce_after": 2 },
+                { "address": "0xb", "balance_before": "0", "balance_after": "600" },
+                { "address": "0xc", "balance_before": "5", "balance_after": "5",
+                  "storage_changes": [{ "storage_key": "0x01", "from": "0x00", "to": "0x02" }] },
+            ]
+        }));
+
+        let changes = item.balance_changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].delta(), -600);
+        assert_eq!(changes[1].delta(), 600);
+
+        let writes = item.storage_changes_for("0xC");
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].after.as_deref(), Some("0x02"));
+    }
+
+    #[test]
+    fn test_method_selector_and_decode() {
+        let item = tx(serde_json::json!({
+            "to_address": "0x3",
+            "input": "0x095ea7b3000000000000000000000000742d35cc6634c0532925a3b8d4fc24f3c4ad6a8bffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        }));
+        assert_eq!(item.method_selector(), Some("0x095ea7b3"));
+
+        let mut registry = SelectorRegistry::new();
+        registry.register("approve(address,uint256)").unwrap();
+        let call = item.decode_input(&registry).unwrap();
+        assert_eq!(call.name, "approve");
+        assert_eq!(call.args.len(), 2);
+
+        assert_eq!(tx(serde_json::json!({ "input": "0x" })).method_selector(), None);
+    }
+}
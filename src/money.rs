@@ -0,0 +1,243 @@
+//! Conversion hooks for monetary fields.
+//!
+//! REST responses carry money in two shapes: raw token amounts as integer
+//! strings (`balance`, `delta`, `value`, `fees_paid`, ...) and fiat quotes as
+//! floats (`quote`, `quote_rate`, `gas_quote`, ...). A [`MoneyHook`] registered
+//! with [`ClientConfig::with_money_hook`](crate::ClientConfig::with_money_hook)
+//! sees every such field before the response is deserialized, so a precision
+//! policy is enforced in one place for every model.
+//!
+//! ```rust
+//! use goldrush_sdk::{ClientConfig, MoneyPolicy, Rounding};
+//!
+//! let config = ClientConfig::default().with_money_hook(
+//!     MoneyPolicy::new()
+//!         .quote_decimals(2)
+//!         .rounding(Rounding::HalfEven)
+//!         .null_quotes_as_zero(true),
+//! );
+//! ```
+
+use serde_json::Value;
+use std::fmt;
+
+/// The shape of a monetary field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyKind {
+    /// Raw integer token amount in the token's smallest unit, sent as a string.
+    Amount,
+    /// Fiat or crypto quote value, sent as a number.
+    Quote,
+}
+
+/// Classify a response field by name.
+pub fn classify(field: &str) -> Option<MoneyKind> {
+    const AMOUNTS: &[&str] = &[
+        "balance", "balance_24h", "balance_before", "balance_after", "token_balance", "total_balance",
+        "delta", "value", "fees_paid", "total_supply", "token_total_supply", "allowance",
+        "value_at_risk", "total_gas_fee", "total_spend",
+    ];
+    if AMOUNTS.contains(&field) {
+        return Some(MoneyKind::Amount);
+    }
+    if field.starts_with("pretty_") {
+        return None;
+    }
+    let is_quote = field == "quote"
+        || field == "price"
+        || field.ends_with("_quote")
+        || field.starts_with("quote_rate")
+        || field == "gas_quote_rate";
+    is_quote.then_some(MoneyKind::Quote)
+}
+
+/// A conversion applied to monetary fields of REST responses.
+pub trait MoneyHook: fmt::Debug + Send + Sync {
+    /// Return the value to deserialize in place of `value`.
+    ///
+    /// The result must still fit the model field: a string (or `null` for
+    /// optional fields) for amounts, a number (or `null`) for quotes.
+    fn convert(&self, kind: MoneyKind, field: &str, value: Value) -> Value;
+}
+
+/// How quotes are rounded to [`MoneyPolicy::quote_decimals`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round half to even (banker's rounding).
+    #[default]
+    HalfEven,
+    /// Round half away from zero.
+    HalfUp,
+    /// Truncate toward zero.
+    TowardZero,
+}
+
+impl Rounding {
+    /// Round `value` to `decimals` decimal places.
+    pub fn apply(&self, value: f64, decimals: u32) -> f64 {
+        let scale = 10f64.powi(decimals as i32);
+        let scaled = value * scale;
+        let rounded = match self {
+            Rounding::HalfUp => scaled.round(),
+            Rounding::TowardZero => scaled.trunc(),
+            Rounding::HalfEven => {
+                let floor = scaled.floor();
+                let diff = scaled - floor;
+                if diff > 0.5 || (diff == 0.5 && floor % 2.0 != 0.0) {
+                    floor + 1.0
+                } else {
+                    floor
+                }
+            }
+        };
+        rounded / scale
+    }
+}
+
+/// Built-in [`MoneyHook`] covering rounding, amount normalization and nulls.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MoneyPolicy {
+    /// Round quotes to this many decimal places.
+    pub quote_decimals: Option<u32>,
+    pub rounding: Rounding,
+    /// Rewrite amounts such as `"1e+21"` or `"00042"` as plain integer strings.
+    pub normalize_amounts: bool,
+    pub null_quotes_as_zero: bool,
+    pub null_amounts_as_zero: bool,
+}
+
+impl MoneyPolicy {
+    pub fn new() -> Self { Self::default() }
+    pub fn quote_decimals(mut self, v: u32) -> Self { self.quote_decimals = Some(v); self }
+    pub fn rounding(mut self, v: Rounding) -> Self { self.rounding = v; self }
+    pub fn normalize_amounts(mut self, v: bool) -> Self { self.normalize_amounts = v; self }
+    pub fn null_quotes_as_zero(mut self, v: bool) -> Self { self.null_quotes_as_zero = v; self }
+    pub fn null_amounts_as_zero(mut self, v: bool) -> Self { self.null_amounts_as_zero = v; self }
+}
+
+impl MoneyHook for MoneyPolicy {
+    fn convert(&self, kind: MoneyKind, _field: &str, value: Value) -> Value {
+        match (kind, value) {
+            (MoneyKind::Quote, Value::Null) if self.null_quotes_as_zero => Value::from(0.0),
+            (MoneyKind::Quote, Value::Number(n)) => match (self.quote_decimals, n.as_f64()) {
+                (Some(decimals), Some(f)) => Value::from(self.rounding.apply(f, decimals)),
+                _ => Value::Number(n),
+            },
+            (MoneyKind::Amount, Value::Null) if self.null_amounts_as_zero => Value::from("0"),
+            (MoneyKind::Amount, Value::String(s)) if self.normalize_amounts => {
+                Value::String(canonical_integer(&s).unwrap_or(s))
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+/// Plain base-10 form of an integer string, accepting sign, leading zeros and
+/// exact scientific notation (`1.5e+21`). `None` if it isn't an integer.
+fn canonical_integer(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let (negative, unsigned) = match raw.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, raw.strip_prefix('+').unwrap_or(raw)),
+    };
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((m, e)) => (m, e.strip_prefix('+').unwrap_or(e).parse::<usize>().ok()?),
+        None => (unsigned, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > exponent {
+        return None;
+    }
+
+    let mut digits = format!("{}{}", whole, fraction);
+    digits.extend(std::iter::repeat('0').take(exponent - fraction.len()));
+    let digits = digits.trim_start_matches('0');
+    Some(match (digits.is_empty(), negative) {
+        (true, _) => "0".to_string(),
+        (false, true) => format!("-{}", digits),
+        (false, false) => digits.to_string(),
+    })
+}
+
+/// Keys whose subtrees hold decoded event parameters rather than model fields.
+const SKIPPED: &[&str] = &["decoded", "params", "raw_log_topics"];
+
+/// Run `hook` over every monetary field in a response body.
+pub(crate) fn apply(hook: &dyn MoneyHook, value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if SKIPPED.contains(&key.as_str()) {
+                    continue;
+                }
+                match classify(key) {
+                    Some(kind) if !field.is_object() && !field.is_array() => {
+                        *field = hook.convert(kind, key, field.take());
+                    }
+                    _ => apply(hook, field),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| apply(hook, item)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rounding_modes() {
+        assert_eq!(Rounding::HalfEven.apply(2.5, 0), 2.0);
+        assert_eq!(Rounding::HalfEven.apply(3.5, 0), 4.0);
+        assert_eq!(Rounding::HalfUp.apply(2.5, 0), 3.0);
+        assert_eq!(Rounding::TowardZero.apply(-1.239, 2), -1.23);
+        assert_eq!(Rounding::HalfEven.apply(1.2345, 2), 1.23);
+    }
+
+    #[test]
+    fn test_canonical_integer() {
+        assert_eq!(canonical_integer("1.5e+21").as_deref(), Some("1500000000000000000000"));
+        assert_eq!(canonical_integer("00042").as_deref(), Some("42"));
+        assert_eq!(canonical_integer("-000").as_deref(), Some("0"));
+        assert_eq!(canonical_integer("-12").as_deref(), Some("-12"));
+        assert_eq!(canonical_integer("1.25e1"), None);
+        assert_eq!(canonical_integer("0xabc"), None);
+    }
+
+    #[test]
+    fn test_apply_policy_to_response() {
+        let policy = MoneyPolicy::new()
+            .quote_decimals(2)
+            .normalize_amounts(true)
+            .null_quotes_as_zero(true);
+        let mut body = json!({
+            "data": {
+                "items": [{
+                    "balance": "1e3",
+                    "quote": 12.3456,
+                    "quote_rate": null,
+                    "pretty_quote": "$12.35",
+                    "log_events": [{ "decoded": { "params": [{ "name": "value", "value": "0xabc" }] } }],
+                }]
+            }
+        });
+        apply(&policy, &mut body);
+
+        let item = &body["data"]["items"][0];
+        assert_eq!(item["balance"], "1000");
+        assert_eq!(item["quote"], 12.35);
+        assert_eq!(item["quote_rate"], 0.0);
+        assert_eq!(item["pretty_quote"], "$12.35");
+        assert_eq!(item["log_events"][0]["decoded"]["params"][0]["value"], "0xabc");
+    }
+}
@@ -0,0 +1,245 @@
+//! Cursor-walking stream over multichain transactions.
+//!
+//! [`AllChainsService::transactions_stream`](crate::AllChainsService::transactions_stream)
+//! follows the `cursor_before` of each page of `/v1/allchains/transactions/`
+//! until the history is exhausted, and yields transactions newest first.
+//!
+//! A page can interleave the chains unevenly: one chain's oldest item in a
+//! page may be newer than items of another chain that only arrive on the next
+//! page. Items are therefore held back until no later page can contain a
+//! newer one, i.e. until they are at least as new as the oldest item of every
+//! chain in the latest page. Held-back items are yielded with the next page,
+//! or when the stream ends.
+
+use crate::activity::parse_unix;
+use crate::models::all_chains::{MultiChainTransactionItem, MultiChainTransactionsResponse};
+use crate::services::all_chains_service::{AllChainsService, MultiChainTxOptions};
+use crate::services::ServiceContext;
+use crate::Error;
+use futures_core::stream::{FusedStream, Stream};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+type ResponseFuture = Pin<Box<dyn Future<Output = Result<MultiChainTransactionsResponse, Error>> + Send>>;
+
+/// How far a [`MultiChainTxStream`] has walked one chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainProgress {
+    pub chain_name: String,
+    /// Items received from the API, including ones not yet yielded.
+    pub fetched: u64,
+    pub yielded: u64,
+    /// `block_signed_at` of the last item yielded; the stream has passed everything newer.
+    pub last_signed_at: Option<String>,
+}
+
+/// Held-back item, ordered newest first, then in arrival order.
+struct Pending {
+    key: (u64, Reverse<u64>),
+    item: MultiChainTransactionItem,
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Multichain transactions across all pages, newest first. See the
+/// [module docs](self).
+///
+/// Yields `Err` at most once; the stream terminates after an error.
+pub struct MultiChainTxStream {
+    ctx: Arc<ServiceContext>,
+    options: MultiChainTxOptions,
+    in_flight: Option<ResponseFuture>,
+    merge: Merge,
+    pages: u64,
+    exhausted: bool,
+}
+
+impl MultiChainTxStream {
+    pub(crate) fn new(ctx: Arc<ServiceContext>, options: MultiChainTxOptions) -> Self {
+        Self {
+            ctx,
+            options,
+            in_flight: None,
+            merge: Merge::default(),
+            pages: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Pages fetched so far.
+    pub fn pages(&self) -> u64 {
+        self.pages
+    }
+
+    /// Progress of every chain seen so far, by chain name.
+    pub fn chain_progress(&self) -> impl Iterator<Item = &ChainProgress> {
+        self.merge.progress.values()
+    }
+
+    /// Progress of one chain, once it has appeared in a page.
+    pub fn progress_for(&self, chain_name: &str) -> Option<&ChainProgress> {
+        self.merge.progress.get(chain_name)
+    }
+}
+
+fn fetch(ctx: &Arc<ServiceContext>, options: &MultiChainTxOptions) -> ResponseFuture {
+    let service = AllChainsService::new(Arc::clone(ctx));
+    let options = options.clone();
+    Box::pin(async move { service.get_multi_chain_transactions(Some(options)).await })
+}
+
+/// Items received but not yet yielded, and per-chain progress.
+#[derive(Default)]
+struct Merge {
+    pending: BinaryHeap<Pending>,
+    ready: VecDeque<MultiChainTransactionItem>,
+    received: u64,
+    progress: BTreeMap<String, ChainProgress>,
+}
+
+impl Merge {
+    /// Hold back the items of a page, then release those no later page can
+    /// precede; everything once `last` is set.
+    fn receive(&mut self, items: Vec<MultiChainTransactionItem>, last: bool) {
+        let mut oldest: HashMap<String, u64> = HashMap::new();
+        for item in items {
+            let chain_name = chain_name(&item);
+            // Items without a timestamp can't be ordered; pass them on with their page.
+            let signed_at = item.block_signed_at.as_deref().and_then(parse_unix).unwrap_or(u64::MAX);
+            if signed_at != u64::MAX {
+                let chain_oldest = oldest.entry(chain_name.clone()).or_insert(signed_at);
+                *chain_oldest = (*chain_oldest).min(signed_at);
+            }
+            let progress = self.progress.entry(chain_name.clone()).or_insert_with(|| ChainProgress {
+                chain_name,
+                ..ChainProgress::default()
+            });
+            progress.fetched += 1;
+            self.pending.push(Pending { key: (signed_at, Reverse(self.received)), item });
+            self.received += 1;
+        }
+
+        let watermark = if last { 0 } else { oldest.into_values().max().unwrap_or(u64::MAX) };
+        while self.pending.peek().is_some_and(|p| p.key.0 >= watermark) {
+            let item = self.pending.pop().expect("peeked").item;
+            self.ready.push_back(item);
+        }
+    }
+
+    fn next(&mut self) -> Option<MultiChainTransactionItem> {
+        let item = self.ready.pop_front()?;
+        if let Some(progress) = self.progress.get_mut(&chain_name(&item)) {
+            progress.yielded += 1;
+            if item.block_signed_at.is_some() {
+                progress.last_signed_at = item.block_signed_at.clone();
+            }
+        }
+        Some(item)
+    }
+}
+
+fn chain_name(item: &MultiChainTransactionItem) -> String {
+    item.chain_name.clone().or_else(|| item.chain_id.map(|id| id.to_string())).unwrap_or_default()
+}
+
+impl Stream for MultiChainTxStream {
+    type Item = Result<MultiChainTransactionItem, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.merge.next() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.exhausted {
+                return Poll::Ready(None);
+            }
+
+            let (ctx, options) = (&this.ctx, &this.options);
+            let future = this.in_flight.get_or_insert_with(|| fetch(ctx, options));
+            match future.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => {
+                    this.in_flight = None;
+                    this.exhausted = true;
+                    this.merge.pending.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(Ok(response)) => {
+                    this.in_flight = None;
+                    this.pages += 1;
+                    let (items, cursor) = match response.data {
+                        Some(data) => (data.items, data.cursor_before),
+                        None => (Vec::new(), None),
+                    };
+                    // A repeated cursor would fetch the same page forever.
+                    let next = cursor.filter(|c| !items.is_empty() && this.options.before.as_ref() != Some(c));
+                    match next {
+                        Some(cursor) => this.options.before = Some(cursor),
+                        None => this.exhausted = true,
+                    }
+                    this.merge.receive(items, this.exhausted);
+                }
+            }
+        }
+    }
+}
+
+impl FusedStream for MultiChainTxStream {
+    fn is_terminated(&self) -> bool {
+        self.exhausted && self.merge.ready.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn receive(merge: &mut Merge, items: serde_json::Value, last: bool) -> Vec<String> {
+        merge.receive(serde_json::from_value(items).unwrap(), last);
+        std::iter::from_fn(|| merge.next()).map(|item| item.tx_hash.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_holds_back_items_a_later_page_may_precede() {
+        let mut merge = Merge::default();
+        let first = receive(&mut merge, json!([
+            { "chain_name": "eth-mainnet", "tx_hash": "e1", "block_signed_at": "2024-01-01T10:00:00Z" },
+            { "chain_name": "eth-mainnet", "tx_hash": "e2", "block_signed_at": "2024-01-01T06:00:00Z" },
+            { "chain_name": "base-mainnet", "tx_hash": "b1", "block_signed_at": "2024-01-01T09:00:00Z" },
+            { "chain_name": "base-mainnet", "tx_hash": "b2", "block_signed_at": "2024-01-01T08:00:00Z" },
+        ]), false);
+        // eth's next page may hold anything older than 06:00, base's anything older than 08:00.
+        assert_eq!(first, ["e1", "b1", "b2"]);
+
+        let last = receive(&mut merge, json!([
+            { "chain_name": "eth-mainnet", "tx_hash": "e3", "block_signed_at": "2024-01-01T07:00:00Z" },
+        ]), true);
+        assert_eq!(last, ["e3", "e2"]);
+        let eth = &merge.progress["eth-mainnet"];
+        assert_eq!((eth.fetched, eth.yielded, eth.last_signed_at.as_deref()), (3, 3, Some("2024-01-01T06:00:00Z")));
+    }
+}
@@ -0,0 +1,831 @@
+//! Auto-paginating streams over page-numbered endpoints.
+//!
+//! [`PageStream`] fetches one page at a time and yields individual items. It
+//! implements [`Stream`] with a `size_hint` derived from the API's
+//! `total_count` and [`FusedStream`], so adapters such as `take(n)`,
+//! `buffer_unordered` or progress bars behave as expected.
+//!
+//! Dropping the stream mid-fetch is safe: the in-flight request is dropped
+//! and no already-fetched items are lost, because items are only buffered
+//! once a page has been fully received. Streams built from a service handle
+//! with a [`CancellationToken`] stop at the next page fetch once it is
+//! cancelled; [`PageStream::with_cancellation`] does the same for any stream.
+//!
+//! [`CursorStream`] does the same for v3 endpoints by following the
+//! `links.next` of each page, optionally fetching a few pages ahead.
+//!
+//! [`Paginator`] hands out whole pages on request, over page numbers, cursors
+//! or a single response alike, and caps how many pages it fetches.
+
+use crate::models::ApiResponse;
+use crate::progress::{ProgressReporter, ProgressTracker};
+use crate::Error;
+use futures_core::stream::{FusedStream, Stream};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio_util::sync::CancellationToken;
+
+/// One fetched page of items.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Whether another page is available after this one.
+    pub has_more: bool,
+    /// Total number of items across all pages, when reported by the API.
+    pub total_count: Option<u64>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from an API response, extracting items from its data.
+    ///
+    /// `has_more` comes from the `pagination` block, falling back to the
+    /// presence of a `links.next` cursor on v3 endpoints.
+    pub fn from_response<D>(response: ApiResponse<D>, items: impl FnOnce(D) -> Vec<T>) -> Self {
+        let has_more = response.pagination.as_ref()
+            .and_then(|p| p.has_more)
+            .or_else(|| response.links.as_ref().map(|l| l.next.is_some()))
+            .unwrap_or(false);
+        let total_count = response.pagination.as_ref().and_then(|p| p.total_count);
+
+        Self {
+            items: response.data.map(items).unwrap_or_default(),
+            has_more,
+            total_count,
+        }
+    }
+}
+
+type PageFuture<T> = Pin<Box<dyn Future<Output = Result<Page<T>, Error>> + Send>>;
+type PageFetcher<T> = Box<dyn FnMut(u32) -> PageFuture<T> + Send>;
+
+/// A stream of items fetched page by page.
+///
+/// Yields `Err` at most once; the stream terminates after an error.
+pub struct PageStream<T> {
+    fetch: PageFetcher<T>,
+    next_page: u32,
+    buffer: VecDeque<T>,
+    in_flight: Option<PageFuture<T>>,
+    exhausted: bool,
+    yielded: u64,
+    total_count: Option<u64>,
+    progress: Option<ProgressTracker>,
+}
+
+impl<T> PageStream<T> {
+    /// Create a stream starting at `start_page`, calling `fetch` with each page number.
+    pub fn new<F, Fut>(start_page: u32, mut fetch: F) -> Self
+    where
+        F: FnMut(u32) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Page<T>, Error>> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(move |page| Box::pin(fetch(page))),
+            next_page: start_page,
+            buffer: VecDeque::new(),
+            in_flight: None,
+            exhausted: false,
+            yielded: 0,
+            total_count: None,
+            progress: None,
+        }
+    }
+
+    /// Abort the in-flight page fetch and end the stream with
+    /// [`Error::Cancelled`] once `token` is cancelled.
+    ///
+    /// Items from pages already received are still yielded first.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self
+    where
+        T: 'static,
+    {
+        let mut fetch = self.fetch;
+        self.fetch = Box::new(move |page| {
+            let token = token.clone();
+            let future = fetch(page);
+            Box::pin(async move {
+                tokio::select! {
+                    biased;
+                    _ = token.cancelled() => Err(Error::Cancelled),
+                    page = future => page,
+                }
+            })
+        });
+        self
+    }
+
+    /// Report items fetched against the API's total count after every page.
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter.tracker(None));
+        self
+    }
+
+    /// Total item count reported by the API, once the first page has arrived.
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count
+    }
+
+    /// Number of items yielded so far.
+    pub fn yielded(&self) -> u64 {
+        self.yielded
+    }
+}
+
+// Items are never pinned in place and the in-flight future is boxed, so the
+// stream is `Unpin` regardless of `T`.
+impl<T> Unpin for PageStream<T> {}
+
+impl<T> Stream for PageStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                this.yielded += 1;
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.exhausted {
+                return Poll::Ready(None);
+            }
+
+            let page = this.next_page;
+            let fetch = &mut this.fetch;
+            let future = this.in_flight.get_or_insert_with(|| fetch(page));
+
+            match future.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => {
+                    this.in_flight = None;
+                    this.exhausted = true;
+                    if let Some(progress) = this.progress.as_mut() {
+                        progress.record_error(&e);
+                        progress.advance(0);
+                    }
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(Ok(page)) => {
+                    this.in_flight = None;
+                    this.next_page += 1;
+                    if page.total_count.is_some() {
+                        this.total_count = page.total_count;
+                    }
+                    if !page.has_more || page.items.is_empty() {
+                        this.exhausted = true;
+                    }
+                    if let Some(progress) = this.progress.as_mut() {
+                        progress.set_total(this.total_count);
+                        progress.advance(page.items.len() as u64);
+                    }
+                    this.buffer.extend(page.items);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buffer.len();
+        if self.exhausted {
+            return (buffered, Some(buffered));
+        }
+        let upper = self.total_count
+            .map(|total| total.saturating_sub(self.yielded))
+            .and_then(|remaining| usize::try_from(remaining).ok())
+            .map(|remaining| remaining.max(buffered));
+        (buffered, upper)
+    }
+}
+
+impl<T> FusedStream for PageStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.exhausted && self.buffer.is_empty()
+    }
+}
+
+/// One fetched page of a cursor-paginated endpoint.
+#[derive(Debug, Clone)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    /// Path of the next page, from `links.next`; `None` on the last page.
+    pub next: Option<String>,
+}
+
+impl<T> CursorPage<T> {
+    /// Build a page from a v3 response, extracting items from its data.
+    pub fn from_response<D>(response: ApiResponse<D>, items: impl FnOnce(D) -> Vec<T>) -> Self {
+        let next = response.links.as_ref().and_then(|l| l.next.as_deref()).map(link_path);
+        Self { items: response.data.map(items).unwrap_or_default(), next }
+    }
+}
+
+/// Path of a pagination link, without scheme, host or query.
+///
+/// The path is requested against the configured base URL, so the API key is
+/// never sent to another host, and with the stream's own options, which links
+/// don't carry.
+fn link_path(link: &str) -> String {
+    let path = match link.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        None => link,
+    };
+    path.split(['?', '#']).next().unwrap_or_default().to_string()
+}
+
+/// `path` with its `/page/{n}/` segment advanced by one, if it has one.
+fn following_page(path: &str) -> Option<String> {
+    let start = path.rfind("/page/")? + "/page/".len();
+    let len = path[start..].find('/')?;
+    let page: u64 = path[start..start + len].parse().ok()?;
+    Some(format!("{}{}{}", &path[..start], page + 1, &path[start + len..]))
+}
+
+/// How a [`CursorStream`] fetches ahead.
+#[derive(Debug, Clone)]
+pub struct CursorStreamOptions {
+    /// Pages requested at once. Pages past the one a link points to are
+    /// guessed by advancing its `/page/{n}/` segment, and refetched from the
+    /// real link if the guess was wrong.
+    pub concurrency: usize,
+    /// No further pages are requested while this many items are waiting to
+    /// be consumed.
+    pub max_buffered: usize,
+}
+
+impl Default for CursorStreamOptions {
+    fn default() -> Self {
+        Self { concurrency: 1, max_buffered: 1000 }
+    }
+}
+
+impl CursorStreamOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn concurrency(mut self, v: usize) -> Self { self.concurrency = v; self }
+    pub fn max_buffered(mut self, v: usize) -> Self { self.max_buffered = v; self }
+}
+
+type CursorFuture<T> = Pin<Box<dyn Future<Output = Result<CursorPage<T>, Error>> + Send>>;
+type CursorFetcher<T> = Box<dyn FnMut(String) -> CursorFuture<T> + Send>;
+
+/// A page request, kept in order until it is its turn to be consumed.
+struct PendingPage<T> {
+    path: String,
+    future: CursorFuture<T>,
+    result: Option<Result<CursorPage<T>, Error>>,
+}
+
+/// A stream of items fetched by following each page's `links.next`.
+///
+/// Pages are only requested while the stream is polled and fewer than
+/// [`max_buffered`](CursorStreamOptions::max_buffered) items are waiting, so
+/// a slow consumer slows the fetching down. Yields `Err` at most once, after
+/// the items of earlier pages; the stream terminates after an error.
+pub struct CursorStream<T> {
+    fetch: CursorFetcher<T>,
+    options: CursorStreamOptions,
+    next: Option<String>,
+    in_flight: VecDeque<PendingPage<T>>,
+    buffer: VecDeque<T>,
+    error: Option<Error>,
+    exhausted: bool,
+    pages: u64,
+}
+
+impl<T> CursorStream<T> {
+    /// Create a stream starting at `first_path`, calling `fetch` with the path of each page.
+    pub fn new<F, Fut>(first_path: impl Into<String>, options: CursorStreamOptions, mut fetch: F) -> Self
+    where
+        F: FnMut(String) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<CursorPage<T>, Error>> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(move |path| Box::pin(fetch(path))),
+            options,
+            next: Some(first_path.into()),
+            in_flight: VecDeque::new(),
+            buffer: VecDeque::new(),
+            error: None,
+            exhausted: false,
+            pages: 0,
+        }
+    }
+
+    /// Pages consumed so far, not counting pages fetched ahead.
+    pub fn pages(&self) -> u64 {
+        self.pages
+    }
+
+    /// Consume finished pages in order, stopping at the first still in flight.
+    fn settle(&mut self) {
+        while self.in_flight.front().is_some_and(|p| p.result.is_some()) {
+            let page = self.in_flight.pop_front().and_then(|p| p.result).expect("checked above");
+            match page {
+                Err(e) => {
+                    self.in_flight.clear();
+                    self.exhausted = true;
+                    self.error = Some(e);
+                }
+                Ok(page) => {
+                    self.pages += 1;
+                    self.buffer.extend(page.items);
+                    match page.next {
+                        None => {
+                            self.in_flight.clear();
+                            self.exhausted = true;
+                        }
+                        Some(next) => {
+                            // Discard pages fetched ahead from a wrong guess.
+                            if self.in_flight.front().map(|p| &p.path) != Some(&next) {
+                                self.in_flight.clear();
+                                self.next = Some(next);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Request pages up to the concurrency limit, returning whether any were.
+    fn launch(&mut self) -> bool {
+        let mut launched = false;
+        while !self.exhausted
+            && self.in_flight.len() < self.options.concurrency.max(1)
+            && self.buffer.len() < self.options.max_buffered
+        {
+            let path = match self.in_flight.back() {
+                Some(last) => following_page(&last.path),
+                None => self.next.take(),
+            };
+            let Some(path) = path else { break };
+            let future = (self.fetch)(path.clone());
+            self.in_flight.push_back(PendingPage { path, future, result: None });
+            launched = true;
+        }
+        launched
+    }
+}
+
+// The in-flight futures are boxed, so the stream is `Unpin` regardless of `T`.
+impl<T> Unpin for CursorStream<T> {}
+
+impl<T> Stream for CursorStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            for page in this.in_flight.iter_mut().filter(|p| p.result.is_none()) {
+                if let Poll::Ready(result) = page.future.as_mut().poll(cx) {
+                    page.result = Some(result);
+                }
+            }
+            this.settle();
+            // Newly requested pages are polled once to register for wake-ups.
+            if !this.launch() {
+                break;
+            }
+        }
+
+        if let Some(item) = this.buffer.pop_front() {
+            return Poll::Ready(Some(Ok(item)));
+        }
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Some(Err(e)));
+        }
+        if this.in_flight.is_empty() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> FusedStream for CursorStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.exhausted && self.buffer.is_empty() && self.error.is_none()
+    }
+}
+
+/// Pages a [`Paginator`] fetches before giving up, unless set with
+/// [`Paginator::max_pages`].
+pub const DEFAULT_MAX_PAGES: u32 = 1000;
+
+/// Where the next page of a [`Paginator`] is fetched from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PageToken {
+    Number(u32),
+    /// `None` for the first page of a cursor-paginated endpoint.
+    Cursor(Option<String>),
+}
+
+/// One fetched page, and the token of the page after it.
+struct Fetched<T> {
+    items: Vec<T>,
+    next: Option<PageToken>,
+    total_count: Option<u64>,
+}
+
+type FetchedFuture<T> = Pin<Box<dyn Future<Output = Result<Fetched<T>, Error>> + Send>>;
+type TokenFetcher<T> = Box<dyn FnMut(PageToken) -> FetchedFuture<T> + Send>;
+
+/// Page-by-page access to a list endpoint, whatever its pagination scheme.
+///
+/// Page-numbered v2 endpoints, cursor-linked v3 and multichain endpoints, and
+/// endpoints that return everything at once are all walked the same way. The
+/// `*_paged` methods of the services return one.
+///
+/// At most [`max_pages`](Self::max_pages) pages are fetched; asking for more
+/// fails with [`Error::Config`], so a misbehaving cursor can't loop forever.
+///
+/// ```rust,no_run
+/// use goldrush_sdk::{GoldRushClient, NftOptions};
+///
+/// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+/// let mut pages = client.nft_service()
+///     .get_nfts_for_address_paged("eth-mainnet", "0x...", Some(NftOptions::new().page_size(50)))
+///     .max_pages(10);
+/// while let Some(page) = pages.next_page().await {
+///     println!("{} NFTs", page?.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginator<T> {
+    fetch: TokenFetcher<T>,
+    next: Option<PageToken>,
+    pages: u32,
+    max_pages: u32,
+    total_count: Option<u64>,
+}
+
+impl<T> Paginator<T> {
+    /// Walk a page-numbered endpoint from `start_page`, calling `fetch` with each page number.
+    pub fn numbered<F, Fut>(start_page: u32, mut fetch: F) -> Self
+    where
+        F: FnMut(u32) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Page<T>, Error>> + Send + 'static,
+    {
+        Self::from_fetcher(PageToken::Number(start_page), Box::new(move |token| {
+            let PageToken::Number(number) = token else { unreachable!("numbered paginators only use page numbers") };
+            let page = fetch(number);
+            Box::pin(async move {
+                let page = page.await?;
+                let more = page.has_more && !page.items.is_empty();
+                let next = more.then_some(PageToken::Number(number + 1));
+                Ok(Fetched { items: page.items, next, total_count: page.total_count })
+            })
+        }))
+    }
+
+    /// Walk a cursor-paginated endpoint, calling `fetch` with `None` for the
+    /// first page and then with the `next` of each page until it is `None`.
+    pub fn cursor<F, Fut>(mut fetch: F) -> Self
+    where
+        F: FnMut(Option<String>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<CursorPage<T>, Error>> + Send + 'static,
+    {
+        Self::from_fetcher(PageToken::Cursor(None), Box::new(move |token| {
+            let PageToken::Cursor(cursor) = token else { unreachable!("cursor paginators only use cursors") };
+            let page = fetch(cursor.clone());
+            Box::pin(async move {
+                let page = page.await?;
+                // A repeated cursor would fetch the same page forever.
+                let next = page.next.filter(|next| !page.items.is_empty() && cursor.as_ref() != Some(next));
+                Ok(Fetched { items: page.items, next: next.map(|c| PageToken::Cursor(Some(c))), total_count: None })
+            })
+        }))
+    }
+
+    /// A single page holding everything `fetch` returns, for unpaginated endpoints.
+    pub fn single<Fut>(fetch: Fut) -> Self
+    where
+        Fut: Future<Output = Result<Vec<T>, Error>> + Send + 'static,
+    {
+        let mut fetch = Some(fetch);
+        Self::from_fetcher(PageToken::Number(0), Box::new(move |_| {
+            let fetch = fetch.take().expect("single-page paginators fetch once");
+            Box::pin(async move {
+                let items = fetch.await?;
+                let total_count = Some(items.len() as u64);
+                Ok(Fetched { items, next: None, total_count })
+            })
+        }))
+    }
+
+    fn from_fetcher(first: PageToken, fetch: TokenFetcher<T>) -> Self {
+        Self { fetch, next: Some(first), pages: 0, max_pages: DEFAULT_MAX_PAGES, total_count: None }
+    }
+
+    /// Fail instead of fetching more than `max` pages (default [`DEFAULT_MAX_PAGES`]).
+    pub fn max_pages(mut self, max: u32) -> Self {
+        self.max_pages = max;
+        self
+    }
+
+    /// Fetch the next page, or `None` once the last page has been returned.
+    ///
+    /// After an error, including hitting the page cap, no more pages are fetched.
+    pub async fn next_page(&mut self) -> Option<Result<Vec<T>, Error>> {
+        let token = self.next.take()?;
+        if self.pages >= self.max_pages {
+            return Some(Err(Error::Config(format!(
+                "Stopped after {} pages with more remaining; raise max_pages to fetch further",
+                self.max_pages
+            ))));
+        }
+        match (self.fetch)(token).await {
+            Ok(page) => {
+                self.pages += 1;
+                self.next = page.next;
+                if page.total_count.is_some() {
+                    self.total_count = page.total_count;
+                }
+                Some(Ok(page.items))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Whether [`next_page`](Self::next_page) may return another page.
+    pub fn has_more(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Pages fetched so far.
+    pub fn pages_fetched(&self) -> u32 {
+        self.pages
+    }
+
+    /// Total item count reported by the API, once a page has arrived.
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count
+    }
+
+    /// Fetch the remaining pages and return all their items, or the first error.
+    pub async fn collect_all(mut self) -> Result<Vec<T>, Error> {
+        let mut items = Vec::new();
+        while let Some(page) = self.next_page().await {
+            items.extend(page?);
+        }
+        Ok(items)
+    }
+
+    /// Yield the items of the remaining pages one by one, fetching pages on demand.
+    pub fn into_stream(self) -> PaginatorStream<T>
+    where
+        T: Send + 'static,
+    {
+        PaginatorStream { paginator: Some(self), in_flight: None, buffer: VecDeque::new() }
+    }
+}
+
+type NextPageFuture<T> = Pin<Box<dyn Future<Output = (Paginator<T>, Option<Result<Vec<T>, Error>>)> + Send>>;
+
+/// Items of a [`Paginator`], from [`Paginator::into_stream`].
+///
+/// Yields `Err` at most once; the stream terminates after an error.
+pub struct PaginatorStream<T> {
+    paginator: Option<Paginator<T>>,
+    in_flight: Option<NextPageFuture<T>>,
+    buffer: VecDeque<T>,
+}
+
+// The in-flight future is boxed, so the stream is `Unpin` regardless of `T`.
+impl<T> Unpin for PaginatorStream<T> {}
+
+impl<T: Send + 'static> Stream for PaginatorStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.in_flight.is_none() {
+                let Some(mut paginator) = this.paginator.take() else { return Poll::Ready(None) };
+                this.in_flight = Some(Box::pin(async move {
+                    let page = paginator.next_page().await;
+                    (paginator, page)
+                }));
+            }
+
+            let future = this.in_flight.as_mut().expect("set above");
+            match future.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready((paginator, page)) => {
+                    this.in_flight = None;
+                    match page {
+                        None => return Poll::Ready(None),
+                        Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                        Some(Ok(items)) => {
+                            this.paginator = Some(paginator);
+                            this.buffer.extend(items);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Send + 'static> FusedStream for PaginatorStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.paginator.is_none() && self.in_flight.is_none() && self.buffer.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::sync::{Arc, Mutex};
+
+    fn pages(total: u32, page_size: u32) -> PageStream<u32> {
+        PageStream::new(0, move |page| async move {
+            let start = page * page_size;
+            let end = (start + page_size).min(total);
+            Ok(Page {
+                items: (start..end).collect(),
+                has_more: end < total,
+                total_count: Some(total as u64),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_collects_all_pages() {
+        let items: Vec<u32> = pages(7, 3).map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_size_hint_and_fused() {
+        let mut stream = pages(7, 3);
+        assert_eq!(stream.size_hint(), (0, None));
+
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(stream.total_count(), Some(7));
+        assert_eq!(stream.size_hint(), (2, Some(6)));
+
+        let rest: Vec<_> = stream.by_ref().collect().await;
+        assert_eq!(rest.len(), 6);
+        assert!(stream.is_terminated());
+        assert_eq!(stream.size_hint(), (0, Some(0)));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_take_stops_fetching() {
+        let items: Vec<u32> = pages(1000, 10).take(5).map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_reports_progress_per_page() {
+        let updates = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = std::sync::Arc::clone(&updates);
+        let reporter = ProgressReporter::new(move |p| recorded.lock().unwrap().push((p.done, p.total)));
+
+        let items: Vec<u32> = pages(7, 3).with_progress(reporter).map(|r| r.unwrap()).collect().await;
+        assert_eq!(items.len(), 7);
+        assert_eq!(*updates.lock().unwrap(), vec![(3, Some(7)), (6, Some(7)), (7, Some(7))]);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_ends_stream_after_buffered_items() {
+        let token = CancellationToken::new();
+        let mut stream = pages(1000, 2).with_cancellation(token.clone());
+        assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+
+        token.cancel();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert!(matches!(stream.next().await, Some(Err(Error::Cancelled))));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_terminates_stream() {
+        let mut stream: PageStream<u32> = PageStream::new(0, |_| async {
+            Err(Error::Config("boom".to_string()))
+        });
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.is_terminated());
+        assert!(stream.next().await.is_none());
+    }
+
+    /// Cursor pages of `page_size` numbers up to `total`, linked by `/page/{n}/` paths.
+    fn cursor(total: u32, page_size: u32, options: CursorStreamOptions) -> (CursorStream<u32>, Arc<Mutex<Vec<String>>>) {
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requested);
+        let stream = CursorStream::new("/items/page/0/", options, move |path: String| {
+            log.lock().unwrap().push(path.clone());
+            async move {
+                let page: u32 = path.trim_end_matches('/').rsplit('/').next().unwrap().parse().unwrap();
+                let start = page * page_size;
+                let end = (start + page_size).min(total);
+                let next = (end < total).then(|| format!("https://api.example.com/items/page/{}/?key=x", page + 1));
+                Ok(CursorPage { items: (start..end).collect(), next: next.as_deref().map(link_path) })
+            }
+        });
+        (stream, requested)
+    }
+
+    #[tokio::test]
+    async fn test_cursor_stream_follows_links() {
+        let (stream, requested) = cursor(7, 3, CursorStreamOptions::new());
+        let items: Vec<u32> = stream.map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+        assert_eq!(*requested.lock().unwrap(), ["/items/page/0/", "/items/page/1/", "/items/page/2/"]);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_stream_fetches_ahead_in_order() {
+        let (mut stream, requested) = cursor(7, 3, CursorStreamOptions::new().concurrency(4));
+        let items: Vec<u32> = stream.by_ref().map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+        assert_eq!(stream.pages(), 3);
+        assert!(stream.is_terminated());
+        assert_eq!(requested.lock().unwrap()[..4], ["/items/page/0/", "/items/page/1/", "/items/page/2/", "/items/page/3/"]);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_stream_backpressure() {
+        let (mut stream, requested) = cursor(100, 10, CursorStreamOptions::new().concurrency(4).max_buffered(10));
+        assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+        // The first page fills the buffer, so nothing more is requested until it drains.
+        assert_eq!(requested.lock().unwrap().len(), 4);
+        for _ in 0..9 {
+            stream.next().await.unwrap().unwrap();
+        }
+        assert_eq!(requested.lock().unwrap().len(), 4);
+    }
+
+    fn numbered(total: u32, page_size: u32) -> Paginator<u32> {
+        Paginator::numbered(0, move |page| async move {
+            let start = page * page_size;
+            let end = (start + page_size).min(total);
+            Ok(Page { items: (start..end).collect(), has_more: end < total, total_count: Some(total as u64) })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_paginator_pages_and_collects() {
+        let mut pager = numbered(7, 3);
+        assert_eq!(pager.next_page().await.unwrap().unwrap(), [0, 1, 2]);
+        assert_eq!((pager.pages_fetched(), pager.total_count(), pager.has_more()), (1, Some(7), true));
+        assert_eq!(pager.collect_all().await.unwrap(), [3, 4, 5, 6]);
+
+        let items: Vec<u32> = numbered(7, 3).into_stream().map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_paginator_caps_pages() {
+        let mut pager = numbered(100, 10).max_pages(2);
+        assert_eq!(pager.next_page().await.unwrap().unwrap().len(), 10);
+        assert_eq!(pager.next_page().await.unwrap().unwrap().len(), 10);
+        assert!(matches!(pager.next_page().await, Some(Err(Error::Config(_)))));
+        assert!(pager.next_page().await.is_none());
+
+        // Exactly `max_pages` pages is not an error.
+        assert_eq!(numbered(20, 10).max_pages(2).collect_all().await.unwrap().len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_paginator_cursor_stops_on_repeated_cursor() {
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requested);
+        let pager = Paginator::cursor(move |cursor: Option<String>| {
+            log.lock().unwrap().push(cursor.clone());
+            async move {
+                let next = match cursor.as_deref() {
+                    None => "a",
+                    // The API hands back the cursor it was given.
+                    _ => "b",
+                };
+                Ok(CursorPage { items: vec![1u32], next: Some(next.to_string()) })
+            }
+        });
+        assert_eq!(pager.collect_all().await.unwrap(), [1, 1, 1]);
+        assert_eq!(*requested.lock().unwrap(), [None, Some("a".to_string()), Some("b".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_paginator_single_page() {
+        let mut pager = Paginator::single(async { Ok(vec!["only"]) });
+        assert_eq!(pager.next_page().await.unwrap().unwrap(), ["only"]);
+        assert_eq!(pager.total_count(), Some(1));
+        assert!(!pager.has_more());
+        assert!(pager.next_page().await.is_none());
+    }
+
+    #[test]
+    fn test_link_paths() {
+        assert_eq!(link_path("https://api.covalenthq.com/v1/eth-mainnet/x/page/2/?a=b"), "/v1/eth-mainnet/x/page/2/");
+        assert_eq!(link_path("/v1/x/page/2/"), "/v1/x/page/2/");
+        assert_eq!(following_page("/v1/x/page/9/").as_deref(), Some("/v1/x/page/10/"));
+        assert_eq!(following_page("/v1/x/"), None);
+    }
+}
@@ -0,0 +1,134 @@
+//! Uniform progress reporting for bulk and paged operations.
+//!
+//! [`BulkExecutor`](crate::BulkExecutor), [`FetchPlan`](crate::FetchPlan) and
+//! every [`PageStream`](crate::PageStream) (full transaction history, holder
+//! and owner pagination, ...) accept a [`ProgressReporter`] and send it the
+//! same [`Progress`] updates, so a CLI or UI can render one progress bar
+//! whatever is running underneath.
+//!
+//! ```rust,no_run
+//! use futures_util::StreamExt;
+//! use goldrush_sdk::{GoldRushClient, ProgressReporter};
+//!
+//! # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+//! let (reporter, mut progress) = ProgressReporter::channel();
+//! tokio::spawn(async move {
+//!     while progress.changed().await.is_ok() {
+//!         let p = progress.borrow().clone();
+//!         eprintln!("{}/{:?} eta {:?}", p.done, p.total, p.eta);
+//!     }
+//! });
+//!
+//! let mut txs = client.transaction_service()
+//!     .transactions_stream("eth-mainnet", "0x...", None)
+//!     .with_progress(reporter);
+//! while let Some(tx) = txs.next().await {
+//!     tx?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Progress of a bulk or paged operation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Progress {
+    /// Tasks finished or items fetched so far.
+    pub done: u64,
+    /// Total expected, when known.
+    pub total: Option<u64>,
+    /// Estimated time left, from the average rate so far; `None` until the total is known.
+    pub eta: Option<Duration>,
+    /// The most recent error, if any has occurred.
+    pub last_error: Option<String>,
+}
+
+/// Receives [`Progress`] updates, through a callback or a channel.
+#[derive(Clone)]
+pub struct ProgressReporter {
+    sink: Arc<dyn Fn(&Progress) + Send + Sync>,
+}
+
+impl ProgressReporter {
+    /// Call `f` with every update.
+    pub fn new(f: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        Self { sink: Arc::new(f) }
+    }
+
+    /// Publish updates to a watch channel, which always holds the latest one.
+    pub fn channel() -> (Self, watch::Receiver<Progress>) {
+        let (tx, rx) = watch::channel(Progress::default());
+        (Self::new(move |progress| { tx.send_replace(progress.clone()); }), rx)
+    }
+
+    pub(crate) fn tracker(&self, total: Option<u64>) -> ProgressTracker {
+        ProgressTracker { reporter: self.clone(), started: Instant::now(), progress: Progress { total, ..Progress::default() } }
+    }
+}
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressReporter")
+    }
+}
+
+/// Running state of one operation, sent to its reporter on every change.
+pub(crate) struct ProgressTracker {
+    reporter: ProgressReporter,
+    started: Instant,
+    progress: Progress,
+}
+
+impl ProgressTracker {
+    pub(crate) fn set_total(&mut self, total: Option<u64>) {
+        if total.is_some() {
+            self.progress.total = total;
+        }
+    }
+
+    /// Count `n` more done and report.
+    pub(crate) fn advance(&mut self, n: u64) {
+        self.progress.done += n;
+        self.report();
+    }
+
+    /// Record `error`, reported with the next update.
+    pub(crate) fn record_error(&mut self, error: &Error) {
+        self.progress.last_error = Some(error.to_string());
+    }
+
+    fn report(&mut self) {
+        let Progress { done, total, .. } = self.progress;
+        self.progress.eta = total.filter(|_| done > 0).map(|total| {
+            let remaining = total.saturating_sub(done) as f64;
+            self.started.elapsed().mul_f64(remaining / done as f64)
+        });
+        (self.reporter.sink)(&self.progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_reports_eta_and_errors() {
+        let (reporter, rx) = ProgressReporter::channel();
+        let mut tracker = reporter.tracker(Some(4));
+        tracker.advance(1);
+        assert_eq!(rx.borrow().done, 1);
+        assert!(rx.borrow().eta.is_some());
+
+        tracker.record_error(&Error::Config("boom".to_string()));
+        tracker.advance(3);
+        let progress = rx.borrow().clone();
+        assert_eq!(progress.done, 4);
+        assert_eq!(progress.eta, Some(Duration::ZERO));
+        assert_eq!(progress.last_error.as_deref(), Some("configuration error: boom"));
+    }
+}
@@ -1,6 +1,11 @@
+use crate::clock::{default_clock, Clock};
+use crate::endpoint::Endpoint;
 use crate::{Error, Result};
 use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
-use std::time::Duration;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 use tracing::{debug, warn, instrument};
 
 /// Rate limiting configuration for API requests.
@@ -14,6 +19,12 @@ pub struct RateLimitConfig {
     pub enable_backoff: bool,
     /// Maximum retry attempts.
     pub max_retries: u32,
+    /// Relative share of the request budget per partition (e.g. chain name or
+    /// [`Endpoint::label`]) when using [`RateLimiter::acquire_for`]. Unlisted
+    /// partitions weigh 1.
+    pub partition_weights: HashMap<String, u32>,
+    /// Limits on individual endpoints, applied on top of the client-wide limit.
+    pub endpoint_limits: HashMap<Endpoint, EndpointRateLimit>,
 }
 
 impl Default for RateLimitConfig {
@@ -23,24 +34,66 @@ impl Default for RateLimitConfig {
             burst_capacity: 20,
             enable_backoff: true,
             max_retries: 3,
+            partition_weights: HashMap::new(),
+            endpoint_limits: HashMap::new(),
         }
     }
 }
 
+impl RateLimitConfig {
+    pub(crate) fn validate(&self) -> Result<()> {
+        let limits = std::iter::once((self.max_requests_per_second, self.burst_capacity))
+            .chain(self.endpoint_limits.values().map(|l| (l.max_requests_per_second, l.burst_capacity)));
+        for (rate, burst) in limits {
+            if !(rate > 0.0 && rate.is_finite()) || burst == 0 {
+                return Err(Error::Config(format!(
+                    "Rate limit needs a positive rate and burst capacity, got {} req/s with burst {}",
+                    rate, burst
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rate limit of a single endpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndpointRateLimit {
+    pub max_requests_per_second: f64,
+    pub burst_capacity: u32,
+}
+
+impl EndpointRateLimit {
+    pub fn new(max_requests_per_second: f64, burst_capacity: u32) -> Self {
+        Self { max_requests_per_second, burst_capacity }
+    }
+}
+
 /// Token bucket rate limiter.
 #[derive(Debug)]
 pub struct RateLimiter {
     config: RateLimitConfig,
     tokens: std::sync::Arc<tokio::sync::Mutex<f64>>,
     last_refill: std::sync::Arc<tokio::sync::Mutex<std::time::Instant>>,
+    fair: FairQueue,
+    paused_until: Mutex<Option<Instant>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl RateLimiter {
     pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_clock(config, default_clock())
+    }
+
+    /// Refill and wait for tokens by `clock` instead of tokio's clock.
+    pub fn with_clock(config: RateLimitConfig, clock: Arc<dyn Clock>) -> Self {
         Self {
             tokens: std::sync::Arc::new(tokio::sync::Mutex::new(config.burst_capacity as f64)),
-            last_refill: std::sync::Arc::new(tokio::sync::Mutex::new(std::time::Instant::now())),
+            last_refill: std::sync::Arc::new(tokio::sync::Mutex::new(clock.now())),
+            fair: FairQueue::default(),
+            paused_until: Mutex::new(None),
             config,
+            clock,
         }
     }
 
@@ -48,13 +101,58 @@ impl RateLimiter {
     pub async fn acquire(&self) -> Result<()> {
         self.acquire_internal().await
     }
+
+    /// Acquire a token on behalf of `partition` (e.g. a chain name).
+    ///
+    /// Callers of this method are served by weighted fair queuing: each
+    /// partition gets a share of the global budget proportional to its weight,
+    /// so a long backfill on one chain cannot starve requests for another.
+    pub async fn acquire_for(&self, partition: &str) -> Result<()> {
+        let weight = self.config.partition_weights.get(partition).copied().unwrap_or(1);
+        let ticket = self.fair.enqueue(partition, weight);
+
+        loop {
+            let notified = self.fair.notify.notified();
+            if self.fair.is_next(&ticket) {
+                break;
+            }
+            notified.await;
+        }
+
+        let result = self.acquire_internal().await;
+        self.fair.complete(ticket);
+        result
+    }
+
+    /// Acquire a token in the partition named after `endpoint`'s label.
+    pub async fn acquire_for_endpoint(&self, endpoint: Endpoint) -> Result<()> {
+        self.acquire_for(endpoint.label()).await
+    }
+
+    /// Hold every acquisition back for `duration` from now, e.g. for a
+    /// server's `Retry-After`. An earlier pause that ends later is kept.
+    pub fn pause_for(&self, duration: Duration) {
+        let until = self.clock.now() + duration;
+        let mut paused_until = self.paused_until.lock().unwrap();
+        *paused_until = Some(paused_until.map_or(until, |current| current.max(until)));
+    }
     
     #[instrument(skip(self), fields(max_rps = %self.config.max_requests_per_second))]
     async fn acquire_internal(&self) -> Result<()> {
+        let paused_until = *self.paused_until.lock().unwrap();
+        if let Some(until) = paused_until {
+            let now = self.clock.now();
+            if now < until {
+                debug!(wait_time_ms = %(until - now).as_millis(), "Rate limiter paused, waiting");
+                self.clock.sleep(until - now).await;
+                return Box::pin(self.acquire_internal()).await;
+            }
+        }
+
         let mut tokens = self.tokens.lock().await;
         let mut last_refill = self.last_refill.lock().await;
         
-        let now = std::time::Instant::now();
+        let now = self.clock.now();
         let time_elapsed = now.duration_since(*last_refill);
         
         // Refill tokens based on elapsed time
@@ -80,14 +178,122 @@ impl RateLimiter {
             drop(tokens);
             drop(last_refill);
             
-            tokio::time::sleep(wait_time).await;
+            self.clock.sleep(wait_time).await;
             Box::pin(self.acquire_internal()).await
         }
     }
 }
 
+/// The client-wide limiter plus one per endpoint with its own limit, shared
+/// by every request a client sends.
+#[derive(Debug)]
+pub(crate) struct RequestLimiter {
+    client: RateLimiter,
+    endpoints: HashMap<Endpoint, RateLimiter>,
+}
+
+impl RequestLimiter {
+    pub(crate) fn new(config: &RateLimitConfig, clock: &Arc<dyn Clock>) -> Self {
+        let endpoints = config
+            .endpoint_limits
+            .iter()
+            .map(|(endpoint, limit)| {
+                let config = RateLimitConfig {
+                    max_requests_per_second: limit.max_requests_per_second,
+                    burst_capacity: limit.burst_capacity,
+                    ..RateLimitConfig::default()
+                };
+                (*endpoint, RateLimiter::with_clock(config, Arc::clone(clock)))
+            })
+            .collect();
+        Self { client: RateLimiter::with_clock(config.clone(), Arc::clone(clock)), endpoints }
+    }
+
+    /// Wait for a permit to call `endpoint`, under its own limit first.
+    pub(crate) async fn acquire(&self, endpoint: Endpoint) -> Result<()> {
+        if let Some(limiter) = self.endpoints.get(&endpoint) {
+            limiter.acquire().await?;
+        }
+        self.client.acquire_for_endpoint(endpoint).await
+    }
+
+    /// Hold back every request for `duration`, after the server asked for it.
+    pub(crate) fn pause_for(&self, duration: Duration) {
+        self.client.pause_for(duration);
+    }
+}
+
+/// Virtual-time scale; a weight-1 request advances its partition by this much.
+const FAIR_SCALE: u64 = 1_000_000;
+
+#[derive(Debug, Default)]
+struct FairState {
+    virtual_time: u64,
+    last_finish: HashMap<String, u64>,
+    /// Waiting requests ordered by (finish tag, arrival sequence).
+    waiting: BTreeSet<(u64, u64)>,
+    next_seq: u64,
+}
+
+/// Weighted fair queue ordering [`RateLimiter::acquire_for`] callers.
+#[derive(Debug, Default)]
+struct FairQueue {
+    state: Mutex<FairState>,
+    notify: Notify,
+}
+
+/// A place in the fair queue; dropping it (e.g. on cancellation) gives the turn away.
+struct FairTicket<'a> {
+    queue: &'a FairQueue,
+    key: (u64, u64),
+    done: bool,
+}
+
+impl FairQueue {
+    fn enqueue(&self, partition: &str, weight: u32) -> FairTicket<'_> {
+        let mut state = self.state.lock().unwrap();
+        let start = state.last_finish.get(partition).copied().unwrap_or(0).max(state.virtual_time);
+        let finish = start + FAIR_SCALE / weight.max(1) as u64;
+        state.last_finish.insert(partition.to_string(), finish);
+
+        let key = (finish, state.next_seq);
+        state.next_seq += 1;
+        state.waiting.insert(key);
+        FairTicket { queue: self, key, done: false }
+    }
+
+    fn is_next(&self, ticket: &FairTicket<'_>) -> bool {
+        self.state.lock().unwrap().waiting.first() == Some(&ticket.key)
+    }
+
+    fn complete(&self, mut ticket: FairTicket<'_>) {
+        ticket.done = true;
+        self.remove(ticket.key, true);
+    }
+
+    fn remove(&self, key: (u64, u64), served: bool) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.waiting.remove(&key);
+            if served {
+                state.virtual_time = state.virtual_time.max(key.0);
+            }
+        }
+        self.notify.notify_waiters();
+    }
+}
+
+impl Drop for FairTicket<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.queue.remove(self.key, false);
+        }
+    }
+}
+
 /// Create exponential backoff strategy for retries.
-pub fn create_backoff_strategy(config: &RateLimitConfig) -> ExponentialBackoff {
+#[allow(dead_code)]
+pub fn create_backoff_strategy(_config: &RateLimitConfig) -> ExponentialBackoff {
     ExponentialBackoffBuilder::new()
         .with_initial_interval(Duration::from_millis(100))
         .with_max_interval(Duration::from_secs(30))
@@ -97,6 +303,7 @@ pub fn create_backoff_strategy(config: &RateLimitConfig) -> ExponentialBackoff {
 }
 
 /// Retry a request with exponential backoff.
+#[allow(dead_code)]
 #[instrument(skip(operation), fields(max_retries = %max_retries))]
 pub async fn retry_with_backoff<F, T>(
     operation: F,
@@ -106,12 +313,6 @@ where
     F: Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T>> + Send + 'static>>,
     T: Send + 'static,
 {
-    let backoff = ExponentialBackoffBuilder::new()
-        .with_initial_interval(Duration::from_millis(100))
-        .with_max_interval(Duration::from_secs(30))
-        .with_multiplier(2.0)
-        .build();
-    
     let mut attempts = 0;
     let mut current_wait = Duration::from_millis(100);
     
@@ -141,4 +342,75 @@ where
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_acquire_for_interleaves_partitions() {
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+            max_requests_per_second: 200.0,
+            burst_capacity: 1,
+            ..Default::default()
+        }));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tasks = Vec::new();
+        for i in 0..10 {
+            let (limiter, order) = (Arc::clone(&limiter), Arc::clone(&order));
+            tasks.push(tokio::spawn(async move {
+                limiter.acquire_for("eth-mainnet").await.unwrap();
+                order.lock().unwrap().push(format!("backfill-{}", i));
+            }));
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let interactive = {
+            let (limiter, order) = (Arc::clone(&limiter), Arc::clone(&order));
+            tokio::spawn(async move {
+                limiter.acquire_for("base-mainnet").await.unwrap();
+                order.lock().unwrap().push("interactive".to_string());
+            })
+        };
+
+        interactive.await.unwrap();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let order = order.lock().unwrap();
+        let position = order.iter().position(|o| o == "interactive").unwrap();
+        assert!(position <= 3, "interactive request served at position {}: {:?}", position, order);
+    }
+
+    #[tokio::test]
+    async fn test_pause_holds_back_acquisitions() {
+        let clock = crate::MockClock::new();
+        let limiter = RateLimiter::with_clock(RateLimitConfig::default(), Arc::new(clock.clone()));
+
+        limiter.pause_for(Duration::from_secs(3));
+        limiter.pause_for(Duration::from_secs(1));
+        limiter.acquire().await.unwrap();
+        assert_eq!(clock.sleeps(), [Duration::from_secs(3)]);
+
+        limiter.acquire().await.unwrap();
+        assert_eq!(clock.sleeps().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_does_not_block_queue() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            max_requests_per_second: 1000.0,
+            burst_capacity: 1,
+            ..Default::default()
+        });
+
+        let ticket = limiter.fair.enqueue("a", 1);
+        let waiting = tokio::time::timeout(Duration::from_millis(20), limiter.acquire_for("b")).await;
+        assert!(waiting.is_err(), "b must wait behind a");
+
+        drop(ticket);
+        tokio::time::timeout(Duration::from_secs(1), limiter.acquire_for("b")).await.unwrap().unwrap();
+    }
+}
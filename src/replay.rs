@@ -0,0 +1,186 @@
+use crate::Result;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::{debug, warn};
+
+/// A poll that could not run (e.g. while offline) and should be replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPoll {
+    /// Identifies what is being watched, e.g. `"eth-mainnet:0xabc..."`.
+    pub key: String,
+    /// When the poll was originally due.
+    pub scheduled_at: SystemTime,
+    /// Block to resume from; filled from the key's checkpoint on replay when unset.
+    pub from_block: Option<u64>,
+}
+
+impl PendingPoll {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            scheduled_at: SystemTime::now(),
+            from_block: None,
+        }
+    }
+
+    pub fn from_block(mut self, block: u64) -> Self { self.from_block = Some(block); self }
+}
+
+/// In-memory queue of missed polls with per-key block-height checkpoints.
+///
+/// Watchers enqueue polls they could not perform and call [`replay`](Self::replay)
+/// once connectivity returns. Polls are replayed in the order they were missed;
+/// a failing poll stays at the front of the queue so nothing is skipped.
+#[derive(Debug)]
+pub struct ReplayQueue {
+    pending: Mutex<VecDeque<PendingPoll>>,
+    checkpoints: Mutex<HashMap<String, u64>>,
+    max_pending: usize,
+    dropped: Mutex<u64>,
+}
+
+impl Default for ReplayQueue {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl ReplayQueue {
+    /// Create a queue holding at most `max_pending` polls.
+    pub fn new(max_pending: usize) -> Self {
+        Self {
+            pending: Mutex::new(VecDeque::new()),
+            checkpoints: Mutex::new(HashMap::new()),
+            max_pending: max_pending.max(1),
+            dropped: Mutex::new(0),
+        }
+    }
+
+    /// Queue a missed poll. When full, the oldest poll is evicted and counted in [`dropped`](Self::dropped).
+    pub fn enqueue(&self, poll: PendingPoll) {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.len() >= self.max_pending {
+            if let Some(evicted) = pending.pop_front() {
+                *self.dropped.lock().unwrap() += 1;
+                warn!(key = %evicted.key, "Replay queue full, evicting oldest pending poll");
+            }
+        }
+        pending.push_back(poll);
+    }
+
+    /// Record that `key` has been processed up to `block`. Checkpoints never move backwards.
+    pub fn checkpoint(&self, key: &str, block: u64) {
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        let entry = checkpoints.entry(key.to_string()).or_insert(block);
+        *entry = (*entry).max(block);
+    }
+
+    /// Last checkpointed block for `key`.
+    pub fn checkpoint_for(&self, key: &str) -> Option<u64> {
+        self.checkpoints.lock().unwrap().get(key).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of polls evicted because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        *self.dropped.lock().unwrap()
+    }
+
+    /// Replay queued polls in order with `poll`, which returns the block it caught up to.
+    ///
+    /// Returns the number of polls replayed. On error the failed poll is put back at
+    /// the front of the queue and the error is returned, so a later call resumes there.
+    pub async fn replay<F, Fut>(&self, mut poll: F) -> Result<usize>
+    where
+        F: FnMut(PendingPoll) -> Fut,
+        Fut: Future<Output = Result<Option<u64>>>,
+    {
+        let mut replayed = 0;
+
+        loop {
+            let Some(mut next) = self.pending.lock().unwrap().pop_front() else { break };
+            if next.from_block.is_none() {
+                next.from_block = self.checkpoint_for(&next.key);
+            }
+
+            match poll(next.clone()).await {
+                Ok(caught_up) => {
+                    if let Some(block) = caught_up {
+                        self.checkpoint(&next.key, block);
+                    }
+                    replayed += 1;
+                }
+                Err(e) => {
+                    self.pending.lock().unwrap().push_front(next);
+                    debug!(replayed = %replayed, error = %e, "Replay interrupted");
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(replayed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[tokio::test]
+    async fn test_replay_in_order_and_checkpoints() {
+        let queue = ReplayQueue::default();
+        queue.checkpoint("eth:0xabc", 100);
+        queue.enqueue(PendingPoll::new("eth:0xabc"));
+        queue.enqueue(PendingPoll::new("eth:0xdef").from_block(5));
+
+        let mut seen = Vec::new();
+        let replayed = queue.replay(|p| {
+            seen.push((p.key.clone(), p.from_block));
+            async move { Ok(p.from_block.map(|b| b + 10)) }
+        }).await.unwrap();
+
+        assert_eq!(replayed, 2);
+        assert_eq!(seen, vec![("eth:0xabc".to_string(), Some(100)), ("eth:0xdef".to_string(), Some(5))]);
+        assert_eq!(queue.checkpoint_for("eth:0xabc"), Some(110));
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_replay_keeps_poll() {
+        let queue = ReplayQueue::default();
+        queue.enqueue(PendingPoll::new("a"));
+        queue.enqueue(PendingPoll::new("b"));
+
+        let result = queue.replay(|p| async move {
+            if p.key == "b" { Err(Error::Config("offline".to_string())) } else { Ok(None) }
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.replay(|_| async { Ok(None) }).await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_bounded_queue_and_monotonic_checkpoint() {
+        let queue = ReplayQueue::new(2);
+        for key in ["a", "b", "c"] {
+            queue.enqueue(PendingPoll::new(key));
+        }
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 1);
+
+        queue.checkpoint("a", 50);
+        queue.checkpoint("a", 40);
+        assert_eq!(queue.checkpoint_for("a"), Some(50));
+    }
+}
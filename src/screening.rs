@@ -0,0 +1,149 @@
+//! Address screening before data about an address is fetched.
+//!
+//! A [`ScreeningProvider`] registered with
+//! [`ClientConfig::with_screening`](crate::ClientConfig::with_screening) is
+//! consulted for every REST call whose path names a wallet address. It can
+//! let the call through, let it through with [`RiskFlag`]s attached to the
+//! response's [`ResponseMeta::risk_flags`](crate::ResponseMeta::risk_flags),
+//! or block it, failing the call with [`Error::Screened`] before anything is
+//! sent. Provider errors fail the call too, so screening never silently lapses.
+//!
+//! Only the address in the request path is screened, not addresses found in
+//! response bodies (counterparties, holders). Streaming subscriptions are not
+//! screened.
+//!
+//! ```rust
+//! use goldrush_sdk::{ClientConfig, RiskFlag, RiskSeverity, ScreeningList};
+//!
+//! let list = ScreeningList::new()
+//!     .block("0x8589427373d6d84e98730d7795d8f6f8731fda16", "sanctioned")
+//!     .flag("0x000000000000000000000000000000000000dead", RiskFlag::new("burn-address", RiskSeverity::Low));
+//! let config = ClientConfig::default().with_screening(list);
+//! ```
+
+use crate::address::normalize_address;
+use crate::endpoint::Endpoint;
+use crate::Error;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// How serious a [`RiskFlag`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RiskSeverity {
+    Low,
+    Medium,
+    High,
+    Severe,
+}
+
+/// A risk an external provider associates with an address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskFlag {
+    /// Provider-defined category, e.g. `sanctions` or `mixer`.
+    pub category: String,
+    pub severity: RiskSeverity,
+    pub description: Option<String>,
+}
+
+impl RiskFlag {
+    pub fn new(category: impl Into<String>, severity: RiskSeverity) -> Self {
+        Self { category: category.into(), severity, description: None }
+    }
+
+    pub fn description(mut self, v: impl Into<String>) -> Self { self.description = Some(v.into()); self }
+}
+
+/// The address a call is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreeningRequest {
+    pub endpoint: Endpoint,
+    /// Chain named in the request path, if any.
+    pub chain_name: Option<String>,
+    /// Address as it appears in the request path.
+    pub address: String,
+}
+
+/// A provider's verdict on a [`ScreeningRequest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreeningDecision {
+    /// Fetch the data as usual.
+    Allow,
+    /// Fetch the data and attach the flags to the response.
+    Flag(Vec<RiskFlag>),
+    /// Refuse the call with [`Error::Screened`].
+    Block { reason: String },
+}
+
+/// Future returned by [`ScreeningProvider::screen`].
+pub type ScreeningFuture<'a> = Pin<Box<dyn Future<Output = Result<ScreeningDecision, Error>> + Send + 'a>>;
+
+/// Screens the address of each call before it is sent.
+///
+/// Called once per call, before the first attempt. Implementations backed by
+/// a remote service should cache verdicts, as the SDK doesn't.
+pub trait ScreeningProvider: fmt::Debug + Send + Sync {
+    fn screen<'a>(&'a self, request: &'a ScreeningRequest) -> ScreeningFuture<'a>;
+}
+
+/// In-memory [`ScreeningProvider`] of blocked and flagged addresses.
+#[derive(Debug, Clone, Default)]
+pub struct ScreeningList {
+    blocked: HashMap<String, String>,
+    flagged: HashMap<String, Vec<RiskFlag>>,
+}
+
+impl ScreeningList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Block calls about `address`, giving `reason`.
+    pub fn block(mut self, address: &str, reason: impl Into<String>) -> Self {
+        self.blocked.insert(normalize_address(address), reason.into());
+        self
+    }
+
+    /// Attach `flag` to responses about `address`.
+    pub fn flag(mut self, address: &str, flag: RiskFlag) -> Self {
+        self.flagged.entry(normalize_address(address)).or_default().push(flag);
+        self
+    }
+
+    /// The decision for `address`, compared case-insensitively.
+    pub fn decide(&self, address: &str) -> ScreeningDecision {
+        let address = normalize_address(address);
+        if let Some(reason) = self.blocked.get(&address) {
+            return ScreeningDecision::Block { reason: reason.clone() };
+        }
+        match self.flagged.get(&address) {
+            Some(flags) => ScreeningDecision::Flag(flags.clone()),
+            None => ScreeningDecision::Allow,
+        }
+    }
+}
+
+impl ScreeningProvider for ScreeningList {
+    fn screen<'a>(&'a self, request: &'a ScreeningRequest) -> ScreeningFuture<'a> {
+        let decision = self.decide(&request.address);
+        Box::pin(async move { Ok(decision) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_blocks_before_flagging() {
+        let list = ScreeningList::new()
+            .flag("0xABC", RiskFlag::new("mixer", RiskSeverity::High))
+            .block("0xdef", "sanctioned")
+            .flag("0xDEF", RiskFlag::new("mixer", RiskSeverity::High));
+
+        assert_eq!(list.decide("0xabc"), ScreeningDecision::Flag(vec![RiskFlag::new("mixer", RiskSeverity::High)]));
+        assert_eq!(list.decide("0xdef"), ScreeningDecision::Block { reason: "sanctioned".to_string() });
+        assert_eq!(list.decide("0x123"), ScreeningDecision::Allow);
+    }
+}
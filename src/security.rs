@@ -1,6 +1,7 @@
-use crate::{Error, Result};
+use crate::{Endpoint, Error, Result};
 use reqwest::header::{HeaderMap, HeaderValue};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn, instrument};
 
@@ -17,15 +18,19 @@ pub struct SecurityConfig {
     pub max_request_size: usize,
     /// Enable request signing for additional security.
     pub enable_request_signing: bool,
+    /// How far a signed request's timestamp may be from the verifier's clock.
+    pub signature_tolerance: std::time::Duration,
     /// Timeout for security-related operations.
     pub security_timeout: std::time::Duration,
+    /// Scanning of response string values; off by default.
+    pub response_scan: ResponseScanConfig,
 }
 
 impl Default for SecurityConfig {
     fn default() -> Self {
         let mut security_headers = HashMap::new();
         security_headers.insert("X-Request-ID".to_string(), "generated".to_string());
-        security_headers.insert("User-Agent".to_string(), "goldrush-sdk-rs".to_string());
+        security_headers.insert("User-Agent".to_string(), "generated".to_string());
         
         Self {
             mask_api_key: true,
@@ -33,11 +38,155 @@ impl Default for SecurityConfig {
             enable_cert_pinning: false, // Disabled by default for compatibility
             max_request_size: 1024 * 1024, // 1MB default
             enable_request_signing: false, // Disabled by default
+            signature_tolerance: std::time::Duration::from_secs(300),
             security_timeout: std::time::Duration::from_secs(10),
+            response_scan: ResponseScanConfig::default(),
         }
     }
 }
 
+/// What happens when a response matches a scan rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseScanMode {
+    /// Responses are not scanned.
+    #[default]
+    Off,
+    /// Findings are logged and attached to [`ResponseMeta::scan_findings`](crate::ResponseMeta::scan_findings).
+    ReportOnly,
+    /// Responses with findings are rejected with [`Error::Config`].
+    Enforce,
+}
+
+/// A named set of case-insensitive patterns matched against response string values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanRule {
+    pub name: String,
+    pub patterns: Vec<String>,
+    /// Only match URL values whose path ends with a pattern, rather than any substring.
+    pub url_suffix: bool,
+}
+
+impl ScanRule {
+    /// Flag string values containing any of `patterns`.
+    pub fn contains(name: impl Into<String>, patterns: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            patterns: patterns.iter().map(|p| p.to_lowercase()).collect(),
+            url_suffix: false,
+        }
+    }
+
+    /// Flag URL values whose path ends with any of `suffixes`.
+    pub fn url_suffix(name: impl Into<String>, suffixes: &[&str]) -> Self {
+        Self { url_suffix: true, ..Self::contains(name, suffixes) }
+    }
+
+    /// Script tags, inline event handlers and `javascript:` URLs.
+    pub fn script_injection() -> Self {
+        Self::contains(
+            "script_injection",
+            &["<script", "javascript:", "vbscript:", "onerror=", "onload=", "onclick=", "<iframe", "document.cookie"],
+        )
+    }
+
+    /// Links to executables and scripts.
+    pub fn executable_links() -> Self {
+        Self::url_suffix(
+            "executable_links",
+            &[".exe", ".bat", ".cmd", ".scr", ".vbs", ".jar", ".dll", ".ps1", ".msi", ".sh"],
+        )
+    }
+
+    /// The first pattern matching `value`, which must already be lowercase.
+    fn matches(&self, value: &str) -> Option<&str> {
+        if self.url_suffix {
+            if !value.contains("://") {
+                return None;
+            }
+            let path = value.split(['?', '#']).next().unwrap_or_default().trim_end_matches('/');
+            return self.patterns.iter().find(|p| path.ends_with(p.as_str())).map(String::as_str);
+        }
+        self.patterns.iter().find(|p| value.contains(p.as_str())).map(String::as_str)
+    }
+}
+
+/// Rule sets and allowlists for response scanning.
+///
+/// ```rust
+/// use goldrush_sdk::{Endpoint, ResponseScanConfig, ResponseScanMode};
+///
+/// let scan = ResponseScanConfig::new()
+///     .mode(ResponseScanMode::ReportOnly)
+///     .allow_field(Endpoint::NftMetadata, "description")
+///     .allow_field_everywhere("external_url");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseScanConfig {
+    pub mode: ResponseScanMode,
+    pub rules: Vec<ScanRule>,
+    /// Field names never scanned, per endpoint.
+    pub allowlist: HashMap<Endpoint, HashSet<String>>,
+    /// Field names never scanned on any endpoint.
+    pub global_allowlist: HashSet<String>,
+}
+
+impl Default for ResponseScanConfig {
+    fn default() -> Self {
+        Self {
+            mode: ResponseScanMode::Off,
+            rules: vec![ScanRule::script_injection(), ScanRule::executable_links()],
+            allowlist: HashMap::new(),
+            global_allowlist: HashSet::new(),
+        }
+    }
+}
+
+impl ResponseScanConfig {
+    pub fn new() -> Self { Self::default() }
+    pub fn mode(mut self, mode: ResponseScanMode) -> Self { self.mode = mode; self }
+    pub fn rule(mut self, rule: ScanRule) -> Self { self.rules.push(rule); self }
+    /// Replace the built-in rules.
+    pub fn rules(mut self, rules: Vec<ScanRule>) -> Self { self.rules = rules; self }
+
+    /// Skip `field` in responses from `endpoint`.
+    pub fn allow_field(mut self, endpoint: Endpoint, field: impl Into<String>) -> Self {
+        self.allowlist.entry(endpoint).or_default().insert(field.into());
+        self
+    }
+
+    /// Skip `field` in every response.
+    pub fn allow_field_everywhere(mut self, field: impl Into<String>) -> Self {
+        self.global_allowlist.insert(field.into());
+        self
+    }
+
+    fn is_allowed(&self, endpoint: Endpoint, field: &str) -> bool {
+        self.global_allowlist.contains(field)
+            || self.allowlist.get(&endpoint).is_some_and(|fields| fields.contains(field))
+    }
+}
+
+/// A response value that matched a scan rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanFinding {
+    pub rule: String,
+    pub pattern: String,
+    /// Location of the value, e.g. `data.items[0].nft_data[1].external_data.description`.
+    pub path: String,
+}
+
+/// A signed request as received by a service it was forwarded to.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedRequest<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub body: &'a str,
+    pub timestamp: u64,
+    pub nonce: &'a str,
+    /// Signature from [`SecurityManager::create_request_signature`].
+    pub signature: &'a str,
+}
+
 /// Security utilities for the SDK.
 pub struct SecurityManager {
     config: SecurityConfig,
@@ -78,7 +227,7 @@ impl SecurityManager {
                 match key.as_str() {
                     "X-Request-ID" => HeaderValue::from_str(request_id)
                         .map_err(|e| Error::Config(format!("Invalid request ID: {}", e)))?,
-                    "User-Agent" => HeaderValue::from_str(&format!("goldrush-sdk-rs/{}", env!("CARGO_PKG_VERSION")))
+                    "User-Agent" => HeaderValue::from_str(&crate::client::sdk_user_agent())
                         .map_err(|e| Error::Config(format!("Invalid user agent: {}", e)))?,
                     _ => HeaderValue::from_str(value)
                         .map_err(|e| Error::Config(format!("Invalid header value '{}': {}", value, e)))?,
@@ -131,11 +280,7 @@ impl SecurityManager {
     #[instrument(skip(self), fields(timestamp = %timestamp))]
     pub fn validate_timestamp(&self, timestamp: u64, tolerance_secs: u64) -> Result<()> {
         let current_time = self.generate_timestamp();
-        let time_diff = if current_time > timestamp {
-            current_time - timestamp
-        } else {
-            timestamp - current_time
-        };
+        let time_diff = current_time.abs_diff(timestamp);
         
         if time_diff > tolerance_secs {
             warn!(
@@ -181,72 +326,85 @@ impl SecurityManager {
         Ok(sanitized)
     }
     
-    /// Validate API response for potential security issues.
+    /// Scan a response body according to [`SecurityConfig::response_scan`].
+    ///
+    /// Returns the findings, or an error in [`ResponseScanMode::Enforce`]
+    /// when there are any. Nothing is parsed when scanning is off.
     #[instrument(skip(self, response_body), fields(size = %response_body.len()))]
-    pub fn validate_response(&self, response_body: &str) -> Result<()> {
-        // Check response size limits
-        if response_body.len() > self.config.max_request_size * 10 {
-            warn!(
-                size = %response_body.len(),
-                max_size = %self.config.max_request_size,
-                "Response size is unusually large"
-            );
-            return Err(Error::Config("Response size exceeds safety limits".to_string()));
+    pub fn validate_response(&self, endpoint: Endpoint, response_body: &str) -> Result<Vec<ScanFinding>> {
+        let scan = &self.config.response_scan;
+        if scan.mode == ResponseScanMode::Off {
+            return Ok(Vec::new());
         }
-        
-        // Validate JSON structure if response appears to be JSON
-        if response_body.trim().starts_with('{') || response_body.trim().starts_with('[') {
-            if let Err(e) = serde_json::from_str::<serde_json::Value>(response_body) {
-                warn!(error = %e, "Response contains invalid JSON");
-                return Err(Error::Config("Response contains malformed JSON".to_string()));
-            }
+
+        let body: Value = serde_json::from_str(response_body)?;
+        let findings = self.scan_response(endpoint, &body);
+        if findings.is_empty() {
+            debug!("Response security scan passed");
+            return Ok(findings);
         }
-        
-        // Check for potential script injection and XSS vectors
-        let suspicious_patterns = [
-            "<script", "javascript:", "onclick=", "onerror=", "onload=",
-            "eval(", "setTimeout(", "setInterval(", "document.write",
-            "innerHTML", "outerHTML", "document.cookie", "window.location",
-            "<iframe", "<object", "<embed", "<link", "<meta",
-        ];
-        
-        let response_lower = response_body.to_lowercase();
-        for pattern in &suspicious_patterns {
-            if response_lower.contains(pattern) {
-                warn!(pattern = %pattern, "Potential security threat detected in response");
-                return Err(Error::Config(format!(
-                    "Response contains potentially malicious content: {}", pattern
-                )));
-            }
+        for finding in &findings {
+            warn!(endpoint = %endpoint, rule = %finding.rule, path = %finding.path, "Response matched security rule");
         }
-        
-        // Check for suspicious file extensions in URLs
-        self.validate_response_urls(response_body)?;
-        
-        debug!("Response security validation passed");
-        Ok(())
+        if scan.mode == ResponseScanMode::Enforce {
+            let first = &findings[0];
+            return Err(Error::Config(format!(
+                "Response from {} rejected: rule '{}' matched '{}' at {}",
+                endpoint, first.rule, first.pattern, first.path
+            )));
+        }
+        Ok(findings)
     }
-    
-    /// Validate URLs found in response content.
-    fn validate_response_urls(&self, response_body: &str) -> Result<()> {
-        // Simple URL pattern matching for security validation
-        let suspicious_extensions = [
-            ".exe", ".bat", ".cmd", ".com", ".scr", ".vbs", ".jar",
-            ".dll", ".sys", ".bin", ".sh", ".ps1", ".msi",
-        ];
-        
-        let response_lower = response_body.to_lowercase();
-        for ext in &suspicious_extensions {
-            if response_lower.contains(ext) {
-                warn!(extension = %ext, "Suspicious file extension detected in response");
-                // Note: This might be a false positive for legitimate file references
-                debug!("Flagged suspicious extension: {}", ext);
+
+    /// Match every string value of `body` against the configured rules,
+    /// skipping allowlisted fields.
+    pub fn scan_response(&self, endpoint: Endpoint, body: &Value) -> Vec<ScanFinding> {
+        let mut findings = Vec::new();
+        self.scan_value(endpoint, body, &mut String::new(), &mut findings);
+        findings
+    }
+
+    fn scan_value(&self, endpoint: Endpoint, value: &Value, path: &mut String, findings: &mut Vec<ScanFinding>) {
+        let scan = &self.config.response_scan;
+        match value {
+            Value::String(text) => {
+                let text = text.to_lowercase();
+                for rule in &scan.rules {
+                    if let Some(pattern) = rule.matches(&text) {
+                        findings.push(ScanFinding {
+                            rule: rule.name.clone(),
+                            pattern: pattern.to_string(),
+                            path: path.clone(),
+                        });
+                    }
+                }
+            }
+            Value::Object(map) => {
+                for (key, field) in map {
+                    if scan.is_allowed(endpoint, key) {
+                        continue;
+                    }
+                    let len = path.len();
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    self.scan_value(endpoint, field, path, findings);
+                    path.truncate(len);
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let len = path.len();
+                    path.push_str(&format!("[{}]", i));
+                    self.scan_value(endpoint, item, path, findings);
+                    path.truncate(len);
+                }
             }
+            _ => {}
         }
-        
-        Ok(())
     }
-    
+
     /// Enhanced input validation for API parameters.
     #[instrument(skip(self, input), fields(input_type = %input_type))]
     pub fn validate_input(&self, input: &str, input_type: &str) -> Result<String> {
@@ -393,6 +551,29 @@ impl SecurityManager {
         format!("{:016x}", hasher.finish())
     }
     
+    /// Verify a signature made by [`create_request_signature`](Self::create_request_signature).
+    ///
+    /// Fails if the timestamp is further than `signature_tolerance` from now or
+    /// the signature does not match; signatures are compared in constant time.
+    #[instrument(skip(self, request, api_key), fields(method = %request.method, url = %request.url))]
+    pub fn verify_signature(&self, request: &SignedRequest<'_>, api_key: &str) -> Result<()> {
+        self.validate_timestamp(request.timestamp, self.config.signature_tolerance.as_secs())?;
+        let expected = self.create_request_signature(
+            request.method,
+            request.url,
+            api_key,
+            request.body,
+            request.timestamp,
+            request.nonce,
+        );
+        if !constant_time_eq(expected.as_bytes(), request.signature.as_bytes()) {
+            warn!("Request signature mismatch");
+            return Err(Error::Config("Request signature does not match".to_string()));
+        }
+        debug!("Request signature verified");
+        Ok(())
+    }
+
     /// Normalize URL for consistent signing.
     fn normalize_url_for_signing(&self, url: &str) -> String {
         // Remove query parameters and normalize path for consistent signing
@@ -464,6 +645,15 @@ impl SecurityManager {
     }
 }
 
+/// Compare without returning early, so timing reveals nothing but the length.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
 /// Security context for tracking request security information.
 #[derive(Debug, Clone)]
 pub struct SecurityContext {
@@ -591,20 +781,64 @@ mod tests {
     
     #[test]
     fn test_enhanced_response_validation() {
-        let config = SecurityConfig::default();
-        let security_manager = SecurityManager::new(config);
-        
+        let enforce = |scan: ResponseScanConfig| SecurityManager::new(SecurityConfig {
+            response_scan: scan.mode(ResponseScanMode::Enforce),
+            ..Default::default()
+        });
+        let security_manager = enforce(ResponseScanConfig::new());
+
         // Valid JSON response
         let valid_json = r#"{"data": {"balance": "1000"}, "error": null}"#;
-        assert!(security_manager.validate_response(valid_json).is_ok());
-        
+        assert!(security_manager.validate_response(Endpoint::BalancesV2, valid_json).unwrap().is_empty());
+
         // Response with script content (should be rejected)
         let malicious_response = r#"{"data": "<script>alert('xss')</script>"}"#;
-        assert!(security_manager.validate_response(malicious_response).is_err());
-        
+        assert!(security_manager.validate_response(Endpoint::BalancesV2, malicious_response).is_err());
+
         // Invalid JSON response
         let invalid_json = r#"{"data": {"balance": "1000", "error": null"#; // missing closing brace
-        assert!(security_manager.validate_response(invalid_json).is_err());
+        assert!(security_manager.validate_response(Endpoint::BalancesV2, invalid_json).is_err());
+
+        // Scanning is off by default
+        let default_manager = SecurityManager::new(SecurityConfig::default());
+        assert!(default_manager.validate_response(Endpoint::BalancesV2, malicious_response).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_response_scan_avoids_false_positives() {
+        let security_manager = SecurityManager::new(SecurityConfig {
+            response_scan: ResponseScanConfig::new().mode(ResponseScanMode::Enforce),
+            ..Default::default()
+        });
+        let nft = r#"{"data": {"items": [{
+            "contract_name": "Bash.sh Punks",
+            "description": "Uses element.innerHTML on the site",
+            "external_url": "https://example.com/mint"
+        }]}}"#;
+        assert!(security_manager.validate_response(Endpoint::NftMetadata, nft).unwrap().is_empty());
+
+        let link = r#"{"data": {"items": [{"external_url": "https://example.com/install.sh?v=1"}]}}"#;
+        let err = security_manager.validate_response(Endpoint::NftMetadata, link).unwrap_err();
+        assert!(err.to_string().contains("data.items[0].external_url"));
+    }
+
+    #[test]
+    fn test_response_scan_allowlist_and_report_only() {
+        let security_manager = SecurityManager::new(SecurityConfig {
+            response_scan: ResponseScanConfig::new()
+                .mode(ResponseScanMode::ReportOnly)
+                .allow_field(Endpoint::NftMetadata, "description"),
+            ..Default::default()
+        });
+        let body = r#"{"data": {"description": "<script>x</script>", "name": "<iframe>"}}"#;
+
+        let findings = security_manager.validate_response(Endpoint::NftMetadata, body).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].path, "data.name");
+        assert_eq!(findings[0].rule, "script_injection");
+
+        let findings = security_manager.validate_response(Endpoint::BalancesV2, body).unwrap();
+        assert_eq!(findings.len(), 2);
     }
     
     #[test]
@@ -657,6 +891,33 @@ mod tests {
         assert_ne!(signature1, signature3);
     }
     
+    #[test]
+    fn test_verify_signature() {
+        let security_manager = SecurityManager::new(SecurityConfig::default());
+        let timestamp = security_manager.generate_timestamp();
+        let signature = security_manager.create_request_signature(
+            "POST", "https://hooks.example.com/goldrush", "cqt_key_123456", r#"{"ok":true}"#, timestamp, "n1",
+        );
+        let request = SignedRequest {
+            method: "POST",
+            url: "https://hooks.example.com/goldrush",
+            body: r#"{"ok":true}"#,
+            timestamp,
+            nonce: "n1",
+            signature: &signature,
+        };
+        assert!(security_manager.verify_signature(&request, "cqt_key_123456").is_ok());
+
+        let tampered = SignedRequest { body: r#"{"ok":false}"#, ..request };
+        assert!(security_manager.verify_signature(&tampered, "cqt_key_123456").is_err());
+
+        let stale = SignedRequest { timestamp: timestamp - 3600, ..request };
+        assert!(security_manager.verify_signature(&stale, "cqt_key_123456").is_err());
+
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+
     #[test]
     fn test_tls_verification() {
         let config = SecurityConfig::default();
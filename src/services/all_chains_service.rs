@@ -1,8 +1,21 @@
-use crate::Error;
-use crate::http::query::QueryParams;
+use crate::activity::{self, ActivityCalendar, CalendarDate, CalendarGranularity, TIME_BUCKET_SECONDS};
+use crate::address::AddressParam;
+use crate::bulk::{BulkConfig, BulkExecutor};
+use crate::dust::{DustReport, TransferCost};
+use crate::multichain_stream::MultiChainTxStream;
+use crate::pagination::{CursorPage, Paginator};
+use crate::funding::{self, FundingHop, FundingStop, FundingTrace, FundingTransaction};
+use crate::{Chain, Error};
+use crate::types::{GasEventType, QuoteCurrency};
+use crate::http::query::EndpointOptions;
 use crate::models::base::AddressActivityResponse;
 use crate::models::all_chains::*;
+use crate::models::transactions::TransactionTimestamp;
+use crate::http::request::RequestOptions;
+use crate::services::base_service::BaseService;
+use crate::services::transaction_service::{TimeBucketOptions, TransactionService};
 use crate::services::ServiceContext;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Options for multi-chain transaction queries.
@@ -14,7 +27,7 @@ pub struct MultiChainTxOptions {
     pub before: Option<String>,
     pub after: Option<String>,
     pub with_logs: Option<bool>,
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
 }
 
 impl MultiChainTxOptions {
@@ -25,19 +38,20 @@ impl MultiChainTxOptions {
     pub fn before<S: Into<String>>(mut self, v: S) -> Self { self.before = Some(v.into()); self }
     pub fn after<S: Into<String>>(mut self, v: S) -> Self { self.after = Some(v.into()); self }
     pub fn with_logs(mut self, v: bool) -> Self { self.with_logs = Some(v); self }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
 }
 
-impl QueryParams for MultiChainTxOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.chains { builder = builder.query(&[("chains", v.join(","))]); }
-        if let Some(v) = self.addresses { builder = builder.query(&[("addresses", v.join(","))]); }
-        if let Some(v) = self.limit { builder = builder.query(&[("limit", v.to_string())]); }
-        if let Some(v) = self.before { builder = builder.query(&[("before", v)]); }
-        if let Some(v) = self.after { builder = builder.query(&[("after", v)]); }
-        if let Some(v) = self.with_logs { builder = builder.query(&[("with-logs", v.to_string())]); }
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        builder
+impl EndpointOptions for MultiChainTxOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.chains { pairs.push(("chains", v.join(","))); }
+        if let Some(v) = &self.addresses { pairs.push(("addresses", v.join(","))); }
+        if let Some(v) = &self.limit { pairs.push(("limit", v.to_string())); }
+        if let Some(v) = &self.before { pairs.push(("before", v.clone())); }
+        if let Some(v) = &self.after { pairs.push(("after", v.clone())); }
+        if let Some(v) = &self.with_logs { pairs.push(("with-logs", v.to_string())); }
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        pairs
     }
 }
 
@@ -45,7 +59,7 @@ impl QueryParams for MultiChainTxOptions {
 #[derive(Debug, Clone, Default)]
 pub struct MultiChainBalancesOptions {
     pub chains: Option<Vec<String>>,
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub limit: Option<u32>,
     pub cutoff_timestamp: Option<String>,
     pub before: Option<String>,
@@ -54,23 +68,75 @@ pub struct MultiChainBalancesOptions {
 impl MultiChainBalancesOptions {
     pub fn new() -> Self { Self::default() }
     pub fn chains(mut self, v: Vec<String>) -> Self { self.chains = Some(v); self }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn limit(mut self, v: u32) -> Self { self.limit = Some(v); self }
     pub fn cutoff_timestamp<S: Into<String>>(mut self, v: S) -> Self { self.cutoff_timestamp = Some(v.into()); self }
     pub fn before<S: Into<String>>(mut self, v: S) -> Self { self.before = Some(v.into()); self }
 }
 
-impl QueryParams for MultiChainBalancesOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.chains { builder = builder.query(&[("chains", v.join(","))]); }
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.limit { builder = builder.query(&[("limit", v.to_string())]); }
-        if let Some(v) = self.cutoff_timestamp { builder = builder.query(&[("cutoff-timestamp", v)]); }
-        if let Some(v) = self.before { builder = builder.query(&[("before", v)]); }
-        builder
+impl EndpointOptions for MultiChainBalancesOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.chains { pairs.push(("chains", v.join(","))); }
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.limit { pairs.push(("limit", v.to_string())); }
+        if let Some(v) = &self.cutoff_timestamp { pairs.push(("cutoff-timestamp", v.clone())); }
+        if let Some(v) = &self.before { pairs.push(("before", v.clone())); }
+        pairs
     }
 }
 
+/// Most chains the all-chains balances endpoint accepts in one call.
+pub const MAX_CHAINS_PER_CALL: usize = 10;
+
+/// Options for [`AllChainsService::get_activity_calendars`].
+#[derive(Debug, Clone, Default)]
+pub struct ActivityCalendarOptions {
+    pub granularity: CalendarGranularity,
+    /// Days covered per chain, ending on the day of its latest transaction. Defaults to 7.
+    pub days: Option<u32>,
+    pub include_testnets: Option<bool>,
+}
+
+impl ActivityCalendarOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn granularity(mut self, v: CalendarGranularity) -> Self { self.granularity = v; self }
+    pub fn days(mut self, v: u32) -> Self { self.days = Some(v); self }
+    pub fn include_testnets(mut self, v: bool) -> Self { self.include_testnets = Some(v); self }
+}
+
+/// Options for [`AllChainsService::trace_funding`].
+#[derive(Debug, Clone, Default)]
+pub struct FundingTraceOptions {
+    /// Funders to follow beyond the first; `0` only finds the wallet's own funder.
+    pub max_hops: u32,
+    /// Only trace activity on these chains; all chains by default.
+    pub chains: Option<Vec<String>>,
+    pub include_testnets: Option<bool>,
+}
+
+impl FundingTraceOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn max_hops(mut self, v: u32) -> Self { self.max_hops = v; self }
+    pub fn chains(mut self, v: Vec<String>) -> Self { self.chains = Some(v); self }
+    pub fn include_testnets(mut self, v: bool) -> Self { self.include_testnets = Some(v); self }
+}
+
+/// Options for [`AllChainsService::dust_report`].
+#[derive(Debug, Clone, Default)]
+pub struct DustReportOptions {
+    /// Chains to scan, as slugs or chain IDs; required.
+    pub chains: Option<Vec<String>>,
+    /// Balances worth less than this are dust. Defaults to 1.0.
+    pub threshold_quote: Option<f64>,
+}
+
+impl DustReportOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn chains(mut self, v: Vec<String>) -> Self { self.chains = Some(v); self }
+    pub fn threshold_quote(mut self, v: f64) -> Self { self.threshold_quote = Some(v); self }
+}
+
 /// Service for cross-chain API endpoints.
 pub struct AllChainsService {
     ctx: Arc<ServiceContext>,
@@ -81,15 +147,21 @@ impl AllChainsService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get address activity across all chains.
     pub async fn get_address_activity(
         &self,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<MultiChainBalancesOptions>,
     ) -> Result<AddressActivityResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/address/{}/activity/", address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -99,22 +171,108 @@ impl AllChainsService {
         options: Option<MultiChainTxOptions>,
     ) -> Result<MultiChainTransactionsResponse, Error> {
         let builder = self.ctx.get("/v1/allchains/transactions/");
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_multi_chain_transactions`](Self::get_multi_chain_transactions),
+    /// page by page, following `cursor_before` to older transactions.
+    pub fn get_multi_chain_transactions_paged(
+        &self,
+        options: Option<MultiChainTxOptions>,
+    ) -> Paginator<MultiChainTransactionItem> {
+        let service = AllChainsService { ctx: Arc::clone(&self.ctx) };
+        let options = options.unwrap_or_default();
+        Paginator::cursor(move |before: Option<String>| {
+            let service = AllChainsService { ctx: Arc::clone(&service.ctx) };
+            let mut options = options.clone();
+            if before.is_some() {
+                options.before = before;
+            }
+            async move {
+                let response = service.get_multi_chain_transactions(Some(options)).await?;
+                let (items, next) = match response.data {
+                    Some(data) => (data.items, data.cursor_before),
+                    None => (Vec::new(), None),
+                };
+                Ok(CursorPage { items, next })
+            }
+        })
+    }
+
     /// Get multi-chain balances for an address.
     pub async fn get_multi_chain_balances(
         &self,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<MultiChainBalancesOptions>,
     ) -> Result<MultiChainBalancesResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/allchains/address/{}/balances/", address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Multi-chain balances for any number of chains, reporting chains that failed.
+    ///
+    /// `options.chains` is required and may hold slugs or chain IDs. It is
+    /// checked against [`Chain`], de-duplicated and split into calls of at
+    /// most [`MAX_CHAINS_PER_CALL`] chains, which run concurrently. A failed
+    /// call lists its chains in [`PartitionedBalances::failed_chains`] instead
+    /// of failing the whole request.
+    pub async fn get_multi_chain_balances_partitioned(
+        &self,
+        address: impl Into<AddressParam>,
+        options: MultiChainBalancesOptions,
+    ) -> Result<PartitionedBalances, Error> {
+        let address: AddressParam = address.into();
+        let chains = normalize_chains(options.chains.as_deref().unwrap_or_default())?;
+        let groups: Vec<Vec<String>> = chains.chunks(MAX_CHAINS_PER_CALL).map(<[String]>::to_vec).collect();
+
+        let service = Arc::new(AllChainsService { ctx: Arc::clone(&self.ctx) });
+        let tasks: Vec<_> = groups.iter().map(|group| {
+            let service = Arc::clone(&service);
+            let address = address.clone();
+            let options = options.clone().chains(group.clone());
+            move || {
+                let service = Arc::clone(&service);
+                let address = address.clone();
+                let options = options.clone();
+                async move { service.get_multi_chain_balances(address, Some(options)).await }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let outcomes = executor.run(tasks).await.outcomes;
+        Ok(merge_partitions(groups.into_iter().zip(outcomes).collect()))
+    }
+
+    /// Stream multichain transactions newest first, following the page cursor
+    /// until the history is exhausted. See [`MultiChainTxStream`].
+    ///
+    /// `options.before` sets where to start; `options.after`, if set, bounds
+    /// every page.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use goldrush_sdk::{GoldRushClient, MultiChainTxOptions};
+    ///
+    /// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+    /// let options = MultiChainTxOptions::new()
+    ///     .chains(vec!["eth-mainnet".into(), "base-mainnet".into()])
+    ///     .addresses(vec!["0x...".into()]);
+    /// let mut txs = client.all_chains_service().transactions_stream(Some(options));
+    /// while let Some(tx) = txs.next().await {
+    ///     println!("{:?} {:?}", tx?.chain_name, txs.progress_for("eth-mainnet"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transactions_stream(&self, options: Option<MultiChainTxOptions>) -> MultiChainTxStream {
+        MultiChainTxStream::new(Arc::clone(&self.ctx), options.unwrap_or_default())
+    }
+
     /// Deprecated: alias for get_multi_chain_transactions.
     #[deprecated(note = "Use get_multi_chain_transactions instead")]
     pub async fn get_multi_chain_and_multi_address_transactions(
@@ -123,4 +281,281 @@ impl AllChainsService {
     ) -> Result<MultiChainTransactionsResponse, Error> {
         self.get_multi_chain_transactions(options).await
     }
+
+    /// Transaction calendars for every chain an address is active on.
+    ///
+    /// Chains come from [`get_address_activity`](Self::get_address_activity),
+    /// each chain's latest transaction from its transaction summary, and the
+    /// counts from one bulk time-bucket request per 15 minutes of the window,
+    /// so a 7-day window costs 672 requests per chain. Bucket requests run
+    /// concurrently; any failure fails the call.
+    pub async fn get_activity_calendars(
+        &self,
+        address: impl Into<AddressParam>,
+        options: ActivityCalendarOptions,
+    ) -> Result<Vec<ActivityCalendar>, Error> {
+        let address: AddressParam = address.into();
+        let include_testnets = options.include_testnets.unwrap_or(false);
+        let activity = self.get_address_activity(address.clone(), None).await?;
+        let chains: Vec<String> = activity.data.map(|data| data.items).unwrap_or_default()
+            .into_iter()
+            .filter(|item| include_testnets || item.is_testnet != Some(true))
+            .filter_map(|item| item.chain_name)
+            .collect();
+
+        let transactions = Arc::new(TransactionService::new(Arc::clone(&self.ctx)));
+        let mut calendars = Vec::with_capacity(chains.len());
+        for chain_name in chains {
+            let mut calendar = ActivityCalendar::new(chain_name.clone(), options.granularity);
+            let summary = transactions.get_transaction_summary(&chain_name, address.clone(), None).await?;
+            let item = summary.data.and_then(|data| data.items.into_iter().next());
+            let timestamp = |t: Option<TransactionTimestamp>| {
+                t.and_then(|t| t.block_signed_at).and_then(|at| activity::parse_unix(&at))
+            };
+            let (earliest, latest) = match item {
+                Some(item) => (timestamp(item.earliest_transaction), timestamp(item.latest_transaction)),
+                None => (None, None),
+            };
+            let Some(latest) = latest else {
+                calendars.push(calendar);
+                continue;
+            };
+
+            let last_day = CalendarDate::from_unix(latest);
+            let first_day = last_day.add_days(1 - options.days.unwrap_or(7).max(1) as i64);
+            let window_start = first_day.unix_start().unwrap_or(0).max(earliest.unwrap_or(0));
+            calendar.cover(first_day, last_day);
+
+            let tasks: Vec<_> = (window_start / TIME_BUCKET_SECONDS..=latest / TIME_BUCKET_SECONDS).map(|bucket| {
+                let transactions = Arc::clone(&transactions);
+                let chain_name = chain_name.clone();
+                let address = address.clone();
+                move || {
+                    let transactions = Arc::clone(&transactions);
+                    let chain_name = chain_name.clone();
+                    let address = address.clone();
+                    async move {
+                        let options = TimeBucketOptions::new().no_logs(true);
+                        let response = transactions
+                            .get_time_bucket_transactions(chain_name, address, bucket as u32, Some(options))
+                            .await?;
+                        Ok((bucket, response.data.map_or(0, |data| data.items.len() as u32)))
+                    }
+                }
+            }).collect();
+
+            // send_with_retry already retries each request.
+            let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+            for outcome in executor.run(tasks).await.outcomes {
+                let (bucket, count) = outcome?;
+                calendar.record_time_bucket(bucket, count);
+            }
+            calendars.push(calendar);
+        }
+        Ok(calendars)
+    }
+
+    /// Balances worth less than a threshold, with the gas cost of sweeping
+    /// each one. See [`DustReport`].
+    ///
+    /// Balances come from
+    /// [`get_multi_chain_balances_partitioned`](Self::get_multi_chain_balances_partitioned),
+    /// transfer costs from the ERC-20 and native-token gas prices of each chain
+    /// holding dust, fetched concurrently. A chain whose gas prices fail has
+    /// its tokens reported with an unknown cost.
+    pub async fn dust_report(
+        &self,
+        address: impl Into<AddressParam>,
+        options: DustReportOptions,
+    ) -> Result<DustReport, Error> {
+        let threshold_quote = options.threshold_quote.unwrap_or(1.0);
+        let mut balances_options = MultiChainBalancesOptions::new();
+        balances_options.chains = options.chains;
+        let balances = self.get_multi_chain_balances_partitioned(address, balances_options).await?;
+
+        let base = Arc::new(BaseService::new(Arc::clone(&self.ctx)));
+        let chains = DustReport::chains(&balances, threshold_quote);
+        let tasks: Vec<_> = chains.iter().flat_map(|chain_name| {
+            [GasEventType::Erc20, GasEventType::NativeTokens].map(|event_type| {
+                let base = Arc::clone(&base);
+                let chain_name = chain_name.clone();
+                move || {
+                    let base = Arc::clone(&base);
+                    let chain_name = chain_name.clone();
+                    let event_type = event_type.clone();
+                    async move { base.get_gas_prices(chain_name, event_type).await }
+                }
+            })
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let mut outcomes = executor.run(tasks).await.outcomes.into_iter();
+        let cheapest = |outcome: Option<Result<crate::models::base::GasPricesResponse, Error>>| {
+            outcome.and_then(Result::ok).and_then(|r| r.data).as_ref().and_then(TransferCost::cheapest)
+        };
+        let costs: HashMap<String, TransferCost> = chains
+            .into_iter()
+            .map(|chain_name| {
+                let erc20 = cheapest(outcomes.next());
+                let native = cheapest(outcomes.next());
+                (chain_name, TransferCost { erc20, native })
+            })
+            .collect();
+        Ok(DustReport::build(&balances, threshold_quote, &costs))
+    }
+
+    /// Trace where a wallet's first funds came from. See [`FundingTrace`].
+    ///
+    /// Each hop costs one address activity request, to find the chains the
+    /// wallet was active on, and one earliest-transactions request per chain,
+    /// in first-seen order, until one shows an incoming value transfer.
+    pub async fn trace_funding(
+        &self,
+        address: impl Into<AddressParam>,
+        options: FundingTraceOptions,
+    ) -> Result<FundingTrace, Error> {
+        let address: AddressParam = address.into();
+        let transactions = TransactionService::new(Arc::clone(&self.ctx));
+        let include_testnets = options.include_testnets.unwrap_or(false);
+        let mut trace = FundingTrace { address: address.to_string(), hops: Vec::new(), stop: FundingStop::MaxHops };
+        let mut seen = HashSet::from([address.to_string()]);
+        let mut wallet = address.to_string();
+
+        loop {
+            let activity = self.get_address_activity(wallet.as_str(), None).await?;
+            let chains = funding::first_seen_order(
+                activity.data.map(|data| data.items).unwrap_or_default(),
+                options.chains.as_deref(),
+                include_testnets,
+            );
+            let Some((first_seen_chain, first_seen_at)) = chains.first().cloned() else {
+                trace.stop = FundingStop::NoActivity;
+                return Ok(trace);
+            };
+
+            let mut funding = None;
+            for (chain_name, _) in &chains {
+                let earliest = transactions.get_earliest_transactions(chain_name, wallet.as_str(), None).await?;
+                let items = earliest.data.map(|data| data.items).unwrap_or_default();
+                funding = FundingTransaction::find(chain_name, &wallet, &items);
+                if funding.is_some() {
+                    break;
+                }
+            }
+
+            let funder = funding.as_ref().map(|f| f.funder.clone());
+            trace.hops.push(FundingHop {
+                address: wallet,
+                first_seen_chain: Some(first_seen_chain),
+                first_seen_at,
+                funding,
+            });
+            let Some(funder) = funder else {
+                trace.stop = FundingStop::FunderNotFound;
+                return Ok(trace);
+            };
+            if trace.hops.len() > options.max_hops as usize {
+                return Ok(trace);
+            }
+            if !seen.insert(funder.clone()) {
+                trace.stop = FundingStop::Cycle;
+                return Ok(trace);
+            }
+            wallet = funder;
+        }
+    }
+}
+
+/// Resolve slugs and chain IDs to slugs, dropping duplicates and keeping order.
+fn normalize_chains(chains: &[String]) -> Result<Vec<String>, Error> {
+    if chains.is_empty() {
+        return Err(Error::Config("at least one chain is required".to_string()));
+    }
+    let mut unknown = Vec::new();
+    let mut slugs: Vec<String> = Vec::with_capacity(chains.len());
+    for chain in chains {
+        let name = chain.trim();
+        let resolved = name.parse::<Chain>().ok().or_else(|| {
+            let id: u64 = name.parse().ok()?;
+            Chain::all().find(|c| c.chain_id() == id)
+        });
+        match resolved {
+            Some(chain) if !slugs.iter().any(|s| s == chain.slug()) => slugs.push(chain.slug().to_string()),
+            Some(_) => {}
+            None => unknown.push(name.to_string()),
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(Error::Config(format!("unknown chains: {}", unknown.join(", "))));
+    }
+    Ok(slugs)
+}
+
+/// Merge per-group responses, recording every chain of a failed group.
+fn merge_partitions(results: Vec<(Vec<String>, Result<MultiChainBalancesResponse, Error>)>) -> PartitionedBalances {
+    let mut merged = PartitionedBalances { address: None, updated_at: None, items: Vec::new(), failed_chains: Vec::new() };
+    for (group, result) in results {
+        match result {
+            Ok(response) => {
+                let Some(data) = response.data else { continue };
+                merged.address = merged.address.or(data.address);
+                merged.updated_at = match (merged.updated_at, data.updated_at) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                merged.items.extend(data.items);
+            }
+            Err(e) => {
+                let error = e.to_string();
+                merged.failed_chains.extend(group.into_iter().map(|chain_name| ChainFailure {
+                    chain_name,
+                    error: error.clone(),
+                }));
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_chains() {
+        let chains = vec!["eth-mainnet".to_string(), "8453".to_string(), " 1 ".to_string()];
+        assert_eq!(normalize_chains(&chains).unwrap(), vec!["eth-mainnet", "base-mainnet"]);
+
+        let err = normalize_chains(&["eth-mainnet".to_string(), "moon-mainnet".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("unknown chains: moon-mainnet"));
+        assert!(normalize_chains(&[]).is_err());
+    }
+
+    #[test]
+    fn test_merge_partitions_reports_failed_chains() {
+        let ok: MultiChainBalancesResponse = serde_json::from_value(serde_json::json!({
+            "data": {
+                "address": "0xabc",
+                "updated_at": "2024-06-02T00:00:00Z",
+                "items": [
+                    { "chain_name": "eth-mainnet", "quote": 10.0 },
+                    { "chain_name": "base-mainnet", "quote": 2.5 },
+                ],
+            },
+        }))
+        .unwrap();
+        let failed = Err(Error::api(400, "bad chain".to_string(), None));
+
+        let merged = merge_partitions(vec![
+            (vec!["eth-mainnet".to_string(), "base-mainnet".to_string()], Ok(ok)),
+            (vec!["bsc-mainnet".to_string(), "matic-mainnet".to_string()], failed),
+        ]);
+        assert!(!merged.is_complete());
+        assert_eq!(merged.address.as_deref(), Some("0xabc"));
+        assert_eq!(merged.total_quote(), 12.5);
+        let failed: Vec<&str> = merged.failed_chains.iter().map(|f| f.chain_name.as_str()).collect();
+        assert_eq!(failed, vec!["bsc-mainnet", "matic-mainnet"]);
+        assert!(merged.failed_chains[0].error.contains("bad chain"));
+    }
 }
@@ -1,13 +1,17 @@
+use crate::address::AddressParam;
 use crate::Error;
-use crate::http::query::QueryParams;
-use crate::models::balances::{BalancesResponse, Erc20TransfersResponse, TokenHoldersResponse, HistoricalBalancesResponse, NativeTokenBalanceResponse};
+use crate::types::QuoteCurrency;
+use crate::http::query::{impl_paged_options, EndpointOptions};
+use crate::models::balances::{BalanceItem, BalancesData, BalancesResponse, Erc20TransferItem, Erc20TransfersData, Erc20TransfersResponse, TokenHolderItem, TokenHoldersData, TokenHoldersResponse, HistoricalBalancesResponse, NativeTokenBalanceResponse, PortfolioResponse};
+use crate::pagination::Paginator;
+use crate::http::request::RequestOptions;
 use crate::services::ServiceContext;
 use std::sync::Arc;
 
 /// Options for balance queries.
 #[derive(Debug, Clone, Default)]
 pub struct BalancesOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub nft: Option<bool>,
     pub no_spam: Option<bool>,
     pub no_nft_fetch: Option<bool>,
@@ -18,7 +22,7 @@ pub struct BalancesOptions {
 
 impl BalancesOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn nft(mut self, v: bool) -> Self { self.nft = Some(v); self }
     pub fn no_spam(mut self, v: bool) -> Self { self.no_spam = Some(v); self }
     pub fn no_nft_fetch(mut self, v: bool) -> Self { self.no_nft_fetch = Some(v); self }
@@ -27,23 +31,24 @@ impl BalancesOptions {
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
 }
 
-impl QueryParams for BalancesOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.nft { builder = builder.query(&[("nft", v.to_string())]); }
-        if let Some(v) = self.no_spam { builder = builder.query(&[("no-spam", v.to_string())]); }
-        if let Some(v) = self.no_nft_fetch { builder = builder.query(&[("no-nft-fetch", v.to_string())]); }
-        if let Some(v) = self.no_nft_asset_metadata { builder = builder.query(&[("no-nft-asset-metadata", v.to_string())]); }
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        builder
+impl EndpointOptions for BalancesOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.nft { pairs.push(("nft", v.to_string())); }
+        if let Some(v) = &self.no_spam { pairs.push(("no-spam", v.to_string())); }
+        if let Some(v) = &self.no_nft_fetch { pairs.push(("no-nft-fetch", v.to_string())); }
+        if let Some(v) = &self.no_nft_asset_metadata { pairs.push(("no-nft-asset-metadata", v.to_string())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for portfolio queries.
 #[derive(Debug, Clone, Default)]
 pub struct PortfolioOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub days: Option<u32>,
     pub page_number: Option<u32>,
     pub page_size: Option<u32>,
@@ -51,26 +56,27 @@ pub struct PortfolioOptions {
 
 impl PortfolioOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn days(mut self, v: u32) -> Self { self.days = Some(v); self }
     pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
 }
 
-impl QueryParams for PortfolioOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.days { builder = builder.query(&[("days", v.to_string())]); }
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        builder
+impl EndpointOptions for PortfolioOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.days { pairs.push(("days", v.to_string())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for ERC20 transfer queries.
 #[derive(Debug, Clone, Default)]
 pub struct Erc20TransfersOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub contract_address: Option<String>,
     pub starting_block: Option<u64>,
     pub ending_block: Option<u64>,
@@ -80,7 +86,7 @@ pub struct Erc20TransfersOptions {
 
 impl Erc20TransfersOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn contract_address<S: Into<String>>(mut self, c: S) -> Self { self.contract_address = Some(c.into()); self }
     pub fn starting_block(mut self, v: u64) -> Self { self.starting_block = Some(v); self }
     pub fn ending_block(mut self, v: u64) -> Self { self.ending_block = Some(v); self }
@@ -88,22 +94,23 @@ impl Erc20TransfersOptions {
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
 }
 
-impl QueryParams for Erc20TransfersOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.contract_address { builder = builder.query(&[("contract-address", v)]); }
-        if let Some(v) = self.starting_block { builder = builder.query(&[("starting-block", v.to_string())]); }
-        if let Some(v) = self.ending_block { builder = builder.query(&[("ending-block", v.to_string())]); }
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        builder
+impl EndpointOptions for Erc20TransfersOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.contract_address { pairs.push(("contract-address", v.clone())); }
+        if let Some(v) = &self.starting_block { pairs.push(("starting-block", v.to_string())); }
+        if let Some(v) = &self.ending_block { pairs.push(("ending-block", v.to_string())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for token holder queries.
 #[derive(Debug, Clone, Default)]
 pub struct TokenHoldersOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub page_number: Option<u32>,
     pub page_size: Option<u32>,
     pub block_height: Option<u64>,
@@ -111,67 +118,72 @@ pub struct TokenHoldersOptions {
 
 impl TokenHoldersOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
     pub fn block_height(mut self, v: u64) -> Self { self.block_height = Some(v); self }
 }
 
-impl QueryParams for TokenHoldersOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        if let Some(v) = self.block_height { builder = builder.query(&[("block-height", v.to_string())]); }
-        builder
+impl EndpointOptions for TokenHoldersOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        if let Some(v) = &self.block_height { pairs.push(("block-height", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for historical balance queries.
 #[derive(Debug, Clone, Default)]
 pub struct HistoricalBalancesOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub date: Option<String>,
     pub block_height: Option<u64>,
 }
 
 impl HistoricalBalancesOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn date<S: Into<String>>(mut self, d: S) -> Self { self.date = Some(d.into()); self }
     pub fn block_height(mut self, v: u64) -> Self { self.block_height = Some(v); self }
 }
 
-impl QueryParams for HistoricalBalancesOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.date { builder = builder.query(&[("date", v)]); }
-        if let Some(v) = self.block_height { builder = builder.query(&[("block-height", v.to_string())]); }
-        builder
+impl EndpointOptions for HistoricalBalancesOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.date { pairs.push(("date", v.clone())); }
+        if let Some(v) = &self.block_height { pairs.push(("block-height", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for native token balance queries.
 #[derive(Debug, Clone, Default)]
 pub struct NativeBalanceOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub block_height: Option<u64>,
 }
 
 impl NativeBalanceOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn block_height(mut self, v: u64) -> Self { self.block_height = Some(v); self }
 }
 
-impl QueryParams for NativeBalanceOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.block_height { builder = builder.query(&[("block-height", v.to_string())]); }
-        builder
+impl EndpointOptions for NativeBalanceOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.block_height { pairs.push(("block-height", v.to_string())); }
+        pairs
     }
 }
 
+impl_paged_options!(BalancesOptions, PortfolioOptions, Erc20TransfersOptions, TokenHoldersOptions);
+
 /// Service for balance-related API endpoints.
 pub struct BalanceService {
     ctx: Arc<ServiceContext>,
@@ -182,35 +194,48 @@ impl BalanceService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get token balances for a wallet address.
     pub async fn get_token_balances_for_wallet_address(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<BalancesOptions>,
     ) -> Result<BalancesResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/balances_v2/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options {
-            Some(opts) => opts.apply_to(builder),
-            None => builder,
-        };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_token_balances_for_wallet_address`](Self::get_token_balances_for_wallet_address), page by page.
+    pub fn get_token_balances_for_wallet_address_paged(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<BalancesOptions>,
+    ) -> Paginator<BalanceItem> {
+        let address: AddressParam = address.into();
+        let path = format!("/v1/{}/address/{}/balances_v2/", chain_name.as_ref(), address);
+        self.ctx.paginate(path, options, |data: BalancesData| data.items)
+    }
+
     /// Get historical portfolio balances for an address.
     pub async fn get_historical_portfolio_for_wallet_address(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<PortfolioOptions>,
-    ) -> Result<BalancesResponse, Error> {
+    ) -> Result<PortfolioResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/portfolio_v2/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options {
-            Some(opts) => opts.apply_to(builder),
-            None => builder,
-        };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -218,47 +243,65 @@ impl BalanceService {
     pub async fn get_erc20_transfers_for_wallet_address(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<Erc20TransfersOptions>,
     ) -> Result<Erc20TransfersResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/transfers_v2/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options {
-            Some(opts) => opts.apply_to(builder),
-            None => builder,
-        };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_erc20_transfers_for_wallet_address`](Self::get_erc20_transfers_for_wallet_address), page by page.
+    pub fn get_erc20_transfers_for_wallet_address_paged(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<Erc20TransfersOptions>,
+    ) -> Paginator<Erc20TransferItem> {
+        let address: AddressParam = address.into();
+        let path = format!("/v1/{}/address/{}/transfers_v2/", chain_name.as_ref(), address);
+        self.ctx.paginate(path, options, |data: Erc20TransfersData| data.items)
+    }
+
     /// Get token holders for a token address (v2).
     pub async fn get_token_holders_v2_for_token_address(
         &self,
         chain_name: impl AsRef<str>,
-        token_address: &str,
+        token_address: impl Into<AddressParam>,
         options: Option<TokenHoldersOptions>,
     ) -> Result<TokenHoldersResponse, Error> {
+        let token_address: AddressParam = token_address.into();
         let path = format!("/v1/{}/tokens/{}/token_holders_v2/", chain_name.as_ref(), token_address);
         let builder = self.ctx.get(&path);
-        let builder = match options {
-            Some(opts) => opts.apply_to(builder),
-            None => builder,
-        };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_token_holders_v2_for_token_address`](Self::get_token_holders_v2_for_token_address), page by page.
+    pub fn get_token_holders_v2_for_token_address_paged(
+        &self,
+        chain_name: impl AsRef<str>,
+        token_address: impl Into<AddressParam>,
+        options: Option<TokenHoldersOptions>,
+    ) -> Paginator<TokenHolderItem> {
+        let token_address: AddressParam = token_address.into();
+        let path = format!("/v1/{}/tokens/{}/token_holders_v2/", chain_name.as_ref(), token_address);
+        self.ctx.paginate(path, options, |data: TokenHoldersData| data.items)
+    }
+
     /// Get historical token balances for an address.
     pub async fn get_historical_token_balances(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<HistoricalBalancesOptions>,
     ) -> Result<HistoricalBalancesResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/historical_balances/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options {
-            Some(opts) => opts.apply_to(builder),
-            None => builder,
-        };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -266,15 +309,13 @@ impl BalanceService {
     pub async fn get_native_token_balance(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<NativeBalanceOptions>,
     ) -> Result<NativeTokenBalanceResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/balances_native/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options {
-            Some(opts) => opts.apply_to(builder),
-            None => builder,
-        };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 }
@@ -1,8 +1,37 @@
-use crate::Error;
-use crate::http::query::QueryParams;
+use crate::address::AddressParam;
+use crate::block_stream::{BlockStream, Poller};
+use crate::bulk::{BulkConfig, BulkExecutor};
+use crate::checkpoint::{CheckpointStore, CheckpointedStream};
+use crate::clock::Clock;
+use crate::endpoint::Endpoint;
+use crate::{CacheConfig, Error, MemoryCache};
+use crate::http::query::{impl_paged_options, EndpointOptions, PagedOptions};
 use crate::models::base::*;
+use crate::http::request::RequestOptions;
+use crate::pagination::{Page, PageStream, Paginator};
 use crate::services::ServiceContext;
+use crate::types::GasEventType;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Options for [`BaseService::block_stream`].
+#[derive(Debug, Clone, Default)]
+pub struct BlockStreamOptions {
+    /// Time between polls once caught up. Defaults to 12 seconds.
+    pub poll_interval: Option<Duration>,
+    /// Only yield blocks this far below the latest block. Defaults to 0.
+    pub confirmations: Option<u64>,
+    /// First block to yield; defaults to the latest block when the stream starts.
+    pub from_block: Option<u64>,
+}
+
+impl BlockStreamOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn poll_interval(mut self, v: Duration) -> Self { self.poll_interval = Some(v); self }
+    pub fn confirmations(mut self, v: u64) -> Self { self.confirmations = Some(v); self }
+    pub fn from_block(mut self, v: u64) -> Self { self.from_block = Some(v); self }
+}
 
 /// Options for block height queries.
 #[derive(Debug, Clone, Default)]
@@ -17,11 +46,12 @@ impl BlockHeightsOptions {
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
 }
 
-impl QueryParams for BlockHeightsOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        builder
+impl EndpointOptions for BlockHeightsOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
     }
 }
 
@@ -42,13 +72,14 @@ impl LogEventsByAddressOptions {
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
 }
 
-impl QueryParams for LogEventsByAddressOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.starting_block { builder = builder.query(&[("starting-block", v.to_string())]); }
-        if let Some(v) = self.ending_block { builder = builder.query(&[("ending-block", v.to_string())]); }
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        builder
+impl EndpointOptions for LogEventsByAddressOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.starting_block { pairs.push(("starting-block", v.to_string())); }
+        if let Some(v) = &self.ending_block { pairs.push(("ending-block", v.to_string())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
     }
 }
 
@@ -71,14 +102,43 @@ impl LogEventsByTopicOptions {
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
 }
 
-impl QueryParams for LogEventsByTopicOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.starting_block { builder = builder.query(&[("starting-block", v.to_string())]); }
-        if let Some(v) = self.ending_block { builder = builder.query(&[("ending-block", v.to_string())]); }
-        if let Some(v) = self.secondary_topics { builder = builder.query(&[("secondary-topics", v)]); }
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        builder
+impl EndpointOptions for LogEventsByTopicOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.starting_block { pairs.push(("starting-block", v.to_string())); }
+        if let Some(v) = &self.ending_block { pairs.push(("ending-block", v.to_string())); }
+        if let Some(v) = &self.secondary_topics { pairs.push(("secondary-topics", v.clone())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
+    }
+}
+
+impl_paged_options!(BlockHeightsOptions, LogEventsByAddressOptions, LogEventsByTopicOptions);
+
+/// Short-TTL cache for chain status and latest-block lookups, which health
+/// checks and polling loops hit far more often than the data changes.
+///
+/// Reverse name resolutions are cached here too, with the regular data TTL,
+/// so feeds that label many rows do not look up the same address twice.
+pub(crate) struct StatusCache {
+    pub(crate) chain_status: MemoryCache<AllChainStatusResponse>,
+    pub(crate) latest_blocks: MemoryCache<BlockResponse>,
+    pub(crate) enabled: bool,
+    pub(crate) names: MemoryCache<Option<String>>,
+    pub(crate) names_enabled: bool,
+}
+
+impl StatusCache {
+    pub(crate) fn new(config: &CacheConfig, clock: &Arc<dyn Clock>) -> Self {
+        Self {
+            chain_status: MemoryCache::new(config.status_ttl, 1).with_clock(Arc::clone(clock)),
+            latest_blocks: MemoryCache::new(config.status_ttl, 256).with_clock(Arc::clone(clock)),
+            enabled: config.enabled && config.status_ttl > Duration::ZERO,
+            names: MemoryCache::new(config.ttl_for(Endpoint::ResolveAddress), config.max_entries)
+                .with_clock(Arc::clone(clock)),
+            names_enabled: config.enabled,
+        }
     }
 }
 
@@ -92,75 +152,245 @@ impl BaseService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get block data by block height.
+    ///
+    /// `"latest"` lookups are served from a short-TTL cache (see `CacheConfig::status_ttl`).
     pub async fn get_block(
         &self, chain_name: impl AsRef<str>, block_height: &str,
     ) -> Result<BlockResponse, Error> {
-        let path = format!("/v1/{}/block_v2/{}/", chain_name.as_ref(), block_height);
-        self.ctx.send_with_retry(self.ctx.get(&path)).await
+        let chain_name = chain_name.as_ref();
+        let path = format!("/v1/{}/block_v2/{}/", chain_name, block_height);
+        if !block_height.eq_ignore_ascii_case("latest") {
+            return self.ctx.send_with_retry(self.ctx.get(&path)).await;
+        }
+
+        let cache = &self.ctx.status_cache;
+        let fetch = self.ctx.send_with_retry(self.ctx.get(&path));
+        self.ctx.cached(&cache.latest_blocks, cache.enabled, chain_name, fetch).await
+    }
+
+    /// Poll `chain_name` for new blocks and yield each one once, in height
+    /// order. See [`BlockStream`].
+    pub fn block_stream(&self, chain_name: impl AsRef<str>, options: Option<BlockStreamOptions>) -> BlockStream {
+        let options = options.unwrap_or_default();
+        let service = BaseService { ctx: Arc::clone(&self.ctx) };
+        BlockStream::new(Poller::new(
+            service,
+            chain_name.as_ref().to_string(),
+            options.poll_interval.unwrap_or(Duration::from_secs(12)),
+            options.confirmations.unwrap_or(0),
+            options.from_block,
+            Arc::clone(&self.ctx.config.clock),
+        ))
     }
 
     /// Resolve an address to an ENS or other domain name.
     pub async fn get_resolved_address(
-        &self, chain_name: impl AsRef<str>, address: &str,
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>,
     ) -> Result<ResolvedAddressResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/resolve_address/", chain_name.as_ref(), address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
 
+    /// The primary name of an address, or `None` if it has none.
+    ///
+    /// Results, including misses, are cached per chain and address.
+    pub async fn reverse_resolve(
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>,
+    ) -> Result<Option<String>, Error> {
+        let chain_name = chain_name.as_ref();
+        let address: AddressParam = address.into();
+        let key = Endpoint::ResolveAddress.cache_key(&[chain_name, &address.to_string().to_ascii_lowercase()]);
+        let cache = &self.ctx.status_cache;
+        let fetch = async {
+            match self.get_resolved_address(chain_name, address.clone()).await {
+                Ok(response) => Ok(response.data.and_then(|data| data.name).filter(|name| !name.is_empty())),
+                Err(Error::Api { status: 404, .. }) => Ok(None),
+                Err(e) => Err(e),
+            }
+        };
+        self.ctx.cached(&cache.names, cache.names_enabled, &key, fetch).await
+    }
+
+    /// Primary names for many addresses, looked up concurrently.
+    ///
+    /// Returns only addresses that have a name, keyed by lowercase address.
+    /// Repeated and cached addresses cost no requests; any failed lookup
+    /// fails the call.
+    pub async fn resolve_names<A: AsRef<str>>(
+        &self, chain_name: impl AsRef<str>, addresses: &[A],
+    ) -> Result<HashMap<String, String>, Error> {
+        let chain_name = chain_name.as_ref().to_string();
+        let mut unique: Vec<String> = addresses.iter().map(|a| a.as_ref().to_ascii_lowercase()).collect();
+        unique.sort();
+        unique.dedup();
+
+        let service = Arc::new(BaseService { ctx: Arc::clone(&self.ctx) });
+        let tasks: Vec<_> = unique.iter().cloned().map(|address| {
+            let service = Arc::clone(&service);
+            let chain_name = chain_name.clone();
+            move || {
+                let service = Arc::clone(&service);
+                let chain_name = chain_name.clone();
+                let address = address.clone();
+                async move { service.reverse_resolve(chain_name, address.as_str()).await }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let mut names = HashMap::new();
+        for (address, outcome) in unique.into_iter().zip(executor.run(tasks).await.outcomes) {
+            if let Some(name) = outcome? {
+                names.insert(address, name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Get name, symbol, decimals, logo and total supply of a token contract.
+    pub async fn get_token_metadata(
+        &self, chain_name: impl AsRef<str>, contract: impl Into<AddressParam>,
+    ) -> Result<TokenMetadataResponse, Error> {
+        let contract: AddressParam = contract.into();
+        let path = format!("/v1/{}/tokens/{}/contract_metadata/", chain_name.as_ref(), contract);
+        self.ctx.send_with_retry(self.ctx.get(&path)).await
+    }
+
     /// Get block heights between two dates.
     pub async fn get_block_heights(
         &self, chain_name: impl AsRef<str>, start_date: &str, end_date: &str, options: Option<BlockHeightsOptions>,
     ) -> Result<BlockHeightsResponse, Error> {
         let path = format!("/v1/{}/block_v2/{}/{}/", chain_name.as_ref(), start_date, end_date);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_block_heights`](Self::get_block_heights), page by page.
+    pub fn get_block_heights_paged(
+        &self, chain_name: impl AsRef<str>, start_date: &str, end_date: &str, options: Option<BlockHeightsOptions>,
+    ) -> Paginator<BlockHeightItem> {
+        let path = format!("/v1/{}/block_v2/{}/{}/", chain_name.as_ref(), start_date, end_date);
+        self.ctx.paginate(path, options, |data: BlockHeightsData| data.items)
+    }
+
     /// Get log events by contract address.
     pub async fn get_log_events_by_address(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, options: Option<LogEventsByAddressOptions>,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, options: Option<LogEventsByAddressOptions>,
     ) -> Result<LogsResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/events/address/{}/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_log_events_by_address`](Self::get_log_events_by_address), page by page.
+    pub fn get_log_events_by_address_paged(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, options: Option<LogEventsByAddressOptions>,
+    ) -> Paginator<LogEventItem> {
+        let contract_address: AddressParam = contract_address.into();
+        let path = format!("/v1/{}/events/address/{}/", chain_name.as_ref(), contract_address);
+        self.ctx.paginate(path, options, |data: LogsData| data.items)
+    }
+
+    /// Stream every log event emitted by a contract, fetching pages on demand.
+    ///
+    /// Paging starts at `options.page_number`, or the first page if unset.
+    pub fn log_events_stream(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, options: Option<LogEventsByAddressOptions>,
+    ) -> PageStream<LogEventItem> {
+        let ctx = Arc::clone(&self.ctx);
+        let chain_name = chain_name.as_ref().to_string();
+        let contract_address: AddressParam = contract_address.into();
+        let options = options.unwrap_or_default();
+        let start_page = options.page_number.unwrap_or(0);
+
+        PageStream::new(start_page, move |page| {
+            let service = BaseService { ctx: Arc::clone(&ctx) };
+            let chain_name = chain_name.clone();
+            let contract_address = contract_address.clone();
+            let options = options.at_page(page);
+            async move {
+                let response = service.get_log_events_by_address(chain_name, contract_address, Some(options)).await?;
+                Ok(Page::from_response(response, |data| data.items))
+            }
+        })
+    }
+
+    /// Stream a contract's log events after the block checkpointed in `store`.
+    ///
+    /// Starts from `options.starting_block` when nothing is checkpointed yet,
+    /// and advances the checkpoint as events are consumed.
+    pub fn resume_log_events_stream(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
+        options: Option<LogEventsByAddressOptions>, store: Arc<dyn CheckpointStore>,
+    ) -> Result<CheckpointedStream<PageStream<LogEventItem>>, Error> {
+        let chain_name = chain_name.as_ref();
+        let contract_address: AddressParam = contract_address.into();
+        let key = contract_address.to_string();
+        let mut options = options.unwrap_or_default();
+        if let Some(last) = store.last_block(chain_name, &key)? {
+            options = options.starting_block(last + 1);
+        }
+        let stream = self.log_events_stream(chain_name, contract_address, Some(options));
+        Ok(CheckpointedStream::new(stream, store, chain_name, vec![key]))
+    }
+
     /// Get log events by topic hash.
     pub async fn get_log_events_by_topic_hash(
         &self, chain_name: impl AsRef<str>, topic: &str, options: Option<LogEventsByTopicOptions>,
     ) -> Result<LogsResponse, Error> {
         let path = format!("/v1/{}/events/topics/{}/", chain_name.as_ref(), topic);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_log_events_by_topic_hash`](Self::get_log_events_by_topic_hash), page by page.
+    pub fn get_log_events_by_topic_hash_paged(
+        &self, chain_name: impl AsRef<str>, topic: &str, options: Option<LogEventsByTopicOptions>,
+    ) -> Paginator<LogEventItem> {
+        let path = format!("/v1/{}/events/topics/{}/", chain_name.as_ref(), topic);
+        self.ctx.paginate(path, options, |data: LogsData| data.items)
+    }
+
     /// Get all supported chains.
     pub async fn get_all_chains(&self) -> Result<AllChainsResponse, Error> {
         self.ctx.send_with_retry(self.ctx.get("/v1/chains/")).await
     }
 
     /// Get status of all supported chains.
+    ///
+    /// Served from a short-TTL cache (see `CacheConfig::status_ttl`) according
+    /// to the configured [`CachePolicy`](crate::CachePolicy).
     pub async fn get_all_chain_status(&self) -> Result<AllChainStatusResponse, Error> {
-        self.ctx.send_with_retry(self.ctx.get("/v1/chains/status/")).await
+        let cache = &self.ctx.status_cache;
+        let fetch = self.ctx.send_with_retry(self.ctx.get("/v1/chains/status/"));
+        self.ctx.cached(&cache.chain_status, cache.enabled, "all", fetch).await
     }
 
     /// Get gas prices for a specific event type.
     pub async fn get_gas_prices(
-        &self, chain_name: impl AsRef<str>, event_type: impl AsRef<str>,
+        &self, chain_name: impl AsRef<str>, event_type: GasEventType,
     ) -> Result<GasPricesResponse, Error> {
-        let path = format!("/v1/{}/event/{}/gas_prices/", chain_name.as_ref(), event_type.as_ref());
+        let path = format!("/v1/{}/event/{}/gas_prices/", chain_name.as_ref(), event_type);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
 
     /// Get address activity across chains.
     #[deprecated(note = "Use AllChainsService::get_address_activity instead")]
     pub async fn get_address_activity(
-        &self, address: &str,
+        &self, address: impl Into<AddressParam>,
     ) -> Result<AddressActivityResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/address/{}/activity/", address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
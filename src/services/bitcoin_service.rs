@@ -1,6 +1,9 @@
+use crate::address::{AddressParam, BtcAddress};
 use crate::Error;
 use crate::models::bitcoin::*;
 use crate::models::balances::BalancesResponse;
+use crate::pagination::{CursorPage, Paginator};
+use crate::http::request::RequestOptions;
 use crate::services::ServiceContext;
 use std::sync::Arc;
 
@@ -14,11 +17,17 @@ impl BitcoinService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get Bitcoin HD wallet balances.
     pub async fn get_bitcoin_hd_wallet_balances(
         &self,
-        address: &str,
+        address: impl Into<AddressParam<BtcAddress>>,
     ) -> Result<BtcHdWalletResponse, Error> {
+        let address: AddressParam<BtcAddress> = address.into();
         let path = format!("/v1/btc-mainnet/address/{}/hd_wallets/", address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
@@ -26,17 +35,38 @@ impl BitcoinService {
     /// Get transactions for a Bitcoin address.
     pub async fn get_transactions_for_btc_address(
         &self,
-        address: &str,
+        address: impl Into<AddressParam<BtcAddress>>,
     ) -> Result<BtcTransactionsResponse, Error> {
+        let address: AddressParam<BtcAddress> = address.into();
         let path = format!("/v1/btc-mainnet/address/{}/transactions_v3/", address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
 
+    /// [`get_transactions_for_btc_address`](Self::get_transactions_for_btc_address),
+    /// page by page, following the `links.next` cursor.
+    pub fn get_transactions_for_btc_address_paged(
+        &self,
+        address: impl Into<AddressParam<BtcAddress>>,
+    ) -> Paginator<BtcTransactionItem> {
+        let ctx = Arc::clone(&self.ctx);
+        let address: AddressParam<BtcAddress> = address.into();
+        let first = format!("/v1/btc-mainnet/address/{}/transactions_v3/", address);
+        Paginator::cursor(move |next: Option<String>| {
+            let ctx = Arc::clone(&ctx);
+            let path = next.unwrap_or_else(|| first.clone());
+            async move {
+                let response: BtcTransactionsResponse = ctx.send_with_retry(ctx.get(&path)).await?;
+                Ok(CursorPage::from_response(response, |data| data.items))
+            }
+        })
+    }
+
     /// Get Bitcoin non-HD wallet balances.
     pub async fn get_bitcoin_non_hd_wallet_balances(
         &self,
-        address: &str,
+        address: impl Into<AddressParam<BtcAddress>>,
     ) -> Result<BalancesResponse, Error> {
+        let address: AddressParam<BtcAddress> = address.into();
         let path = format!("/v1/btc-mainnet/address/{}/balances_v2/", address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
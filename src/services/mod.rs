@@ -10,15 +10,30 @@ pub mod pricing_service;
 pub mod security_service;
 pub mod bitcoin_service;
 pub mod all_chains_service;
+pub mod solana_service;
 
 #[cfg(feature = "streaming")]
 pub mod streaming_service;
 
-use crate::{ClientConfig, Error, MetricsCollector};
+use crate::http::query::{canonical_request_key, EndpointOptions, PagedOptions, QueryParams};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::screening::{RiskFlag, ScreeningDecision, ScreeningRequest};
+use crate::{ApiErrorCode, CachePolicy, ClientConfig, Endpoint, EndpointCircuitBreakers, Error, MemoryCache, MetricsCollector, SecurityManager};
+use crate::http::request::RequestOptions;
+use crate::credits::CreditEstimator;
+use crate::lifecycle::Lifecycle;
+use crate::rate_limit::RequestLimiter;
+use crate::audit::AuditEntry;
+use crate::models::{server_request_id, ApiResponse, ResponseMeta, WithResponseMeta};
+use crate::pagination::{CursorPage, Page, Paginator};
+use reqwest::header::HeaderMap;
 use reqwest::{Client as HttpClient, Method, RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
+use tokio_util::sync::CancellationToken;
 
 /// Shared context for all service implementations.
 pub(crate) struct ServiceContext {
@@ -26,6 +41,22 @@ pub(crate) struct ServiceContext {
     pub api_key: String,
     pub config: ClientConfig,
     pub metrics: Option<Arc<MetricsCollector>>,
+    /// Custom headers applied last in `build_request`, overriding defaults.
+    pub headers: HeaderMap,
+    pub security: Arc<SecurityManager>,
+    /// Short-TTL cache shared by all `BaseService` handles.
+    pub status_cache: Arc<base_service::StatusCache>,
+    /// Set through [`RequestOptions::cancellation`].
+    pub cancellation: Option<CancellationToken>,
+    pub lifecycle: Arc<Lifecycle>,
+    /// Estimated credits charged by this client's calls.
+    pub credits: Arc<CreditEstimator>,
+    /// Set once the warm-up keepalive task has been started.
+    pub keepalive: Arc<AtomicBool>,
+    /// Paces every request, shared with contexts derived from this one.
+    pub rate_limiter: Arc<RequestLimiter>,
+    /// Set when [`ClientConfig::circuit_breaker`] is.
+    pub circuit_breakers: Option<Arc<EndpointCircuitBreakers>>,
 }
 
 impl ServiceContext {
@@ -40,8 +71,42 @@ impl ServiceContext {
         self.http
             .request(method, &url)
             .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("User-Agent", self.config.full_user_agent())
             .header("Accept", "application/json")
             .header("Content-Type", "application/json")
+            .headers(self.headers.clone())
+    }
+
+    /// Derive a context whose requests also carry the given per-request options.
+    pub fn with_request_options(&self, options: &RequestOptions) -> Result<Self, Error> {
+        let mut headers = self.headers.clone();
+        for (name, value) in options.header_map()? {
+            if let Some(name) = name {
+                headers.insert(name, value);
+            }
+        }
+        let mut config = self.config.clone();
+        if let Some(version) = options.api_version {
+            config.api_version = version;
+        }
+        if let Some(policy) = options.cache_policy {
+            config.cache_policy = policy;
+        }
+        Ok(Self {
+            http: self.http.clone(),
+            api_key: self.api_key.clone(),
+            config,
+            metrics: self.metrics.clone(),
+            headers,
+            security: Arc::clone(&self.security),
+            status_cache: Arc::clone(&self.status_cache),
+            cancellation: options.cancellation.clone().or_else(|| self.cancellation.clone()),
+            lifecycle: Arc::clone(&self.lifecycle),
+            credits: Arc::clone(&self.credits),
+            keepalive: Arc::clone(&self.keepalive),
+            rate_limiter: Arc::clone(&self.rate_limiter),
+            circuit_breakers: self.circuit_breakers.clone(),
+        })
     }
 
     /// Build a GET request with the given path.
@@ -49,12 +114,246 @@ impl ServiceContext {
         self.build_request(Method::GET, path)
     }
 
+    /// Build a POST request with the given path.
+    #[allow(dead_code)]
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.build_request(Method::POST, path)
+    }
+
+    /// Send a JSON body to `path` through the retry pipeline.
+    ///
+    /// The serialized body is checked against the configured maximum request
+    /// size before anything is sent. Only use this for idempotent endpoints,
+    /// since failed requests are retried.
+    #[allow(dead_code)]
+    pub async fn post_json<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+    where
+        T: DeserializeOwned + WithResponseMeta,
+        B: Serialize + ?Sized,
+    {
+        let bytes = serde_json::to_vec(body)?;
+        self.security.validate_request_size(bytes.len())?;
+        self.send_with_retry(self.post(path).body(bytes)).await
+    }
+
+    /// Serve `key` from `cache` according to the configured [`CachePolicy`],
+    /// running `fetch` and caching its result when the cache can't answer.
+    ///
+    /// When `enabled` is false the cache is neither read nor written.
+    pub async fn cached<T, F>(&self, cache: &MemoryCache<T>, enabled: bool, key: &str, fetch: F) -> Result<T, Error>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: std::future::Future<Output = Result<T, Error>>,
+    {
+        let policy = self.config.cache_policy;
+        if !enabled {
+            return match policy {
+                CachePolicy::CacheOnly => Err(Error::Config(format!("Cache-only request for '{}' but caching is disabled", key))),
+                _ => fetch.await,
+            };
+        }
+
+        let hit = match policy {
+            CachePolicy::Standard | CachePolicy::CacheOnly => cache.get(key).await,
+            CachePolicy::RefreshIfOlderThan(max_age) => cache
+                .get_with_age(key)
+                .await
+                .and_then(|(value, age)| (age <= max_age).then_some(value)),
+            CachePolicy::Bypass => None,
+        };
+        if let Some(metrics) = &self.metrics {
+            match hit {
+                Some(_) => metrics.record_cache_hit(),
+                None => metrics.record_cache_miss(),
+            }
+        }
+        if let Some(value) = hit {
+            return Ok(value);
+        }
+        if policy == CachePolicy::CacheOnly {
+            return Err(Error::Config(format!("Cache-only request for '{}' missed the cache", key)));
+        }
+
+        let value = fetch.await?;
+        cache.set(key.to_string(), value.clone()).await;
+        Ok(value)
+    }
+
+    /// Validate endpoint options and apply them as query parameters.
+    pub fn apply_options<O: EndpointOptions>(
+        &self,
+        builder: RequestBuilder,
+        options: Option<O>,
+    ) -> Result<RequestBuilder, Error> {
+        match options {
+            Some(o) => {
+                o.validate()?;
+                Ok(o.apply_to(builder))
+            }
+            None => Ok(builder),
+        }
+    }
+
+    /// A [`Paginator`] over the page-numbered endpoint at `path`, starting at
+    /// the page number in `options`, if any, and sending `options` with every page.
+    pub(crate) fn paginate<O, D, T>(self: &Arc<Self>, path: String, options: Option<O>, items: fn(D) -> Vec<T>) -> Paginator<T>
+    where
+        O: PagedOptions + Default + Send + 'static,
+        D: DeserializeOwned + Send + 'static,
+        T: Send + 'static,
+    {
+        let ctx = Arc::clone(self);
+        let options = options.unwrap_or_default();
+        Paginator::numbered(options.current_page().unwrap_or(0), move |page| {
+            let ctx = Arc::clone(&ctx);
+            let path = path.clone();
+            let options = options.at_page(page);
+            async move {
+                let builder = ctx.apply_options(ctx.get(&path), Some(options))?;
+                let response: ApiResponse<D> = ctx.send_with_retry(builder).await?;
+                Ok(Page::from_response(response, items))
+            }
+        })
+    }
+
+    /// A [`Paginator`] over the v3 endpoint at `first_path`, following the
+    /// `links.next` of each page and sending `options` with every page.
+    pub(crate) fn paginate_links<O, D, T>(
+        self: &Arc<Self>, first_path: String, options: Option<O>, items: fn(D) -> Vec<T>,
+    ) -> Paginator<T>
+    where
+        O: EndpointOptions + Clone + Send + 'static,
+        D: DeserializeOwned + Send + 'static,
+        T: Send + 'static,
+    {
+        let ctx = Arc::clone(self);
+        Paginator::cursor(move |next: Option<String>| {
+            let ctx = Arc::clone(&ctx);
+            let path = next.unwrap_or_else(|| first_path.clone());
+            let options = options.clone();
+            async move {
+                let builder = ctx.apply_options(ctx.get(&path), options)?;
+                let response: ApiResponse<D> = ctx.send_with_retry(builder).await?;
+                Ok(CursorPage::from_response(response, items))
+            }
+        })
+    }
+
     /// Send a request with retry logic for transient failures.
+    ///
+    /// Successful responses carry [`ResponseMeta`](crate::ResponseMeta) for the call.
+    /// Cancelling the context's token drops the in-flight request and any
+    /// pending backoff, returning [`Error::Cancelled`]. Calls are refused
+    /// with [`Error::ShuttingDown`] once the client is shutting down, and
+    /// aborted if still running when the shutdown timeout passes.
     pub async fn send_with_retry<T>(&self, builder: RequestBuilder) -> Result<T, Error>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + WithResponseMeta,
+    {
+        let request = builder.try_clone().and_then(|b| b.build().ok());
+        let endpoint = request
+            .as_ref()
+            .map_or(Endpoint::Other, |req| Endpoint::from_path(req.url().path()));
+
+        let (chain_name, address) = request
+            .as_ref()
+            .map_or((None, None), |req| crate::audit::scope(req.url().path()));
+        let mut builder = builder;
+        let mut audit = None;
+        if self.config.audit_sink.is_some() {
+            let sent_id = request
+                .as_ref()
+                .and_then(|req| req.headers().get("x-request-id"))
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let request_id = match sent_id {
+                Some(id) => id,
+                None => {
+                    let id = crate::RequestId::new().to_string();
+                    builder = builder.header("X-Request-ID", id.as_str());
+                    id
+                }
+            };
+            audit = Some(AuditEntry {
+                endpoint,
+                chain_name: chain_name.clone(),
+                address: address.clone(),
+                timestamp: SystemTime::now(),
+                request_id,
+                server_request_id: None,
+                status: None,
+                succeeded: false,
+            });
+        }
+
+        let _in_flight = self.lifecycle.enter()?;
+        let mut last_response = None;
+        let breaker = self.circuit_breakers.as_ref().map(|b| b.for_endpoint(endpoint));
+        let rejected = match &breaker {
+            Some(breaker) if !breaker.breaker().can_proceed().await => Some(breaker.breaker().retry_after().await),
+            _ => None,
+        };
+        let result = match rejected {
+            Some(retry_after) => Err(Error::CircuitOpen { retry_after }),
+            None => {
+                let result = tokio::select! {
+                    biased;
+                    _ = cancelled(self.cancellation.as_ref()) => Err(Error::Cancelled),
+                    _ = self.lifecycle.abort_token().cancelled() => Err(Error::ShuttingDown),
+                    result = async {
+                        let risk_flags = self.screen(endpoint, chain_name, address).await?;
+                        self.retry_loop(builder, endpoint, risk_flags, &mut last_response).await
+                    } => result,
+                };
+                if let Some(breaker) = &breaker {
+                    record_circuit_outcome(breaker.breaker(), result.as_ref().err()).await;
+                }
+                result
+            }
+        };
+        if let (Some(metrics), Err(e)) = (&self.metrics, &result) {
+            metrics.record_error(endpoint, error_kind(e)).await;
+        }
+        if let (Some(sink), Some(mut entry)) = (&self.config.audit_sink, audit) {
+            if let Some((status, server_request_id)) = last_response {
+                entry.status = Some(status);
+                entry.server_request_id = server_request_id;
+            }
+            entry.succeeded = result.is_ok();
+            sink.record(&entry);
+        }
+        result
+    }
+
+    /// Ask the configured screening provider about the address a call names,
+    /// returning the flags to attach to the response.
+    async fn screen(&self, endpoint: Endpoint, chain_name: Option<String>, address: Option<String>) -> Result<Vec<RiskFlag>, Error> {
+        let (Some(provider), Some(address)) = (&self.config.screening, address) else {
+            return Ok(Vec::new());
+        };
+        let request = ScreeningRequest { endpoint, chain_name, address };
+        match provider.screen(&request).await? {
+            ScreeningDecision::Allow => Ok(Vec::new()),
+            ScreeningDecision::Flag(flags) => Ok(flags),
+            ScreeningDecision::Block { reason } => Err(Error::Screened { address: request.address, reason }),
+        }
+    }
+
+    /// `last_response` is set to the status and server request id of each response received.
+    async fn retry_loop<T>(
+        &self,
+        builder: RequestBuilder,
+        endpoint: Endpoint,
+        risk_flags: Vec<RiskFlag>,
+        last_response: &mut Option<(u16, Option<String>)>,
+    ) -> Result<T, Error>
+    where
+        T: DeserializeOwned + WithResponseMeta,
     {
         let mut attempt = 0u8;
+        let started = Instant::now();
+        let mut backoff = Duration::ZERO;
+        let mut retried_statuses = Vec::new();
 
         loop {
             let request = match builder.try_clone() {
@@ -64,6 +363,7 @@ impl ServiceContext {
                 }
             };
 
+            self.rate_limiter.acquire(endpoint).await?;
             let response = request.send().await;
 
             match response {
@@ -74,8 +374,7 @@ impl ServiceContext {
                     }
 
                     if self.should_retry_error(&e) {
-                        let backoff_ms = self.calculate_backoff(attempt);
-                        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                        backoff += self.sleep_backoff(attempt).await;
                         continue;
                     } else {
                         return Err(Error::Http(e));
@@ -83,27 +382,76 @@ impl ServiceContext {
                 }
                 Ok(response) => {
                     let status = response.status();
+                    *last_response = Some((status.as_u16(), server_request_id(response.headers())));
+
+                    let retry_after = if status.is_success() {
+                        None
+                    } else {
+                        parse_retry_after(response.headers(), SystemTime::now())
+                    };
+                    if status == StatusCode::TOO_MANY_REQUESTS {
+                        if let Some(metrics) = &self.metrics {
+                            metrics.record_rate_limit_hit();
+                        }
+                        if let Some(wait) = retry_after {
+                            self.rate_limiter.pause_for(wait);
+                        }
+                    }
 
                     if self.should_retry_status(status) {
                         attempt += 1;
                         if attempt > self.config.max_retries {
                             let text = response.text().await.unwrap_or_default();
-                            return self.handle_error_response(status, text);
+                            return self.handle_error_response(status, retry_after, text);
                         }
 
-                        let backoff_ms = self.calculate_backoff(attempt);
-                        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                        retried_statuses.push(status.as_u16());
+                        // The paused limiter holds the retry back for `Retry-After`.
+                        backoff += match retry_after {
+                            Some(wait) if status == StatusCode::TOO_MANY_REQUESTS => wait,
+                            _ => self.sleep_backoff(attempt).await,
+                        };
                         continue;
                     }
 
+                    let headers = response.headers().clone();
+                    let request_key = canonical_request_key(response.url());
                     let text = response.text().await?;
 
                     if !status.is_success() {
-                        return self.handle_error_response(status, text);
+                        return self.handle_error_response(status, retry_after, text);
                     }
+                    let scan_findings = self.security.validate_response(endpoint, &text)?;
 
-                    match serde_json::from_str::<T>(&text) {
-                        Ok(parsed) => return Ok(parsed),
+                    let parsed = crate::models::decode_with_strictness(self.config.strict_enums, || {
+                        match &self.config.money_hook {
+                            Some(hook) => serde_json::from_str::<serde_json::Value>(&text).and_then(|mut value| {
+                                crate::money::apply(hook.as_ref(), &mut value);
+                                serde_json::from_value::<T>(value)
+                            }),
+                            None => serde_json::from_str::<T>(&text),
+                        }
+                    });
+                    match parsed {
+                        Ok(mut parsed) => {
+                            let credits = self.credits.record(endpoint);
+                            if let Some(metrics) = &self.metrics {
+                                metrics.record_success(endpoint, started.elapsed()).await;
+                                metrics.record_retried_success(endpoint, attempt as u32, backoff, &retried_statuses).await;
+                                metrics.record_credits(endpoint, credits).await;
+                            }
+                            let mut meta = ResponseMeta::new(status.as_u16(), &headers, started.elapsed(), attempt as u32 + 1);
+                            meta.backoff = backoff;
+                            meta.retried_statuses = retried_statuses;
+                            meta.scan_findings = scan_findings;
+                            meta.request_key = request_key;
+                            meta.risk_flags = risk_flags;
+                            if self.config.keep_raw_body {
+                                meta.raw_body = Some(text);
+                            }
+                            parsed.set_response_meta(meta);
+                            return Ok(parsed);
+                        }
                         Err(e) => return Err(Error::Serialization(e)),
                     }
                 }
@@ -111,6 +459,13 @@ impl ServiceContext {
         }
     }
 
+    /// Sleep before the given retry attempt, returning how long it slept.
+    async fn sleep_backoff(&self, attempt: u8) -> Duration {
+        let delay = Duration::from_millis(self.calculate_backoff(attempt));
+        self.config.clock.sleep(delay).await;
+        delay
+    }
+
     fn should_retry_error(&self, error: &reqwest::Error) -> bool {
         error.is_timeout() || error.is_connect() || error.is_request()
     }
@@ -126,7 +481,8 @@ impl ServiceContext {
         std::cmp::min(exponential_delay + jitter, 5000)
     }
 
-    fn handle_error_response<T>(&self, status: StatusCode, text: String) -> Result<T, Error> {
+    fn handle_error_response<T>(&self, status: StatusCode, retry_after: Option<Duration>, text: String) -> Result<T, Error> {
+        let body = text.clone();
         let (code, message) = if let Ok(error_envelope) =
             serde_json::from_str::<crate::models::ApiErrorEnvelope>(&text)
         {
@@ -138,15 +494,14 @@ impl ServiceContext {
             } else {
                 (None, text.clone())
             }
+        } else if let Some((code, message)) = flat_error(&text) {
+            // `{"error": true, "error_message": ..., "error_code": ...}`
+            (code, message)
         } else {
             (None, text)
         };
 
-        Err(Error::Api {
-            status: status.as_u16(),
-            message,
-            code,
-        })
+        Err(Error::api(status.as_u16(), message, code).with_response(retry_after, body))
     }
 }
 
@@ -158,6 +513,779 @@ pub use pricing_service::PricingService;
 pub use security_service::SecurityService;
 pub use bitcoin_service::BitcoinService;
 pub use all_chains_service::AllChainsService;
+pub use solana_service::SolanaService;
 
 #[cfg(feature = "streaming")]
 pub use streaming_service::StreamingService;
+
+/// Resolves when `token` is cancelled; never resolves without one.
+async fn cancelled(token: Option<&CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Count a call's outcome on its endpoint's circuit breaker. Transport errors
+/// and 5xx responses are failures; any other answer shows the endpoint is up.
+/// Calls cut short by the caller or blocked by screening aren't counted.
+async fn record_circuit_outcome(breaker: &CircuitBreaker, error: Option<&Error>) {
+    match error {
+        Some(Error::Cancelled | Error::ShuttingDown | Error::Screened { .. }) => {}
+        Some(error @ (Error::Http(_) | Error::Api { kind: ApiErrorCode::ServerError, .. })) => {
+            breaker.record_failure(error).await;
+        }
+        _ => breaker.record_success().await,
+    }
+}
+
+/// How long a `Retry-After` header asks to wait, given in seconds or as an HTTP date.
+fn parse_retry_after(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+/// Error category recorded in metrics.
+/// Code and message of an error body with top-level `error_code`/`error_message` fields.
+fn flat_error(text: &str) -> Option<(Option<u32>, String)> {
+    let body: serde_json::Value = serde_json::from_str(text).ok()?;
+    let message = body.get("error_message")?.as_str()?.to_string();
+    let code = body.get("error_code").and_then(|c| c.as_u64()).and_then(|c| u32::try_from(c).ok());
+    Some((code, message))
+}
+
+fn error_kind(error: &Error) -> &'static str {
+    match error {
+        Error::Http(e) if e.is_timeout() => "timeout",
+        Error::Http(_) => "http",
+        Error::Serialization(_) => "serialization",
+        Error::Api { status: 429, .. } => "rate_limited",
+        Error::Api { status, .. } if *status >= 500 => "server_error",
+        Error::Api { .. } => "client_error",
+        Error::Cancelled => "cancelled",
+        Error::CircuitOpen { .. } => "circuit_open",
+        Error::Screened { .. } => "screened",
+        Error::ShuttingDown => "shutting_down",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CacheConfig, SecurityConfig};
+
+    fn test_context(security: SecurityConfig) -> ServiceContext {
+        context_with_cache(security, &CacheConfig::default())
+    }
+
+    fn context_with_cache(security: SecurityConfig, cache: &CacheConfig) -> ServiceContext {
+        ServiceContext {
+            http: HttpClient::new(),
+            api_key: "cqt_test".to_string(),
+            config: ClientConfig::new("http://127.0.0.1:9"),
+            metrics: None,
+            headers: HeaderMap::new(),
+            security: Arc::new(SecurityManager::new(security)),
+            status_cache: Arc::new(base_service::StatusCache::new(cache, &crate::clock::default_clock())),
+            cancellation: None,
+            lifecycle: Arc::new(Lifecycle::new()),
+            credits: Arc::new(CreditEstimator::new()),
+            keepalive: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            rate_limiter: Arc::new(RequestLimiter::new(&Default::default(), &crate::clock::default_clock())),
+            circuit_breakers: None,
+        }
+    }
+
+    /// Serve one canned HTTP response per connection, in order.
+    fn serve(responses: Vec<(&'static str, &'static str)>) -> std::net::SocketAddr {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_post_json_rejects_oversized_body() {
+        let ctx = test_context(SecurityConfig { max_request_size: 16, ..Default::default() });
+        let body = serde_json::json!({ "query": "x".repeat(64) });
+
+        let result: Result<serde_json::Value, Error> = ctx.post_json("/v1/graphql/", &body).await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("exceeds maximum")));
+    }
+
+    #[tokio::test]
+    async fn test_chain_status_served_from_short_ttl_cache() {
+        let ctx = Arc::new(test_context(SecurityConfig::default()));
+        let status: crate::AllChainStatusResponse = serde_json::from_value(serde_json::json!({
+            "data": { "updated_at": null, "items": [] }
+        })).unwrap();
+        ctx.status_cache.chain_status.set("all".to_string(), status).await;
+
+        // The base URL is unreachable, so only a cache hit can succeed.
+        assert!(BaseService::new(Arc::clone(&ctx)).get_all_chain_status().await.is_ok());
+
+        let disabled = CacheConfig { status_ttl: Duration::ZERO, ..Default::default() };
+        let ctx = Arc::new(context_with_cache(SecurityConfig::default(), &disabled));
+        assert!(!ctx.status_cache.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_names_dedupes_and_caches() {
+        let named = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045";
+        let unnamed = "0x0000000000000000000000000000000000000001";
+        let addr = serve(vec![("200 OK", r#"{"data":{"address":"0xd8da6bf26964af9d7eed9e03e53415d37aa96045","name":"vitalik.eth"}}"#)]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let ctx = Arc::new(ctx);
+        let key = Endpoint::ResolveAddress.cache_key(&["eth-mainnet", unnamed]);
+        ctx.status_cache.names.set(key, None).await;
+
+        let base = BaseService::new(Arc::clone(&ctx));
+        let names = base
+            .resolve_names("eth-mainnet", &[named.to_uppercase().replacen("0X", "0x", 1).as_str(), named, unnamed])
+            .await
+            .unwrap();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[named], "vitalik.eth");
+
+        // The server has no responses left, so this must come from the cache.
+        assert_eq!(base.reverse_resolve("eth-mainnet", named).await.unwrap().as_deref(), Some("vitalik.eth"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_telemetry_on_eventual_success() {
+        let addr = serve(vec![
+            ("503 Service Unavailable", ""),
+            ("200 OK", r#"{"data":{"updated_at":null,"items":[]}}"#),
+        ]);
+
+        let metrics = Arc::new(MetricsCollector::new());
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        ctx.metrics = Some(Arc::clone(&metrics));
+
+        let resp: crate::AllChainStatusResponse = ctx.send_with_retry(ctx.get("/v1/chains/status/")).await.unwrap();
+        let meta = resp.response_meta.unwrap();
+        assert_eq!(meta.attempts, 2);
+        assert!(meta.was_retried());
+        assert_eq!(meta.retried_statuses, vec![503]);
+        assert_eq!(meta.backoff, Duration::from_millis(250));
+        assert_eq!(meta.request_key, "/v1/chains/status/");
+
+        let summary = metrics.get_metrics().await;
+        assert_eq!(summary.retried_success_count, 1);
+        assert_eq!(summary.retry_count, 1);
+        assert_eq!(summary.retried_statuses.get(&503), Some(&1));
+        assert_eq!(summary.success_count, 1);
+        assert_eq!(summary.endpoint_stats[&Endpoint::ChainStatus].retry_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_aborts_in_flight_request() {
+        // Accepts the connection but never answers.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(5));
+        });
+
+        let token = CancellationToken::new();
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let ctx = ctx.with_request_options(&RequestOptions::new().cancellation(token.clone())).unwrap();
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+        let started = Instant::now();
+        let result: Result<crate::AllChainStatusResponse, Error> = ctx.send_with_retry(ctx.get("/v1/chains/status/")).await;
+        assert!(matches!(result, Err(Error::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        // Already-cancelled tokens fail without sending anything.
+        let result: Result<crate::AllChainStatusResponse, Error> = ctx.send_with_retry(ctx.get("/v1/chains/status/")).await;
+        assert!(matches!(result, Err(Error::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_response_scan_in_pipeline() {
+        use crate::{ResponseScanConfig, ResponseScanMode};
+
+        let body = r#"{"data":{"updated_at":null,"items":[{"name":"<script>"}]}}"#;
+        let addr = serve(vec![("200 OK", body), ("200 OK", body)]);
+        let scanning = |mode| {
+            let mut ctx = test_context(SecurityConfig {
+                response_scan: ResponseScanConfig::new().mode(mode),
+                ..Default::default()
+            });
+            ctx.config = ClientConfig::new(format!("http://{}", addr));
+            ctx
+        };
+
+        let ctx = scanning(ResponseScanMode::ReportOnly);
+        let resp: crate::AllChainsResponse = ctx.send_with_retry(ctx.get("/v1/chains/")).await.unwrap();
+        assert_eq!(resp.response_meta.unwrap().scan_findings[0].path, "data.items[0].name");
+
+        let ctx = scanning(ResponseScanMode::Enforce);
+        let result: Result<crate::AllChainsResponse, Error> = ctx.send_with_retry(ctx.get("/v1/chains/")).await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("script_injection")));
+    }
+
+    #[test]
+    fn test_request_options_override_api_version() {
+        let ctx = test_context(SecurityConfig::default());
+        assert_eq!(ctx.config.api_version, crate::ApiVersion::V3);
+
+        let options = RequestOptions::new().api_version(crate::ApiVersion::V2);
+        assert_eq!(ctx.with_request_options(&options).unwrap().config.api_version, crate::ApiVersion::V2);
+        assert_eq!(ctx.with_request_options(&RequestOptions::new()).unwrap().config.api_version, crate::ApiVersion::V3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_policy_overrides() {
+        let ctx = test_context(SecurityConfig::default());
+        let cache = MemoryCache::new(Duration::from_secs(60), 8);
+        cache.set("k".to_string(), 1u32).await;
+        let unreachable = || async { Err::<u32, _>(Error::Config("network".to_string())) };
+
+        assert_eq!(ctx.cached(&cache, true, "k", unreachable()).await.unwrap(), 1);
+
+        let cache_only = ctx.with_request_options(&RequestOptions::new().cache_policy(CachePolicy::CacheOnly)).unwrap();
+        assert_eq!(cache_only.cached(&cache, true, "k", unreachable()).await.unwrap(), 1);
+        assert!(cache_only.cached(&cache, true, "missing", async { Ok(2) }).await.is_err());
+        assert!(cache_only.cached(&cache, false, "k", async { Ok(2) }).await.is_err());
+
+        let refresh = ctx.with_request_options(&RequestOptions::new().cache_policy(CachePolicy::RefreshIfOlderThan(Duration::ZERO))).unwrap();
+        assert_eq!(refresh.cached(&cache, true, "k", async { Ok(3) }).await.unwrap(), 3);
+
+        let bypass = ctx.with_request_options(&RequestOptions::new().cache_policy(CachePolicy::Bypass)).unwrap();
+        assert_eq!(bypass.cached(&cache, true, "k", async { Ok(4) }).await.unwrap(), 4);
+        assert_eq!(cache.get("k").await, Some(4));
+    }
+
+    #[test]
+    fn test_response_meta_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", "req-123".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "42".parse().unwrap());
+        headers.insert("x-credits-used", "0.5".parse().unwrap());
+
+        let meta = ResponseMeta::new(200, &headers, Duration::from_millis(15), 2);
+        assert_eq!(meta.request_id.as_deref(), Some("req-123"));
+        assert_eq!(meta.rate_limit_remaining, Some(42));
+        assert_eq!(meta.rate_limit_limit, None);
+        assert_eq!(meta.header("x-credits-used"), Some("0.5"));
+        assert_eq!(meta.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_audit_sink_records_every_call() {
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"address":"0xabc","items":[]}}"#),
+            ("404 Not Found", r#"{"error":true,"error_message":"not found","error_code":404}"#),
+        ]);
+        let log = crate::AuditLog::new();
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_audit_sink(log.clone());
+
+        let _: serde_json::Value = ctx.send_with_retry(ctx.get("/v1/eth-mainnet/address/0xABC/balances_v2/")).await.unwrap();
+        let tagged = ctx.get("/v1/chains/status/").header("X-Request-ID", "mine");
+        assert!(ctx.send_with_retry::<serde_json::Value>(tagged).await.is_err());
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].endpoint, Endpoint::BalancesV2);
+        assert_eq!(entries[0].chain_name.as_deref(), Some("eth-mainnet"));
+        assert_eq!(entries[0].status, Some(200));
+        assert!(entries[0].succeeded);
+        assert!(!entries[0].request_id.is_empty());
+        assert_eq!(log.for_address("0xabc").len(), 1);
+
+        assert_eq!(entries[1].request_id, "mine");
+        assert_eq!(entries[1].status, Some(404));
+        assert!(!entries[1].succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_api_error_code_from_flat_error_body() {
+        let addr = serve(vec![(
+            "400 Bad Request",
+            r#"{"data":null,"error":true,"error_message":"Malformed address provided: 0xnope","error_code":400}"#,
+        )]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let err = ctx.send_with_retry::<serde_json::Value>(ctx.get("/v1/eth-mainnet/address/0xnope/balances_v2/")).await.unwrap_err();
+        assert_eq!(err.api_error_code(), Some(crate::ApiErrorCode::MalformedAddress));
+        assert!(matches!(err, Error::Api { code: Some(400), ref message, .. } if message.starts_with("Malformed address")));
+    }
+
+    #[tokio::test]
+    async fn test_resumed_transactions_advance_checkpoint() {
+        use crate::{CheckpointStore, MemoryCheckpointStore};
+        use futures_util::StreamExt;
+
+        let addr = serve(vec![(
+            "200 OK",
+            r#"{"data":{"items":[
+                {"tx_hash":"0x1","from_address":"0xabc","value":"0","block_height":100},
+                {"tx_hash":"0x2","from_address":"0xabc","value":"0","block_height":100},
+                {"tx_hash":"0x3","from_address":"0xabc","value":"0","block_height":104}
+            ]},"pagination":{"has_more":false}}"#,
+        )]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let store = MemoryCheckpointStore::new();
+        store.set_last_block("eth-mainnet", "0xabc", 99).unwrap();
+
+        let service = TransactionService::new(Arc::new(ctx));
+        let mut txs = service.resume_transactions_stream("eth-mainnet", "0xabc", None, Arc::new(store.clone())).unwrap();
+        txs.next().await.unwrap().unwrap();
+        txs.next().await.unwrap().unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xabc").unwrap(), Some(99));
+        txs.next().await.unwrap().unwrap();
+        assert_eq!(store.last_block("eth-mainnet", "0xabc").unwrap(), Some(100));
+        assert!(txs.next().await.is_none());
+        assert_eq!(store.last_block("eth-mainnet", "0xabc").unwrap(), Some(104));
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_starts_keepalive_once() {
+        let addr = serve(vec![("404 Not Found", ""), ("404 Not Found", "")]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr))
+            .with_warm_up(crate::WarmUpConfig::new().keepalive_interval(Duration::from_secs(3600)));
+        let ctx = Arc::new(ctx);
+
+        let report = crate::warm_up::warm_up(&ctx).await.unwrap();
+        assert_eq!(report.status, 404);
+        assert!(report.dns_lookup.is_some());
+        assert_eq!(report.resolved_addresses, 1);
+        assert!(report.keepalive_started);
+        assert!(!crate::warm_up::warm_up(&ctx).await.unwrap().keepalive_started);
+
+        let shutdown = ctx.lifecycle.shutdown(Duration::from_secs(1)).await;
+        assert!(shutdown.drained);
+    }
+
+    #[tokio::test]
+    async fn test_collection_stats_merges_series() {
+        // Requests run concurrently, so every response serves all three series.
+        const BODY: &str = r#"{"data":{"chain_name":"eth-mainnet","items":[
+            {"date":"2024-01-02","floor_price_quote":2.0,"volume_quote":30.0,"sale_count":3},
+            {"date":"2024-01-01","floor_price_quote":1.0,"volume_quote":10.0,"sale_count":1}
+        ]}}"#;
+        let addr = serve(vec![("200 OK", BODY), ("200 OK", BODY), ("200 OK", BODY)]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = crate::services::nft_service::NftService::new(Arc::new(ctx));
+        let stats = service.collection_stats("eth-mainnet", "0xabc", 1).await.unwrap();
+        assert_eq!(stats.chain_name.as_deref(), Some("eth-mainnet"));
+        assert_eq!(stats.floor_price.change(), Some(1.0));
+        assert_eq!(stats.volume.change_pct(), Some(200.0));
+        assert_eq!(stats.sales.previous, Some(1.0));
+        assert!(matches!(service.collection_stats("eth-mainnet", "0xabc", 0).await, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_trace_funding_follows_funders() {
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"items":[
+                {"chain_name":"base-mainnet","first_seen_at":"2023-08-01T00:00:00Z"},
+                {"chain_name":"eth-mainnet","first_seen_at":"2021-05-01T00:00:00Z"}
+            ]}}"#),
+            ("200 OK", r#"{"data":{"items":[
+                {"tx_hash":"0x1","from_address":"0xFUNDER","to_address":"0xabc","value":"10","block_height":7}
+            ]}}"#),
+            ("200 OK", r#"{"data":{"items":[]}}"#),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = crate::services::all_chains_service::AllChainsService::new(Arc::new(ctx));
+        let options = crate::FundingTraceOptions::new().max_hops(2);
+        let trace = service.trace_funding("0xabc", options).await.unwrap();
+        assert_eq!(trace.hops.len(), 1);
+        assert_eq!(trace.hops[0].first_seen_chain.as_deref(), Some("eth-mainnet"));
+        assert_eq!(trace.hops[0].funding.as_ref().unwrap().chain_name, "eth-mainnet");
+        assert_eq!(trace.origin(), Some("0xfunder"));
+        assert_eq!(trace.stop, crate::FundingStop::NoActivity);
+    }
+
+    #[tokio::test]
+    async fn test_counterparty_report_streams_pages() {
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"items":[
+                {"tx_hash":"0x1","from_address":"0xabc","to_address":"0xdef","value":"3"}
+            ]},"pagination":{"has_more":true}}"#),
+            ("200 OK", r#"{"data":{"items":[
+                {"tx_hash":"0x2","from_address":"0xdef","to_address":"0xabc","value":"2"}
+            ]},"pagination":{"has_more":false}}"#),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = TransactionService::new(Arc::new(ctx));
+        let report = service.counterparty_report("eth-mainnet", "0xabc", None).await.unwrap();
+        assert_eq!(report.transactions, 2);
+        assert_eq!(report.counterparties.len(), 1);
+        assert_eq!((report.counterparties[0].value_out, report.counterparties[0].value_in), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn test_dust_report_prices_sweeps() {
+        const GAS: &str = r#"{"data":{"items":[{"gas_quote":0.4},{"gas_quote":0.1}]}}"#;
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"address":"0xabc","items":[
+                {"chain_name":"eth-mainnet","contract_address":"0xa","quote":0.5},
+                {"chain_name":"eth-mainnet","contract_address":"0xb","quote":50.0}
+            ]}}"#),
+            ("200 OK", GAS),
+            ("200 OK", GAS),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = crate::services::all_chains_service::AllChainsService::new(Arc::new(ctx));
+        let options = crate::DustReportOptions::new().chains(vec!["eth-mainnet".to_string()]);
+        let report = service.dust_report("0xabc", options).await.unwrap();
+        assert_eq!(report.tokens.len(), 1);
+        assert_eq!(report.tokens[0].sweep_cost_quote, Some(0.1));
+        assert_eq!(report.tokens[0].worth_sweeping(), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_depeg_check_and_watch() {
+        const PRICES: &str = r#"{"data":[{"prices":[{"date":"2024-03-11","price":0.97},{"date":"2024-03-10","price":1.0}]}]}"#;
+        let addr = serve(vec![("200 OK", PRICES), ("200 OK", PRICES)]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let holding = crate::StablecoinHolding {
+            chain_name: "eth-mainnet".to_string(),
+            contract_address: "0xusdc".to_string(),
+            symbol: "USDC".to_string(),
+            issuer: Some("Circle".to_string()),
+            peg: 1.0,
+            balance: None,
+            quote: 100.0,
+        };
+
+        let service = crate::services::pricing_service::PricingService::new(Arc::new(ctx));
+        let alerts = service.check_depegs(&[holding.clone(), holding.clone()], 0.005).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].date.as_deref(), Some("2024-03-11"));
+
+        let mut watch = service.watch_depegs(vec![holding], crate::DepegConfig::new().threshold(0.01)).unwrap();
+        let alert = watch.recv().await.unwrap();
+        assert_eq!((alert.symbol.as_str(), alert.recovered), ("USDC", false));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_transfer_quotes() {
+        let addr = serve(vec![(
+            "200 OK",
+            r#"{"data":[{"prices":[{"date":"2020-01-02","price":2.0},{"date":"2020-01-01","price":1.0}]}]}"#,
+        )]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let mut data: crate::models::balances::Erc20TransfersData = serde_json::from_value(serde_json::json!({
+            "chain_name": "eth-mainnet",
+            "items": [
+                { "contract_address": "0xa", "block_signed_at": "2020-01-01T00:00:00Z", "delta": "3", "contract_decimals": 0 },
+                { "contract_address": "0xa", "block_signed_at": "2020-01-02T00:00:00Z", "delta": "3", "contract_decimals": 0 },
+            ],
+        }))
+        .unwrap();
+
+        let service = crate::services::pricing_service::PricingService::new(Arc::new(ctx));
+        let backfill = service.backfill_transfer_quotes(&mut data).await.unwrap();
+        assert_eq!((backfill.filled, backfill.unpriced, backfill.requests), (2, 0, 1));
+        assert_eq!(data.items[1].delta_quote, Some(crate::decimal::QuoteValue::from_f64(6.0)));
+    }
+
+    #[tokio::test]
+    async fn test_block_stream_catches_up_without_repeats() {
+        use futures_util::StreamExt;
+
+        // Latest is 10, then a lagging replica reports 9, then 11.
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"items":[{"height":10}]}}"#),
+            ("200 OK", r#"{"data":{"items":[{"height":8}]}}"#),
+            ("200 OK", r#"{"data":{"items":[{"height":9}]}}"#),
+            ("200 OK", r#"{"data":{"items":[{"height":9}]}}"#),
+            ("200 OK", r#"{"data":{"items":[{"height":11}]}}"#),
+        ]);
+        let disabled = CacheConfig { status_ttl: Duration::ZERO, ..Default::default() };
+        let mut ctx = context_with_cache(SecurityConfig::default(), &disabled);
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = BaseService::new(Arc::new(ctx));
+        let options = crate::BlockStreamOptions::new().from_block(8).poll_interval(Duration::from_millis(10));
+        let mut blocks = service.block_stream("eth-mainnet", Some(options));
+        let mut heights = Vec::new();
+        for _ in 0..4 {
+            let block = blocks.next().await.unwrap().unwrap();
+            heights.push(block.data.unwrap().items[0].height.unwrap());
+        }
+        assert_eq!(heights, [8, 9, 10, 11]);
+        assert_eq!(blocks.next_height(), Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_multichain_transactions_stream_follows_cursor() {
+        use futures_util::StreamExt;
+
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"cursor_before":"c1","items":[
+                {"chain_name":"eth-mainnet","tx_hash":"e1","block_signed_at":"2024-01-01T10:00:00Z"},
+                {"chain_name":"eth-mainnet","tx_hash":"e2","block_signed_at":"2024-01-01T06:00:00Z"},
+                {"chain_name":"base-mainnet","tx_hash":"b1","block_signed_at":"2024-01-01T08:00:00Z"}]}}"#),
+            ("200 OK", r#"{"data":{"cursor_before":null,"items":[
+                {"chain_name":"base-mainnet","tx_hash":"b2","block_signed_at":"2024-01-01T07:00:00Z"}]}}"#),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let mut txs = AllChainsService::new(Arc::new(ctx)).transactions_stream(None);
+        let mut hashes = Vec::new();
+        while let Some(tx) = txs.next().await {
+            hashes.push(tx.unwrap().tx_hash.unwrap());
+        }
+        assert_eq!(hashes, ["e1", "b1", "b2", "e2"]);
+        assert_eq!(txs.pages(), 2);
+        let progress: Vec<(&str, u64)> = txs.chain_progress().map(|p| (p.chain_name.as_str(), p.yielded)).collect();
+        assert_eq!(progress, [("base-mainnet", 2), ("eth-mainnet", 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_all_transactions_follows_links() {
+        use futures_util::StreamExt;
+
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"items":[{"tx_hash":"0x1","from_address":"0xabc","value":"0"},{"tx_hash":"0x2","from_address":"0xabc","value":"0"}]},
+                "links":{"prev":null,"next":"https://api.covalenthq.com/v1/eth-mainnet/address/0xabc/transactions_v3/page/1/"}}"#),
+            ("200 OK", r#"{"data":{"items":[{"tx_hash":"0x3","from_address":"0xabc","value":"0"}]},"links":{"prev":null,"next":null}}"#),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = TransactionService::new(Arc::new(ctx));
+        let txs = service.stream_all_transactions_for_address("eth-mainnet", "0xabc", None, None);
+        let hashes: Vec<String> = txs.map(|tx| tx.unwrap().tx_hash).collect().await;
+        assert_eq!(hashes, ["0x1", "0x2", "0x3"]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_backoff_waits_on_configured_clock() {
+        let addr = serve(vec![
+            ("503 Service Unavailable", ""),
+            ("503 Service Unavailable", ""),
+            ("200 OK", r#"{"data":{"updated_at":null,"items":[]}}"#),
+        ]);
+        let clock = crate::MockClock::new();
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_clock(clock.clone());
+
+        let started = Instant::now();
+        let resp: crate::AllChainStatusResponse = ctx.send_with_retry(ctx.get("/v1/chains/status/")).await.unwrap();
+        assert_eq!(resp.response_meta.unwrap().attempts, 3);
+        assert_eq!(clock.sleeps(), [Duration::from_millis(250), Duration::from_millis(500)]);
+        assert!(started.elapsed() < Duration::from_millis(750));
+    }
+
+    #[tokio::test]
+    async fn test_paged_variant_walks_page_numbers() {
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"items":[{"address":"0x1"},{"address":"0x2"}]},
+                "pagination":{"has_more":true,"page_number":0,"page_size":2,"total_count":3}}"#),
+            ("200 OK", r#"{"data":{"items":[{"address":"0x3"}]},
+                "pagination":{"has_more":false,"page_number":1,"page_size":2,"total_count":3}}"#),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = crate::services::balance_service::BalanceService::new(Arc::new(ctx));
+        let mut holders = service.get_token_holders_v2_for_token_address_paged("eth-mainnet", "0xtoken", None);
+        assert_eq!(holders.next_page().await.unwrap().unwrap().len(), 2);
+        assert_eq!(holders.total_count(), Some(3));
+        let rest = holders.collect_all().await.unwrap();
+        assert_eq!(rest[0].address.as_deref(), Some("0x3"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_enums_reject_unknown_values() {
+        let body = r#"{"data":{"items":[{"transfer_type":"SELF","delta":"1"}]}}"#;
+        let addr = serve(vec![("200 OK", body), ("200 OK", body)]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let lenient = crate::services::balance_service::BalanceService::new(Arc::new(ctx));
+
+        let transfers = lenient.get_erc20_transfers_for_wallet_address("eth-mainnet", "0xabc", None).await.unwrap();
+        let transfer_type = transfers.data.unwrap().items[0].transfer_type.clone().unwrap();
+        assert_eq!(transfer_type, crate::models::TransferType::Unknown("SELF".to_string()));
+
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_strict_enums(true);
+        let strict = crate::services::balance_service::BalanceService::new(Arc::new(ctx));
+        let err = strict.get_erc20_transfers_for_wallet_address("eth-mainnet", "0xabc", None).await.unwrap_err();
+        assert!(matches!(err, Error::Serialization(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_batch_prices_dedupe_contracts_into_one_call() {
+        let addr = serve(vec![(
+            "200 OK",
+            r#"{"data":[
+                {"contract_address":"0xA0B86991C6218B36C1D19D4A2E9EB0CE3606EB48","prices":[{"date":"2024-01-01","price":1.0}]},
+                {"contract_address":"0xdac17f958d2ee523a2206206994597c13d831ec7","prices":[{"date":"2024-01-01","price":0.99}]}
+            ]}"#,
+        )]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+        let service = crate::services::pricing_service::PricingService::new(Arc::new(ctx));
+
+        let contracts = [
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "0xdac17f958d2ee523a2206206994597c13d831ec7",
+            "0x6b175474e89094c44da98b954eedeac495271d0f",
+        ];
+        let prices = service.get_token_prices_batch("eth-mainnet", "USD", &contracts, None).await.unwrap();
+        assert_eq!(prices.len(), 3);
+        assert_eq!(prices["0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"].len(), 1);
+        assert_eq!(prices["0xdac17f958d2ee523a2206206994597c13d831ec7"][0].price, Some(crate::decimal::QuoteValue::from_f64(0.99)));
+        assert!(prices["0x6b175474e89094c44da98b954eedeac495271d0f"].is_empty());
+
+        let err = service.get_token_prices_batch("eth-mainnet", "USD", &["0x12"], None).await.unwrap_err();
+        assert!(matches!(err, Error::Config(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_gas_spend_report_stops_before_range() {
+        let addr = serve(vec![
+            ("200 OK", r#"{"data":{"items":[
+                {"tx_hash":"0x1","from_address":"0xabc","to_address":"0xdef","value":"0","fees_paid":"1000","gas_quote":2.0,"block_signed_at":"2024-03-02T00:00:00Z"},
+                {"tx_hash":"0x2","from_address":"0xdef","to_address":"0xabc","value":"5","fees_paid":"9","gas_quote":1.0,"block_signed_at":"2024-03-01T00:00:00Z"}
+            ]},"pagination":{"has_more":true}}"#),
+            ("200 OK", r#"{"data":{"items":[
+                {"tx_hash":"0x3","from_address":"0xabc","to_address":"0xdef","value":"0","fees_paid":"500","gas_quote":1.5,"block_signed_at":"2024-02-15T00:00:00Z"},
+                {"tx_hash":"0x4","from_address":"0xabc","to_address":"0xdef","value":"0","fees_paid":"7","gas_quote":9.0,"block_signed_at":"2024-01-31T00:00:00Z"}
+            ]},"pagination":{"has_more":true}}"#),
+        ]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr));
+
+        let service = TransactionService::new(Arc::new(ctx));
+        let options = crate::GasSpendOptions::new().from(crate::CalendarDate::from_ymd(2024, 2, 1).unwrap());
+        let report = service.gas_spend_report(&["eth-mainnet"], "0xabc", Some(options)).await.unwrap();
+        let chain = report.chain("eth-mainnet").unwrap();
+        assert_eq!((chain.spend.tx_count, chain.spend.fee_wei, chain.spend.fee_quote), (2, 1500, 3.5));
+        assert_eq!(chain.contracts.len(), 1);
+        assert_eq!(chain.contracts[0].address.as_deref(), Some("0xdef"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_pauses_the_rate_limiter() {
+        let addr = serve(vec![
+            ("429 Too Many Requests\r\nRetry-After: 2", ""),
+            ("200 OK", r#"{"data":{"updated_at":null,"items":[]}}"#),
+        ]);
+        let clock = crate::MockClock::new();
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_clock(clock.clone());
+        ctx.rate_limiter = Arc::new(RequestLimiter::new(&ctx.config.rate_limit, &ctx.config.clock));
+
+        let resp: crate::AllChainStatusResponse = ctx.send_with_retry(ctx.get("/v1/chains/status/")).await.unwrap();
+        let meta = resp.response_meta.unwrap();
+        assert_eq!((meta.attempts, meta.backoff), (2, Duration::from_secs(2)));
+        assert_eq!(clock.sleeps(), [Duration::from_secs(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_keep_retry_after_and_body() {
+        let body = r#"{"error":true,"error_message":"Too many requests","error_code":429}"#;
+        let addr = serve(vec![("429 Too Many Requests\r\nRetry-After: 7", body)]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_max_retries(0);
+
+        let err = ctx.send_with_retry::<crate::AllChainStatusResponse>(ctx.get("/v1/chains/status/")).await.unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(err.api_error_code(), Some(crate::ApiErrorCode::RateLimited));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(7)));
+        assert_eq!(err.response_body(), Some(body));
+    }
+
+    #[test]
+    fn test_retry_after_accepts_seconds_and_dates() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_470);
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", " 30 ".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers, now), Some(Duration::from_secs(30)));
+        headers.insert("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers, now), Some(Duration::from_secs(10)));
+        assert_eq!(parse_retry_after(&headers, now + Duration::from_secs(60)), Some(Duration::ZERO));
+        headers.insert("retry-after", "soon".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers, now), None);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_opens_on_server_errors() {
+        let addr = serve(vec![("404 Not Found", ""), ("503 Service Unavailable", "")]);
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_max_retries(0);
+        ctx.circuit_breakers = Some(Arc::new(EndpointCircuitBreakers::new(crate::CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..Default::default()
+        })));
+
+        let call = || async { ctx.send_with_retry::<crate::AllChainStatusResponse>(ctx.get("/v1/chains/status/")).await };
+        assert!(matches!(call().await, Err(Error::Api { status: 404, .. })));
+        assert!(matches!(call().await, Err(Error::Api { status: 503, .. })));
+        assert!(matches!(call().await, Err(Error::CircuitOpen { retry_after }) if retry_after > Duration::ZERO));
+
+        let stats = ctx.circuit_breakers.as_ref().unwrap().stats().await;
+        assert_eq!(stats[&Endpoint::ChainStatus].state, crate::CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_screening_flags_and_blocks_addresses() {
+        let addr = serve(vec![("200 OK", r#"{"data":{"address":"0xabc","items":[]}}"#)]);
+        let list = crate::ScreeningList::new()
+            .flag("0xABC", crate::RiskFlag::new("mixer", crate::RiskSeverity::High))
+            .block("0xdef", "sanctioned");
+        let mut ctx = test_context(SecurityConfig::default());
+        ctx.config = ClientConfig::new(format!("http://{}", addr)).with_screening(list);
+        let service = crate::services::balance_service::BalanceService::new(Arc::new(ctx));
+
+        let flagged = service.get_token_balances_for_wallet_address("eth-mainnet", "0xabc", None).await.unwrap();
+        let flags = flagged.response_meta.unwrap().risk_flags;
+        assert_eq!(flags, [crate::RiskFlag::new("mixer", crate::RiskSeverity::High)]);
+
+        let blocked = service.get_token_balances_for_wallet_address("eth-mainnet", "0xdef", None).await.unwrap_err();
+        assert!(matches!(blocked, Error::Screened { ref reason, .. } if reason == "sanctioned"), "{:?}", blocked);
+    }
+}
@@ -1,7 +1,15 @@
+use crate::address::AddressParam;
+use crate::amount::AmountValue;
+use crate::bulk::{BulkConfig, BulkExecutor};
+use crate::collection_stats::CollectionStats;
 use crate::Error;
-use crate::http::query::QueryParams;
+use crate::types::QuoteCurrency;
+use crate::http::query::{impl_paged_options, EndpointOptions, PagedOptions};
 use crate::models::nfts::*;
+use crate::http::request::RequestOptions;
+use crate::pagination::{Page, PageStream, Paginator};
 use crate::services::ServiceContext;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Options for NFT queries.
@@ -9,7 +17,7 @@ use std::sync::Arc;
 pub struct NftOptions {
     pub page_number: Option<u32>,
     pub page_size: Option<u32>,
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub with_metadata: Option<bool>,
     pub no_spam: Option<bool>,
     pub with_uncached: Option<bool>,
@@ -22,30 +30,100 @@ impl NftOptions {
     pub fn new() -> Self { Self::default() }
     pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn with_metadata(mut self, v: bool) -> Self { self.with_metadata = Some(v); self }
     pub fn no_spam(mut self, v: bool) -> Self { self.no_spam = Some(v); self }
     pub fn with_uncached(mut self, v: bool) -> Self { self.with_uncached = Some(v); self }
     pub fn traits_filter<S: Into<String>>(mut self, v: S) -> Self { self.traits_filter = Some(v.into()); self }
     pub fn values_filter<S: Into<String>>(mut self, v: S) -> Self { self.values_filter = Some(v.into()); self }
     pub fn no_nft_asset_metadata(mut self, v: bool) -> Self { self.no_nft_asset_metadata = Some(v); self }
+
+    /// Set `traits_filter` and `values_filter` from `(trait, value)` pairs.
+    ///
+    /// Traits are listed once each in first-seen order and values in the
+    /// order given. Commas and `%` inside a name are percent-encoded so they
+    /// are not read as list separators.
+    pub fn trait_values<T, V>(mut self, pairs: impl IntoIterator<Item = (T, V)>) -> Self
+    where
+        T: Into<String>,
+        V: Into<String>,
+    {
+        let mut traits: Vec<String> = Vec::new();
+        let mut values: Vec<String> = Vec::new();
+        for (trait_name, value) in pairs {
+            let trait_name = encode_filter_component(&trait_name.into());
+            if !traits.contains(&trait_name) {
+                traits.push(trait_name);
+            }
+            values.push(encode_filter_component(&value.into()));
+        }
+        if traits.is_empty() {
+            self.traits_filter = None;
+            self.values_filter = None;
+        } else {
+            self.traits_filter = Some(traits.join(","));
+            self.values_filter = Some(values.join(","));
+        }
+        self
+    }
+}
+
+fn encode_filter_component(raw: &str) -> String {
+    raw.trim().replace('%', "%25").replace(',', "%2C")
+}
+
+impl EndpointOptions for NftOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.with_metadata { pairs.push(("with-metadata", v.to_string())); }
+        if let Some(v) = &self.no_spam { pairs.push(("no-spam", v.to_string())); }
+        if let Some(v) = &self.with_uncached { pairs.push(("with-uncached", v.to_string())); }
+        if let Some(v) = &self.traits_filter { pairs.push(("traits-filter", v.clone())); }
+        if let Some(v) = &self.values_filter { pairs.push(("values-filter", v.clone())); }
+        if let Some(v) = &self.no_nft_asset_metadata { pairs.push(("no-nft-asset-metadata", v.to_string())); }
+        pairs
+    }
+}
+
+/// Options for NFT transaction queries on a single token id.
+#[derive(Debug, Clone, Default)]
+pub struct NftTransactionsOptions {
+    pub page_number: Option<u32>,
+    pub page_size: Option<u32>,
+    pub starting_block: Option<u64>,
+    pub ending_block: Option<u64>,
+    pub quote_currency: Option<QuoteCurrency>,
+    pub no_spam: Option<bool>,
+}
+
+impl NftTransactionsOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
+    pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
+    pub fn starting_block(mut self, v: u64) -> Self { self.starting_block = Some(v); self }
+    pub fn ending_block(mut self, v: u64) -> Self { self.ending_block = Some(v); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
+    pub fn no_spam(mut self, v: bool) -> Self { self.no_spam = Some(v); self }
 }
 
-impl QueryParams for NftOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.with_metadata { builder = builder.query(&[("with-metadata", v.to_string())]); }
-        if let Some(v) = self.no_spam { builder = builder.query(&[("no-spam", v.to_string())]); }
-        if let Some(v) = self.with_uncached { builder = builder.query(&[("with-uncached", v.to_string())]); }
-        if let Some(v) = self.traits_filter { builder = builder.query(&[("traits-filter", v)]); }
-        if let Some(v) = self.values_filter { builder = builder.query(&[("values-filter", v)]); }
-        if let Some(v) = self.no_nft_asset_metadata { builder = builder.query(&[("no-nft-asset-metadata", v.to_string())]); }
-        builder
+impl EndpointOptions for NftTransactionsOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        if let Some(v) = &self.starting_block { pairs.push(("starting-block", v.to_string())); }
+        if let Some(v) = &self.ending_block { pairs.push(("ending-block", v.to_string())); }
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.no_spam { pairs.push(("no-spam", v.to_string())); }
+        pairs
     }
 }
 
+impl_paged_options!(NftOptions, NftTransactionsOptions);
+
 /// Service for NFT-related API endpoints.
 pub struct NftService {
     ctx: Arc<ServiceContext>,
@@ -56,20 +134,36 @@ impl NftService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get NFTs owned by an address.
     pub async fn get_nfts_for_address(
-        &self, chain_name: impl AsRef<str>, address: &str, options: Option<NftOptions>,
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>, options: Option<NftOptions>,
     ) -> Result<NftsResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/balances_nft/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_nfts_for_address`](Self::get_nfts_for_address), page by page.
+    pub fn get_nfts_for_address_paged(
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>, options: Option<NftOptions>,
+    ) -> Paginator<NftItem> {
+        let address: AddressParam = address.into();
+        let path = format!("/v1/{}/address/{}/balances_nft/", chain_name.as_ref(), address);
+        self.ctx.paginate(path, options, |data: NftsData| data.items)
+    }
+
     /// Get metadata for a specific NFT.
     pub async fn get_nft_metadata(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, token_id: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, token_id: &str,
     ) -> Result<NftMetadataResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/tokens/{}/nft_metadata/{}/", chain_name.as_ref(), contract_address, token_id);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
@@ -77,21 +171,23 @@ impl NftService {
 
     /// Get all NFTs from a specific collection (token IDs).
     pub async fn get_nfts_for_collection(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, options: Option<NftOptions>,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, options: Option<NftOptions>,
     ) -> Result<NftsResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/tokens/{}/nft_token_ids/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
     /// Get NFT owners for a collection.
     pub async fn get_nft_owners_for_collection(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, options: Option<NftOptions>,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, options: Option<NftOptions>,
     ) -> Result<NftsResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/tokens/{}/nft_token_owners/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -101,42 +197,265 @@ impl NftService {
     ) -> Result<ChainCollectionsResponse, Error> {
         let path = format!("/v1/{}/nft/collections/", chain_name.as_ref());
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_chain_collections`](Self::get_chain_collections), page by page.
+    pub fn get_chain_collections_paged(
+        &self, chain_name: impl AsRef<str>, options: Option<NftOptions>,
+    ) -> Paginator<ChainCollectionItem> {
+        let path = format!("/v1/{}/nft/collections/", chain_name.as_ref());
+        self.ctx.paginate(path, options, |data: ChainCollectionsData| data.items)
+    }
+
     /// Get token IDs for a contract with metadata.
     pub async fn get_token_ids_for_contract_with_metadata(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, options: Option<NftOptions>,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, options: Option<NftOptions>,
     ) -> Result<NftsResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft/{}/metadata/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Stream every token in a collection carrying the given trait values,
+    /// fetching pages of `nft/{contract}/metadata` on demand.
+    ///
+    /// Pairs are applied with [`NftOptions::trait_values`] on top of `options`;
+    /// paging starts at `options.page_number`, or the first page if unset.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use goldrush_sdk::{Chain, GoldRushClient};
+    ///
+    /// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+    /// let mut tokens = client.nft_service().tokens_with_traits(
+    ///     Chain::EthereumMainnet,
+    ///     "0x...",
+    ///     vec![("Background", "Blue"), ("Eyes", "Laser")],
+    ///     None,
+    /// );
+    /// while let Some(token) = tokens.next().await {
+    ///     println!("{}", token?.token_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tokens_with_traits<T, V>(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
+        traits: Vec<(T, V)>, options: Option<NftOptions>,
+    ) -> PageStream<NftItem>
+    where
+        T: Into<String>,
+        V: Into<String>,
+    {
+        let ctx = Arc::clone(&self.ctx);
+        let chain_name = chain_name.as_ref().to_string();
+        let contract_address: AddressParam = contract_address.into();
+        let options = options.unwrap_or_default().trait_values(traits);
+        let start_page = options.page_number.unwrap_or(0);
+
+        PageStream::new(start_page, move |page| {
+            let service = NftService { ctx: Arc::clone(&ctx) };
+            let chain_name = chain_name.clone();
+            let contract_address = contract_address.clone();
+            let options = options.at_page(page);
+            async move {
+                let response = service
+                    .get_token_ids_for_contract_with_metadata(chain_name, contract_address, Some(options))
+                    .await?;
+                Ok(Page::from_response(response, |data| data.items))
+            }
+        })
+    }
+
     /// Get NFT transactions for a contract and token ID.
     pub async fn get_nft_transactions_for_contract_token_id(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, token_id: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, token_id: &str,
+        options: Option<NftTransactionsOptions>,
     ) -> Result<NftTransactionsResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/tokens/{}/nft_transactions/{}/", chain_name.as_ref(), contract_address, token_id);
         let builder = self.ctx.get(&path);
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Stream every transaction for a token id, fetching pages on demand.
+    ///
+    /// Paging starts at `options.page_number`, or the first page if unset.
+    pub fn nft_transactions_stream(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, token_id: &str,
+        options: Option<NftTransactionsOptions>,
+    ) -> PageStream<NftTransactionItem> {
+        let ctx = Arc::clone(&self.ctx);
+        let chain_name = chain_name.as_ref().to_string();
+        let contract_address: AddressParam = contract_address.into();
+        let token_id = token_id.to_string();
+        let options = options.unwrap_or_default();
+        let start_page = options.page_number.unwrap_or(0);
+
+        PageStream::new(start_page, move |page| {
+            let service = NftService { ctx: Arc::clone(&ctx) };
+            let chain_name = chain_name.clone();
+            let contract_address = contract_address.clone();
+            let token_id = token_id.clone();
+            let options = options.at_page(page);
+            async move {
+                let response = service
+                    .get_nft_transactions_for_contract_token_id(chain_name, contract_address, &token_id, Some(options))
+                    .await?;
+                Ok(Page::from_response(response, |data| data.items))
+            }
+        })
+    }
+
+    /// Transfers of every token in a collection from `from_block` on, oldest first.
+    ///
+    /// Token ids are paged from `nft_token_ids`, each token's transactions from
+    /// `from_block` on are paged concurrently, and the results are merged and ordered by block
+    /// height, transaction hash and token id. Ordering requires the whole
+    /// history, so nothing is yielded until every token has been fetched; any
+    /// failed fetch ends the stream with that error.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use goldrush_sdk::{Chain, GoldRushClient, NftActivityCheckpoint};
+    ///
+    /// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+    /// let mut activity = client.nft_service().stream_collection_activity(Chain::EthereumMainnet, "0x...", 19_000_000);
+    /// let mut checkpoint = None;
+    /// while let Some(tx) = activity.next().await {
+    ///     let tx = tx?;
+    ///     checkpoint = NftActivityCheckpoint::from_item(&tx).or(checkpoint);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_collection_activity(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, from_block: u64,
+    ) -> PageStream<NftTransactionItem> {
+        self.collection_activity(chain_name.as_ref(), contract_address.into(), from_block, None)
+    }
+
+    /// Continue a [`stream_collection_activity`](Self::stream_collection_activity)
+    /// feed with the items after `checkpoint`.
+    pub fn resume_collection_activity(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, checkpoint: NftActivityCheckpoint,
+    ) -> PageStream<NftTransactionItem> {
+        let from_block = checkpoint.block_height;
+        self.collection_activity(chain_name.as_ref(), contract_address.into(), from_block, Some(checkpoint))
+    }
+
+    fn collection_activity(
+        &self, chain_name: &str, contract_address: AddressParam, from_block: u64, after: Option<NftActivityCheckpoint>,
+    ) -> PageStream<NftTransactionItem> {
+        let ctx = Arc::clone(&self.ctx);
+        let chain_name = chain_name.to_string();
+
+        PageStream::new(0, move |_| {
+            let service = NftService { ctx: Arc::clone(&ctx) };
+            let chain_name = chain_name.clone();
+            let contract_address = contract_address.clone();
+            let after = after.clone();
+            async move {
+                let token_ids = service.collection_token_ids(&chain_name, &contract_address).await?;
+                let service = Arc::new(service);
+                let tasks: Vec<_> = token_ids.into_iter().map(|token_id| {
+                    let service = Arc::clone(&service);
+                    let chain_name = chain_name.clone();
+                    let contract_address = contract_address.clone();
+                    move || {
+                        let service = Arc::clone(&service);
+                        let chain_name = chain_name.clone();
+                        let contract_address = contract_address.clone();
+                        let token_id = token_id.clone();
+                        async move {
+                            let items = service
+                                .token_history(&chain_name, &contract_address, &token_id, from_block)
+                                .await?;
+                            Ok((token_id, items))
+                        }
+                    }
+                }).collect();
+
+                // send_with_retry already retries each request.
+                let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+                let histories = executor.run(tasks).await.outcomes.into_iter().collect::<Result<Vec<_>, Error>>()?;
+                let items = merge_activity(histories, from_block, after.as_ref());
+                let total_count = Some(items.len() as u64);
+                Ok(Page { items, has_more: false, total_count })
+            }
+        })
+    }
+
+    /// Every transaction for one token id from `from_block` on, across all pages.
+    async fn token_history(
+        &self, chain_name: &str, contract_address: &AddressParam, token_id: &str, from_block: u64,
+    ) -> Result<Vec<NftTransactionItem>, Error> {
+        let mut items = Vec::new();
+        let mut page_number = 0;
+        loop {
+            let options = NftTransactionsOptions::new().page_number(page_number).page_size(100).starting_block(from_block);
+            let response = self
+                .get_nft_transactions_for_contract_token_id(chain_name, contract_address.clone(), token_id, Some(options))
+                .await?;
+            let page = Page::from_response(response, |data| data.items);
+            items.extend(page.items);
+            if !page.has_more {
+                return Ok(items);
+            }
+            page_number += 1;
+        }
+    }
+
+    /// Every token id in a collection, across all pages.
+    async fn collection_token_ids(&self, chain_name: &str, contract_address: &AddressParam) -> Result<Vec<String>, Error> {
+        let mut token_ids = Vec::new();
+        let mut page_number = 0;
+        loop {
+            let options = NftOptions::new().page_number(page_number).page_size(100);
+            let response = self.get_nfts_for_collection(chain_name, contract_address.clone(), Some(options)).await?;
+            let page = Page::from_response(response, |data| data.items);
+            token_ids.extend(page.items.into_iter().map(|item| item.token_id));
+            if !page.has_more {
+                return Ok(token_ids);
+            }
+            page_number += 1;
+        }
+    }
+
     /// Get traits for a collection.
     pub async fn get_traits_for_collection(
-        &self, chain_name: impl AsRef<str>, contract_address: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
     ) -> Result<TraitsResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft/{}/traits/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_traits_for_collection`](Self::get_traits_for_collection) as a
+    /// [`Paginator`]; the endpoint isn't paginated, so there is one page.
+    pub fn get_traits_for_collection_paged(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
+    ) -> Paginator<TraitItem> {
+        let service = NftService { ctx: Arc::clone(&self.ctx) };
+        let chain_name = chain_name.as_ref().to_string();
+        let contract_address: AddressParam = contract_address.into();
+        Paginator::single(async move {
+            let response = service.get_traits_for_collection(chain_name, contract_address).await?;
+            Ok(response.data.map(|data| data.items).unwrap_or_default())
+        })
+    }
+
     /// Get attributes for a trait in a collection.
     pub async fn get_attributes_for_trait_in_collection(
-        &self, chain_name: impl AsRef<str>, contract_address: &str, trait_name: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, trait_name: &str,
     ) -> Result<AttributesResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft/{}/traits/{}/attributes/", chain_name.as_ref(), contract_address, trait_name);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
@@ -144,8 +463,9 @@ impl NftService {
 
     /// Get collection traits summary.
     pub async fn get_collection_traits_summary(
-        &self, chain_name: impl AsRef<str>, contract_address: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
     ) -> Result<TraitsSummaryResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft/{}/traits_summary/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
@@ -153,8 +473,9 @@ impl NftService {
 
     /// Get historical floor prices for an NFT collection.
     pub async fn get_historical_floor_prices(
-        &self, chain_name: impl AsRef<str>, contract_address: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
     ) -> Result<FloorPricesResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft_market/{}/floor_price/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
@@ -162,8 +483,9 @@ impl NftService {
 
     /// Get historical volume for an NFT collection.
     pub async fn get_historical_volume(
-        &self, chain_name: impl AsRef<str>, contract_address: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
     ) -> Result<VolumeResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft_market/{}/volume/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
@@ -171,28 +493,211 @@ impl NftService {
 
     /// Get historical sales count for an NFT collection.
     pub async fn get_historical_sales_count(
-        &self, chain_name: impl AsRef<str>, contract_address: &str,
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>,
     ) -> Result<SalesCountResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/nft_market/{}/sale_count/", chain_name.as_ref(), contract_address);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Floor price, volume and sales of a collection over the last `days`,
+    /// compared with the `days` before. The three series are fetched
+    /// concurrently. See [`CollectionStats`].
+    pub async fn collection_stats(
+        &self, chain_name: impl AsRef<str>, contract_address: impl Into<AddressParam>, days: u32,
+    ) -> Result<CollectionStats, Error> {
+        if days == 0 {
+            return Err(Error::Config("days must be at least 1".to_string()));
+        }
+        let chain_name = chain_name.as_ref();
+        let contract_address: AddressParam = contract_address.into();
+        // Both windows, from one request per series.
+        let window = days.saturating_mul(2);
+        let (floor, volume, sales) = tokio::join!(
+            self.get_market_series::<FloorPricesResponse>(chain_name, &contract_address, "floor_price", window),
+            self.get_market_series::<VolumeResponse>(chain_name, &contract_address, "volume", window),
+            self.get_market_series::<SalesCountResponse>(chain_name, &contract_address, "sale_count", window),
+        );
+        let (floor, volume, sales) = (floor?, volume?, sales?);
+        match (floor.data, volume.data, sales.data) {
+            (Some(floor), Some(volume), Some(sales)) => {
+                Ok(CollectionStats::build(contract_address.to_string(), days, &floor, &volume, &sales))
+            }
+            _ => Err(Error::Config("Floor price, volume or sales response has no data".to_string())),
+        }
+    }
+
+    async fn get_market_series<T>(
+        &self, chain_name: &str, contract_address: &AddressParam, series: &str, days: u32,
+    ) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned + crate::models::WithResponseMeta,
+    {
+        let path = format!("/v1/{}/nft_market/{}/{}/", chain_name, contract_address, series);
+        let builder = self.ctx.get(&path).query(&[("days", days.to_string())]);
+        self.ctx.send_with_retry(builder).await
+    }
+
     /// Check ownership in an NFT collection.
     pub async fn check_ownership_in_nft(
-        &self, chain_name: impl AsRef<str>, address: &str, contract_address: &str,
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>, contract_address: impl Into<AddressParam>,
     ) -> Result<OwnershipCheckResponse, Error> {
+        let address: AddressParam = address.into();
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/address/{}/collection/{}/", chain_name.as_ref(), address, contract_address);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Check ownership of many collections at once, concurrently.
+    ///
+    /// Returns every requested contract mapped to the token ids `address`
+    /// holds in it, empty when it holds none. Any failed check fails the call,
+    /// so a missing collection is never mistaken for "not owned".
+    pub async fn check_ownership_bulk<C: AsRef<str>>(
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>, contracts: &[C],
+    ) -> Result<HashMap<String, Vec<String>>, Error> {
+        let chain_name = chain_name.as_ref().to_string();
+        let address: AddressParam = address.into();
+        let service = Arc::new(NftService { ctx: Arc::clone(&self.ctx) });
+        let contracts: Vec<String> = contracts.iter().map(|c| c.as_ref().to_string()).collect();
+
+        let tasks: Vec<_> = contracts.iter().cloned().map(|contract| {
+            let service = Arc::clone(&service);
+            let chain_name = chain_name.clone();
+            let address = address.clone();
+            move || {
+                let service = Arc::clone(&service);
+                let chain_name = chain_name.clone();
+                let address = address.clone();
+                let contract = contract.clone();
+                async move { service.check_ownership_in_nft(chain_name, address, contract.as_str()).await }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let outcomes = executor.run(tasks).await.outcomes;
+        contracts
+            .into_iter()
+            .zip(outcomes)
+            .map(|(contract, outcome)| Ok((contract, owned_token_ids(outcome?))))
+            .collect()
+    }
+
     /// Check ownership in an NFT for a specific token ID.
     pub async fn check_ownership_in_nft_for_token_id(
-        &self, chain_name: impl AsRef<str>, address: &str, contract_address: &str, token_id: &str,
+        &self, chain_name: impl AsRef<str>, address: impl Into<AddressParam>, contract_address: impl Into<AddressParam>, token_id: &str,
     ) -> Result<OwnershipCheckResponse, Error> {
+        let address: AddressParam = address.into();
+        let contract_address: AddressParam = contract_address.into();
         let path = format!("/v1/{}/address/{}/collection/{}/token/{}/", chain_name.as_ref(), address, contract_address, token_id);
         let builder = self.ctx.get(&path);
         self.ctx.send_with_retry(builder).await
     }
 }
+
+/// Token ids held with a non-zero balance in an ownership check response.
+fn owned_token_ids(response: OwnershipCheckResponse) -> Vec<String> {
+    let Some(data) = response.data else { return Vec::new() };
+    if data.is_owner == Some(false) {
+        return Vec::new();
+    }
+    data.items
+        .into_iter()
+        .filter(|item| item.token_balance.as_ref().map_or(true, |b| !b.is_zero()))
+        .filter_map(|item| item.token_id)
+        .collect()
+}
+
+/// Merge per-token histories into one feed ordered by [`NftActivityCheckpoint`],
+/// keeping items at or above `from_block` and strictly after `after`.
+fn merge_activity(
+    histories: Vec<(String, Vec<NftTransactionItem>)>, from_block: u64, after: Option<&NftActivityCheckpoint>,
+) -> Vec<NftTransactionItem> {
+    let mut keyed: Vec<(NftActivityCheckpoint, NftTransactionItem)> = histories
+        .into_iter()
+        .flat_map(|(token_id, items)| {
+            items.into_iter().map(move |mut item| {
+                item.token_id.get_or_insert_with(|| token_id.clone());
+                item
+            })
+        })
+        .filter_map(|item| NftActivityCheckpoint::from_item(&item).map(|key| (key, item)))
+        .filter(|(key, _)| key.block_height >= from_block && after.map_or(true, |after| key > after))
+        .collect();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+    keyed.dedup_by(|a, b| a.0 == b.0);
+    keyed.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(block_height: u64, tx_hash: &str) -> NftTransactionItem {
+        serde_json::from_value(serde_json::json!({ "block_height": block_height, "tx_hash": tx_hash })).unwrap()
+    }
+
+    #[test]
+    fn test_nft_transactions_options() {
+        let options = NftTransactionsOptions::new().starting_block(100).ending_block(200).no_spam(true).page_size(50);
+        assert_eq!(options.cache_key(), "ending-block=200&no-spam=true&page-size=50&starting-block=100");
+        assert_eq!(options.at_page(3).page_number, Some(3));
+        assert!(NftTransactionsOptions::new().starting_block(2).ending_block(1).validate().is_err());
+    }
+
+    #[test]
+    fn test_trait_values_filters() {
+        let options = NftOptions::new().trait_values(vec![
+            ("Background", "Blue"),
+            ("Eyes", "Laser, Red"),
+            ("Background", "100%"),
+        ]);
+        assert_eq!(options.traits_filter.as_deref(), Some("Background,Eyes"));
+        assert_eq!(options.values_filter.as_deref(), Some("Blue,Laser%2C Red,100%25"));
+
+        let cleared = options.trait_values(Vec::<(String, String)>::new());
+        assert!(cleared.traits_filter.is_none() && cleared.values_filter.is_none());
+    }
+
+    #[test]
+    fn test_owned_token_ids() {
+        let response: OwnershipCheckResponse = serde_json::from_value(serde_json::json!({
+            "data": {
+                "is_owner": true,
+                "items": [
+                    { "token_id": "1", "token_balance": "1" },
+                    { "token_id": "2", "token_balance": "0" },
+                    { "token_id": "3" },
+                ],
+            },
+        }))
+        .unwrap();
+        assert_eq!(owned_token_ids(response), vec!["1", "3"]);
+
+        let not_owner: OwnershipCheckResponse = serde_json::from_value(serde_json::json!({
+            "data": { "is_owner": false, "items": [{ "token_id": "1", "token_balance": "1" }] },
+        }))
+        .unwrap();
+        assert!(owned_token_ids(not_owner).is_empty());
+    }
+
+    #[test]
+    fn test_merge_activity_orders_and_resumes() {
+        let histories = vec![
+            ("2".to_string(), vec![transfer(30, "0xc"), transfer(10, "0xa")]),
+            ("1".to_string(), vec![transfer(20, "0xb"), transfer(30, "0xc"), transfer(5, "0x0")]),
+        ];
+
+        let merged = merge_activity(histories.clone(), 10, None);
+        let keys: Vec<_> = merged.iter().map(|item| NftActivityCheckpoint::from_item(item).unwrap()).collect();
+        let order: Vec<(u64, &str)> = keys.iter().map(|k| (k.block_height, k.token_id.as_str())).collect();
+        assert_eq!(order, vec![(10, "2"), (20, "1"), (30, "1"), (30, "2")]);
+
+        let resumed = merge_activity(histories, keys[2].block_height, Some(&keys[2]));
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].token_id.as_deref(), Some("2"));
+    }
+}
@@ -1,8 +1,30 @@
+use crate::address::{normalize_address, AddressParam, EvmAddress};
+use crate::backfill::{self, QuoteBackfill};
+use crate::bulk::{BulkConfig, BulkExecutor};
 use crate::Error;
-use crate::http::query::QueryParams;
+use crate::types::QuoteCurrency;
+use crate::http::query::EndpointOptions;
+use crate::models::balances::Erc20TransfersData;
 use crate::models::pricing::*;
+use crate::http::request::RequestOptions;
 use crate::services::ServiceContext;
+use crate::decimal::to_f64;
+use crate::fx::{FxRate, Normalized, QuoteNormalizer};
+use crate::stablecoin::{self, DepegAlert, DepegConfig, StablecoinHolding};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Asset priced in both currencies to derive FX rates: WETH on Ethereum.
+const FX_REFERENCE_CHAIN: &str = "eth-mainnet";
+const FX_REFERENCE_TOKEN: &str = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+/// Most contracts the historical prices endpoint accepts in one call.
+pub const MAX_PRICE_CONTRACTS_PER_CALL: usize = 25;
 
 /// Options for token pricing queries.
 #[derive(Debug, Clone, Default)]
@@ -10,7 +32,7 @@ pub struct PricingOptions {
     pub from: Option<String>,
     pub to: Option<String>,
     pub prices_at_asc: Option<bool>,
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
 }
 
 impl PricingOptions {
@@ -18,16 +40,17 @@ impl PricingOptions {
     pub fn from<S: Into<String>>(mut self, v: S) -> Self { self.from = Some(v.into()); self }
     pub fn to<S: Into<String>>(mut self, v: S) -> Self { self.to = Some(v.into()); self }
     pub fn prices_at_asc(mut self, v: bool) -> Self { self.prices_at_asc = Some(v); self }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
 }
 
-impl QueryParams for PricingOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.from { builder = builder.query(&[("from", v)]); }
-        if let Some(v) = self.to { builder = builder.query(&[("to", v)]); }
-        if let Some(v) = self.prices_at_asc { builder = builder.query(&[("prices-at-asc", v.to_string())]); }
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        builder
+impl EndpointOptions for PricingOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.from { pairs.push(("from", v.clone())); }
+        if let Some(v) = &self.to { pairs.push(("to", v.clone())); }
+        if let Some(v) = &self.prices_at_asc { pairs.push(("prices-at-asc", v.to_string())); }
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        pairs
     }
 }
 
@@ -41,33 +64,293 @@ impl PricingService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get historical token prices.
     pub async fn get_token_prices(
         &self,
         chain_name: impl AsRef<str>,
         quote_currency: impl AsRef<str>,
-        contract_address: &str,
+        contract_address: impl Into<AddressParam>,
         options: Option<PricingOptions>,
     ) -> Result<TokenPricesResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!(
             "/v1/pricing/historical_by_addresses_v2/{}/{}/{}/",
             chain_name.as_ref(), quote_currency.as_ref(), contract_address
         );
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(o) => o.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Price series of many tokens at once, keyed by lowercase contract address.
+    ///
+    /// Contracts are de-duplicated and sent comma-separated in calls of at
+    /// most [`MAX_PRICE_CONTRACTS_PER_CALL`], which run concurrently. Every
+    /// requested contract is in the result, with no points when the API has
+    /// no price for it. Any failed call fails the whole lookup.
+    pub async fn get_token_prices_batch<C: AsRef<str>>(
+        &self,
+        chain_name: impl AsRef<str>,
+        quote_currency: impl AsRef<str>,
+        contracts: &[C],
+        options: Option<PricingOptions>,
+    ) -> Result<HashMap<String, Vec<PricePoint>>, Error> {
+        let mut seen = HashSet::new();
+        let mut unique = Vec::new();
+        for contract in contracts {
+            let contract = AddressParam::<EvmAddress>::from(contract.as_ref());
+            contract.validate()?;
+            if seen.insert(contract.as_str().to_string()) {
+                unique.push(contract.as_str().to_string());
+            }
+        }
+        let groups: Vec<String> = unique.chunks(MAX_PRICE_CONTRACTS_PER_CALL).map(|group| group.join(",")).collect();
+
+        let chain_name = chain_name.as_ref().to_string();
+        let quote_currency = quote_currency.as_ref().to_string();
+        let service = Arc::new(PricingService { ctx: Arc::clone(&self.ctx) });
+        let tasks: Vec<_> = groups.into_iter().map(|group| {
+            let service = Arc::clone(&service);
+            let chain_name = chain_name.clone();
+            let quote_currency = quote_currency.clone();
+            let options = options.clone();
+            move || {
+                let service = Arc::clone(&service);
+                let chain_name = chain_name.clone();
+                let quote_currency = quote_currency.clone();
+                let group = group.clone();
+                let options = options.clone();
+                async move { service.get_token_prices(chain_name, quote_currency, group.as_str(), options).await }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let mut prices: HashMap<String, Vec<PricePoint>> = unique.into_iter().map(|c| (c, Vec::new())).collect();
+        for outcome in executor.run(tasks).await.outcomes {
+            for item in outcome?.data.into_iter().flatten() {
+                let Some(contract) = item.contract_address.as_deref().map(normalize_address) else { continue };
+                if let Some(points) = prices.get_mut(&contract) {
+                    points.extend(item.prices.unwrap_or_default());
+                }
+            }
+        }
+        Ok(prices)
+    }
+
     /// Get pool spot prices.
     pub async fn get_pool_spot_prices(
         &self,
         chain_name: impl AsRef<str>,
-        contract_address: &str,
+        contract_address: impl Into<AddressParam>,
     ) -> Result<PoolSpotPricesResponse, Error> {
+        let contract_address: AddressParam = contract_address.into();
         let path = format!(
             "/v1/pricing/spot_prices/{}/pools/{}/",
             chain_name.as_ref(), contract_address
         );
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
+
+    /// Rate converting quotes in `from` into `to`, derived from the latest
+    /// price of a reference asset in both currencies.
+    pub async fn get_fx_rate(&self, from: QuoteCurrency, to: QuoteCurrency) -> Result<FxRate, Error> {
+        if from == to {
+            return Ok(FxRate::new(from, to, 1.0));
+        }
+        let (from_price, to_price) = tokio::join!(self.reference_price(from), self.reference_price(to));
+        let ((from_date, from_price), (to_date, to_price)) = (from_price?, to_price?);
+        let mut rate = FxRate::new(from, to, to_price / from_price);
+        // Report the older of the two price dates.
+        rate.as_of = match (from_date, to_date) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        Ok(rate)
+    }
+
+    /// A normalizer into `target` with a rate for each of `sources`.
+    pub async fn quote_normalizer(&self, target: QuoteCurrency, sources: &[QuoteCurrency]) -> Result<QuoteNormalizer, Error> {
+        let mut normalizer = QuoteNormalizer::new(target);
+        for &from in sources {
+            if from != target && normalizer.rate(from).is_none() {
+                normalizer = normalizer.with_rate(self.get_fx_rate(from, target).await?)?;
+            }
+        }
+        Ok(normalizer)
+    }
+
+    /// Convert every quote in `responses` into `target`, fetching one rate
+    /// per source currency found. See [`QuoteNormalizer::normalize`].
+    pub async fn normalize_quotes<T>(&self, target: QuoteCurrency, responses: &[T]) -> Result<Vec<Normalized<T>>, Error>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut sources = Vec::new();
+        for response in responses {
+            for currency in crate::fx::currencies_in(response)? {
+                if !sources.contains(&currency) {
+                    sources.push(currency);
+                }
+            }
+        }
+        let normalizer = self.quote_normalizer(target, &sources).await?;
+        responses.iter().map(|response| normalizer.normalize(response)).collect()
+    }
+
+    /// Fill in `quote_rate`, `delta_quote` and `balance_quote` on transfers
+    /// returned without a quote, from the token's price on the transfer's day.
+    /// See [`QuoteBackfill`].
+    ///
+    /// Makes one concurrent price request per token, covering every day it
+    /// needs a price for, in the response's quote currency.
+    pub async fn backfill_transfer_quotes(&self, data: &mut Erc20TransfersData) -> Result<QuoteBackfill, Error> {
+        let ranges = backfill::price_ranges(data);
+        if ranges.is_empty() {
+            return Ok(QuoteBackfill::default());
+        }
+        let chain_name = data
+            .chain_name
+            .clone()
+            .ok_or_else(|| Error::Config("Transfers response has no chain name".to_string()))?;
+        let quote_currency = data.quote_currency.clone().unwrap_or_else(|| QuoteCurrency::USD.to_string());
+
+        let service = Arc::new(PricingService { ctx: Arc::clone(&self.ctx) });
+        let tasks: Vec<_> = ranges.iter().map(|range| {
+            let service = Arc::clone(&service);
+            let chain_name = chain_name.clone();
+            let quote_currency = quote_currency.clone();
+            let range = range.clone();
+            move || {
+                let service = Arc::clone(&service);
+                let chain_name = chain_name.clone();
+                let quote_currency = quote_currency.clone();
+                let range = range.clone();
+                async move {
+                    let options = PricingOptions::new().from(range.from).to(range.to);
+                    let response = service
+                        .get_token_prices(chain_name, quote_currency, range.contract_address.as_str(), Some(options))
+                        .await?;
+                    let prices: Vec<_> = response
+                        .data
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|item| item.prices.unwrap_or_default())
+                        .filter_map(|point| Some(((range.contract_address.clone(), point.date?.get(..10)?.to_string()), point.price?)))
+                        .collect();
+                    Ok(prices)
+                }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let mut prices = HashMap::new();
+        for outcome in executor.run(tasks).await.outcomes {
+            prices.extend(outcome?);
+        }
+        Ok(QuoteBackfill { requests: ranges.len(), ..backfill::fill(data, &prices) })
+    }
+
+    /// Latest USD price of each distinct stablecoin in `holdings`, reported
+    /// for those off their peg by more than `threshold`. See [`DepegAlert`].
+    pub async fn check_depegs(&self, holdings: &[StablecoinHolding], threshold: f64) -> Result<Vec<DepegAlert>, Error> {
+        let prices = self.stablecoin_prices(holdings).await?;
+        Ok(prices.into_iter().filter(|alert| alert.is_depegged(threshold)).collect())
+    }
+
+    /// Check the stablecoins in `holdings` every `config.interval` in the
+    /// background, sending an alert when one moves off its peg and another,
+    /// with `recovered` set, when it is back.
+    ///
+    /// The watcher stops when the receiver is dropped or the client shuts
+    /// down. Failed checks are logged and retried on the next tick.
+    pub fn watch_depegs(
+        &self,
+        holdings: Vec<StablecoinHolding>,
+        config: DepegConfig,
+    ) -> Result<mpsc::Receiver<DepegAlert>, Error> {
+        let (tx, rx) = mpsc::channel(16);
+        let service = PricingService { ctx: Arc::clone(&self.ctx) };
+        self.ctx.lifecycle.spawn(move |shutdown| async move {
+            let mut depegged = HashSet::new();
+            let clock = Arc::clone(&service.ctx.config.clock);
+            // Check at once, then every interval.
+            let mut wait = Duration::ZERO;
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tx.closed() => break,
+                    _ = clock.sleep(wait) => {}
+                }
+                wait = config.interval;
+                let prices = match service.stablecoin_prices(&holdings).await {
+                    Ok(prices) => prices,
+                    Err(e) => {
+                        warn!(error = %e, "Stablecoin price check failed");
+                        continue;
+                    }
+                };
+                for mut alert in prices {
+                    let key = (alert.chain_name.clone(), alert.contract_address.clone());
+                    let send = if alert.is_depegged(config.threshold) {
+                        depegged.insert(key)
+                    } else {
+                        alert.recovered = true;
+                        depegged.remove(&key)
+                    };
+                    if send && tx.send(alert).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        })?;
+        Ok(rx)
+    }
+
+    /// Latest USD price of each distinct stablecoin in `holdings`, fetched concurrently.
+    async fn stablecoin_prices(&self, holdings: &[StablecoinHolding]) -> Result<Vec<DepegAlert>, Error> {
+        let service = Arc::new(PricingService { ctx: Arc::clone(&self.ctx) });
+        let tasks: Vec<_> = stablecoin::distinct_tokens(holdings).into_iter().map(|holding| {
+            let service = Arc::clone(&service);
+            move || {
+                let service = Arc::clone(&service);
+                let holding = holding.clone();
+                async move {
+                    let response = service
+                        .get_token_prices(&holding.chain_name, QuoteCurrency::USD, holding.contract_address.as_str(), None)
+                        .await?;
+                    let latest = response
+                        .data
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|item| item.prices.unwrap_or_default())
+                        .find_map(|point| Some((point.date, to_f64(point.price?))));
+                    Ok(latest.map(|(date, price)| DepegAlert::new(&holding, price, date)))
+                }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let outcomes = executor.run(tasks).await.outcomes;
+        Ok(outcomes.into_iter().collect::<Result<Vec<_>, Error>>()?.into_iter().flatten().collect())
+    }
+
+    /// Latest price date and price of the FX reference asset in `currency`.
+    async fn reference_price(&self, currency: QuoteCurrency) -> Result<(Option<String>, f64), Error> {
+        let response = self.get_token_prices(FX_REFERENCE_CHAIN, currency, FX_REFERENCE_TOKEN, None).await?;
+        response
+            .data
+            .into_iter()
+            .flatten()
+            .flat_map(|item| item.prices.unwrap_or_default())
+            .find_map(|point| Some((point.date, point.price.map(to_f64).filter(|p| *p > 0.0)?)))
+            .ok_or_else(|| Error::Config(format!("No {} price for the FX reference asset", currency)))
+    }
 }
@@ -1,8 +1,28 @@
+use crate::abi::event_topic;
+use crate::address::AddressParam;
+use crate::approval_history::{ApprovalHistory, APPROVAL_EVENT, APPROVAL_FOR_ALL_EVENT};
+use crate::exposure::ExposureReport;
 use crate::Error;
 use crate::models::approvals::*;
-use crate::services::ServiceContext;
+use crate::pagination::Paginator;
+use crate::http::request::RequestOptions;
+use crate::services::base_service::LogEventsByTopicOptions;
+use crate::services::{BalanceService, BaseService, ServiceContext};
 use std::sync::Arc;
 
+/// Options for [`SecurityService::get_approval_history`].
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalHistoryOptions {
+    pub starting_block: Option<u64>,
+    pub ending_block: Option<u64>,
+}
+
+impl ApprovalHistoryOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn starting_block(mut self, v: u64) -> Self { self.starting_block = Some(v); self }
+    pub fn ending_block(mut self, v: u64) -> Self { self.ending_block = Some(v); self }
+}
+
 /// Service for security/approval-related API endpoints.
 pub struct SecurityService {
     ctx: Arc<ServiceContext>,
@@ -13,23 +33,96 @@ impl SecurityService {
         Self { ctx }
     }
 
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
     /// Get ERC20 token approvals for an address.
     pub async fn get_approvals(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
     ) -> Result<ApprovalsResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/approvals/{}/", chain_name.as_ref(), address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
 
+    /// [`get_approvals`](Self::get_approvals) as a [`Paginator`]; the endpoint
+    /// isn't paginated, so there is one page.
+    pub fn get_approvals_paged(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+    ) -> Paginator<ApprovalItem> {
+        let service = SecurityService { ctx: Arc::clone(&self.ctx) };
+        let chain_name = chain_name.as_ref().to_string();
+        let address: AddressParam = address.into();
+        Paginator::single(async move {
+            let response = service.get_approvals(chain_name, address).await?;
+            Ok(response.data.map(|data| data.items).unwrap_or_default())
+        })
+    }
+
     /// Get NFT approvals for an address.
     pub async fn get_nft_approvals(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
     ) -> Result<NftApprovalsResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/nft/approvals/{}/", chain_name.as_ref(), address);
         self.ctx.send_with_retry(self.ctx.get(&path)).await
     }
+
+    /// Reconstruct the approvals `address` granted and revoked from its
+    /// `Approval` and `ApprovalForAll` logs. See [`ApprovalHistory`].
+    ///
+    /// Both events are fetched through the topic endpoint, filtered on the
+    /// owner, with every page of each walked.
+    pub async fn get_approval_history(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<ApprovalHistoryOptions>,
+    ) -> Result<ApprovalHistory, Error> {
+        let chain_name = chain_name.as_ref();
+        let owner = address.into().to_string();
+        let options = options.unwrap_or_default();
+        let mut topic_options = LogEventsByTopicOptions::new().secondary_topics(owner.as_str());
+        topic_options.starting_block = options.starting_block;
+        topic_options.ending_block = options.ending_block;
+
+        let base = BaseService::new(Arc::clone(&self.ctx));
+        let (approvals, for_all) = tokio::join!(
+            base.get_log_events_by_topic_hash_paged(chain_name, &event_topic(APPROVAL_EVENT), Some(topic_options.clone()))
+                .collect_all(),
+            base.get_log_events_by_topic_hash_paged(chain_name, &event_topic(APPROVAL_FOR_ALL_EVENT), Some(topic_options))
+                .collect_all(),
+        );
+        let (approvals, for_all) = (approvals?, for_all?);
+        Ok(ApprovalHistory::build(chain_name, &owner, approvals.iter().chain(&for_all)))
+    }
+
+    /// Fetch balances and approvals for an address and rank spenders by the
+    /// value they could move. See [`ExposureReport`].
+    pub async fn get_exposure_report(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+    ) -> Result<ExposureReport, Error> {
+        let chain_name = chain_name.as_ref();
+        let address: AddressParam = address.into();
+        let balances = BalanceService::new(Arc::clone(&self.ctx));
+        let (balances, approvals) = tokio::join!(
+            balances.get_token_balances_for_wallet_address(chain_name, address.clone(), None),
+            self.get_approvals(chain_name, address),
+        );
+        let (balances, approvals) = (balances?, approvals?);
+        match (balances.data, approvals.data) {
+            (Some(balances), Some(approvals)) => Ok(ExposureReport::build(&balances, &approvals)),
+            _ => Err(Error::Config("Balances or approvals response has no data".to_string())),
+        }
+    }
 }
@@ -0,0 +1,178 @@
+use crate::address::{AddressParam, SolanaAddress};
+use crate::Error;
+use crate::types::QuoteCurrency;
+use crate::chains::Chain;
+use crate::http::query::{impl_paged_options, EndpointOptions};
+use crate::models::solana::*;
+use crate::pagination::Paginator;
+use crate::http::request::RequestOptions;
+use crate::services::ServiceContext;
+use crate::validation::Validator;
+use std::sync::Arc;
+
+/// Options for Solana token balance queries.
+#[derive(Debug, Clone, Default)]
+pub struct SolanaBalancesOptions {
+    pub quote_currency: Option<QuoteCurrency>,
+    pub no_spam: Option<bool>,
+}
+
+impl SolanaBalancesOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
+    pub fn no_spam(mut self, v: bool) -> Self { self.no_spam = Some(v); self }
+}
+
+impl EndpointOptions for SolanaBalancesOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.no_spam { pairs.push(("no-spam", v.to_string())); }
+        pairs
+    }
+}
+
+/// Options for SPL transfer queries.
+#[derive(Debug, Clone, Default)]
+pub struct SplTransfersOptions {
+    pub quote_currency: Option<QuoteCurrency>,
+    pub mint_address: Option<String>,
+    pub page_number: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl SplTransfersOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
+    pub fn mint_address<S: Into<String>>(mut self, v: S) -> Self { self.mint_address = Some(v.into()); self }
+    pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
+    pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
+}
+
+impl EndpointOptions for SplTransfersOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.mint_address { pairs.push(("contract-address", v.clone())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
+    }
+}
+
+/// Options for Solana transaction queries.
+#[derive(Debug, Clone, Default)]
+pub struct SolanaTxOptions {
+    pub quote_currency: Option<QuoteCurrency>,
+    pub page_number: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl SolanaTxOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
+    pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
+    pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
+}
+
+impl EndpointOptions for SolanaTxOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        pairs
+    }
+}
+
+impl_paged_options!(SplTransfersOptions, SolanaTxOptions);
+
+/// Service for Solana-specific API endpoints.
+///
+/// Addresses are base58-encoded and validated before any request is sent.
+pub struct SolanaService {
+    ctx: Arc<ServiceContext>,
+}
+
+impl SolanaService {
+    pub(crate) fn new(ctx: Arc<ServiceContext>) -> Self {
+        Self { ctx }
+    }
+
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
+    /// Get SOL and SPL token balances for a Solana account.
+    pub async fn get_token_balances(
+        &self,
+        address: impl Into<AddressParam<SolanaAddress>>,
+        options: Option<SolanaBalancesOptions>,
+    ) -> Result<SolanaBalancesResponse, Error> {
+        let address: AddressParam<SolanaAddress> = address.into();
+        address.validate()?;
+        let path = format!("/v1/{}/address/{}/balances_v2/", Chain::SolanaMainnet, address);
+        let builder = self.ctx.get(&path);
+        let builder = self.ctx.apply_options(builder, options)?;
+        self.ctx.send_with_retry(builder).await
+    }
+
+    /// Get SPL token transfers for a Solana account.
+    pub async fn get_spl_transfers(
+        &self,
+        address: impl Into<AddressParam<SolanaAddress>>,
+        options: Option<SplTransfersOptions>,
+    ) -> Result<SplTransfersResponse, Error> {
+        let address: AddressParam<SolanaAddress> = address.into();
+        address.validate()?;
+        if let Some(mint) = options.as_ref().and_then(|o| o.mint_address.as_deref()) {
+            Validator::validate_solana_address(mint)?;
+        }
+        let path = format!("/v1/{}/address/{}/transfers_v2/", Chain::SolanaMainnet, address);
+        let builder = self.ctx.get(&path);
+        let builder = self.ctx.apply_options(builder, options)?;
+        self.ctx.send_with_retry(builder).await
+    }
+
+    /// [`get_spl_transfers`](Self::get_spl_transfers), page by page.
+    pub fn get_spl_transfers_paged(
+        &self,
+        address: impl Into<AddressParam<SolanaAddress>>,
+        options: Option<SplTransfersOptions>,
+    ) -> Result<Paginator<SplTransferItem>, Error> {
+        let address: AddressParam<SolanaAddress> = address.into();
+        address.validate()?;
+        if let Some(mint) = options.as_ref().and_then(|o| o.mint_address.as_deref()) {
+            Validator::validate_solana_address(mint)?;
+        }
+        let path = format!("/v1/{}/address/{}/transfers_v2/", Chain::SolanaMainnet, address);
+        Ok(self.ctx.paginate(path, options, |data: SplTransfersData| data.items))
+    }
+
+    /// Get transactions (with invoked programs) for a Solana account.
+    pub async fn get_transactions(
+        &self,
+        address: impl Into<AddressParam<SolanaAddress>>,
+        options: Option<SolanaTxOptions>,
+    ) -> Result<SolanaTransactionsResponse, Error> {
+        let address: AddressParam<SolanaAddress> = address.into();
+        address.validate()?;
+        let path = format!("/v1/{}/address/{}/transactions_v3/", Chain::SolanaMainnet, address);
+        let builder = self.ctx.get(&path);
+        let builder = self.ctx.apply_options(builder, options)?;
+        self.ctx.send_with_retry(builder).await
+    }
+
+    /// [`get_transactions`](Self::get_transactions), page by page.
+    pub fn get_transactions_paged(
+        &self,
+        address: impl Into<AddressParam<SolanaAddress>>,
+        options: Option<SolanaTxOptions>,
+    ) -> Result<Paginator<SolanaTransactionItem>, Error> {
+        let address: AddressParam<SolanaAddress> = address.into();
+        address.validate()?;
+        let path = format!("/v1/{}/address/{}/transactions_v3/", Chain::SolanaMainnet, address);
+        Ok(self.ctx.paginate(path, options, |data: SolanaTransactionsData| data.items))
+    }
+}
@@ -1,7 +1,19 @@
+use crate::address::AddressParam;
+use crate::checkpoint::{CheckpointStore, CheckpointedStream};
+use crate::activity::CalendarDate;
+use crate::bulk::{BulkConfig, BulkExecutor};
+use crate::counterparty::{Counterparties, CounterpartyReport};
+use crate::gas_spend::{ChainGasSpend, GasSpendReport, GasTally};
 use crate::Error;
-use crate::http::query::QueryParams;
-use crate::models::transactions::{TransactionsResponse, TransactionResponse, TransactionSummaryResponse, TimeBucketResponse};
+use crate::types::{ApiVersion, QuoteCurrency};
+use crate::http::query::{impl_paged_options, EndpointOptions};
+use crate::pagination::{CursorPage, CursorStream, CursorStreamOptions, Page, PageStream, Paginator};
+use crate::models::transactions::{TransactionItem, TransactionsData, TransactionsResponse, TransactionResponse, TransactionSummaryResponse, TimeBucketResponse};
+use crate::http::request::RequestOptions;
 use crate::services::ServiceContext;
+use futures_core::Stream;
+use std::future::poll_fn;
+use std::pin::Pin;
 use std::sync::Arc;
 
 /// Options for transaction queries.
@@ -9,7 +21,7 @@ use std::sync::Arc;
 pub struct TxOptions {
     pub page_number: Option<u32>,
     pub page_size: Option<u32>,
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub no_logs: Option<bool>,
     pub block_signed_at_asc: Option<bool>,
     pub with_internal: Option<bool>,
@@ -23,7 +35,7 @@ impl TxOptions {
     pub fn new() -> Self { Self::default() }
     pub fn page_number(mut self, v: u32) -> Self { self.page_number = Some(v); self }
     pub fn page_size(mut self, v: u32) -> Self { self.page_size = Some(v); self }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn no_logs(mut self, v: bool) -> Self { self.no_logs = Some(v); self }
     pub fn block_signed_at_asc(mut self, v: bool) -> Self { self.block_signed_at_asc = Some(v); self }
     pub fn with_internal(mut self, v: bool) -> Self { self.with_internal = Some(v); self }
@@ -33,26 +45,27 @@ impl TxOptions {
     pub fn ending_block(mut self, v: u64) -> Self { self.ending_block = Some(v); self }
 }
 
-impl QueryParams for TxOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.page_number { builder = builder.query(&[("page-number", v.to_string())]); }
-        if let Some(v) = self.page_size { builder = builder.query(&[("page-size", v.to_string())]); }
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.no_logs { builder = builder.query(&[("no-logs", v.to_string())]); }
-        if let Some(v) = self.block_signed_at_asc { builder = builder.query(&[("block-signed-at-asc", v.to_string())]); }
-        if let Some(v) = self.with_internal { builder = builder.query(&[("with-internal", v.to_string())]); }
-        if let Some(v) = self.with_state { builder = builder.query(&[("with-state", v.to_string())]); }
-        if let Some(v) = self.with_input_data { builder = builder.query(&[("with-input-data", v.to_string())]); }
-        if let Some(v) = self.starting_block { builder = builder.query(&[("starting-block", v.to_string())]); }
-        if let Some(v) = self.ending_block { builder = builder.query(&[("ending-block", v.to_string())]); }
-        builder
+impl EndpointOptions for TxOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.page_number { pairs.push(("page-number", v.to_string())); }
+        if let Some(v) = &self.page_size { pairs.push(("page-size", v.to_string())); }
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.no_logs { pairs.push(("no-logs", v.to_string())); }
+        if let Some(v) = &self.block_signed_at_asc { pairs.push(("block-signed-at-asc", v.to_string())); }
+        if let Some(v) = &self.with_internal { pairs.push(("with-internal", v.to_string())); }
+        if let Some(v) = &self.with_state { pairs.push(("with-state", v.to_string())); }
+        if let Some(v) = &self.with_input_data { pairs.push(("with-input-data", v.to_string())); }
+        if let Some(v) = &self.starting_block { pairs.push(("starting-block", v.to_string())); }
+        if let Some(v) = &self.ending_block { pairs.push(("ending-block", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for single transaction queries.
 #[derive(Debug, Clone, Default)]
 pub struct SingleTxOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub no_logs: Option<bool>,
     pub with_internal: Option<bool>,
     pub with_state: Option<bool>,
@@ -61,63 +74,85 @@ pub struct SingleTxOptions {
 
 impl SingleTxOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn no_logs(mut self, v: bool) -> Self { self.no_logs = Some(v); self }
     pub fn with_internal(mut self, v: bool) -> Self { self.with_internal = Some(v); self }
     pub fn with_state(mut self, v: bool) -> Self { self.with_state = Some(v); self }
     pub fn with_input_data(mut self, v: bool) -> Self { self.with_input_data = Some(v); self }
 }
 
-impl QueryParams for SingleTxOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.no_logs { builder = builder.query(&[("no-logs", v.to_string())]); }
-        if let Some(v) = self.with_internal { builder = builder.query(&[("with-internal", v.to_string())]); }
-        if let Some(v) = self.with_state { builder = builder.query(&[("with-state", v.to_string())]); }
-        if let Some(v) = self.with_input_data { builder = builder.query(&[("with-input-data", v.to_string())]); }
-        builder
+impl EndpointOptions for SingleTxOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.no_logs { pairs.push(("no-logs", v.to_string())); }
+        if let Some(v) = &self.with_internal { pairs.push(("with-internal", v.to_string())); }
+        if let Some(v) = &self.with_state { pairs.push(("with-state", v.to_string())); }
+        if let Some(v) = &self.with_input_data { pairs.push(("with-input-data", v.to_string())); }
+        pairs
     }
 }
 
+/// Options for [`TransactionService::gas_spend_report`].
+#[derive(Debug, Clone, Default)]
+pub struct GasSpendOptions {
+    /// First day counted, inclusive.
+    pub from: Option<CalendarDate>,
+    /// Last day counted, inclusive.
+    pub to: Option<CalendarDate>,
+    pub quote_currency: Option<QuoteCurrency>,
+}
+
+impl GasSpendOptions {
+    pub fn new() -> Self { Self::default() }
+    pub fn from(mut self, v: CalendarDate) -> Self { self.from = Some(v); self }
+    pub fn to(mut self, v: CalendarDate) -> Self { self.to = Some(v); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
+}
+
 /// Options for transaction summary queries.
 #[derive(Debug, Clone, Default)]
 pub struct TransactionSummaryOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
 }
 
 impl TransactionSummaryOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
 }
 
-impl QueryParams for TransactionSummaryOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        builder
+impl EndpointOptions for TransactionSummaryOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        pairs
     }
 }
 
 /// Options for time bucket transaction queries.
 #[derive(Debug, Clone, Default)]
 pub struct TimeBucketOptions {
-    pub quote_currency: Option<String>,
+    pub quote_currency: Option<QuoteCurrency>,
     pub no_logs: Option<bool>,
 }
 
 impl TimeBucketOptions {
     pub fn new() -> Self { Self::default() }
-    pub fn quote_currency<S: Into<String>>(mut self, c: S) -> Self { self.quote_currency = Some(c.into()); self }
+    pub fn quote_currency(mut self, c: QuoteCurrency) -> Self { self.quote_currency = Some(c); self }
     pub fn no_logs(mut self, v: bool) -> Self { self.no_logs = Some(v); self }
 }
 
-impl QueryParams for TimeBucketOptions {
-    fn apply_to(self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        if let Some(v) = self.quote_currency { builder = builder.query(&[("quote-currency", v)]); }
-        if let Some(v) = self.no_logs { builder = builder.query(&[("no-logs", v.to_string())]); }
-        builder
+impl EndpointOptions for TimeBucketOptions {
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.quote_currency { pairs.push(("quote-currency", v.to_string())); }
+        if let Some(v) = &self.no_logs { pairs.push(("no-logs", v.to_string())); }
+        pairs
     }
 }
 
+impl_paged_options!(TxOptions);
+
 /// Service for transaction-related API endpoints.
 pub struct TransactionService {
     ctx: Arc<ServiceContext>,
@@ -128,19 +163,99 @@ impl TransactionService {
         Self { ctx }
     }
 
-    /// Get all transactions for an address (v3).
+    /// Apply per-request options (e.g. extra headers) to calls made through this handle.
+    pub fn with_request_options(self, options: RequestOptions) -> Result<Self, Error> {
+        Ok(Self { ctx: Arc::new(self.ctx.with_request_options(&options)?) })
+    }
+
+    /// Get all transactions for an address (`transactions_v3`, or `transactions_v2`
+    /// when the configured [`ApiVersion`] is `V2`).
     pub async fn get_all_transactions_for_address(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<TxOptions>,
     ) -> Result<TransactionsResponse, Error> {
-        let path = format!("/v1/{}/address/{}/transactions_v3/", chain_name.as_ref(), address);
+        let address: AddressParam = address.into();
+        let path = format!(
+            "/v1/{}/address/{}/transactions_{}/",
+            chain_name.as_ref(),
+            address,
+            self.ctx.config.api_version
+        );
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// [`get_all_transactions_for_address`](Self::get_all_transactions_for_address),
+    /// page by page: following `links.next` on `transactions_v3`, or by page
+    /// number on `transactions_v2`.
+    pub fn get_all_transactions_for_address_paged(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<TxOptions>,
+    ) -> Paginator<TransactionItem> {
+        let address: AddressParam = address.into();
+        let items = |data: TransactionsData| data.items;
+        match self.ctx.config.api_version {
+            ApiVersion::V3 => {
+                let first = format!("/v1/{}/address/{}/transactions_v3/page/0/", chain_name.as_ref(), address);
+                self.ctx.paginate_links(first, options, items)
+            }
+            ApiVersion::V2 => {
+                let path = format!("/v1/{}/address/{}/transactions_v2/", chain_name.as_ref(), address);
+                self.ctx.paginate(path, options, items)
+            }
+        }
+    }
+
+    /// Stream every transaction for an address, oldest first, following the
+    /// `links.next` cursor of `transactions_v3` until the last page.
+    ///
+    /// `options` are sent with every page. See [`CursorStreamOptions`] for
+    /// fetching pages ahead.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use goldrush_sdk::{Chain, CursorStreamOptions, GoldRushClient, TxOptions};
+    ///
+    /// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+    /// let mut txs = client.transaction_service().stream_all_transactions_for_address(
+    ///     Chain::EthereumMainnet,
+    ///     "0x...",
+    ///     Some(TxOptions::new().no_logs(true)),
+    ///     Some(CursorStreamOptions::new().concurrency(4)),
+    /// );
+    /// while let Some(tx) = txs.next().await {
+    ///     println!("{}", tx?.tx_hash);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_all_transactions_for_address(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<TxOptions>,
+        stream_options: Option<CursorStreamOptions>,
+    ) -> CursorStream<TransactionItem> {
+        let ctx = Arc::clone(&self.ctx);
+        let address: AddressParam = address.into();
+        let first = format!("/v1/{}/address/{}/transactions_v3/page/0/", chain_name.as_ref(), address);
+
+        CursorStream::new(first, stream_options.unwrap_or_default(), move |path| {
+            let ctx = Arc::clone(&ctx);
+            let options = options.clone();
+            async move {
+                let builder = ctx.apply_options(ctx.get(&path), options)?;
+                let response: TransactionsResponse = ctx.send_with_retry(builder).await?;
+                Ok(CursorPage::from_response(response, |data| data.items))
+            }
+        })
+    }
+
     /// Get a specific transaction by hash.
     pub async fn get_transaction(
         &self,
@@ -150,7 +265,7 @@ impl TransactionService {
     ) -> Result<TransactionResponse, Error> {
         let path = format!("/v1/{}/transaction_v2/{}/", chain_name.as_ref(), tx_hash);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -158,12 +273,13 @@ impl TransactionService {
     pub async fn get_transaction_summary(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<TransactionSummaryOptions>,
     ) -> Result<TransactionSummaryResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/address/{}/transactions_summary/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -171,44 +287,197 @@ impl TransactionService {
     pub async fn get_earliest_transactions(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         options: Option<TxOptions>,
     ) -> Result<TransactionsResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/bulk/transactions/{}/", chain_name.as_ref(), address);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
-    /// Get paginated transactions for an address (v3, specific page).
+    /// Get one page of transactions for an address.
+    ///
+    /// With [`ApiVersion::V3`] the page is a path segment; with `V2` it is sent
+    /// as `page-number`, overriding any page number in `options`.
     pub async fn get_paginated_transactions(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         page: u32,
         options: Option<TxOptions>,
     ) -> Result<TransactionsResponse, Error> {
-        let path = format!("/v1/{}/address/{}/transactions_v3/page/{}/", chain_name.as_ref(), address, page);
+        let address: AddressParam = address.into();
+        let (path, options) = match self.ctx.config.api_version {
+            ApiVersion::V3 => (
+                format!("/v1/{}/address/{}/transactions_v3/page/{}/", chain_name.as_ref(), address, page),
+                options,
+            ),
+            ApiVersion::V2 => (
+                format!("/v1/{}/address/{}/transactions_v2/", chain_name.as_ref(), address),
+                Some(options.unwrap_or_default().page_number(page)),
+            ),
+        };
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
+    /// Stream every transaction for an address, fetching pages on demand.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use goldrush_sdk::{Chain, GoldRushClient, TxOptions};
+    ///
+    /// # async fn example(client: GoldRushClient) -> Result<(), goldrush_sdk::Error> {
+    /// let mut txs = client.transaction_service()
+    ///     .transactions_stream(Chain::EthereumMainnet, "0x...", Some(TxOptions::new().no_logs(true)));
+    ///
+    /// while let Some(tx) = txs.next().await {
+    ///     println!("{}", tx?.tx_hash);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transactions_stream(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<TxOptions>,
+    ) -> PageStream<TransactionItem> {
+        let service = TransactionService { ctx: Arc::clone(&self.ctx) };
+        let chain_name = chain_name.as_ref().to_string();
+        let address: AddressParam = address.into();
+
+        PageStream::new(0, move |page| {
+            let service = TransactionService { ctx: Arc::clone(&service.ctx) };
+            let chain_name = chain_name.clone();
+            let address = address.clone();
+            let options = options.clone();
+            async move {
+                let response = service.get_paginated_transactions(chain_name, address, page, options).await?;
+                Ok(Page::from_response(response, |data| data.items))
+            }
+        })
+    }
+
+    /// Rank the counterparties of `address` over its whole transaction history.
+    /// See [`CounterpartyReport`].
+    ///
+    /// Pages are fetched and folded in one at a time. Without `options`, logs
+    /// are left out of the responses, as the report doesn't use them.
+    pub async fn counterparty_report(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<TxOptions>,
+    ) -> Result<CounterpartyReport, Error> {
+        let chain_name = chain_name.as_ref();
+        let address: AddressParam = address.into();
+        let options = options.unwrap_or_else(|| TxOptions::new().no_logs(true));
+        let mut counterparties = Counterparties::new(chain_name, address.to_string());
+        let mut stream = self.transactions_stream(chain_name, address, Some(options));
+        while let Some(tx) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            counterparties.record(&tx?);
+        }
+        Ok(counterparties.into_report())
+    }
+
+    /// Sum the gas fees `address` paid on each of `chains` within the date
+    /// range, by chain and by contract called. See [`GasSpendReport`].
+    ///
+    /// Chains are walked concurrently, newest transaction first, up to the
+    /// start of the range. A chain that fails to load fails the report.
+    pub async fn gas_spend_report<C: AsRef<str>>(
+        &self,
+        chains: &[C],
+        address: impl Into<AddressParam>,
+        options: Option<GasSpendOptions>,
+    ) -> Result<GasSpendReport, Error> {
+        let address: AddressParam = address.into();
+        let options = options.unwrap_or_default();
+        let service = Arc::new(TransactionService { ctx: Arc::clone(&self.ctx) });
+        let tasks: Vec<_> = chains.iter().map(|chain| {
+            let service = Arc::clone(&service);
+            let chain_name = chain.as_ref().to_string();
+            let address = address.clone();
+            let options = options.clone();
+            move || {
+                let service = Arc::clone(&service);
+                let chain_name = chain_name.clone();
+                let address = address.clone();
+                let options = options.clone();
+                async move { service.chain_gas_spend(chain_name, address, options).await }
+            }
+        }).collect();
+
+        // send_with_retry already retries each request.
+        let executor = BulkExecutor::new(BulkConfig { max_retries: 0, ..BulkConfig::default() });
+        let chains = executor.run(tasks).await.outcomes.into_iter().collect::<Result<Vec<_>, Error>>()?;
+        Ok(GasSpendReport::new(address.to_string(), options.from, options.to, chains))
+    }
+
+    async fn chain_gas_spend(
+        &self,
+        chain_name: String,
+        address: AddressParam,
+        options: GasSpendOptions,
+    ) -> Result<ChainGasSpend, Error> {
+        let mut tx_options = TxOptions::new().no_logs(true);
+        tx_options.quote_currency = options.quote_currency;
+        let mut tally = GasTally::new(&chain_name, address.to_string(), options.from, options.to);
+        let mut stream = self.transactions_stream(&chain_name, address, Some(tx_options));
+        while let Some(tx) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            let tx = tx?;
+            if tally.is_before_range(&tx) {
+                break;
+            }
+            tally.record(&tx);
+        }
+        Ok(tally.into_chain())
+    }
+
+    /// Stream transactions after the block checkpointed in `store`, oldest first.
+    ///
+    /// Starts from `options.starting_block` when nothing is checkpointed yet,
+    /// and advances the checkpoint as transactions are consumed.
+    pub fn resume_transactions_stream(
+        &self,
+        chain_name: impl AsRef<str>,
+        address: impl Into<AddressParam>,
+        options: Option<TxOptions>,
+        store: Arc<dyn CheckpointStore>,
+    ) -> Result<CheckpointedStream<PageStream<TransactionItem>>, Error> {
+        let chain_name = chain_name.as_ref();
+        let address: AddressParam = address.into();
+        let key = address.to_string();
+        let mut options = options.unwrap_or_default().block_signed_at_asc(true);
+        if let Some(last) = store.last_block(chain_name, &key)? {
+            options = options.starting_block(last + 1);
+        }
+        let stream = self.transactions_stream(chain_name, address, Some(options));
+        Ok(CheckpointedStream::new(stream, store, chain_name, vec![key]))
+    }
+
     /// Get transactions in a time bucket.
     pub async fn get_time_bucket_transactions(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         time_bucket: u32,
         options: Option<TimeBucketOptions>,
     ) -> Result<TimeBucketResponse, Error> {
+        let address: AddressParam = address.into();
         let path = format!("/v1/{}/bulk/transactions/{}/{}/", chain_name.as_ref(), address, time_bucket);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
     /// Get transactions for a block by page number.
+    ///
+    /// Paging follows the configured [`ApiVersion`] as in [`get_paginated_transactions`](Self::get_paginated_transactions).
     pub async fn get_transactions_for_block_by_page(
         &self,
         chain_name: impl AsRef<str>,
@@ -216,9 +485,18 @@ impl TransactionService {
         page: u32,
         options: Option<TxOptions>,
     ) -> Result<TransactionsResponse, Error> {
-        let path = format!("/v1/{}/block/{}/transactions_v3/page/{}/", chain_name.as_ref(), block_height, page);
+        let (path, options) = match self.ctx.config.api_version {
+            ApiVersion::V3 => (
+                format!("/v1/{}/block/{}/transactions_v3/page/{}/", chain_name.as_ref(), block_height, page),
+                options,
+            ),
+            ApiVersion::V2 => (
+                format!("/v1/{}/block/{}/transactions_v2/", chain_name.as_ref(), block_height),
+                Some(options.unwrap_or_default().page_number(page)),
+            ),
+        };
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -231,7 +509,7 @@ impl TransactionService {
     ) -> Result<TransactionsResponse, Error> {
         let path = format!("/v1/{}/block_hash/{}/transactions_v3/", chain_name.as_ref(), block_hash);
         let builder = self.ctx.get(&path);
-        let builder = match options { Some(opts) => opts.apply_to(builder), None => builder };
+        let builder = self.ctx.apply_options(builder, options)?;
         self.ctx.send_with_retry(builder).await
     }
 
@@ -240,10 +518,11 @@ impl TransactionService {
     pub async fn get_transactions_for_address_v3(
         &self,
         chain_name: impl AsRef<str>,
-        address: &str,
+        address: impl Into<AddressParam>,
         page: u32,
         options: Option<TxOptions>,
     ) -> Result<TransactionsResponse, Error> {
+        let address: AddressParam = address.into();
         self.get_paginated_transactions(chain_name, address, page, options).await
     }
 }
@@ -0,0 +1,475 @@
+//! SQLite sink for fetched data.
+//!
+//! [`SqliteSink`] owns the schema and idempotent upserts for transactions,
+//! ERC-20 transfers, token balances and NFT holdings. [`SyncJob`] keeps a
+//! database incrementally up to date for a set of addresses, resuming each
+//! address from the last block it stored. [`SqliteSink`] is also a
+//! [`CheckpointStore`] for the resumable walkers.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{GoldRushClient, ClientConfig};
+//! use goldrush_sdk::sqlite::{SqliteSink, SyncJob};
+//!
+//! # async fn example() -> Result<(), goldrush_sdk::Error> {
+//! let client = GoldRushClient::new("cqt_your_api_key_here", ClientConfig::default())?;
+//! let sink = SqliteSink::open("wallets.db")?;
+//!
+//! let report = SyncJob::new(&client, &sink)
+//!     .address("eth-mainnet", "0x...")
+//!     .run()
+//!     .await?;
+//! println!("{} new transactions", report.transactions);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::checkpoint::CheckpointStore;
+use crate::decimal::to_f64;
+use crate::models::balances::BalanceItem;
+use crate::models::nfts::NftItem;
+use crate::models::transactions::{LogEvent, TransactionItem};
+use crate::{BalancesOptions, GoldRushClient, NftOptions, Result, TxOptions};
+use futures_core::stream::Stream;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::future::poll_fn;
+use std::path::Path;
+use std::pin::pin;
+use std::sync::Mutex;
+use tracing::{debug, instrument};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    chain_name      TEXT NOT NULL,
+    tx_hash         TEXT NOT NULL,
+    block_height    INTEGER,
+    block_signed_at TEXT,
+    from_address    TEXT NOT NULL,
+    to_address      TEXT,
+    value           TEXT NOT NULL,
+    successful      INTEGER,
+    gas_spent       INTEGER,
+    fees_paid       TEXT,
+    value_quote     REAL,
+    raw             TEXT NOT NULL,
+    PRIMARY KEY (chain_name, tx_hash)
+);
+CREATE INDEX IF NOT EXISTS transactions_from ON transactions (chain_name, from_address);
+CREATE INDEX IF NOT EXISTS transactions_to ON transactions (chain_name, to_address);
+
+CREATE TABLE IF NOT EXISTS transfers (
+    chain_name       TEXT NOT NULL,
+    tx_hash          TEXT NOT NULL,
+    transfer_index   INTEGER NOT NULL,
+    contract_address TEXT NOT NULL,
+    from_address     TEXT,
+    to_address       TEXT,
+    amount           TEXT,
+    block_height     INTEGER,
+    PRIMARY KEY (chain_name, tx_hash, transfer_index)
+);
+
+CREATE TABLE IF NOT EXISTS balances (
+    chain_name       TEXT NOT NULL,
+    address          TEXT NOT NULL,
+    contract_address TEXT NOT NULL,
+    symbol           TEXT,
+    decimals         INTEGER,
+    balance          TEXT NOT NULL,
+    quote_rate       REAL,
+    quote            REAL,
+    PRIMARY KEY (chain_name, address, contract_address)
+);
+
+CREATE TABLE IF NOT EXISTS nft_holdings (
+    chain_name       TEXT NOT NULL,
+    owner            TEXT NOT NULL,
+    contract_address TEXT NOT NULL,
+    token_id         TEXT NOT NULL,
+    token_balance    TEXT,
+    contract_name    TEXT,
+    PRIMARY KEY (chain_name, owner, contract_address, token_id)
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    chain_name        TEXT NOT NULL,
+    address           TEXT NOT NULL,
+    last_block_height INTEGER NOT NULL,
+    PRIMARY KEY (chain_name, address)
+);
+";
+
+/// A SQLite database holding fetched GoldRush data.
+#[derive(Debug)]
+pub struct SqliteSink {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteSink {
+    /// Open (or create) a database file and apply the schema.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// An in-memory database, mainly for tests.
+    pub fn in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Wrap an existing connection and apply the schema.
+    pub fn from_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Run `f` with the underlying connection, e.g. for ad-hoc queries.
+    pub fn with_connection<T>(&self, f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T> {
+        let conn = self.conn.lock().unwrap();
+        Ok(f(&conn)?)
+    }
+
+    /// Upsert transactions along with the ERC-20 transfers in their logs.
+    ///
+    /// Returns the number of transactions written.
+    pub fn upsert_transactions(&self, chain_name: &str, items: &[TransactionItem]) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let db = conn.transaction()?;
+        {
+            let mut tx_stmt = db.prepare_cached(
+                "INSERT INTO transactions (chain_name, tx_hash, block_height, block_signed_at, from_address,
+                     to_address, value, successful, gas_spent, fees_paid, value_quote, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                 ON CONFLICT (chain_name, tx_hash) DO UPDATE SET
+                     block_height = excluded.block_height, block_signed_at = excluded.block_signed_at,
+                     successful = excluded.successful, gas_spent = excluded.gas_spent,
+                     fees_paid = excluded.fees_paid, value_quote = excluded.value_quote, raw = excluded.raw",
+            )?;
+            let mut transfer_stmt = db.prepare_cached(
+                "INSERT INTO transfers (chain_name, tx_hash, transfer_index, contract_address, from_address,
+                     to_address, amount, block_height)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (chain_name, tx_hash, transfer_index) DO UPDATE SET
+                     from_address = excluded.from_address, to_address = excluded.to_address,
+                     amount = excluded.amount, block_height = excluded.block_height",
+            )?;
+
+            for tx in items {
+                tx_stmt.execute(params![
+                    chain_name,
+                    tx.tx_hash,
+                    tx.block_height.map(|h| h as i64),
+                    tx.block_signed_at,
+                    tx.from_address,
+                    tx.to_address,
+                    tx.value,
+                    tx.successful,
+                    tx.gas_units().map(|g| g as i64),
+                    tx.fees_paid,
+                    tx.value_quote,
+                    serde_json::to_string(tx)?,
+                ])?;
+
+                for (index, log) in tx.erc20_transfer_events().enumerate() {
+                    transfer_stmt.execute(params![
+                        chain_name,
+                        tx.tx_hash,
+                        index as i64,
+                        log.sender_contract_address,
+                        transfer_param(log, "from"),
+                        transfer_param(log, "to"),
+                        transfer_param(log, "value"),
+                        tx.block_height.map(|h| h as i64),
+                    ])?;
+                }
+            }
+        }
+        db.commit()?;
+        Ok(items.len())
+    }
+
+    /// Replace the stored token balances of `address`.
+    pub fn replace_balances(&self, chain_name: &str, address: &str, items: &[BalanceItem]) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let db = conn.transaction()?;
+        db.execute(
+            "DELETE FROM balances WHERE chain_name = ?1 AND address = ?2",
+            params![chain_name, address],
+        )?;
+        {
+            let mut stmt = db.prepare_cached(
+                "INSERT INTO balances (chain_name, address, contract_address, symbol, decimals, balance, quote_rate, quote)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (chain_name, address, contract_address) DO UPDATE SET
+                     balance = excluded.balance, quote_rate = excluded.quote_rate, quote = excluded.quote",
+            )?;
+            for item in items {
+                stmt.execute(params![
+                    chain_name,
+                    address,
+                    item.contract_address,
+                    item.contract_ticker_symbol,
+                    item.contract_decimals,
+                    item.balance.to_string(),
+                    item.quote_rate.map(to_f64),
+                    item.quote.map(to_f64),
+                ])?;
+            }
+        }
+        db.commit()?;
+        Ok(items.len())
+    }
+
+    /// Replace the stored NFT holdings of `owner`.
+    pub fn replace_nft_holdings(&self, chain_name: &str, owner: &str, items: &[NftItem]) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let db = conn.transaction()?;
+        db.execute(
+            "DELETE FROM nft_holdings WHERE chain_name = ?1 AND owner = ?2",
+            params![chain_name, owner],
+        )?;
+        {
+            let mut stmt = db.prepare_cached(
+                "INSERT INTO nft_holdings (chain_name, owner, contract_address, token_id, token_balance, contract_name)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT (chain_name, owner, contract_address, token_id) DO UPDATE SET
+                     token_balance = excluded.token_balance, contract_name = excluded.contract_name",
+            )?;
+            for item in items {
+                stmt.execute(params![
+                    chain_name,
+                    owner,
+                    item.contract_address,
+                    item.token_id,
+                    item.token_balance.as_ref().map(ToString::to_string),
+                    item.contract_name,
+                ])?;
+            }
+        }
+        db.commit()?;
+        Ok(items.len())
+    }
+
+    /// Highest block stored for `address`, used as the incremental sync checkpoint.
+    pub fn last_synced_block(&self, chain_name: &str, address: &str) -> Result<Option<u64>> {
+        let conn = self.conn.lock().unwrap();
+        let block: Option<i64> = conn.query_row(
+            "SELECT last_block_height FROM sync_state WHERE chain_name = ?1 AND address = ?2",
+            params![chain_name, address],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(block.map(|b| b as u64))
+    }
+
+    /// Advance the checkpoint for `address`; it never moves backwards.
+    pub fn set_last_synced_block(&self, chain_name: &str, address: &str, block: u64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sync_state (chain_name, address, last_block_height) VALUES (?1, ?2, ?3)
+             ON CONFLICT (chain_name, address) DO UPDATE SET
+                 last_block_height = MAX(last_block_height, excluded.last_block_height)",
+            params![chain_name, address, block as i64],
+        )?;
+        Ok(())
+    }
+}
+
+/// Checkpoints share the `sync_state` table with [`SyncJob`].
+impl CheckpointStore for SqliteSink {
+    fn last_block(&self, chain_name: &str, address: &str) -> Result<Option<u64>> {
+        self.last_synced_block(chain_name, address)
+    }
+
+    fn set_last_block(&self, chain_name: &str, address: &str, block: u64) -> Result<()> {
+        self.set_last_synced_block(chain_name, address, block)
+    }
+}
+
+fn transfer_param(log: &LogEvent, name: &str) -> Option<String> {
+    log.decoded.as_ref()?
+        .get("params")?
+        .as_array()?
+        .iter()
+        .find(|p| p.get("name").and_then(|n| n.as_str()) == Some(name))?
+        .get("value")
+        .and_then(|v| v.as_str().map(str::to_string).or_else(|| Some(v.to_string())))
+}
+
+/// Row counts written by a [`SyncJob`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub transactions: usize,
+    pub balances: usize,
+    pub nft_holdings: usize,
+}
+
+/// Keeps a [`SqliteSink`] incrementally up to date for a set of addresses.
+///
+/// Each run fetches only transactions above the stored checkpoint (oldest
+/// first, committing as it goes) and refreshes balances and, optionally, NFT
+/// holdings.
+pub struct SyncJob<'a> {
+    client: &'a GoldRushClient,
+    sink: &'a SqliteSink,
+    targets: Vec<(String, String)>,
+    include_nfts: bool,
+    page_size: u32,
+}
+
+impl<'a> SyncJob<'a> {
+    pub fn new(client: &'a GoldRushClient, sink: &'a SqliteSink) -> Self {
+        Self { client, sink, targets: Vec::new(), include_nfts: false, page_size: 100 }
+    }
+
+    /// Add an address to keep in sync.
+    pub fn address(mut self, chain_name: impl AsRef<str>, address: impl AsRef<str>) -> Self {
+        self.targets.push((chain_name.as_ref().to_string(), address.as_ref().trim().to_string()));
+        self
+    }
+
+    /// Also refresh NFT holdings.
+    pub fn include_nfts(mut self, include: bool) -> Self { self.include_nfts = include; self }
+
+    /// Page size used when fetching transactions and NFTs.
+    pub fn page_size(mut self, size: u32) -> Self { self.page_size = size; self }
+
+    /// Sync every address once.
+    #[instrument(skip(self), fields(targets = self.targets.len()))]
+    pub async fn run(&self) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+        for (chain_name, address) in &self.targets {
+            report.transactions += self.sync_transactions(chain_name, address).await?;
+            report.balances += self.sync_balances(chain_name, address).await?;
+            if self.include_nfts {
+                report.nft_holdings += self.sync_nfts(chain_name, address).await?;
+            }
+        }
+        Ok(report)
+    }
+
+    async fn sync_transactions(&self, chain_name: &str, address: &str) -> Result<usize> {
+        let mut options = TxOptions::new().page_size(self.page_size).block_signed_at_asc(true);
+        if let Some(last) = self.sink.last_synced_block(chain_name, address)? {
+            options = options.starting_block(last + 1);
+        }
+
+        let stream = self.client.transaction_service()
+            .transactions_stream(chain_name, address, Some(options));
+        let mut stream = pin!(stream);
+        let mut batch = Vec::with_capacity(self.page_size as usize);
+        let mut written = 0;
+
+        while let Some(tx) = next(&mut stream).await {
+            batch.push(tx?);
+            if batch.len() >= self.page_size as usize {
+                written += self.commit_transactions(chain_name, address, &mut batch)?;
+            }
+        }
+        written += self.commit_transactions(chain_name, address, &mut batch)?;
+        debug!(chain = %chain_name, address = %address, written = %written, "Transactions synced");
+        Ok(written)
+    }
+
+    fn commit_transactions(&self, chain_name: &str, address: &str, batch: &mut Vec<TransactionItem>) -> Result<usize> {
+        if batch.is_empty() {
+            return Ok(0);
+        }
+        let written = self.sink.upsert_transactions(chain_name, batch)?;
+        if let Some(max) = batch.iter().filter_map(|tx| tx.block_height).max() {
+            self.sink.set_last_synced_block(chain_name, address, max)?;
+        }
+        batch.clear();
+        Ok(written)
+    }
+
+    async fn sync_balances(&self, chain_name: &str, address: &str) -> Result<usize> {
+        let response = self.client.balance_service()
+            .get_token_balances_for_wallet_address(chain_name, address, None::<BalancesOptions>)
+            .await?;
+        let items = response.data.map(|d| d.items).unwrap_or_default();
+        self.sink.replace_balances(chain_name, address, &items)
+    }
+
+    async fn sync_nfts(&self, chain_name: &str, address: &str) -> Result<usize> {
+        let mut options = NftOptions::new().page_size(self.page_size).page_number(0);
+        let mut items = Vec::new();
+        loop {
+            let response = self.client.nft_service()
+                .get_nfts_for_address(chain_name, address, Some(options.clone()))
+                .await?;
+            if let Some(data) = response.data {
+                items.extend(data.items);
+            }
+            match response.pagination.and_then(|p| p.next_options(&options)) {
+                Some(next) => options = next,
+                None => break,
+            }
+        }
+        self.sink.replace_nft_holdings(chain_name, address, &items)
+    }
+}
+
+async fn next<S: Stream + ?Sized>(stream: &mut std::pin::Pin<&mut S>) -> Option<S::Item> {
+    poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(hash: &str, block: u64) -> TransactionItem {
+        serde_json::from_value(serde_json::json!({
+            "tx_hash": hash,
+            "from_address": "0xaaa",
+            "to_address": "0xtoken",
+            "value": "0",
+            "block_height": block,
+            "log_events": [{
+                "sender_contract_address": "0xtoken",
+                "decoded": {
+                    "name": "Transfer",
+                    "params": [
+                        { "name": "from", "value": "0xaaa" },
+                        { "name": "to", "value": "0xbbb" },
+                        { "name": "value", "value": "1000" },
+                    ],
+                },
+            }],
+        })).unwrap()
+    }
+
+    fn count(sink: &SqliteSink, table: &str) -> i64 {
+        sink.with_connection(|c| c.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |r| r.get(0))).unwrap()
+    }
+
+    #[test]
+    fn test_upserts_are_idempotent() {
+        let sink = SqliteSink::in_memory().unwrap();
+        let items = vec![tx("0x01", 10), tx("0x02", 11)];
+
+        sink.upsert_transactions("eth-mainnet", &items).unwrap();
+        sink.upsert_transactions("eth-mainnet", &items).unwrap();
+
+        assert_eq!(count(&sink, "transactions"), 2);
+        assert_eq!(count(&sink, "transfers"), 2);
+        let amount: String = sink.with_connection(|c| {
+            c.query_row("SELECT amount FROM transfers WHERE tx_hash = '0x01'", [], |r| r.get(0))
+        }).unwrap();
+        assert_eq!(amount, "1000");
+    }
+
+    #[test]
+    fn test_replace_balances_and_checkpoint() {
+        let sink = SqliteSink::in_memory().unwrap();
+        let balance = |contract: &str| -> BalanceItem {
+            serde_json::from_value(serde_json::json!({ "contract_address": contract, "balance": "5" })).unwrap()
+        };
+
+        sink.replace_balances("eth-mainnet", "0xaaa", &[balance("0x1"), balance("0x2")]).unwrap();
+        sink.replace_balances("eth-mainnet", "0xaaa", &[balance("0x3")]).unwrap();
+        assert_eq!(count(&sink, "balances"), 1);
+
+        assert_eq!(sink.last_synced_block("eth-mainnet", "0xaaa").unwrap(), None);
+        sink.set_last_synced_block("eth-mainnet", "0xaaa", 100).unwrap();
+        sink.set_last_synced_block("eth-mainnet", "0xaaa", 90).unwrap();
+        assert_eq!(sink.last_synced_block("eth-mainnet", "0xaaa").unwrap(), Some(100));
+    }
+}
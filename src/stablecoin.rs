@@ -0,0 +1,342 @@
+//! Stablecoin exposure by issuer, and depeg alerts.
+//!
+//! A [`StablecoinClassifier`] recognises stablecoins among listed tokens: a
+//! token list entry counts when it carries the `stablecoin` tag or its symbol
+//! has a known issuer. Unlisted contracts never count, so a token copying the
+//! USDC symbol is not mistaken for USDC.
+//!
+//! [`StablecoinExposure`] groups a portfolio's stablecoins by issuer, and
+//! [`PricingService::check_depegs`](crate::PricingService::check_depegs) and
+//! [`watch_depegs`](crate::PricingService::watch_depegs) compare their latest
+//! prices with the peg.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{DepegConfig, GoldRushClient, StablecoinClassifier, TokenList, TokenListRegistry};
+//!
+//! # async fn example(client: GoldRushClient, balances: goldrush_sdk::models::all_chains::MultiChainBalancesData)
+//! # -> Result<(), goldrush_sdk::Error> {
+//! let list = TokenList::fetch("https://tokens.coingecko.com/uniswap/all.json").await?;
+//! let classifier = StablecoinClassifier::new(TokenListRegistry::new().with_list(list));
+//!
+//! let exposure = classifier.exposure(&balances.items);
+//! for issuer in &exposure.issuers {
+//!     println!("{}: {:.2} ({:.0}%)", issuer.name(), issuer.quote, issuer.share * 100.0);
+//! }
+//!
+//! let mut alerts = client.pricing_service().watch_depegs(exposure.holdings, DepegConfig::default())?;
+//! while let Some(alert) = alerts.recv().await {
+//!     println!("{} at {:.4} ({:+.2}%)", alert.symbol, alert.price, alert.deviation * 100.0);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::normalize_address;
+use crate::models::all_chains::MultiChainBalanceItem;
+use crate::token_list::TokenListRegistry;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Token list tag marking stablecoins.
+const STABLECOIN_TAG: &str = "stablecoin";
+
+/// Issuers of widely held stablecoins, by symbol.
+const ISSUERS: &[(&str, &str)] = &[
+    ("USDC", "Circle"),
+    ("EURC", "Circle"),
+    ("USDT", "Tether"),
+    ("DAI", "Sky"),
+    ("USDS", "Sky"),
+    ("PYUSD", "Paxos"),
+    ("USDP", "Paxos"),
+    ("FDUSD", "First Digital"),
+    ("TUSD", "TrueUSD"),
+    ("USDE", "Ethena"),
+    ("FRAX", "Frax"),
+    ("LUSD", "Liquity"),
+    ("GHO", "Aave"),
+    ("CRVUSD", "Curve"),
+    ("GUSD", "Gemini"),
+];
+
+/// A token recognised as a stablecoin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stablecoin {
+    pub symbol: String,
+    pub issuer: Option<String>,
+    /// Target price in the quote currency.
+    pub peg: f64,
+}
+
+/// Recognises stablecoins among the tokens of a [`TokenListRegistry`].
+#[derive(Debug, Clone)]
+pub struct StablecoinClassifier {
+    registry: TokenListRegistry,
+    tag: String,
+    issuers: HashMap<String, String>,
+    pegs: HashMap<String, f64>,
+}
+
+impl StablecoinClassifier {
+    /// Classify against `registry`, with the bundled issuers and a peg of 1.0.
+    pub fn new(registry: TokenListRegistry) -> Self {
+        Self {
+            registry,
+            tag: STABLECOIN_TAG.to_string(),
+            issuers: ISSUERS.iter().map(|(symbol, issuer)| (symbol.to_string(), issuer.to_string())).collect(),
+            pegs: HashMap::new(),
+        }
+    }
+
+    /// Token list tag marking stablecoins; `stablecoin` by default.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// Attribute `symbol` to `issuer`, which also makes listed tokens with that symbol stablecoins.
+    pub fn issuer(mut self, symbol: &str, issuer: impl Into<String>) -> Self {
+        self.issuers.insert(symbol.to_ascii_uppercase(), issuer.into());
+        self
+    }
+
+    /// Target price of `symbol` in the quote currency, e.g. the USD rate for a euro stablecoin.
+    pub fn peg(mut self, symbol: &str, price: f64) -> Self {
+        self.pegs.insert(symbol.to_ascii_uppercase(), price);
+        self
+    }
+
+    /// The stablecoin at `address` on `chain_id`, if it is one.
+    pub fn classify(&self, chain_id: u64, address: &str) -> Option<Stablecoin> {
+        let entry = self.registry.lookup(chain_id, address)?;
+        let symbol = entry.symbol.to_ascii_uppercase();
+        let issuer = self.issuers.get(&symbol).cloned();
+        if issuer.is_none() && !entry.tags.iter().any(|t| t.eq_ignore_ascii_case(&self.tag)) {
+            return None;
+        }
+        Some(Stablecoin { peg: self.pegs.get(&symbol).copied().unwrap_or(1.0), symbol: entry.symbol.clone(), issuer })
+    }
+
+    /// Stablecoin holdings among `items`, grouped by issuer.
+    pub fn exposure(&self, items: &[MultiChainBalanceItem]) -> StablecoinExposure {
+        let mut holdings = Vec::new();
+        let mut portfolio_quote = 0.0;
+        for item in items {
+            portfolio_quote += item.quote.unwrap_or(0.0);
+            let (Some(chain_id), Some(address)) = (item.chain_id, item.contract_address.as_deref()) else {
+                continue;
+            };
+            let Some(stablecoin) = self.classify(chain_id, address) else {
+                continue;
+            };
+            holdings.push(StablecoinHolding {
+                chain_name: item.chain_name.clone().unwrap_or_else(|| chain_id.to_string()),
+                contract_address: normalize_address(address),
+                symbol: stablecoin.symbol,
+                issuer: stablecoin.issuer,
+                peg: stablecoin.peg,
+                balance: item.balance.clone(),
+                quote: item.quote.unwrap_or(0.0),
+            });
+        }
+        StablecoinExposure::new(holdings, portfolio_quote)
+    }
+}
+
+/// One stablecoin balance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StablecoinHolding {
+    pub chain_name: String,
+    pub contract_address: String,
+    pub symbol: String,
+    pub issuer: Option<String>,
+    pub peg: f64,
+    /// Raw balance in the token's smallest unit.
+    pub balance: Option<String>,
+    pub quote: f64,
+}
+
+/// Stablecoins of one issuer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuerExposure {
+    /// `None` for tagged stablecoins of an unknown issuer.
+    pub issuer: Option<String>,
+    pub quote: f64,
+    /// Fraction of all stablecoin holdings.
+    pub share: f64,
+    pub holdings: Vec<StablecoinHolding>,
+}
+
+impl IssuerExposure {
+    /// The issuer, or `unknown`.
+    pub fn name(&self) -> &str {
+        self.issuer.as_deref().unwrap_or("unknown")
+    }
+}
+
+/// A portfolio's stablecoins, by issuer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StablecoinExposure {
+    pub total_quote: f64,
+    /// Value of the whole portfolio, stablecoins included.
+    pub portfolio_quote: f64,
+    /// Largest exposure first.
+    pub issuers: Vec<IssuerExposure>,
+    pub holdings: Vec<StablecoinHolding>,
+}
+
+impl StablecoinExposure {
+    fn new(holdings: Vec<StablecoinHolding>, portfolio_quote: f64) -> Self {
+        let total_quote: f64 = holdings.iter().map(|h| h.quote).sum();
+        let mut by_issuer: HashMap<Option<String>, IssuerExposure> = HashMap::new();
+        for holding in &holdings {
+            let exposure = by_issuer.entry(holding.issuer.clone()).or_insert_with(|| IssuerExposure {
+                issuer: holding.issuer.clone(),
+                quote: 0.0,
+                share: 0.0,
+                holdings: Vec::new(),
+            });
+            exposure.quote += holding.quote;
+            exposure.holdings.push(holding.clone());
+        }
+        let mut issuers: Vec<IssuerExposure> = by_issuer.into_values().collect();
+        for issuer in &mut issuers {
+            issuer.share = if total_quote > 0.0 { issuer.quote / total_quote } else { 0.0 };
+        }
+        issuers.sort_by(|a, b| b.quote.total_cmp(&a.quote).then_with(|| a.issuer.cmp(&b.issuer)));
+        Self { total_quote, portfolio_quote, issuers, holdings }
+    }
+
+    /// Fraction of the portfolio held in stablecoins.
+    pub fn share_of_portfolio(&self) -> f64 {
+        if self.portfolio_quote > 0.0 { self.total_quote / self.portfolio_quote } else { 0.0 }
+    }
+}
+
+/// How [`PricingService::watch_depegs`](crate::PricingService::watch_depegs) monitors prices.
+#[derive(Debug, Clone)]
+pub struct DepegConfig {
+    /// Relative deviation from the peg that counts as a depeg, e.g. `0.005` for 0.5%.
+    pub threshold: f64,
+    /// Time between price checks.
+    pub interval: Duration,
+}
+
+impl Default for DepegConfig {
+    fn default() -> Self {
+        Self { threshold: 0.005, interval: Duration::from_secs(300) }
+    }
+}
+
+impl DepegConfig {
+    pub fn new() -> Self { Self::default() }
+    pub fn threshold(mut self, threshold: f64) -> Self { self.threshold = threshold; self }
+    pub fn interval(mut self, interval: Duration) -> Self { self.interval = interval; self }
+}
+
+/// A stablecoin trading away from its peg, or back at it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepegAlert {
+    pub chain_name: String,
+    pub contract_address: String,
+    pub symbol: String,
+    pub issuer: Option<String>,
+    pub peg: f64,
+    pub price: f64,
+    /// `(price - peg) / peg`.
+    pub deviation: f64,
+    /// Date of the price.
+    pub date: Option<String>,
+    /// Whether the price is back within the threshold; only sent by the watcher.
+    pub recovered: bool,
+}
+
+impl DepegAlert {
+    pub(crate) fn new(holding: &StablecoinHolding, price: f64, date: Option<String>) -> Self {
+        Self {
+            chain_name: holding.chain_name.clone(),
+            contract_address: holding.contract_address.clone(),
+            symbol: holding.symbol.clone(),
+            issuer: holding.issuer.clone(),
+            peg: holding.peg,
+            price,
+            deviation: (price - holding.peg) / holding.peg,
+            date,
+            recovered: false,
+        }
+    }
+
+    /// Whether the deviation exceeds `threshold`.
+    pub fn is_depegged(&self, threshold: f64) -> bool {
+        self.deviation.abs() > threshold
+    }
+}
+
+/// One entry per `(chain, contract)`, keeping the first holding of each.
+pub(crate) fn distinct_tokens(holdings: &[StablecoinHolding]) -> Vec<StablecoinHolding> {
+    let mut seen = std::collections::HashSet::new();
+    holdings
+        .iter()
+        .filter(|h| seen.insert((h.chain_name.clone(), h.contract_address.clone())))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token_list::TokenList;
+    use serde_json::json;
+
+    fn classifier() -> StablecoinClassifier {
+        let list = TokenList::from_json(&json!({
+            "name": "test",
+            "tokens": [
+                { "chainId": 1, "address": "0xUSDC", "name": "USD Coin", "symbol": "USDC", "decimals": 6 },
+                { "chainId": 8453, "address": "0xusdc2", "name": "USD Coin", "symbol": "USDC", "decimals": 6 },
+                { "chainId": 1, "address": "0xusdt", "name": "Tether", "symbol": "USDT", "decimals": 6 },
+                { "chainId": 1, "address": "0xnew", "name": "New Dollar", "symbol": "NUSD", "decimals": 18,
+                  "tags": ["stablecoin"] },
+                { "chainId": 1, "address": "0xweth", "name": "Wrapped Ether", "symbol": "WETH", "decimals": 18 },
+            ],
+        }).to_string())
+        .unwrap();
+        StablecoinClassifier::new(TokenListRegistry::new().with_list(list))
+    }
+
+    #[test]
+    fn test_exposure_by_issuer() {
+        let items: Vec<MultiChainBalanceItem> = serde_json::from_value(json!([
+            { "chain_id": 1, "chain_name": "eth-mainnet", "contract_address": "0xusdc", "quote": 60.0 },
+            { "chain_id": 8453, "chain_name": "base-mainnet", "contract_address": "0xUSDC2", "quote": 15.0 },
+            { "chain_id": 1, "chain_name": "eth-mainnet", "contract_address": "0xusdt", "quote": 20.0 },
+            { "chain_id": 1, "chain_name": "eth-mainnet", "contract_address": "0xnew", "quote": 5.0 },
+            { "chain_id": 1, "chain_name": "eth-mainnet", "contract_address": "0xweth", "quote": 100.0 },
+            { "chain_id": 1, "chain_name": "eth-mainnet", "contract_address": "0xfake", "quote": 50.0 },
+        ]))
+        .unwrap();
+
+        let exposure = classifier().exposure(&items);
+        assert_eq!(exposure.total_quote, 100.0);
+        assert_eq!(exposure.share_of_portfolio(), 0.4);
+        let issuers: Vec<(&str, f64)> = exposure.issuers.iter().map(|i| (i.name(), i.share)).collect();
+        assert_eq!(issuers, [("Circle", 0.75), ("Tether", 0.2), ("unknown", 0.05)]);
+        assert_eq!(exposure.issuers[0].holdings.len(), 2);
+    }
+
+    #[test]
+    fn test_depeg_alert() {
+        let classifier = classifier().peg("USDT", 0.5);
+        let stablecoin = classifier.classify(1, "0xusdt").unwrap();
+        assert_eq!((stablecoin.issuer.as_deref(), stablecoin.peg), (Some("Tether"), 0.5));
+        assert!(classifier.classify(1, "0xweth").is_none());
+
+        let holding = classifier.exposure(&serde_json::from_value::<Vec<MultiChainBalanceItem>>(json!([
+            { "chain_id": 1, "chain_name": "eth-mainnet", "contract_address": "0xusdc", "quote": 1.0 },
+        ])).unwrap()).holdings.remove(0);
+        let alert = DepegAlert::new(&holding, 0.97, None);
+        assert!((alert.deviation + 0.03).abs() < 1e-9);
+        assert!(alert.is_depegged(0.005));
+        assert!(!DepegAlert::new(&holding, 0.999, None).is_depegged(0.005));
+    }
+}
@@ -0,0 +1,416 @@
+//! Streaming Analytics
+//!
+//! Aggregators that turn raw pair update streams into trading metrics.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use async_stream::stream;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+
+use crate::address::normalize_address;
+use crate::decimal::to_f64;
+use crate::error::Result;
+use crate::models::streaming::UpdatePairsResponse;
+
+/// Estimates the price impact of a trade against a constant-product pool
+///
+/// `liquidity_usd` is the pool's total liquidity, assumed to be split evenly
+/// between both sides. Returns the fractional price move (0.01 = 1%).
+pub fn estimate_price_impact(liquidity_usd: f64, trade_usd: f64) -> f64 {
+    let reserve = liquidity_usd / 2.0;
+    if reserve <= 0.0 {
+        return 1.0;
+    }
+    let trade = trade_usd.max(0.0);
+    trade / (reserve + trade)
+}
+
+/// Estimated price impact for one trade size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PriceImpact {
+    /// Trade size in USD
+    pub trade_usd: f64,
+    /// Fractional price move (0.01 = 1%)
+    pub impact: f64,
+}
+
+/// Liquidity metrics for a pair computed from one update
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PairAnalyticsEvent {
+    pub chain_name: String,
+    pub pair_address: String,
+    pub timestamp: String,
+    /// Current pool liquidity in USD
+    pub liquidity: f64,
+    /// Liquidity change since the oldest update in the window
+    pub liquidity_change: f64,
+    /// Liquidity change as a fraction of the oldest value in the window
+    pub liquidity_change_pct: Option<f64>,
+    /// Reported USD volume divided by current liquidity
+    pub volume_liquidity_ratio: Option<f64>,
+    /// Estimated impact for each configured trade size
+    pub price_impact: Vec<PriceImpact>,
+}
+
+/// Rolling liquidity analytics over pair update streams
+///
+/// # Example
+/// ```no_run
+/// use goldrush_sdk::streaming::analytics::PairAnalytics;
+/// use futures_util::StreamExt;
+/// # use goldrush_sdk::*;
+/// # use goldrush_sdk::models::streaming::*;
+///
+/// # async fn example(service: StreamingService, params: UpdatePairsParams) -> Result<()> {
+/// let (updates, _handle) = service.subscribe_to_update_pairs(params).await?;
+/// let mut events = Box::pin(PairAnalytics::default().with_trade_sizes(vec![10_000.0]).analyze(updates));
+///
+/// while let Some(event) = events.next().await {
+///     let event = event?;
+///     println!("{} liquidity {:+.0} USD", event.pair_address, event.liquidity_change);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PairAnalytics {
+    window: Duration,
+    trade_sizes: Vec<f64>,
+    history: HashMap<String, VecDeque<(Instant, f64)>>,
+}
+
+impl Default for PairAnalytics {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300))
+    }
+}
+
+impl PairAnalytics {
+    /// Creates an aggregator measuring liquidity change over `window`
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            trade_sizes: vec![1_000.0, 10_000.0, 100_000.0],
+            history: HashMap::new(),
+        }
+    }
+
+    /// Sets the USD trade sizes to estimate price impact for
+    pub fn with_trade_sizes(mut self, sizes: Vec<f64>) -> Self {
+        self.trade_sizes = sizes;
+        self
+    }
+
+    /// Records an update received now
+    pub fn record(&mut self, update: &UpdatePairsResponse) -> PairAnalyticsEvent {
+        self.record_at(update, Instant::now())
+    }
+
+    /// Records an update received at `at`
+    pub fn record_at(&mut self, update: &UpdatePairsResponse, at: Instant) -> PairAnalyticsEvent {
+        let key = format!("{}:{}", update.chain_name, normalize_address(&update.pair_address));
+        let history = self.history.entry(key).or_default();
+        while history
+            .front()
+            .is_some_and(|(seen, _)| at.saturating_duration_since(*seen) > self.window)
+        {
+            history.pop_front();
+        }
+        let liquidity = to_f64(update.liquidity);
+        history.push_back((at, liquidity));
+
+        let baseline = history.front().map_or(liquidity, |(_, liquidity)| *liquidity);
+        let liquidity_change = liquidity - baseline;
+
+        PairAnalyticsEvent {
+            chain_name: update.chain_name.clone(),
+            pair_address: update.pair_address.clone(),
+            timestamp: update.timestamp.clone(),
+            liquidity,
+            liquidity_change,
+            liquidity_change_pct: (baseline > 0.0).then(|| liquidity_change / baseline),
+            volume_liquidity_ratio: (liquidity > 0.0).then(|| to_f64(update.volume_usd) / liquidity),
+            price_impact: self
+                .trade_sizes
+                .iter()
+                .map(|&trade_usd| PriceImpact {
+                    trade_usd,
+                    impact: estimate_price_impact(liquidity, trade_usd),
+                })
+                .collect(),
+        }
+    }
+
+    /// Maps a pair update stream into analytics events
+    pub fn analyze<S>(mut self, updates: S) -> impl Stream<Item = Result<PairAnalyticsEvent>>
+    where
+        S: Stream<Item = Result<UpdatePairsResponse>>,
+    {
+        stream! {
+            let mut updates = std::pin::pin!(updates);
+            while let Some(update) = updates.next().await {
+                yield update.map(|u| self.record(&u));
+            }
+        }
+    }
+}
+
+/// Lookback used to rank movers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoverPeriod {
+    Last5m,
+    #[default]
+    Last1hr,
+    Last6hr,
+    Last24hr,
+}
+
+/// A pair's position in a top-movers ranking
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mover {
+    pub chain_name: String,
+    pub pair_address: String,
+    pub base_symbol: Option<String>,
+    pub quote_rate_usd: f64,
+    /// Price change over the ranking period
+    pub price_delta: f64,
+    /// Swaps over the ranking period
+    pub swap_count: u32,
+}
+
+/// Ranked gainers and losers at a point in time
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MoversRanking {
+    /// Largest positive price deltas first
+    pub gainers: Vec<Mover>,
+    /// Largest negative price deltas first
+    pub losers: Vec<Mover>,
+}
+
+/// Ranks a pair universe by price change from update-pairs events
+///
+/// Only the latest update per pair is kept. Pairs that have not updated within
+/// the staleness window, or that traded fewer than `min_swaps` times over the
+/// period, are left out of rankings.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use goldrush_sdk::streaming::analytics::{MoverPeriod, TopMovers};
+/// use futures_util::StreamExt;
+/// # use goldrush_sdk::*;
+/// # use goldrush_sdk::models::streaming::*;
+///
+/// # async fn example(service: StreamingService, params: UpdatePairsParams) -> Result<()> {
+/// let (updates, _handle) = service.subscribe_to_update_pairs(params).await?;
+/// let movers = TopMovers::new(5).period(MoverPeriod::Last5m).min_swaps(10);
+/// let mut rankings = Box::pin(movers.track(updates, Duration::from_secs(30)));
+///
+/// while let Some(ranking) = rankings.next().await {
+///     for m in ranking?.gainers {
+///         println!("{} {:+.2}%", m.pair_address, m.price_delta);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopMovers {
+    top_n: usize,
+    period: MoverPeriod,
+    min_swaps: u32,
+    stale_after: Duration,
+    latest: HashMap<String, (Instant, Mover)>,
+}
+
+impl TopMovers {
+    /// Creates a detector reporting `top_n` gainers and losers
+    pub fn new(top_n: usize) -> Self {
+        Self {
+            top_n,
+            period: MoverPeriod::default(),
+            min_swaps: 0,
+            stale_after: Duration::from_secs(600),
+            latest: HashMap::new(),
+        }
+    }
+
+    /// Sets the lookback used for ranking
+    pub fn period(mut self, period: MoverPeriod) -> Self {
+        self.period = period;
+        self
+    }
+
+    /// Ignores pairs with fewer swaps than this over the period
+    pub fn min_swaps(mut self, swaps: u32) -> Self {
+        self.min_swaps = swaps;
+        self
+    }
+
+    /// Drops pairs that have not updated for this long
+    pub fn stale_after(mut self, window: Duration) -> Self {
+        self.stale_after = window;
+        self
+    }
+
+    /// Records an update received now
+    pub fn record(&mut self, update: &UpdatePairsResponse) {
+        self.record_at(update, Instant::now());
+    }
+
+    /// Records an update received at `at`
+    pub fn record_at(&mut self, update: &UpdatePairsResponse, at: Instant) {
+        let (price_delta, swap_count) = match self.period {
+            MoverPeriod::Last5m => (update.price_deltas.last_5m, update.swap_counts.last_5m),
+            MoverPeriod::Last1hr => (update.price_deltas.last_1hr, update.swap_counts.last_1hr),
+            MoverPeriod::Last6hr => (update.price_deltas.last_6hr, update.swap_counts.last_6hr),
+            MoverPeriod::Last24hr => (update.price_deltas.last_24hr, update.swap_counts.last_24hr),
+        };
+        let key = format!("{}:{}", update.chain_name, normalize_address(&update.pair_address));
+        let mover = Mover {
+            chain_name: update.chain_name.clone(),
+            pair_address: update.pair_address.clone(),
+            base_symbol: update.base_token.contract_ticker_symbol.clone(),
+            quote_rate_usd: to_f64(update.quote_rate_usd),
+            price_delta,
+            swap_count,
+        };
+        self.latest.insert(key, (at, mover));
+    }
+
+    /// Ranks pairs as of now
+    pub fn ranking(&mut self) -> MoversRanking {
+        self.ranking_at(Instant::now())
+    }
+
+    /// Ranks pairs as of `now`, evicting stale ones
+    pub fn ranking_at(&mut self, now: Instant) -> MoversRanking {
+        let stale_after = self.stale_after;
+        self.latest
+            .retain(|_, (seen, _)| now.saturating_duration_since(*seen) <= stale_after);
+
+        let mut eligible: Vec<&Mover> = self
+            .latest
+            .values()
+            .map(|(_, mover)| mover)
+            .filter(|m| m.swap_count >= self.min_swaps && m.price_delta.is_finite())
+            .collect();
+        eligible.sort_by(|a, b| b.price_delta.total_cmp(&a.price_delta));
+
+        let gainers = eligible
+            .iter()
+            .filter(|m| m.price_delta > 0.0)
+            .take(self.top_n)
+            .map(|m| (*m).clone())
+            .collect();
+        let losers = eligible
+            .iter()
+            .rev()
+            .filter(|m| m.price_delta < 0.0)
+            .take(self.top_n)
+            .map(|m| (*m).clone())
+            .collect();
+
+        MoversRanking { gainers, losers }
+    }
+
+    /// Consumes a pair update stream, emitting a ranking every `every`
+    ///
+    /// Stream errors are passed through; the ranking stream ends with the updates.
+    pub fn track<S>(mut self, updates: S, every: Duration) -> impl Stream<Item = Result<MoversRanking>>
+    where
+        S: Stream<Item = Result<UpdatePairsResponse>>,
+    {
+        stream! {
+            let mut updates = std::pin::pin!(updates);
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            ticker.tick().await;
+
+            loop {
+                tokio::select! {
+                    update = updates.next() => match update {
+                        Some(Ok(update)) => self.record(&update),
+                        Some(Err(e)) => yield Err(e),
+                        None => break,
+                    },
+                    _ = ticker.tick() => yield Ok(self.ranking()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(pair: &str, liquidity: f64, volume_usd: f64) -> UpdatePairsResponse {
+        mover_update(pair, liquidity, volume_usd, 0.0, 0)
+    }
+
+    fn mover_update(pair: &str, liquidity: f64, volume_usd: f64, delta_1hr: f64, swaps_1hr: u32) -> UpdatePairsResponse {
+        serde_json::from_value(serde_json::json!({
+            "chain_name": "BASE_MAINNET",
+            "pair_address": pair,
+            "timestamp": "2025-01-01T00:00:00Z",
+            "quote_rate": 1.0,
+            "quote_rate_usd": 1.0,
+            "volume": volume_usd,
+            "volume_usd": volume_usd,
+            "market_cap": 0.0,
+            "liquidity": liquidity,
+            "base_token": { "contract_name": "A", "contract_address": "0xa", "contract_decimals": 18, "contract_ticker_symbol": "A" },
+            "quote_token": { "contract_name": "B", "contract_address": "0xb", "contract_decimals": 18, "contract_ticker_symbol": "B" },
+            "price_deltas": { "last_5m": 0.0, "last_1hr": delta_1hr, "last_6hr": 0.0, "last_24hr": 0.0 },
+            "swap_counts": { "last_5m": 0, "last_1hr": swaps_1hr, "last_6hr": 0, "last_24hr": 0 },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_price_impact() {
+        assert_eq!(estimate_price_impact(200_000.0, 100_000.0), 0.5);
+        assert!((estimate_price_impact(2_000_000.0, 10_000.0) - 0.00990).abs() < 1e-5);
+        assert_eq!(estimate_price_impact(0.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_rolling_liquidity_change() {
+        let mut analytics = PairAnalytics::new(Duration::from_secs(60)).with_trade_sizes(vec![1_000.0]);
+        let start = Instant::now();
+
+        analytics.record_at(&update("0xPair", 100_000.0, 0.0), start);
+        let event = analytics.record_at(&update("0xpair", 80_000.0, 40_000.0), start + Duration::from_secs(30));
+        assert_eq!(event.liquidity_change, -20_000.0);
+        assert_eq!(event.liquidity_change_pct, Some(-0.2));
+        assert_eq!(event.volume_liquidity_ratio, Some(0.5));
+        assert_eq!(event.price_impact.len(), 1);
+
+        let event = analytics.record_at(&update("0xpair", 90_000.0, 0.0), start + Duration::from_secs(80));
+        assert_eq!(event.liquidity_change, 10_000.0);
+    }
+
+    #[test]
+    fn test_top_movers_ranking() {
+        let mut movers = TopMovers::new(2).min_swaps(5).stale_after(Duration::from_secs(60));
+        let start = Instant::now();
+
+        movers.record_at(&mover_update("0xa", 1.0, 0.0, 12.0, 50), start);
+        movers.record_at(&mover_update("0xb", 1.0, 0.0, 30.0, 50), start);
+        movers.record_at(&mover_update("0xc", 1.0, 0.0, 99.0, 1), start);
+        movers.record_at(&mover_update("0xd", 1.0, 0.0, -8.0, 20), start);
+        movers.record_at(&mover_update("0xe", 1.0, 0.0, 5.0, 20), start + Duration::from_secs(30));
+
+        let ranking = movers.ranking_at(start + Duration::from_secs(30));
+        let gainers: Vec<_> = ranking.gainers.iter().map(|m| m.pair_address.as_str()).collect();
+        assert_eq!(gainers, vec!["0xb", "0xa"]);
+        assert_eq!(ranking.losers[0].pair_address, "0xd");
+
+        let ranking = movers.ranking_at(start + Duration::from_secs(75));
+        assert_eq!(ranking.gainers.len(), 1);
+        assert!(ranking.losers.is_empty());
+    }
+}
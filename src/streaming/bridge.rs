@@ -0,0 +1,287 @@
+//! Forwarding of streaming subscription events to message brokers.
+//!
+//! [`EventBridge`] drains a subscription stream and publishes each event to an
+//! [`EventSink`]. A NATS sink is provided behind the `nats` feature; other
+//! brokers (e.g. a Kafka producer) plug in by implementing [`EventSink`].
+//!
+//! ```rust,ignore
+//! let (stream, _handle) = client.streaming_service().subscribe_to_wallet_activity(params).await?;
+//! let nats = async_nats::connect("nats://localhost:4222").await?;
+//!
+//! EventBridge::new(nats, "goldrush.wallet-activity")
+//!     .with_key(|event| event.get("tx_hash").and_then(|h| h.as_str()).map(str::to_string))
+//!     .forward(stream)
+//!     .await?;
+//! ```
+
+use crate::Result;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Boxed future returned by [`EventSink::publish`].
+pub type PublishFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// A message broker that events can be published to.
+pub trait EventSink: Send + Sync {
+    /// Publish one message to `topic`, keyed by `key` when the broker supports keys.
+    fn publish<'a>(&'a self, topic: &'a str, key: Option<&'a str>, payload: Vec<u8>) -> PublishFuture<'a>;
+}
+
+impl<S: EventSink + ?Sized> EventSink for Arc<S> {
+    fn publish<'a>(&'a self, topic: &'a str, key: Option<&'a str>, payload: Vec<u8>) -> PublishFuture<'a> {
+        (**self).publish(topic, key, payload)
+    }
+}
+
+#[cfg(feature = "nats")]
+impl EventSink for async_nats::Client {
+    fn publish<'a>(&'a self, topic: &'a str, key: Option<&'a str>, payload: Vec<u8>) -> PublishFuture<'a> {
+        Box::pin(async move {
+            let result = match key {
+                Some(key) => {
+                    let mut headers = async_nats::HeaderMap::new();
+                    headers.insert("Nats-Msg-Id", key);
+                    async_nats::Client::publish_with_headers(self, topic.to_string(), headers, payload.into()).await
+                }
+                None => async_nats::Client::publish(self, topic.to_string(), payload.into()).await,
+            };
+            result.map_err(|e| crate::Error::Streaming(format!("NATS publish failed: {}", e)))
+        })
+    }
+}
+
+/// Encoder turning an event into a message payload.
+pub type Encoder = Arc<dyn Fn(&Value) -> Result<Vec<u8>> + Send + Sync>;
+
+/// How events are encoded before publishing.
+#[derive(Clone, Default)]
+pub enum Serialization {
+    /// UTF-8 JSON, one event per message.
+    #[default]
+    Json,
+    /// A caller-provided encoder, e.g. Avro against a registered schema.
+    Custom(Encoder),
+}
+
+impl std::fmt::Debug for Serialization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Serialization::Json => write!(f, "Json"),
+            Serialization::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl Serialization {
+    fn encode(&self, event: &Value) -> Result<Vec<u8>> {
+        match self {
+            Serialization::Json => Ok(serde_json::to_vec(event)?),
+            Serialization::Custom(encode) => encode(event),
+        }
+    }
+}
+
+/// Delivery semantics for published events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryGuarantee {
+    /// Publish once; failed events are logged and skipped.
+    AtMostOnce,
+    /// Retry failed publishes, then stop forwarding with the error.
+    /// Events may be duplicated if a publish succeeded but reported failure.
+    AtLeastOnce { max_retries: u32 },
+}
+
+impl Default for DeliveryGuarantee {
+    fn default() -> Self {
+        DeliveryGuarantee::AtLeastOnce { max_retries: 3 }
+    }
+}
+
+/// Counters for a [`EventBridge::forward`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BridgeStats {
+    pub published: u64,
+    pub dropped: u64,
+}
+
+/// Key extractor used to partition events.
+pub type KeyFn = Arc<dyn Fn(&Value) -> Option<String> + Send + Sync>;
+
+/// Forwards subscription events into a message broker topic.
+pub struct EventBridge<S> {
+    sink: S,
+    topic: String,
+    serialization: Serialization,
+    delivery: DeliveryGuarantee,
+    key: Option<KeyFn>,
+    retry_delay: Duration,
+}
+
+impl<S: EventSink> EventBridge<S> {
+    pub fn new(sink: S, topic: impl Into<String>) -> Self {
+        Self {
+            sink,
+            topic: topic.into(),
+            serialization: Serialization::default(),
+            delivery: DeliveryGuarantee::default(),
+            key: None,
+            retry_delay: Duration::from_millis(200),
+        }
+    }
+
+    pub fn with_serialization(mut self, serialization: Serialization) -> Self {
+        self.serialization = serialization;
+        self
+    }
+
+    pub fn with_delivery(mut self, delivery: DeliveryGuarantee) -> Self {
+        self.delivery = delivery;
+        self
+    }
+
+    /// Derive a message key (e.g. the wallet address) from each event.
+    pub fn with_key(mut self, key: impl Fn(&Value) -> Option<String> + Send + Sync + 'static) -> Self {
+        self.key = Some(Arc::new(key));
+        self
+    }
+
+    /// Delay before the first retry under [`DeliveryGuarantee::AtLeastOnce`], doubled per attempt.
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Publish every event from a subscription stream until it ends.
+    ///
+    /// Subscriptions yield batches, so each element of a batch becomes one message.
+    /// Stream errors stop forwarding and are returned.
+    pub async fn forward<St, T>(&self, stream: St) -> Result<BridgeStats>
+    where
+        St: Stream<Item = Result<Vec<T>>>,
+        T: Serialize,
+    {
+        let mut stream = std::pin::pin!(stream);
+        let mut stats = BridgeStats::default();
+
+        while let Some(batch) = stream.next().await {
+            for event in batch? {
+                let event = serde_json::to_value(&event)?;
+                if self.publish(&event).await? {
+                    stats.published += 1;
+                } else {
+                    stats.dropped += 1;
+                }
+            }
+        }
+
+        debug!(topic = %self.topic, published = %stats.published, dropped = %stats.dropped, "Event bridge finished");
+        Ok(stats)
+    }
+
+    /// Publish one event, returning whether it was delivered.
+    async fn publish(&self, event: &Value) -> Result<bool> {
+        let payload = self.serialization.encode(event)?;
+        let key = self.key.as_ref().and_then(|k| k(event));
+
+        let max_retries = match self.delivery {
+            DeliveryGuarantee::AtMostOnce => 0,
+            DeliveryGuarantee::AtLeastOnce { max_retries } => max_retries,
+        };
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+
+        loop {
+            match self.sink.publish(&self.topic, key.as_deref(), payload.clone()).await {
+                Ok(()) => return Ok(true),
+                Err(e) if self.delivery == DeliveryGuarantee::AtMostOnce => {
+                    warn!(topic = %self.topic, error = %e, "Dropping event after failed publish");
+                    return Ok(false);
+                }
+                Err(e) if attempt < max_retries => {
+                    attempt += 1;
+                    warn!(topic = %self.topic, attempt = %attempt, error = %e, "Publish failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    delay = std::cmp::min(delay * 2, Duration::from_secs(30));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    type Message = (String, Option<String>, Vec<u8>);
+
+    #[derive(Default)]
+    struct MemorySink {
+        messages: Mutex<Vec<Message>>,
+        failures_left: AtomicU32,
+    }
+
+    impl EventSink for MemorySink {
+        fn publish<'a>(&'a self, topic: &'a str, key: Option<&'a str>, payload: Vec<u8>) -> PublishFuture<'a> {
+            Box::pin(async move {
+                if self.failures_left.load(Ordering::SeqCst) > 0 {
+                    self.failures_left.fetch_sub(1, Ordering::SeqCst);
+                    return Err(Error::Streaming("broker unavailable".to_string()));
+                }
+                self.messages.lock().unwrap().push((topic.to_string(), key.map(str::to_string), payload));
+                Ok(())
+            })
+        }
+    }
+
+    fn batches() -> impl Stream<Item = Result<Vec<Value>>> {
+        futures_util::stream::iter(vec![
+            Ok(vec![serde_json::json!({ "tx_hash": "0x1" }), serde_json::json!({ "tx_hash": "0x2" })]),
+            Ok(vec![serde_json::json!({ "tx_hash": "0x3" })]),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_forwards_each_event_with_key() {
+        let sink = Arc::new(MemorySink::default());
+        let stats = EventBridge::new(Arc::clone(&sink), "wallet")
+            .with_key(|e| e.get("tx_hash").and_then(|h| h.as_str()).map(str::to_string))
+            .forward(batches())
+            .await
+            .unwrap();
+
+        assert_eq!(stats, BridgeStats { published: 3, dropped: 0 });
+        let messages = sink.messages.lock().unwrap();
+        assert_eq!(messages[2].1.as_deref(), Some("0x3"));
+        assert_eq!(messages[0].2, br#"{"tx_hash":"0x1"}"#.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_guarantees() {
+        let sink = Arc::new(MemorySink { failures_left: AtomicU32::new(2), ..Default::default() });
+        let stats = EventBridge::new(Arc::clone(&sink), "wallet")
+            .with_delivery(DeliveryGuarantee::AtLeastOnce { max_retries: 3 })
+            .with_retry_delay(Duration::from_millis(1))
+            .forward(batches())
+            .await
+            .unwrap();
+        assert_eq!(stats.published, 3);
+
+        let sink = Arc::new(MemorySink { failures_left: AtomicU32::new(1), ..Default::default() });
+        let stats = EventBridge::new(Arc::clone(&sink), "wallet")
+            .with_delivery(DeliveryGuarantee::AtMostOnce)
+            .forward(batches())
+            .await
+            .unwrap();
+        assert_eq!(stats, BridgeStats { published: 2, dropped: 1 });
+    }
+}
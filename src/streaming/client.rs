@@ -5,20 +5,24 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures_util::{SinkExt, StreamExt};
 use serde_json::Value;
 use tokio::net::TcpStream;
-use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
 use tokio::time::{sleep, timeout};
+use tokio_util::sync::CancellationToken;
 use tokio_tungstenite::{
-    connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream,
+    connect_async_with_config,
+    tungstenite::{protocol::WebSocketConfig, Message},
+    MaybeTlsStream, WebSocketStream,
 };
 use tracing::{debug, error, info, instrument, warn};
 
-use super::config::StreamingConfig;
-use super::protocol::{GraphQLMessage, SubscriptionId};
+use super::config::{LimitAction, StreamingConfig};
+use super::latency::{LatencyStats, LatencyTracker};
+use super::protocol::{GraphQLError, GraphQLMessage, SubscriptionId};
 use super::types::ConnectionState;
 use crate::error::{Error, Result};
 
@@ -36,8 +40,15 @@ struct ClientInner {
     api_key: String,
     state: RwLock<ConnectionState>,
     subscriptions: RwLock<HashMap<SubscriptionId, MessageSender>>,
+    /// Cancelled when a subscription ends for any reason
+    completions: std::sync::Mutex<HashMap<SubscriptionId, CancellationToken>>,
+    /// Whether the current connection's `connection_init` has been acknowledged
+    acked: watch::Sender<bool>,
+    /// Start of the current one-second window and messages delivered in it
+    rates: std::sync::Mutex<HashMap<SubscriptionId, (Instant, u32)>>,
     reconnect_attempts: RwLock<u32>,
     control_tx: Mutex<Option<mpsc::UnboundedSender<ControlMessage>>>,
+    latency: LatencyTracker,
 }
 
 enum ControlMessage {
@@ -58,12 +69,16 @@ impl WebSocketClient {
     pub fn new(api_key: String, config: StreamingConfig) -> Self {
         Self {
             inner: Arc::new(ClientInner {
-                config,
                 api_key,
                 state: RwLock::new(ConnectionState::Disconnected),
                 subscriptions: RwLock::new(HashMap::new()),
+                completions: std::sync::Mutex::new(HashMap::new()),
+                rates: std::sync::Mutex::new(HashMap::new()),
+                acked: watch::channel(false).0,
                 reconnect_attempts: RwLock::new(0),
+                latency: LatencyTracker::new(config.latency_window),
                 control_tx: Mutex::new(None),
+                config,
             }),
         }
     }
@@ -84,10 +99,15 @@ impl WebSocketClient {
         }
 
         let url = format!("{}?key={}", self.inner.config.ws_url, self.inner.api_key);
+        let mut ws_config = WebSocketConfig::default();
+        if let Some(max) = self.inner.config.max_frame_size {
+            ws_config.max_frame_size = Some(max);
+            ws_config.max_message_size = ws_config.max_message_size.map(|limit| limit.max(max));
+        }
 
         match timeout(
             self.inner.config.connection_timeout,
-            connect_async(&url),
+            connect_async_with_config(&url, Some(ws_config), false),
         )
         .await
         {
@@ -124,7 +144,7 @@ impl WebSocketClient {
                 *state = ConnectionState::Failed;
                 drop(state);
 
-                let err = Error::WebSocket(format!("Connection failed: {}", e));
+                let err = Error::ConnectionFailed { reason: e.to_string() };
                 if let Some(ref callback) = self.inner.config.on_error {
                     callback(&err);
                 }
@@ -136,7 +156,7 @@ impl WebSocketClient {
                 *state = ConnectionState::Failed;
                 drop(state);
 
-                let err = Error::WebSocket("Connection timeout".to_string());
+                let err = Error::ConnectionFailed { reason: "connection timed out".to_string() };
                 if let Some(ref callback) = self.inner.config.on_error {
                     callback(&err);
                 }
@@ -165,6 +185,7 @@ impl WebSocketClient {
             let mut subs = self.inner.subscriptions.write().await;
             subs.insert(id.clone(), tx.clone());
         }
+        self.inner.completions.lock().unwrap().insert(id.clone(), CancellationToken::new());
 
         // Send subscribe message
         let control_tx = self.inner.control_tx.lock().await;
@@ -179,11 +200,37 @@ impl WebSocketClient {
         } else {
             return Err(Error::Streaming("Connection not established".to_string()));
         }
+        drop(control_tx);
+
+        // The connection handler holds the subscribe frame until the ack arrives.
+        if let Err(e) = self.wait_for_ack().await {
+            self.inner.subscriptions.write().await.remove(&id);
+            self.inner.finish(&id);
+            return Err(e);
+        }
 
         debug!("Subscribed with ID: {}", id);
         Ok((id, rx))
     }
 
+    /// Waits up to `ack_timeout` for the server to acknowledge `connection_init`
+    async fn wait_for_ack(&self) -> Result<()> {
+        let mut acked = self.inner.acked.subscribe();
+        let acked = timeout(self.inner.config.ack_timeout, async {
+            acked.wait_for(|acked| *acked).await.map(|_| ())
+        })
+        .await;
+        match acked {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(_)) => Err(Error::ConnectionFailed {
+                reason: "connection closed before connection_init was acknowledged".to_string(),
+            }),
+            Err(_) => Err(Error::ConnectionFailed {
+                reason: format!("connection_init not acknowledged within {:?}", self.inner.config.ack_timeout),
+            }),
+        }
+    }
+
     /// Unsubscribes from a subscription
     #[instrument(skip(self))]
     pub async fn unsubscribe(&self, id: &str) -> Result<()> {
@@ -191,6 +238,7 @@ impl WebSocketClient {
             let mut subs = self.inner.subscriptions.write().await;
             subs.remove(id);
         }
+        self.inner.finish(id);
 
         let control_tx = self.inner.control_tx.lock().await;
         if let Some(ref tx) = *control_tx {
@@ -215,6 +263,7 @@ impl WebSocketClient {
         let mut state = self.inner.state.write().await;
         *state = ConnectionState::Disconnected;
         drop(state);
+        self.inner.finish_all();
 
         if let Some(ref callback) = self.inner.config.on_closed {
             callback();
@@ -224,11 +273,31 @@ impl WebSocketClient {
         Ok(())
     }
 
+    /// Token cancelled once subscription `id` ends: unsubscribed, completed or
+    /// failed by the server, or closed with the connection.
+    ///
+    /// Unknown or already-ended ids get a cancelled token.
+    pub fn completion(&self, id: &str) -> CancellationToken {
+        match self.inner.completions.lock().unwrap().get(id) {
+            Some(token) => token.clone(),
+            None => {
+                let token = CancellationToken::new();
+                token.cancel();
+                token
+            }
+        }
+    }
+
     /// Gets the current connection state
     pub async fn state(&self) -> ConnectionState {
         *self.inner.state.read().await
     }
 
+    /// Gets rolling ping round-trip statistics for the connection
+    pub fn latency_stats(&self) -> LatencyStats {
+        self.inner.latency.stats()
+    }
+
     /// Spawns the connection handler task
     fn spawn_connection_handler(
         &self,
@@ -238,8 +307,13 @@ impl WebSocketClient {
         let inner = self.inner.clone();
         let config = self.inner.config.clone();
 
+        inner.acked.send_replace(false);
+
         tokio::spawn(async move {
             let (mut write, mut read) = ws_stream.split();
+            inner.latency.reset_pending();
+            // Subscribe frames issued before the server acknowledged connection_init
+            let mut queued: Vec<(SubscriptionId, String)> = Vec::new();
 
             // Send connection_init
             let init_msg = GraphQLMessage::connection_init(None);
@@ -260,6 +334,14 @@ impl WebSocketClient {
                                 if let Err(e) = Self::handle_message(&inner, &text).await {
                                     error!("Error handling message: {}", e);
                                 }
+                                if *inner.acked.borrow() && !queued.is_empty() {
+                                    debug!("Sending {} subscriptions queued before ack", queued.len());
+                                    for (_, frame) in queued.drain(..) {
+                                        if let Err(e) = write.send(Message::Text(frame)).await {
+                                            error!("Failed to send subscribe: {}", e);
+                                        }
+                                    }
+                                }
                             }
                             Some(Ok(Message::Close(_))) => {
                                 info!("WebSocket closed by server");
@@ -287,11 +369,19 @@ impl WebSocketClient {
                                     variables,
                                     None,
                                 );
-                                if let Err(e) = write.send(Message::Text(sub_msg.to_json().unwrap())).await {
+                                let frame = sub_msg.to_json().unwrap();
+                                if !*inner.acked.borrow() {
+                                    queued.push((id, frame));
+                                } else if let Err(e) = write.send(Message::Text(frame)).await {
                                     error!("Failed to send subscribe: {}", e);
                                 }
                             }
                             Some(ControlMessage::Unsubscribe { id }) => {
+                                if let Some(pos) = queued.iter().position(|(queued_id, _)| *queued_id == id) {
+                                    // Never sent, so there is nothing to complete.
+                                    queued.remove(pos);
+                                    continue;
+                                }
                                 let complete_msg = GraphQLMessage::complete(id);
                                 if let Err(e) = write.send(Message::Text(complete_msg.to_json().unwrap())).await {
                                     error!("Failed to send complete: {}", e);
@@ -306,16 +396,19 @@ impl WebSocketClient {
 
                     // Send periodic pings
                     _ = ping_interval.tick() => {
+                        Self::check_stalled_ping(&inner);
                         let ping_msg = GraphQLMessage::ping(None);
                         if let Err(e) = write.send(Message::Text(ping_msg.to_json().unwrap())).await {
                             error!("Failed to send ping: {}", e);
                             break;
                         }
+                        inner.latency.ping_sent(Instant::now());
                     }
                 }
             }
 
             // Cleanup
+            inner.acked.send_replace(false);
             let mut state = inner.state.write().await;
             *state = ConnectionState::Disconnected;
             drop(state);
@@ -334,46 +427,56 @@ impl WebSocketClient {
     /// Handles incoming WebSocket messages
     async fn handle_message(inner: &Arc<ClientInner>, text: &str) -> Result<()> {
         let msg = GraphQLMessage::from_json(text)
-            .map_err(|e| Error::Streaming(format!("Failed to parse message: {}", e)))?;
+            .map_err(|e| Error::Deserialization {
+                field: "message".to_string(),
+                message: e.to_string(),
+                payload: Value::String(text.to_string()),
+            })?;
 
         match msg {
             GraphQLMessage::ConnectionAck { .. } => {
                 debug!("Connection acknowledged");
+                inner.acked.send_replace(true);
             }
             GraphQLMessage::Next { id, payload } => {
+                if let Some(violation) = inner.check_limits(&id, text.len(), Instant::now()) {
+                    Self::limit_exceeded(inner, &id, violation).await;
+                    return Ok(());
+                }
                 let subs = inner.subscriptions.read().await;
                 if let Some(sender) = subs.get(&id) {
                     let _ = sender.send(Ok(payload));
                 }
             }
             GraphQLMessage::Error { id, payload } => {
-                let error_msg = payload
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ");
-
                 let subs = inner.subscriptions.read().await;
                 if let Some(sender) = subs.get(&id) {
-                    let _ = sender.send(Err(Error::GraphQL(error_msg.clone())));
+                    let _ = sender.send(Err(GraphQLError::rejection(&payload)));
                 }
                 drop(subs);
+                inner.finish(&id);
 
-                let err = Error::GraphQL(error_msg);
+                let err = GraphQLError::rejection(&payload);
                 if let Some(ref callback) = inner.config.on_error {
                     callback(&err);
                 }
             }
             GraphQLMessage::Complete { id } => {
                 debug!("Subscription {} completed", id);
-                let mut subs = inner.subscriptions.write().await;
-                subs.remove(&id);
+                let sender = inner.subscriptions.write().await.remove(&id);
+                if let Some(sender) = sender {
+                    let _ = sender.send(Err(Error::ServerComplete { subscription_id: id.clone() }));
+                }
+                inner.finish(&id);
             }
             GraphQLMessage::Ping { .. } => {
                 debug!("Received ping");
             }
             GraphQLMessage::Pong { .. } => {
-                debug!("Received pong");
+                if let Some(rtt) = inner.latency.pong_received(Instant::now()) {
+                    debug!("Received pong after {:?}", rtt);
+                    Self::report_latency(inner, rtt);
+                }
             }
             _ => {}
         }
@@ -381,24 +484,65 @@ impl WebSocketClient {
         Ok(())
     }
 
-    /// Attempts to reconnect with exponential backoff
+    /// Drops a message that broke a limit, or fails its subscription
+    async fn limit_exceeded(inner: &Arc<ClientInner>, id: &str, violation: String) {
+        warn!("Subscription {}: {}", id, violation);
+        if inner.config.limit_action == LimitAction::Drop {
+            return;
+        }
+
+        let sender = inner.subscriptions.write().await.remove(id);
+        if let Some(sender) = sender {
+            let _ = sender.send(Err(Error::Streaming(violation)));
+        }
+        inner.finish(id);
+        if let Some(ref tx) = *inner.control_tx.lock().await {
+            let _ = tx.send(ControlMessage::Unsubscribe { id: id.to_string() });
+        }
+    }
+
+    /// Invokes `on_latency` if a round trip reached the configured threshold
+    fn report_latency(inner: &ClientInner, rtt: Duration) {
+        let Some(ref callback) = inner.config.on_latency else {
+            return;
+        };
+        if inner.config.latency_threshold.map_or(true, |threshold| rtt >= threshold) {
+            callback(rtt, &inner.latency.stats());
+        }
+    }
+
+    /// Reports a ping that has gone unanswered past the latency threshold
+    fn check_stalled_ping(inner: &ClientInner) {
+        let (Some(threshold), Some(waited)) = (
+            inner.config.latency_threshold,
+            inner.latency.outstanding(Instant::now()),
+        ) else {
+            return;
+        };
+        if waited >= threshold {
+            warn!("Ping unanswered for {:?}", waited);
+            if let Some(ref callback) = inner.config.on_latency {
+                callback(waited, &inner.latency.stats());
+            }
+        }
+    }
+
+    /// Attempts to reconnect after the configured backoff
     async fn attempt_reconnection(inner: Arc<ClientInner>) {
         let mut attempts = inner.reconnect_attempts.write().await;
         *attempts += 1;
         let attempt = *attempts;
         drop(attempts);
 
-        if !(inner.config.should_retry)(attempt) {
+        let max = inner.config.max_reconnect_attempts;
+        if !(inner.config.should_retry)(attempt) || (max != 0 && attempt > max) {
             error!("Max reconnection attempts reached");
+            inner.finish_all();
             return;
         }
 
-        let backoff = Duration::from_secs(2u64.pow(attempt.min(5)));
-        warn!(
-            "Reconnecting in {} seconds (attempt {})",
-            backoff.as_secs(),
-            attempt
-        );
+        let backoff = inner.config.reconnect_delay(attempt);
+        warn!("Reconnecting in {:?} (attempt {})", backoff, attempt);
 
         sleep(backoff).await;
 
@@ -413,15 +557,132 @@ impl WebSocketClient {
     }
 }
 
+impl ClientInner {
+    fn finish(&self, id: &str) {
+        self.rates.lock().unwrap().remove(id);
+        if let Some(token) = self.completions.lock().unwrap().remove(id) {
+            token.cancel();
+        }
+    }
+
+    fn finish_all(&self) {
+        self.rates.lock().unwrap().clear();
+        for (_, token) in self.completions.lock().unwrap().drain() {
+            token.cancel();
+        }
+    }
+
+    /// Describes the limit a `len`-byte message for `id` breaks, if any,
+    /// counting it toward the subscription's rate otherwise
+    fn check_limits(&self, id: &str, len: usize, now: Instant) -> Option<String> {
+        if let Some(max) = self.config.max_message_size {
+            if len > max {
+                return Some(format!("message of {} bytes exceeds limit of {}", len, max));
+            }
+        }
+        let max = self.config.max_messages_per_second?;
+        let mut rates = self.rates.lock().unwrap();
+        let (window_start, count) = rates.entry(id.to_string()).or_insert((now, 0));
+        if now.duration_since(*window_start) >= Duration::from_secs(1) {
+            *window_start = now;
+            *count = 0;
+        }
+        if *count >= max {
+            return Some(format!("more than {} messages per second", max));
+        }
+        *count += 1;
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_message_limits() {
+        let config = StreamingConfig::builder()
+            .max_message_size(100)
+            .max_messages_per_second(2)
+            .build();
+        let client = WebSocketClient::new("test_key".to_string(), config);
+        let inner = &client.inner;
+        let start = Instant::now();
+
+        assert!(inner.check_limits("a", 101, start).unwrap().contains("101 bytes"));
+        assert!(inner.check_limits("a", 50, start).is_none());
+        assert!(inner.check_limits("a", 50, start).is_none());
+        assert!(inner.check_limits("a", 50, start).unwrap().contains("2 messages"));
+        // Limits are per subscription, and the window resets each second.
+        assert!(inner.check_limits("b", 50, start).is_none());
+        assert!(inner.check_limits("a", 50, start + Duration::from_secs(1)).is_none());
+    }
+
+    /// Accepts one connection, reads `connection_init`, then acks after `ack_delay`
+    /// (or never) and returns the frames received before and after the ack.
+    async fn ack_server(ack_delay: Option<Duration>) -> (String, tokio::task::JoinHandle<(usize, Option<String>)>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/graphql", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await.unwrap().unwrap();
+            let Some(delay) = ack_delay else {
+                sleep(Duration::from_secs(1)).await;
+                return (0, None);
+            };
+            let is_subscribe = |frame: &str| frame.contains(r#""type":"subscribe""#);
+            let deadline = tokio::time::Instant::now() + delay;
+            let mut early = 0;
+            while let Ok(Some(Ok(frame))) = tokio::time::timeout_at(deadline, ws.next()).await {
+                early += usize::from(is_subscribe(&frame.into_text().unwrap()));
+            }
+            ws.send(Message::Text(r#"{"type":"connection_ack"}"#.to_string())).await.unwrap();
+            loop {
+                let frame = ws.next().await.unwrap().unwrap().into_text().unwrap();
+                if is_subscribe(&frame) {
+                    return (early, Some(frame));
+                }
+            }
+        });
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_waits_for_ack() {
+        let (url, server) = ack_server(Some(Duration::from_millis(100))).await;
+        let config = StreamingConfig::builder().ws_url(url).auto_resubscribe(false).build();
+        let client = WebSocketClient::new("test_key".to_string(), config);
+
+        let started = Instant::now();
+        let (id, _rx) = client.subscribe("subscription { x }".to_string(), None).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(100));
+
+        let (early, after) = server.await.unwrap();
+        assert_eq!(early, 0, "subscribe frame sent before connection_ack");
+        assert!(after.unwrap().contains(&id));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_fails_without_ack() {
+        let (url, _server) = ack_server(None).await;
+        let config = StreamingConfig::builder()
+            .ws_url(url)
+            .ack_timeout(Duration::from_millis(100))
+            .auto_resubscribe(false)
+            .build();
+        let client = WebSocketClient::new("test_key".to_string(), config);
+
+        let result = client.subscribe("subscription { x }".to_string(), None).await;
+        assert!(matches!(result, Err(Error::ConnectionFailed { reason }) if reason.contains("not acknowledged")));
+        assert!(client.inner.subscriptions.read().await.is_empty());
+    }
+
     #[test]
     fn test_client_creation() {
         let config = StreamingConfig::default();
         let client = WebSocketClient::new("test_key".to_string(), config);
-        // Client should be created successfully
-        assert!(true);
+        // Unknown subscriptions are reported as already completed
+        assert!(client.completion("unknown").is_cancelled());
     }
 }
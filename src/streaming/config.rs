@@ -5,20 +5,66 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use super::latency::LatencyStats;
 use crate::error::GoldRushError;
 
+/// Callback receiving a ping round-trip time and the current rolling stats
+pub type LatencyCallback = Arc<dyn Fn(Duration, &LatencyStats) + Send + Sync>;
+
+/// Delay before a reconnection attempt, given the attempt number starting at 1
+pub type BackoffStrategy = Arc<dyn Fn(u32) -> Duration + Send + Sync>;
+
+/// How one-shot GraphQL queries (token search, UPnL) are sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryTransport {
+    /// Over the shared WebSocket connection, opening it if needed
+    #[default]
+    WebSocket,
+    /// As a single HTTPS POST, without opening a WebSocket connection
+    Http,
+}
+
+/// What happens to a subscription message that breaks a size or rate limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitAction {
+    /// Drop the message and keep the subscription
+    #[default]
+    Drop,
+    /// Deliver an error to the subscriber and end the subscription
+    Error,
+}
+
 /// Configuration for streaming connections
 #[derive(Clone)]
 pub struct StreamingConfig {
     /// WebSocket endpoint URL
     pub ws_url: String,
 
+    /// HTTPS endpoint for queries sent with [`QueryTransport::Http`]
+    /// (`None` = `ws_url` with an `https` scheme)
+    pub http_url: Option<String>,
+
+    /// Transport for one-shot queries
+    pub query_transport: QueryTransport,
+
     /// Function to determine if reconnection should be attempted
     pub should_retry: Arc<dyn Fn(u32) -> bool + Send + Sync>,
 
     /// Maximum number of reconnection attempts (0 = unlimited)
     pub max_reconnect_attempts: u32,
 
+    /// Delay before the first reconnection attempt, doubled for each later one
+    pub reconnect_base_delay: Duration,
+
+    /// Longest delay between reconnection attempts
+    pub reconnect_max_delay: Duration,
+
+    /// Share of each reconnection delay randomized away, from 0 (none) to 1
+    pub reconnect_jitter: f64,
+
+    /// Custom reconnection delay, replacing the exponential backoff above
+    pub backoff: Option<BackoffStrategy>,
+
     /// Timeout for establishing WebSocket connection
     pub connection_timeout: Duration,
 
@@ -28,9 +74,33 @@ pub struct StreamingConfig {
     /// Timeout waiting for pong response
     pub pong_timeout: Duration,
 
+    /// Timeout waiting for the server to acknowledge `connection_init`
+    pub ack_timeout: Duration,
+
     /// Automatically resubscribe after reconnection
     pub auto_resubscribe: bool,
 
+    /// Number of ping round trips kept for latency statistics
+    pub latency_window: usize,
+
+    /// Largest WebSocket frame accepted (`None` = 16 MiB); a larger frame closes the connection
+    pub max_frame_size: Option<usize>,
+
+    /// Largest subscription message delivered, in bytes (`None` = unlimited)
+    pub max_message_size: Option<usize>,
+
+    /// Most messages delivered per subscription per second (`None` = unlimited)
+    pub max_messages_per_second: Option<u32>,
+
+    /// Handling of messages over `max_message_size` or `max_messages_per_second`
+    pub limit_action: LimitAction,
+
+    /// Fail decoding on enum values the SDK doesn't know instead of keeping them as `Unknown`
+    pub strict_enums: bool,
+
+    /// Round-trip time at or above which `on_latency` fires (`None` = every sample)
+    pub latency_threshold: Option<Duration>,
+
     /// Callback invoked when connection is being established
     pub on_connecting: Option<Arc<dyn Fn() + Send + Sync>>,
 
@@ -42,22 +112,40 @@ pub struct StreamingConfig {
 
     /// Callback invoked when an error occurs
     pub on_error: Option<Arc<dyn Fn(&GoldRushError) + Send + Sync>>,
+
+    /// Callback invoked when a ping round trip reaches `latency_threshold`
+    pub on_latency: Option<LatencyCallback>,
 }
 
 impl Default for StreamingConfig {
     fn default() -> Self {
         Self {
             ws_url: "wss://gr-staging-v2.streaming.covalenthq.com/graphql".to_string(),
+            http_url: None,
+            query_transport: QueryTransport::WebSocket,
             should_retry: Arc::new(|attempt| attempt < 5),
             max_reconnect_attempts: 5,
+            reconnect_base_delay: Duration::from_secs(2),
+            reconnect_max_delay: Duration::from_secs(32),
+            reconnect_jitter: 0.0,
+            backoff: None,
             connection_timeout: Duration::from_secs(30),
             ping_interval: Duration::from_secs(30),
             pong_timeout: Duration::from_secs(10),
+            ack_timeout: Duration::from_secs(10),
             auto_resubscribe: true,
+            latency_window: 20,
+            latency_threshold: None,
+            max_frame_size: None,
+            max_message_size: None,
+            max_messages_per_second: None,
+            limit_action: LimitAction::Drop,
+            strict_enums: false,
             on_connecting: None,
             on_connected: None,
             on_closed: None,
             on_error: None,
+            on_latency: None,
         }
     }
 }
@@ -67,6 +155,35 @@ impl StreamingConfig {
     pub fn builder() -> StreamingConfigBuilder {
         StreamingConfigBuilder::new()
     }
+
+    /// Delay before reconnection attempt `attempt`, counting from 1
+    pub fn reconnect_delay(&self, attempt: u32) -> Duration {
+        if let Some(backoff) = &self.backoff {
+            return backoff(attempt);
+        }
+        let doublings = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .reconnect_base_delay
+            .saturating_mul(1 << doublings)
+            .min(self.reconnect_max_delay);
+        let jitter = self.reconnect_jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+        delay.mul_f64(1.0 - jitter * random_unit())
+    }
+
+    /// URL that queries sent over HTTP are posted to
+    pub fn query_url(&self) -> String {
+        if let Some(url) = &self.http_url {
+            return url.clone();
+        }
+        match self.ws_url.split_once("://") {
+            Some(("wss", rest)) => format!("https://{}", rest),
+            Some(("ws", rest)) => format!("http://{}", rest),
+            _ => self.ws_url.clone(),
+        }
+    }
 }
 
 /// Builder for StreamingConfig
@@ -88,6 +205,18 @@ impl StreamingConfigBuilder {
         self
     }
 
+    /// Sets the HTTPS URL used for queries sent over HTTP
+    pub fn http_url(mut self, url: impl Into<String>) -> Self {
+        self.config.http_url = Some(url.into());
+        self
+    }
+
+    /// Sets the transport for one-shot queries
+    pub fn query_transport(mut self, transport: QueryTransport) -> Self {
+        self.config.query_transport = transport;
+        self
+    }
+
     /// Sets the retry policy
     pub fn should_retry<F>(mut self, f: F) -> Self
     where
@@ -103,6 +232,33 @@ impl StreamingConfigBuilder {
         self
     }
 
+    /// Sets the delay before the first reconnection attempt
+    pub fn reconnect_base_delay(mut self, delay: Duration) -> Self {
+        self.config.reconnect_base_delay = delay;
+        self
+    }
+
+    /// Sets the longest delay between reconnection attempts
+    pub fn reconnect_max_delay(mut self, delay: Duration) -> Self {
+        self.config.reconnect_max_delay = delay;
+        self
+    }
+
+    /// Sets the share of each reconnection delay randomized away, from 0 to 1
+    pub fn reconnect_jitter(mut self, jitter: f64) -> Self {
+        self.config.reconnect_jitter = jitter;
+        self
+    }
+
+    /// Replaces the exponential backoff with a custom delay per attempt
+    pub fn backoff<F>(mut self, f: F) -> Self
+    where
+        F: Fn(u32) -> Duration + Send + Sync + 'static,
+    {
+        self.config.backoff = Some(Arc::new(f));
+        self
+    }
+
     /// Sets the connection timeout
     pub fn connection_timeout(mut self, timeout: Duration) -> Self {
         self.config.connection_timeout = timeout;
@@ -121,12 +277,48 @@ impl StreamingConfigBuilder {
         self
     }
 
+    /// Sets how long subscribing waits for the server to acknowledge `connection_init`
+    pub fn ack_timeout(mut self, timeout: Duration) -> Self {
+        self.config.ack_timeout = timeout;
+        self
+    }
+
     /// Enables or disables automatic resubscription after reconnection
     pub fn auto_resubscribe(mut self, enabled: bool) -> Self {
         self.config.auto_resubscribe = enabled;
         self
     }
 
+    /// Sets the largest WebSocket frame accepted
+    pub fn max_frame_size(mut self, bytes: usize) -> Self {
+        self.config.max_frame_size = Some(bytes);
+        self
+    }
+
+    /// Sets the largest subscription message delivered
+    pub fn max_message_size(mut self, bytes: usize) -> Self {
+        self.config.max_message_size = Some(bytes);
+        self
+    }
+
+    /// Sets the most messages delivered per subscription per second
+    pub fn max_messages_per_second(mut self, messages: u32) -> Self {
+        self.config.max_messages_per_second = Some(messages);
+        self
+    }
+
+    /// Sets how messages breaking a size or rate limit are handled
+    pub fn limit_action(mut self, action: LimitAction) -> Self {
+        self.config.limit_action = action;
+        self
+    }
+
+    /// Rejects messages carrying enum values the SDK doesn't know
+    pub fn strict_enums(mut self, strict: bool) -> Self {
+        self.config.strict_enums = strict;
+        self
+    }
+
     /// Sets the on_connecting callback
     pub fn on_connecting<F>(mut self, f: F) -> Self
     where
@@ -163,6 +355,25 @@ impl StreamingConfigBuilder {
         self
     }
 
+    /// Sets how many ping round trips are kept for latency statistics
+    pub fn latency_window(mut self, samples: usize) -> Self {
+        self.config.latency_window = samples;
+        self
+    }
+
+    /// Sets the on_latency callback, fired when a round trip takes at least `threshold`
+    ///
+    /// A ping left unanswered past `threshold` also fires the callback with the
+    /// time waited so far, so a stalled feed is reported before its pong arrives.
+    pub fn on_latency<F>(mut self, threshold: Duration, f: F) -> Self
+    where
+        F: Fn(Duration, &LatencyStats) + Send + Sync + 'static,
+    {
+        self.config.latency_threshold = Some(threshold);
+        self.config.on_latency = Some(Arc::new(f));
+        self
+    }
+
     /// Builds the configuration
     pub fn build(self) -> StreamingConfig {
         self.config
@@ -175,6 +386,15 @@ impl Default for StreamingConfigBuilder {
     }
 }
 
+/// A number in `[0, 1)` that differs between calls, for jitter
+fn random_unit() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +420,18 @@ mod tests {
         assert!(!config.auto_resubscribe);
     }
 
+    #[test]
+    fn test_query_url() {
+        let config = StreamingConfig::builder().query_transport(QueryTransport::Http).build();
+        assert_eq!(config.query_url(), "https://gr-staging-v2.streaming.covalenthq.com/graphql");
+
+        let config = StreamingConfig::builder().ws_url("ws://localhost:4000/graphql").build();
+        assert_eq!(config.query_url(), "http://localhost:4000/graphql");
+
+        let config = StreamingConfig::builder().http_url("https://example.com/gql").build();
+        assert_eq!(config.query_url(), "https://example.com/gql");
+    }
+
     #[test]
     fn test_custom_retry_policy() {
         let config = StreamingConfig::builder()
@@ -210,4 +442,38 @@ mod tests {
         assert!((config.should_retry)(2));
         assert!(!(config.should_retry)(3));
     }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let config = StreamingConfig::default();
+        let delays: Vec<u64> = (1..=7).map(|a| config.reconnect_delay(a).as_secs()).collect();
+        assert_eq!(delays, vec![2, 4, 8, 16, 32, 32, 32]);
+
+        let config = StreamingConfig::builder()
+            .reconnect_base_delay(Duration::from_millis(100))
+            .reconnect_max_delay(Duration::from_secs(1))
+            .reconnect_jitter(0.5)
+            .build();
+        for attempt in 1..=10 {
+            let delay = config.reconnect_delay(attempt);
+            let full = Duration::from_millis(100 * (1 << (attempt - 1))).min(Duration::from_secs(1));
+            assert!(delay <= full && delay >= full / 2, "{:?} {:?}", delay, full);
+        }
+
+        let config = StreamingConfig::builder()
+            .backoff(|attempt| Duration::from_millis(attempt as u64 * 10))
+            .build();
+        assert_eq!(config.reconnect_delay(3), Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_on_latency_sets_threshold() {
+        let config = StreamingConfig::builder()
+            .on_latency(Duration::from_millis(250), |_, _| {})
+            .build();
+
+        assert_eq!(config.latency_threshold, Some(Duration::from_millis(250)));
+        assert!(config.on_latency.is_some());
+        assert_eq!(config.latency_window, 20);
+    }
 }
@@ -0,0 +1,319 @@
+//! New-Pair Enrichment
+//!
+//! Combines the new-pairs subscription with REST lookups on the pair's token
+//! and deployer, and flags common rug-pull patterns.
+
+use async_stream::stream;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+
+use crate::address::same_address;
+use crate::amount::{Amount, AmountValue};
+use crate::decimal::to_f64;
+use crate::error::Result;
+use crate::models::approvals::ApprovalItem;
+use crate::models::balances::TokenHolderItem;
+use crate::models::streaming::{NewPairsResponse, StreamingChain};
+use crate::services::{BalanceService, SecurityService, TransactionService};
+use crate::{GoldRushClient, TokenHoldersOptions};
+
+/// Thresholds used to flag a new pair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskThresholds {
+    /// Deployer share of supply at or above which the pair is flagged
+    pub deployer_share: f64,
+    /// Combined share of the top holders at or above which the pair is flagged
+    pub top_holders_share: f64,
+    /// Deployers with at most this many transactions are considered fresh
+    pub fresh_deployer_max_txs: u64,
+    /// Liquidity in USD below which the pair is flagged
+    pub min_liquidity_usd: f64,
+}
+
+impl Default for RiskThresholds {
+    fn default() -> Self {
+        Self {
+            deployer_share: 0.5,
+            top_holders_share: 0.8,
+            fresh_deployer_max_txs: 5,
+            min_liquidity_usd: 1_000.0,
+        }
+    }
+}
+
+/// A heuristic warning about a new pair
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RiskFlag {
+    /// The deployer holds a large share of the base token supply
+    DeployerMajorityHolder { share: f64 },
+    /// Supply is concentrated in the top holders
+    ConcentratedSupply { share: f64 },
+    /// The deployer address has little or no history
+    FreshDeployer { tx_count: u64 },
+    /// The pool was seeded with little liquidity
+    LowLiquidity { liquidity_usd: f64 },
+}
+
+/// A new pair with holder, deployer and approval context
+#[derive(Debug, Clone, Serialize)]
+pub struct EnrichedNewPair {
+    pub pair: NewPairsResponse,
+    /// Largest holders of the base token
+    pub top_holders: Vec<TokenHolderItem>,
+    /// Base token total supply as reported by the holders endpoint
+    pub total_supply: Option<Amount>,
+    /// Deployer share of the base token supply
+    pub deployer_share: Option<f64>,
+    /// Combined share of `top_holders`
+    pub top_holders_share: Option<f64>,
+    /// Transactions sent or received by the deployer on this chain
+    pub deployer_tx_count: Option<u64>,
+    /// When the deployer was first active on this chain
+    pub deployer_first_seen: Option<String>,
+    /// Token approvals granted by the deployer
+    pub deployer_approvals: Option<Vec<ApprovalItem>>,
+    pub risk_flags: Vec<RiskFlag>,
+    /// Lookups that failed; the matching fields are left empty
+    pub errors: Vec<String>,
+}
+
+impl EnrichedNewPair {
+    /// Whether any heuristic fired
+    pub fn is_risky(&self) -> bool {
+        !self.risk_flags.is_empty()
+    }
+}
+
+/// Enriches pairs from `subscribe_to_new_pairs` via the REST API
+///
+/// # Example
+/// ```no_run
+/// use goldrush_sdk::streaming::enrichment::NewPairEnricher;
+/// use futures_util::StreamExt;
+/// # use goldrush_sdk::*;
+/// # use goldrush_sdk::models::streaming::*;
+///
+/// # async fn example(client: GoldRushClient, params: NewPairsParams) -> Result<()> {
+/// let (pairs, _handle) = client.streaming_service().subscribe_to_new_pairs(params).await?;
+/// let mut enriched = Box::pin(NewPairEnricher::new(&client).enrich_stream(pairs));
+///
+/// while let Some(pair) = enriched.next().await {
+///     let pair = pair?;
+///     if pair.is_risky() {
+///         println!("{}: {:?}", pair.pair.pair_address, pair.risk_flags);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct NewPairEnricher {
+    balances: BalanceService,
+    transactions: TransactionService,
+    security: SecurityService,
+    holder_sample: u32,
+    thresholds: RiskThresholds,
+}
+
+impl NewPairEnricher {
+    /// Creates an enricher using the client's REST services
+    pub fn new(client: &GoldRushClient) -> Self {
+        Self {
+            balances: client.balance_service(),
+            transactions: client.transaction_service(),
+            security: client.security_service(),
+            holder_sample: 10,
+            thresholds: RiskThresholds::default(),
+        }
+    }
+
+    /// Sets how many top holders are fetched
+    pub fn holder_sample(mut self, holders: u32) -> Self {
+        self.holder_sample = holders;
+        self
+    }
+
+    /// Sets the risk heuristic thresholds
+    pub fn thresholds(mut self, thresholds: RiskThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Enriches one pair; failed lookups are recorded in `errors` rather than returned
+    pub async fn enrich(&self, pair: NewPairsResponse) -> EnrichedNewPair {
+        let chain = rest_chain_name(&pair.chain_name);
+        let token = pair.base_token.contract_address.clone();
+        let deployer = pair.deployer_address.clone();
+
+        let holder_options = TokenHoldersOptions::new().page_size(self.holder_sample);
+        let (holders, summary, approvals) = tokio::join!(
+            self.balances
+                .get_token_holders_v2_for_token_address(&chain, token.as_str(), Some(holder_options)),
+            self.transactions.get_transaction_summary(&chain, deployer.as_str(), None),
+            self.security.get_approvals(&chain, deployer.as_str()),
+        );
+
+        let mut errors = Vec::new();
+        let top_holders = match holders {
+            Ok(resp) => resp.data.map(|d| d.items).unwrap_or_default(),
+            Err(e) => {
+                errors.push(format!("token holders: {}", e));
+                Vec::new()
+            }
+        };
+        let summary = match summary {
+            Ok(resp) => resp.data.and_then(|d| d.items.into_iter().next()),
+            Err(e) => {
+                errors.push(format!("deployer summary: {}", e));
+                None
+            }
+        };
+        let deployer_approvals = match approvals {
+            Ok(resp) => Some(resp.data.map(|d| d.items).unwrap_or_default()),
+            Err(e) => {
+                errors.push(format!("deployer approvals: {}", e));
+                None
+            }
+        };
+
+        let total_supply = top_holders.iter().find_map(|h| h.total_supply.clone());
+        let supply = total_supply.as_ref().and_then(|s| s.as_f64_units(0)).filter(|s| *s > 0.0);
+        let share_of = |holder: &TokenHolderItem| {
+            Some(holder.balance.as_ref().and_then(|b| b.as_f64_units(0))? / supply?)
+        };
+        let deployer_share = supply.map(|_| {
+            top_holders
+                .iter()
+                .filter(|h| h.address.as_deref().is_some_and(|a| same_address(a, &deployer)))
+                .filter_map(share_of)
+                .sum()
+        });
+        let top_holders_share = supply.map(|_| top_holders.iter().filter_map(share_of).sum());
+
+        let mut enriched = EnrichedNewPair {
+            pair,
+            top_holders,
+            total_supply,
+            deployer_share,
+            top_holders_share,
+            deployer_tx_count: summary.as_ref().and_then(|s| s.total_count),
+            deployer_first_seen: summary
+                .and_then(|s| s.earliest_transaction)
+                .and_then(|t| t.block_signed_at),
+            deployer_approvals,
+            risk_flags: Vec::new(),
+            errors,
+        };
+        enriched.risk_flags = assess(&enriched, &self.thresholds);
+        enriched
+    }
+
+    /// Enriches every pair from a new-pairs subscription, one at a time
+    pub fn enrich_stream<S>(self, pairs: S) -> impl Stream<Item = Result<EnrichedNewPair>>
+    where
+        S: Stream<Item = Result<Vec<NewPairsResponse>>>,
+    {
+        stream! {
+            let mut pairs = std::pin::pin!(pairs);
+            while let Some(batch) = pairs.next().await {
+                match batch {
+                    Ok(batch) => {
+                        for pair in batch {
+                            yield Ok(self.enrich(pair).await);
+                        }
+                    }
+                    Err(e) => yield Err(e),
+                }
+            }
+        }
+    }
+}
+
+/// Applies the risk heuristics to an enriched pair
+fn assess(pair: &EnrichedNewPair, thresholds: &RiskThresholds) -> Vec<RiskFlag> {
+    let mut flags = Vec::new();
+    if let Some(share) = pair.deployer_share.filter(|s| *s >= thresholds.deployer_share) {
+        flags.push(RiskFlag::DeployerMajorityHolder { share });
+    }
+    if let Some(share) = pair.top_holders_share.filter(|s| *s >= thresholds.top_holders_share) {
+        flags.push(RiskFlag::ConcentratedSupply { share });
+    }
+    if let Some(tx_count) = pair.deployer_tx_count.filter(|c| *c <= thresholds.fresh_deployer_max_txs) {
+        flags.push(RiskFlag::FreshDeployer { tx_count });
+    }
+    let liquidity = to_f64(pair.pair.liquidity);
+    if liquidity < thresholds.min_liquidity_usd {
+        flags.push(RiskFlag::LowLiquidity { liquidity_usd: liquidity });
+    }
+    flags
+}
+
+/// Converts a streaming chain name (`POLYGON_MAINNET`) to its REST slug (`matic-mainnet`)
+fn rest_chain_name(streaming: &str) -> String {
+    serde_json::from_value::<StreamingChain>(serde_json::Value::String(streaming.to_string()))
+        .ok()
+        .and_then(|chain| chain.rest())
+        .map(|chain| chain.slug().to_string())
+        .unwrap_or_else(|| streaming.to_ascii_lowercase().replace('_', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enriched(liquidity: f64) -> EnrichedNewPair {
+        let pair: NewPairsResponse = serde_json::from_value(serde_json::json!({
+            "chain_name": "BASE_MAINNET",
+            "protocol": "UNISWAP_V2",
+            "protocol_version": "2",
+            "pair_address": "0xpair",
+            "deployer_address": "0xDeployer",
+            "tx_hash": "0x1",
+            "block_signed_at": "2025-01-01T00:00:00Z",
+            "liquidity": liquidity,
+            "supply": 1000.0,
+            "market_cap": 0.0,
+            "event_name": "PairCreated",
+            "quote_rate": 0.0,
+            "quote_rate_usd": 0.0,
+            "base_token": { "contract_name": "T", "contract_address": "0xt", "contract_decimals": 18 },
+            "quote_token": { "contract_name": "W", "contract_address": "0xw", "contract_decimals": 18 },
+            "pair": { "contract_name": "P", "contract_address": "0xpair", "contract_decimals": 18 },
+        }))
+        .unwrap();
+        EnrichedNewPair {
+            pair,
+            deployer_share: Some(0.6),
+            top_holders_share: Some(0.9),
+            top_holders: Vec::new(),
+            total_supply: Some("1000".parse().unwrap()),
+            deployer_tx_count: Some(2),
+            deployer_first_seen: None,
+            deployer_approvals: None,
+            risk_flags: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_assess_flags_rug_patterns() {
+        let pair = enriched(500.0);
+        let flags = assess(&pair, &RiskThresholds::default());
+        assert_eq!(
+            flags,
+            vec![
+                RiskFlag::DeployerMajorityHolder { share: 0.6 },
+                RiskFlag::ConcentratedSupply { share: 0.9 },
+                RiskFlag::FreshDeployer { tx_count: 2 },
+                RiskFlag::LowLiquidity { liquidity_usd: 500.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rest_chain_name() {
+        assert_eq!(rest_chain_name("BASE_MAINNET"), "base-mainnet");
+        assert_eq!(rest_chain_name("eth-mainnet"), "eth-mainnet");
+        assert_eq!(rest_chain_name("POLYGON_MAINNET"), "matic-mainnet");
+    }
+}
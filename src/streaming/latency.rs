@@ -0,0 +1,140 @@
+//! Connection Latency
+//!
+//! Rolling round-trip statistics measured from protocol ping/pong exchanges.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Snapshot of recent ping round-trip times
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Number of samples in the window
+    pub samples: usize,
+    /// Most recent round-trip time
+    pub last: Option<Duration>,
+    /// Fastest round trip in the window
+    pub min: Option<Duration>,
+    /// Slowest round trip in the window
+    pub max: Option<Duration>,
+    /// Mean round trip over the window
+    pub mean: Option<Duration>,
+    /// 95th percentile round trip over the window
+    pub p95: Option<Duration>,
+}
+
+/// Tracks outstanding pings and keeps a rolling window of round-trip samples
+#[derive(Debug)]
+pub(crate) struct LatencyTracker {
+    window: usize,
+    state: Mutex<TrackerState>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    ping_sent_at: Option<Instant>,
+    samples: VecDeque<Duration>,
+}
+
+impl LatencyTracker {
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            state: Mutex::new(TrackerState::default()),
+        }
+    }
+
+    /// Records a ping being sent. An unanswered ping keeps its original send time,
+    /// so a late pong reports the full delay.
+    pub(crate) fn ping_sent(&self, at: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.ping_sent_at.get_or_insert(at);
+    }
+
+    /// Records a pong, returning the round-trip time if a ping was outstanding
+    pub(crate) fn pong_received(&self, at: Instant) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let sent = state.ping_sent_at.take()?;
+        let rtt = at.saturating_duration_since(sent);
+        if state.samples.len() == self.window {
+            state.samples.pop_front();
+        }
+        state.samples.push_back(rtt);
+        Some(rtt)
+    }
+
+    /// How long the current ping has gone unanswered
+    pub(crate) fn outstanding(&self, now: Instant) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        state.ping_sent_at.map(|sent| now.saturating_duration_since(sent))
+    }
+
+    /// Forgets any outstanding ping, e.g. after the connection is replaced
+    pub(crate) fn reset_pending(&self) {
+        self.state.lock().unwrap().ping_sent_at = None;
+    }
+
+    /// Computes statistics over the current window
+    pub(crate) fn stats(&self) -> LatencyStats {
+        let state = self.state.lock().unwrap();
+        if state.samples.is_empty() {
+            return LatencyStats::default();
+        }
+
+        let mut sorted: Vec<Duration> = state.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let total: Duration = sorted.iter().sum();
+        let p95_index = ((sorted.len() as f64 * 0.95).ceil() as usize).saturating_sub(1);
+
+        LatencyStats {
+            samples: sorted.len(),
+            last: state.samples.back().copied(),
+            min: sorted.first().copied(),
+            max: sorted.last().copied(),
+            mean: Some(total / sorted.len() as u32),
+            p95: Some(sorted[p95_index]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_stats() {
+        let tracker = LatencyTracker::new(3);
+        let start = Instant::now();
+        for ms in [10, 40, 20, 30] {
+            tracker.ping_sent(start);
+            tracker.pong_received(start + Duration::from_millis(ms));
+        }
+
+        let stats = tracker.stats();
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.last, Some(Duration::from_millis(30)));
+        assert_eq!(stats.min, Some(Duration::from_millis(20)));
+        assert_eq!(stats.max, Some(Duration::from_millis(40)));
+        assert_eq!(stats.mean, Some(Duration::from_millis(30)));
+        assert_eq!(stats.p95, Some(Duration::from_millis(40)));
+    }
+
+    #[test]
+    fn test_unanswered_ping_keeps_first_send_time() {
+        let tracker = LatencyTracker::new(10);
+        let start = Instant::now();
+        assert_eq!(tracker.pong_received(start), None);
+
+        tracker.ping_sent(start);
+        tracker.ping_sent(start + Duration::from_secs(30));
+        assert_eq!(
+            tracker.outstanding(start + Duration::from_secs(45)),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(
+            tracker.pong_received(start + Duration::from_secs(50)),
+            Some(Duration::from_secs(50))
+        );
+        assert_eq!(tracker.outstanding(start), None);
+    }
+}
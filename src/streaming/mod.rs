@@ -2,11 +2,18 @@
 //!
 //! WebSocket-based GraphQL streaming for real-time data subscriptions.
 
+pub mod analytics;
+pub mod bridge;
 pub mod client;
 pub mod config;
+pub mod enrichment;
+pub mod latency;
 pub mod protocol;
 pub mod types;
 
+pub use analytics::{MoversRanking, PairAnalytics, TopMovers};
+pub use bridge::{BridgeStats, DeliveryGuarantee, EventBridge, EventSink, Serialization};
 pub use client::WebSocketClient;
-pub use config::{StreamingConfig, StreamingConfigBuilder};
+pub use config::{BackoffStrategy, LatencyCallback, LimitAction, QueryTransport, StreamingConfig, StreamingConfigBuilder};
+pub use latency::LatencyStats;
 pub use types::{ConnectionState, SubscriptionHandle};
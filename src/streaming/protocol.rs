@@ -116,6 +116,25 @@ pub enum PathSegment {
     Index(usize),
 }
 
+impl GraphQLError {
+    /// `extensions.code`, as a string
+    pub fn code(&self) -> Option<String> {
+        match self.extensions.as_ref()?.get("code")? {
+            Value::String(code) => Some(code.clone()),
+            Value::Null => None,
+            code => Some(code.to_string()),
+        }
+    }
+
+    /// An [`Error::SubscriptionRejected`](crate::Error::SubscriptionRejected) for a list of errors
+    pub(crate) fn rejection(errors: &[GraphQLError]) -> crate::Error {
+        crate::Error::SubscriptionRejected {
+            code: errors.iter().find_map(GraphQLError::code),
+            message: errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", "),
+        }
+    }
+}
+
 impl GraphQLMessage {
     /// Creates a connection initialization message
     pub fn connection_init(payload: Option<Value>) -> Self {
@@ -267,4 +286,19 @@ mod tests {
             panic!("Expected Error message");
         }
     }
+
+    #[test]
+    fn test_rejection_carries_code() {
+        let errors: Vec<GraphQLError> = serde_json::from_str(
+            r#"[{"message": "bad chain"}, {"message": "unauthorized", "extensions": {"code": "UNAUTHENTICATED"}}]"#,
+        )
+        .unwrap();
+        let err = GraphQLError::rejection(&errors);
+        assert!(matches!(
+            &err,
+            crate::Error::SubscriptionRejected { code: Some(code), message }
+                if code == "UNAUTHENTICATED" && message == "bad chain, unauthorized"
+        ));
+        assert_eq!(err.streaming_recovery(), crate::StreamingRecovery::GiveUp);
+    }
 }
@@ -4,14 +4,24 @@
 
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 use super::protocol::SubscriptionId;
 
 /// Handle for managing a subscription
+///
+/// Dropping the last clone of a handle unsubscribes on the current Tokio
+/// runtime (best effort; nothing is sent if no runtime is running), which also
+/// ends its stream. Keep the handle alive for as long as you consume the stream.
 #[derive(Clone)]
 pub struct SubscriptionHandle {
+    inner: Arc<HandleInner>,
+}
+
+struct HandleInner {
     id: SubscriptionId,
     client: Arc<Mutex<Option<super::client::WebSocketClient>>>,
+    completed: CancellationToken,
 }
 
 impl SubscriptionHandle {
@@ -19,25 +29,56 @@ impl SubscriptionHandle {
     pub(crate) fn new(
         id: SubscriptionId,
         client: Arc<Mutex<Option<super::client::WebSocketClient>>>,
+        completed: CancellationToken,
     ) -> Self {
-        Self { id, client }
+        Self { inner: Arc::new(HandleInner { id, client, completed }) }
     }
 
     /// Gets the subscription ID
     pub fn id(&self) -> &str {
-        &self.id
+        &self.inner.id
+    }
+
+    /// Whether the subscription is still running: not unsubscribed, not
+    /// completed or failed by the server, and its connection not closed
+    pub fn is_active(&self) -> bool {
+        !self.inner.completed.is_cancelled()
+    }
+
+    /// Waits until the subscription is no longer active
+    pub async fn await_completed(&self) {
+        self.inner.completed.cancelled().await
     }
 
     /// Unsubscribes from the stream
     pub async fn unsubscribe(self) -> Result<(), crate::error::GoldRushError> {
-        let client_guard = self.client.lock().await;
+        let client_guard = self.inner.client.lock().await;
         if let Some(client) = client_guard.as_ref() {
-            client.unsubscribe(&self.id).await?;
+            client.unsubscribe(&self.inner.id).await?;
         }
+        self.inner.completed.cancel();
         Ok(())
     }
 }
 
+impl Drop for HandleInner {
+    fn drop(&mut self) {
+        if self.completed.is_cancelled() {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let id = std::mem::take(&mut self.id);
+        let client = Arc::clone(&self.client);
+        runtime.spawn(async move {
+            if let Some(client) = client.lock().await.as_ref() {
+                let _ = client.unsubscribe(&id).await;
+            }
+        });
+    }
+}
+
 /// Connection state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionState {
@@ -73,3 +114,34 @@ impl std::fmt::Display for ConnectionState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle_completion() {
+        let completed = CancellationToken::new();
+        let handle = SubscriptionHandle::new("sub-1".to_string(), Arc::new(Mutex::new(None)), completed.clone());
+        let clone = handle.clone();
+        assert!(clone.is_active());
+
+        drop(handle);
+        assert!(clone.is_active());
+
+        let waiter = tokio::spawn(async move {
+            clone.await_completed().await;
+            clone.is_active()
+        });
+        completed.cancel();
+        assert!(!waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_marks_inactive() {
+        let completed = CancellationToken::new();
+        let handle = SubscriptionHandle::new("sub-1".to_string(), Arc::new(Mutex::new(None)), completed.clone());
+        handle.unsubscribe().await.unwrap();
+        assert!(completed.is_cancelled());
+    }
+}
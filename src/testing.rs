@@ -0,0 +1,273 @@
+//! Factories for building realistic model instances in unit tests.
+//!
+//! Factories assemble the same JSON shape the API returns and run it through
+//! the real deserializers, so the built values behave exactly like live ones.
+//!
+//! ```rust
+//! use goldrush_sdk::testing::{BalanceItemFactory, TransactionItemFactory};
+//!
+//! let usdc = BalanceItemFactory::usdc().with_quote(1000.0).build();
+//! assert_eq!(usdc.contract_ticker_symbol.as_deref(), Some("USDC"));
+//!
+//! let tx = TransactionItemFactory::transfer("0xaaa", "0xbbb", 1_000_000_000_000_000_000).build();
+//! assert!(tx.has_native_value());
+//! ```
+
+use crate::models::{self, balances::BalanceItem, transactions::TransactionItem};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+fn build<T: serde::de::DeserializeOwned>(fields: Map<String, Value>) -> T {
+    serde_json::from_value(Value::Object(fields)).expect("factory produced an invalid model")
+}
+
+/// Builder for [`BalanceItem`] values.
+#[derive(Debug, Clone)]
+pub struct BalanceItemFactory {
+    fields: Map<String, Value>,
+}
+
+impl BalanceItemFactory {
+    /// A token with the given contract, symbol and decimals and a zero balance.
+    pub fn token(contract_address: &str, symbol: &str, decimals: u32) -> Self {
+        let fields = json!({
+            "contract_address": contract_address,
+            "contract_ticker_symbol": symbol,
+            "contract_name": symbol,
+            "contract_decimals": decimals,
+            "balance": "0",
+            "type": "cryptocurrency",
+            "is_spam": false,
+            "native_token": false,
+        });
+        let Value::Object(fields) = fields else { unreachable!() };
+        Self { fields }
+    }
+
+    /// USDC on Ethereum mainnet, quoted at $1.
+    pub fn usdc() -> Self {
+        Self::token("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "USDC", 6)
+            .with_name("USD Coin")
+            .with_type("stablecoin")
+            .with_quote_rate(1.0)
+    }
+
+    /// Native ETH.
+    pub fn eth() -> Self {
+        Self::token("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee", "ETH", 18)
+            .with_name("Ether")
+            .with_field("native_token", true)
+    }
+
+    pub fn with_name(self, name: &str) -> Self { self.with_field("contract_name", name) }
+    pub fn with_type(self, token_type: &str) -> Self { self.with_field("type", token_type) }
+    pub fn with_balance(self, raw: u128) -> Self { self.with_field("balance", raw.to_string()) }
+    pub fn with_quote_rate(self, rate: f64) -> Self { self.with_field("quote_rate", rate) }
+    pub fn spam(self) -> Self { self.with_field("is_spam", true) }
+
+    /// Set the quote value, deriving the raw balance from the quote rate when one is set.
+    pub fn with_quote(self, quote: f64) -> Self {
+        let rate = self.fields.get("quote_rate").and_then(Value::as_f64);
+        let decimals = self.fields.get("contract_decimals").and_then(Value::as_u64).unwrap_or(0);
+        let this = self.with_field("quote", quote);
+        match rate {
+            Some(rate) if rate > 0.0 => {
+                let raw = (quote / rate * 10f64.powi(decimals as i32)).round() as u128;
+                this.with_balance(raw)
+            }
+            _ => this,
+        }
+    }
+
+    /// Set an arbitrary JSON field, including ones without a typed accessor.
+    pub fn with_field(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn build(self) -> BalanceItem {
+        build(self.fields)
+    }
+}
+
+/// Builder for [`TransactionItem`] values.
+#[derive(Debug, Clone)]
+pub struct TransactionItemFactory {
+    fields: Map<String, Value>,
+}
+
+impl TransactionItemFactory {
+    /// A successful native-value transfer.
+    pub fn transfer(from: &str, to: &str, value_wei: u128) -> Self {
+        let fields = json!({
+            "tx_hash": "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+            "from_address": from,
+            "to_address": to,
+            "value": value_wei.to_string(),
+            "successful": true,
+            "block_height": 18_000_000u64,
+            "block_signed_at": "2023-08-25T12:00:00Z",
+            "gas_price": 20_000_000_000u64,
+            "gas_limit": 21_000u64,
+            "gas_spent": 21_000u64,
+            "log_events": [],
+        });
+        let Value::Object(fields) = fields else { unreachable!() };
+        Self { fields }
+    }
+
+    /// A successful contract call with the given calldata and no value.
+    pub fn contract_call(from: &str, to: &str, input: &str) -> Self {
+        Self::transfer(from, to, 0)
+            .with_field("input", input)
+            .with_field("gas_limit", 200_000u64)
+            .with_field("gas_spent", 120_000u64)
+    }
+
+    pub fn with_hash(self, hash: &str) -> Self { self.with_field("tx_hash", hash) }
+    pub fn with_block_height(self, height: u64) -> Self { self.with_field("block_height", height) }
+    pub fn with_signed_at(self, timestamp: &str) -> Self { self.with_field("block_signed_at", timestamp) }
+    pub fn with_value_quote(self, quote: f64) -> Self { self.with_field("value_quote", quote) }
+    pub fn failed(self) -> Self { self.with_field("successful", false) }
+
+    /// Set gas units spent and the gas price in wei.
+    pub fn with_gas(self, spent: u64, price_wei: u64) -> Self {
+        self.with_field("gas_spent", spent).with_field("gas_price", price_wei)
+    }
+
+    /// Append an ERC-20 `Transfer` log emitted by `token`.
+    pub fn with_erc20_transfer(mut self, token: &str, from: &str, to: &str, amount: u128) -> Self {
+        let log = json!({
+            "sender_contract_address": token,
+            "decoded": {
+                "name": "Transfer",
+                "params": [
+                    { "name": "from", "type": "address", "value": from },
+                    { "name": "to", "type": "address", "value": to },
+                    { "name": "value", "type": "uint256", "value": amount.to_string() },
+                ],
+            },
+        });
+        if let Some(Value::Array(logs)) = self.fields.get_mut("log_events") {
+            logs.push(log);
+        } else {
+            self.fields.insert("log_events".to_string(), json!([log]));
+        }
+        self
+    }
+
+    /// Set an arbitrary JSON field, including ones without a typed accessor.
+    pub fn with_field(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn build(self) -> TransactionItem {
+        build(self.fields)
+    }
+}
+
+/// A captured API response body and the model it decodes into.
+///
+/// The bodies live in `tests/corpus/` and include the fields the SDK ignores,
+/// nulls where the API sends them, and error envelopes.
+#[derive(Clone, Copy)]
+pub struct Payload {
+    /// `<service>/<variant>`, the path of the body under `tests/corpus/`.
+    pub name: &'static str,
+    /// The response body as captured.
+    pub json: &'static str,
+    round_trip: fn(Value) -> serde_json::Result<Value>,
+}
+
+impl fmt::Debug for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Payload").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+impl Payload {
+    /// The body parsed as JSON.
+    pub fn value(&self) -> Value {
+        serde_json::from_str(self.json).expect("corpus payload is valid JSON")
+    }
+
+    /// Decode `body` into this payload's model and serialize the model back.
+    pub fn round_trip(&self, body: Value) -> serde_json::Result<Value> {
+        (self.round_trip)(body)
+    }
+}
+
+fn round_trip<T: DeserializeOwned + Serialize>(body: Value) -> serde_json::Result<Value> {
+    serde_json::to_value(serde_json::from_value::<T>(body)?)
+}
+
+macro_rules! corpus {
+    ($($name:literal => $model:ty),* $(,)?) => {
+        &[$(Payload {
+            name: $name,
+            json: include_str!(concat!("../tests/corpus/", $name, ".json")),
+            round_trip: round_trip::<$model>,
+        }),*]
+    };
+}
+
+const CORPUS: &[Payload] = corpus! {
+    "balances/token_balances" => models::balances::BalancesResponse,
+    "balances/erc20_transfers" => models::balances::Erc20TransfersResponse,
+    "balances/error_bad_address" => models::balances::BalancesResponse,
+    "transactions/transactions_v3" => models::transactions::TransactionsResponse,
+    "transactions/summary" => models::transactions::TransactionSummaryResponse,
+    "base/block" => models::base::BlockResponse,
+    "base/chains" => models::base::AllChainsResponse,
+    "base/chain_status" => models::base::AllChainStatusResponse,
+    "base/gas_prices" => models::base::GasPricesResponse,
+    "base/logs" => models::base::LogsResponse,
+    "nfts/nfts_for_address" => models::nfts::NftsResponse,
+    "pricing/historical_prices" => models::pricing::TokenPricesResponse,
+    "approvals/token_approvals" => models::approvals::ApprovalsResponse,
+    "all_chains/transactions" => models::all_chains::MultiChainTransactionsResponse,
+};
+
+/// Every captured payload, for round-tripping through the models in
+/// property tests. See `tests/model_fuzz.rs` for the SDK's own harness.
+pub fn corpus() -> &'static [Payload] {
+    CORPUS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::{Quote, QuoteValue};
+    use crate::TransactionKind;
+
+    #[test]
+    fn test_balance_factory_derives_balance_from_quote() {
+        let item = BalanceItemFactory::usdc().with_quote(1000.0).build();
+        assert_eq!(item.balance.to_string(), "1000000000");
+        assert_eq!(item.quote, Some(Quote::from_f64(1000.0)));
+        assert_eq!(item.contract_decimals, Some(6));
+    }
+
+    #[test]
+    fn test_transaction_factory_kinds() {
+        let transfer = TransactionItemFactory::transfer("0xaaa", "0xbbb", 5).build();
+        assert_eq!(transfer.kind(), TransactionKind::NativeTransfer);
+
+        let erc20 = TransactionItemFactory::contract_call("0xaaa", "0xtoken", "0xa9059cbb")
+            .with_erc20_transfer("0xtoken", "0xaaa", "0xbbb", 10)
+            .failed()
+            .build();
+        assert_eq!(erc20.successful, Some(false));
+        assert_eq!(erc20.erc20_transfer_events().count(), 1);
+    }
+    #[test]
+    fn test_corpus_decodes() {
+        for payload in corpus() {
+            let body = payload.round_trip(payload.value()).unwrap_or_else(|e| panic!("{}: {}", payload.name, e));
+            assert!(body.get("data").is_some(), "{}", payload.name);
+        }
+    }
+}
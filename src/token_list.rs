@@ -0,0 +1,257 @@
+//! Uniswap-style token list support.
+//!
+//! Token lists (<https://tokenlists.org>) are curated `(chainId, address)` →
+//! metadata mappings. A [`TokenListRegistry`] uses them to normalize symbols,
+//! decimals and logos on balance and transfer items, and to flag tokens that
+//! reuse a listed symbol from an unlisted contract, a common phishing pattern.
+//!
+//! ```rust,no_run
+//! use goldrush_sdk::{TokenList, TokenListRegistry};
+//!
+//! # async fn example(mut balances: goldrush_sdk::BalancesData) -> goldrush_sdk::Result<()> {
+//! let list = TokenList::fetch("https://tokens.uniswap.org").await?;
+//! let registry = TokenListRegistry::new().with_list(list);
+//!
+//! let statuses = registry.normalize_balances(&mut balances);
+//! for (item, status) in balances.items.iter().zip(statuses) {
+//!     if status.is_impersonator() {
+//!         println!("{} impersonates a listed token", item.contract_address);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address::normalize_address;
+use crate::models::balances::{BalanceItem, BalancesData, Erc20TransferItem, Erc20TransfersData};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A token list document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenList {
+    pub name: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    pub tokens: Vec<TokenListEntry>,
+}
+
+/// One token in a token list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenListEntry {
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    #[serde(rename = "logoURI", default)]
+    pub logo_uri: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl TokenList {
+    /// Parse a token list from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| Error::Config(format!("Invalid token list: {}", e)))
+    }
+
+    /// Read a token list from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Download a token list.
+    pub async fn fetch(url: &str) -> Result<Self> {
+        let body = reqwest::get(url).await?.error_for_status()?.text().await?;
+        Self::from_json(&body)
+    }
+}
+
+/// How a token relates to the loaded lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenListStatus {
+    /// The contract is on a list.
+    Listed,
+    /// The contract is unlisted but uses the symbol of a listed token.
+    Impersonator {
+        /// Address of the listed token with that symbol.
+        listed_address: String,
+    },
+    /// Neither the contract nor its symbol is listed.
+    Unlisted,
+}
+
+impl TokenListStatus {
+    pub fn is_listed(&self) -> bool {
+        matches!(self, Self::Listed)
+    }
+
+    pub fn is_impersonator(&self) -> bool {
+        matches!(self, Self::Impersonator { .. })
+    }
+}
+
+/// Index over one or more token lists.
+#[derive(Debug, Clone, Default)]
+pub struct TokenListRegistry {
+    by_address: HashMap<(u64, String), TokenListEntry>,
+    by_symbol: HashMap<(u64, String), String>,
+}
+
+impl TokenListRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_list(mut self, list: TokenList) -> Self {
+        self.add_list(list);
+        self
+    }
+
+    /// Index a list. Entries from later lists override earlier ones for the same contract.
+    pub fn add_list(&mut self, list: TokenList) {
+        for entry in list.tokens {
+            let address = normalize_address(&entry.address);
+            self.by_symbol
+                .entry((entry.chain_id, entry.symbol.to_ascii_uppercase()))
+                .or_insert_with(|| address.clone());
+            self.by_address.insert((entry.chain_id, address), entry);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_address.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_address.is_empty()
+    }
+
+    /// The listed entry for a contract.
+    pub fn lookup(&self, chain_id: u64, address: &str) -> Option<&TokenListEntry> {
+        self.by_address.get(&(chain_id, normalize_address(address)))
+    }
+
+    /// Classify a contract and the symbol it reports.
+    pub fn status(&self, chain_id: u64, address: &str, symbol: Option<&str>) -> TokenListStatus {
+        if self.lookup(chain_id, address).is_some() {
+            return TokenListStatus::Listed;
+        }
+        symbol
+            .and_then(|s| self.by_symbol.get(&(chain_id, s.trim().to_ascii_uppercase())))
+            .map_or(TokenListStatus::Unlisted, |listed| TokenListStatus::Impersonator {
+                listed_address: listed.clone(),
+            })
+    }
+
+    /// Overwrite symbol, name, decimals and logo on a listed balance item.
+    pub fn normalize_balance(&self, chain_id: u64, item: &mut BalanceItem) -> TokenListStatus {
+        let status = self.status(chain_id, &item.contract_address, item.contract_ticker_symbol.as_deref());
+        if let Some(entry) = self.lookup(chain_id, &item.contract_address) {
+            item.contract_ticker_symbol = Some(entry.symbol.clone());
+            item.contract_name = Some(entry.name.clone());
+            item.contract_decimals = Some(entry.decimals);
+            if entry.logo_uri.is_some() {
+                item.logo_url = entry.logo_uri.clone();
+            }
+        }
+        status
+    }
+
+    /// Overwrite symbol, name, decimals and logo on a listed transfer item.
+    pub fn normalize_transfer(&self, chain_id: u64, item: &mut Erc20TransferItem) -> TokenListStatus {
+        let Some(address) = item.contract_address.clone() else {
+            return TokenListStatus::Unlisted;
+        };
+        let status = self.status(chain_id, &address, item.contract_ticker_symbol.as_deref());
+        if let Some(entry) = self.lookup(chain_id, &address) {
+            item.contract_ticker_symbol = Some(entry.symbol.clone());
+            item.contract_name = Some(entry.name.clone());
+            item.contract_decimals = Some(entry.decimals);
+            if entry.logo_uri.is_some() {
+                item.logo_url = entry.logo_uri.clone();
+            }
+        }
+        status
+    }
+
+    /// Normalize every item, returning one status per item in order.
+    ///
+    /// Items are left untouched when the response has no `chain_id`.
+    pub fn normalize_balances(&self, data: &mut BalancesData) -> Vec<TokenListStatus> {
+        let chain_id = data.chain_id;
+        data.items
+            .iter_mut()
+            .map(|item| match chain_id {
+                Some(chain_id) => self.normalize_balance(chain_id, item),
+                None => TokenListStatus::Unlisted,
+            })
+            .collect()
+    }
+
+    /// Normalize every transfer, returning one status per item in order.
+    ///
+    /// Items are left untouched when the response has no `chain_id`.
+    pub fn normalize_transfers(&self, data: &mut Erc20TransfersData) -> Vec<TokenListStatus> {
+        let chain_id = data.chain_id;
+        data.items
+            .iter_mut()
+            .map(|item| match chain_id {
+                Some(chain_id) => self.normalize_transfer(chain_id, item),
+                None => TokenListStatus::Unlisted,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = r#"{
+        "name": "Test List",
+        "timestamp": "2024-01-01T00:00:00Z",
+        "version": { "major": 1, "minor": 0, "patch": 0 },
+        "tokens": [{
+            "chainId": 1,
+            "address": "0xA0b86991c6218b36c1d19d4a2e9eB0cE3606eB48",
+            "name": "USD Coin",
+            "symbol": "USDC",
+            "decimals": 6,
+            "logoURI": "https://example.com/usdc.png"
+        }]
+    }"#;
+
+    fn balance(address: &str, symbol: &str) -> BalanceItem {
+        serde_json::from_value(serde_json::json!({
+            "contract_address": address,
+            "contract_ticker_symbol": symbol,
+            "balance": "1",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalizes_listed_and_flags_impersonators() {
+        let registry = TokenListRegistry::new().with_list(TokenList::from_json(LIST).unwrap());
+
+        let mut listed = balance("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "usdc.e");
+        assert!(registry.normalize_balance(1, &mut listed).is_listed());
+        assert_eq!(listed.contract_ticker_symbol.as_deref(), Some("USDC"));
+        assert_eq!(listed.contract_decimals, Some(6));
+        assert_eq!(listed.logo_url.as_deref(), Some("https://example.com/usdc.png"));
+
+        let mut fake = balance("0xbad", " usdc");
+        assert_eq!(
+            registry.normalize_balance(1, &mut fake),
+            TokenListStatus::Impersonator { listed_address: "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string() }
+        );
+        assert_eq!(fake.contract_ticker_symbol.as_deref(), Some(" usdc"));
+
+        assert_eq!(registry.status(137, "0xbad", Some("USDC")), TokenListStatus::Unlisted);
+    }
+}
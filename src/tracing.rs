@@ -1,4 +1,3 @@
-use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
 /// Request correlation ID for tracing requests across the system.
@@ -89,6 +88,7 @@ macro_rules! trace_error {
 }
 
 /// Instrument a function with tracing context.
+#[allow(dead_code)]
 pub fn instrument_request<F, T>(ctx: &TracingContext, f: F) -> T
 where
     F: FnOnce() -> T,
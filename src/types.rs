@@ -1,13 +1,19 @@
 //! Shared types used across the GoldRush SDK.
 
+use crate::models::open_enum;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 pub use crate::chains::Chain;
 
 /// Quote currency for pricing data.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Covers every fiat and crypto quote accepted by the `quote-currency` query
+/// parameter. Serializes and displays as the upper-case ISO code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum QuoteCurrency {
+    #[default]
     USD,
     CAD,
     EUR,
@@ -28,9 +34,32 @@ pub enum QuoteCurrency {
     ETH,
 }
 
-impl fmt::Display for QuoteCurrency {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
+impl QuoteCurrency {
+    /// All supported quote currencies.
+    pub const ALL: &[QuoteCurrency] = &[
+        QuoteCurrency::USD,
+        QuoteCurrency::CAD,
+        QuoteCurrency::EUR,
+        QuoteCurrency::SGD,
+        QuoteCurrency::INR,
+        QuoteCurrency::JPY,
+        QuoteCurrency::VND,
+        QuoteCurrency::CNY,
+        QuoteCurrency::KRW,
+        QuoteCurrency::RUB,
+        QuoteCurrency::TRY,
+        QuoteCurrency::NGN,
+        QuoteCurrency::ARS,
+        QuoteCurrency::AUD,
+        QuoteCurrency::CHF,
+        QuoteCurrency::GBP,
+        QuoteCurrency::BTC,
+        QuoteCurrency::ETH,
+    ];
+
+    /// The currency code sent to the API (e.g. `"USD"`).
+    pub fn code(&self) -> &'static str {
+        match self {
             QuoteCurrency::USD => "USD",
             QuoteCurrency::CAD => "CAD",
             QuoteCurrency::EUR => "EUR",
@@ -49,68 +78,126 @@ impl fmt::Display for QuoteCurrency {
             QuoteCurrency::GBP => "GBP",
             QuoteCurrency::BTC => "BTC",
             QuoteCurrency::ETH => "ETH",
-        };
-        write!(f, "{}", s)
+        }
     }
-}
 
-impl AsRef<str> for QuoteCurrency {
-    fn as_ref(&self) -> &str {
+    /// Display symbol for formatting amounts (e.g. `"$"`, `"€"`, `"Ξ"`).
+    pub fn symbol(&self) -> &'static str {
         match self {
-            QuoteCurrency::USD => "USD",
-            QuoteCurrency::CAD => "CAD",
-            QuoteCurrency::EUR => "EUR",
-            QuoteCurrency::SGD => "SGD",
-            QuoteCurrency::INR => "INR",
-            QuoteCurrency::JPY => "JPY",
-            QuoteCurrency::VND => "VND",
-            QuoteCurrency::CNY => "CNY",
-            QuoteCurrency::KRW => "KRW",
-            QuoteCurrency::RUB => "RUB",
-            QuoteCurrency::TRY => "TRY",
-            QuoteCurrency::NGN => "NGN",
-            QuoteCurrency::ARS => "ARS",
-            QuoteCurrency::AUD => "AUD",
+            QuoteCurrency::USD => "$",
+            QuoteCurrency::CAD => "C$",
+            QuoteCurrency::EUR => "€",
+            QuoteCurrency::SGD => "S$",
+            QuoteCurrency::INR => "₹",
+            QuoteCurrency::JPY => "¥",
+            QuoteCurrency::VND => "₫",
+            QuoteCurrency::CNY => "CN¥",
+            QuoteCurrency::KRW => "₩",
+            QuoteCurrency::RUB => "₽",
+            QuoteCurrency::TRY => "₺",
+            QuoteCurrency::NGN => "₦",
+            QuoteCurrency::ARS => "AR$",
+            QuoteCurrency::AUD => "A$",
             QuoteCurrency::CHF => "CHF",
-            QuoteCurrency::GBP => "GBP",
-            QuoteCurrency::BTC => "BTC",
-            QuoteCurrency::ETH => "ETH",
+            QuoteCurrency::GBP => "£",
+            QuoteCurrency::BTC => "₿",
+            QuoteCurrency::ETH => "Ξ",
         }
     }
-}
 
-/// Gas event type for gas price queries.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum GasEventType {
-    #[serde(rename = "erc20")]
-    Erc20,
-    #[serde(rename = "nativetokens")]
-    NativeTokens,
-    #[serde(rename = "uniswapv3")]
-    UniswapV3,
+    /// Whether this is a crypto (rather than fiat) quote currency.
+    pub fn is_crypto(&self) -> bool {
+        matches!(self, QuoteCurrency::BTC | QuoteCurrency::ETH)
+    }
 }
 
-impl fmt::Display for GasEventType {
+impl fmt::Display for QuoteCurrency {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            GasEventType::Erc20 => "erc20",
-            GasEventType::NativeTokens => "nativetokens",
-            GasEventType::UniswapV3 => "uniswapv3",
-        };
-        write!(f, "{}", s)
+        write!(f, "{}", self.code())
     }
 }
 
-impl AsRef<str> for GasEventType {
+impl AsRef<str> for QuoteCurrency {
     fn as_ref(&self) -> &str {
+        self.code()
+    }
+}
+
+impl FromStr for QuoteCurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let code = s.trim();
+        QuoteCurrency::ALL
+            .iter()
+            .copied()
+            .find(|c| c.code().eq_ignore_ascii_case(code))
+            .ok_or_else(|| format!("Unknown quote currency: {}", s))
+    }
+}
+
+open_enum! {
+    /// Gas event type for gas price queries.
+    ///
+    /// Used as the `{event_type}` path segment of the gas-prices endpoint.
+    pub enum GasEventType {
+        Erc20 = "erc20",
+        NativeTokens = "nativetokens",
+        UniswapV3 = "uniswapv3",
+    }
+}
+
+impl GasEventType {
+    /// All supported gas event types.
+    pub const ALL: &[GasEventType] = &[
+        GasEventType::Erc20,
+        GasEventType::NativeTokens,
+        GasEventType::UniswapV3,
+    ];
+}
+
+impl FromStr for GasEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let value = s.trim();
+        GasEventType::ALL
+            .iter()
+            .find(|e| e.as_ref().eq_ignore_ascii_case(value))
+            .cloned()
+            .ok_or_else(|| format!("Unknown gas event type: {}", s))
+    }
+}
+
+/// Version of endpoints that exist in more than one flavor.
+///
+/// `V3` transaction endpoints page through the URL path (`.../page/{n}/`) and
+/// return `links`; `V2` endpoints take a `page-number` query parameter and
+/// return a `pagination` block. Endpoints that only exist in one version
+/// ignore this setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiVersion {
+    V2,
+    #[default]
+    V3,
+}
+
+impl ApiVersion {
+    /// The path suffix used by versioned endpoints (e.g. `"v3"`).
+    pub fn suffix(&self) -> &'static str {
         match self {
-            GasEventType::Erc20 => "erc20",
-            GasEventType::NativeTokens => "nativetokens",
-            GasEventType::UniswapV3 => "uniswapv3",
+            ApiVersion::V2 => "v2",
+            ApiVersion::V3 => "v3",
         }
     }
 }
 
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.suffix())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,9 +209,23 @@ mod tests {
         assert_eq!(QuoteCurrency::BTC.to_string(), "BTC");
     }
 
+    #[test]
+    fn test_quote_currency_from_str() {
+        for currency in QuoteCurrency::ALL {
+            assert_eq!(currency.code().parse::<QuoteCurrency>(), Ok(*currency));
+        }
+        assert_eq!("eur".parse::<QuoteCurrency>(), Ok(QuoteCurrency::EUR));
+        assert!("XYZ".parse::<QuoteCurrency>().is_err());
+        assert_eq!(QuoteCurrency::ETH.symbol(), "Ξ");
+        assert_eq!(serde_json::to_string(&QuoteCurrency::GBP).unwrap(), "\"GBP\"");
+    }
+
     #[test]
     fn test_gas_event_type_display() {
         assert_eq!(GasEventType::Erc20.to_string(), "erc20");
         assert_eq!(GasEventType::NativeTokens.to_string(), "nativetokens");
+        assert_eq!("uniswapv3".parse::<GasEventType>(), Ok(GasEventType::UniswapV3));
+        assert!("swap".parse::<GasEventType>().is_err());
+        assert_eq!(serde_json::from_str::<GasEventType>("\"erc20\"").unwrap(), GasEventType::Erc20);
     }
 }
@@ -0,0 +1,202 @@
+//! Address, hex and unit helpers for working with SDK responses.
+//!
+//! ```rust
+//! use goldrush_sdk::utils;
+//!
+//! let addr = utils::to_checksum_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+//! assert_eq!(addr, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+//! assert_eq!(utils::short_address(&addr), "0x5aAe…eAed");
+//! assert_eq!(utils::format_ether(1_500_000_000_000_000_000), "1.5");
+//! assert_eq!(utils::parse_gwei("20.5").unwrap(), 20_500_000_000);
+//! ```
+
+use crate::abi::keccak256;
+use crate::validation::Validator;
+use crate::{Error, Result};
+
+/// Decimals of ether (and most EVM native tokens).
+pub const ETHER_DECIMALS: u32 = 18;
+
+/// Decimals of gwei relative to wei.
+pub const GWEI_DECIMALS: u32 = 9;
+
+/// Remove a leading `0x`/`0X`, if present.
+pub fn strip_0x(hex: &str) -> &str {
+    hex.strip_prefix("0x").or_else(|| hex.strip_prefix("0X")).unwrap_or(hex)
+}
+
+/// Lowercase a hex string and ensure it has a `0x` prefix.
+pub fn normalize_hex(hex: &str) -> Result<String> {
+    let digits = strip_0x(hex.trim());
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(Error::Config(format!("Invalid hex character: '{}'", c)));
+    }
+    Ok(format!("0x{}", digits.to_ascii_lowercase()))
+}
+
+/// EIP-55 mixed-case checksum encoding of an EVM address.
+pub fn to_checksum_address(address: &str) -> Result<String> {
+    let lower = normalize_hex(address)?;
+    Validator::validate_address(&lower)?;
+
+    let digits = &lower[2..];
+    let hash = keccak256(digits.as_bytes());
+    let checksummed: String = digits
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if c.is_ascii_alphabetic() && nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    Ok(format!("0x{}", checksummed))
+}
+
+/// Whether an address is valid under EIP-55.
+///
+/// All-lowercase and all-uppercase addresses carry no checksum and are accepted.
+pub fn is_valid_checksum(address: &str) -> bool {
+    let digits = strip_0x(address);
+    if digits == digits.to_ascii_lowercase() || digits == digits.to_ascii_uppercase() {
+        return Validator::validate_address(&format!("0x{}", digits)).is_ok();
+    }
+    to_checksum_address(address).is_ok_and(|checksummed| checksummed[2..] == *digits)
+}
+
+/// `0x1234…abcd` form of an address or hash.
+pub fn short_address(address: &str) -> String {
+    short_address_with(address, 4, 4)
+}
+
+/// Shorten to `lead` hex digits after `0x` and `tail` trailing characters.
+pub fn short_address_with(address: &str, lead: usize, tail: usize) -> String {
+    let prefix = if address.starts_with("0x") { 2 } else { 0 };
+    if !address.is_ascii() || address.len() <= prefix + lead + tail + 1 {
+        return address.to_string();
+    }
+    format!("{}…{}", &address[..prefix + lead], &address[address.len() - tail..])
+}
+
+/// Render a raw integer amount with `decimals` decimal places, trimming trailing zeros.
+pub fn format_units(amount: u128, decimals: u32) -> String {
+    format_digits(&amount.to_string(), decimals)
+}
+
+/// [`format_units`] for an amount given as base-10 digits, of any size.
+pub(crate) fn format_digits(digits: &str, decimals: u32) -> String {
+    let trimmed = digits.trim_start_matches('0');
+    let digits = if trimmed.is_empty() { "0" } else { trimmed };
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits.to_string();
+    }
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Parse a decimal amount into its raw integer form with `decimals` decimal places.
+pub fn parse_units(amount: &str, decimals: u32) -> Result<u128> {
+    let amount = amount.trim();
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let invalid = || Error::Config(format!("Invalid amount: '{}'", amount));
+
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    if fraction.len() > decimals as usize {
+        return Err(Error::Config(format!("Amount '{}' has more than {} decimals", amount, decimals)));
+    }
+
+    let scale = 10u128.checked_pow(decimals).ok_or_else(invalid)?;
+    let whole: u128 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+        padded.parse().map_err(|_| invalid())?
+    };
+    whole
+        .checked_mul(scale)
+        .and_then(|w| w.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+/// Wei rendered as ether.
+pub fn format_ether(wei: u128) -> String {
+    format_units(wei, ETHER_DECIMALS)
+}
+
+/// Wei rendered as gwei.
+pub fn format_gwei(wei: u128) -> String {
+    format_units(wei, GWEI_DECIMALS)
+}
+
+/// Ether amount to wei.
+pub fn parse_ether(ether: &str) -> Result<u128> {
+    parse_units(ether, ETHER_DECIMALS)
+}
+
+/// Gwei amount to wei.
+pub fn parse_gwei(gwei: &str) -> Result<u128> {
+    parse_units(gwei, GWEI_DECIMALS)
+}
+
+/// Wei as a floating-point ether value, for display and arithmetic where precision loss is acceptable.
+pub fn wei_to_ether(wei: u128) -> f64 {
+    wei as f64 / 1e18
+}
+
+/// Wei as a floating-point gwei value.
+pub fn wei_to_gwei(wei: u128) -> f64 {
+    wei as f64 / 1e9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eip55_vectors() {
+        for expected in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert_eq!(to_checksum_address(&expected.to_lowercase()).unwrap(), expected);
+            assert!(is_valid_checksum(expected));
+        }
+        assert!(!is_valid_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(to_checksum_address("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_units_round_trip() {
+        assert_eq!(format_units(0, 18), "0");
+        assert_eq!(format_units(1, 6), "0.000001");
+        assert_eq!(format_units(1_000_000, 6), "1");
+        assert_eq!(parse_units("0.000001", 6).unwrap(), 1);
+        assert_eq!(parse_ether("1.5").unwrap(), 1_500_000_000_000_000_000);
+        assert_eq!(format_gwei(parse_gwei(".25").unwrap()), "0.25");
+        assert!(parse_units("1.0000001", 6).is_err());
+        assert!(parse_units("1e5", 6).is_err());
+        assert!(parse_units(".", 6).is_err());
+    }
+
+    #[test]
+    fn test_hex_and_short_address() {
+        assert_eq!(normalize_hex("0XABcd").unwrap(), "0xabcd");
+        assert_eq!(normalize_hex("abcd").unwrap(), "0xabcd");
+        assert!(normalize_hex("0xzz").is_err());
+        assert_eq!(short_address("0x1234567890abcdef1234"), "0x1234…1234");
+        assert_eq!(short_address("0x1234"), "0x1234");
+    }
+}
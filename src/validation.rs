@@ -2,6 +2,9 @@ use crate::{Error, Result};
 use std::collections::HashSet;
 use tracing::{debug, instrument};
 
+/// Characters allowed in base58-encoded (Bitcoin/Solana) strings.
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
 /// Validation utilities for blockchain data.
 pub struct Validator;
 
@@ -37,6 +40,94 @@ impl Validator {
         Ok(())
     }
 
+    /// Validate a base58-encoded Solana address (32-44 characters).
+    #[instrument(fields(address = %address))]
+    pub fn validate_solana_address(address: &str) -> Result<()> {
+        let address = address.trim();
+
+        if address.is_empty() {
+            return Err(Error::Config("Solana address cannot be empty".to_string()));
+        }
+
+        if address.len() < 32 || address.len() > 44 {
+            return Err(Error::Config(format!(
+                "Solana address must be 32-44 characters long, got {}", address.len()
+            )));
+        }
+
+        for char in address.chars() {
+            if !BASE58_ALPHABET.contains(char) {
+                return Err(Error::Config(format!(
+                    "Solana address contains invalid base58 character: '{}'", char
+                )));
+            }
+        }
+
+        debug!("Solana address validation passed");
+        Ok(())
+    }
+
+    /// Validate an application identifier of the form `name/version`.
+    pub fn validate_app_info(app_info: &str) -> Result<()> {
+        let (name, version) = app_info.split_once('/')
+            .ok_or_else(|| Error::Config(format!("App info must be 'name/version', got '{}'", app_info)))?;
+
+        let is_token = |s: &str| !s.is_empty()
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || "-._+".contains(c));
+        if !is_token(name) || !is_token(version) {
+            return Err(Error::Config(format!(
+                "App name and version may only contain letters, digits and '-._+', got '{}'", app_info
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validate a Bitcoin address (legacy base58 `1`/`3` or bech32 `bc1`).
+    #[instrument(fields(address = %address))]
+    pub fn validate_btc_address(address: &str) -> Result<()> {
+        const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+        let address = address.trim();
+
+        if address.is_empty() {
+            return Err(Error::Config("Bitcoin address cannot be empty".to_string()));
+        }
+
+        let lower = address.to_lowercase();
+        if let Some(data) = lower.strip_prefix("bc1") {
+            if address != lower && address != address.to_uppercase() {
+                return Err(Error::Config("Bech32 address must not mix upper and lower case".to_string()));
+            }
+            if lower.len() < 14 || lower.len() > 74 {
+                return Err(Error::Config(format!(
+                    "Bech32 address must be 14-74 characters long, got {}", lower.len()
+                )));
+            }
+            if let Some(char) = data.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+                return Err(Error::Config(format!(
+                    "Bech32 address contains invalid character: '{}'", char
+                )));
+            }
+        } else {
+            if !address.starts_with('1') && !address.starts_with('3') {
+                return Err(Error::Config("Bitcoin address must start with '1', '3' or 'bc1'".to_string()));
+            }
+            if address.len() < 25 || address.len() > 34 {
+                return Err(Error::Config(format!(
+                    "Bitcoin address must be 25-34 characters long, got {}", address.len()
+                )));
+            }
+            if let Some(char) = address.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+                return Err(Error::Config(format!(
+                    "Bitcoin address contains invalid base58 character: '{}'", char
+                )));
+            }
+        }
+
+        debug!("Bitcoin address validation passed");
+        Ok(())
+    }
+
     /// Validate a transaction hash (66 characters, starts with 0x).
     #[instrument(fields(tx_hash = %tx_hash))]
     pub fn validate_tx_hash(tx_hash: &str) -> Result<()> {
@@ -283,6 +374,35 @@ mod tests {
         assert!(Validator::validate_tx_hash("0x1234").is_err());
     }
 
+    #[test]
+    fn test_solana_address_validation() {
+        assert!(Validator::validate_solana_address("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").is_ok());
+        assert!(Validator::validate_solana_address("So11111111111111111111111111111111111111112").is_ok());
+
+        assert!(Validator::validate_solana_address("").is_err());
+        assert!(Validator::validate_solana_address("0x742d35Cc6634C0532925a3b8D4fc24f3C4aD6a8b").is_err());
+        assert!(Validator::validate_solana_address("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWW0").is_err());
+    }
+
+    #[test]
+    fn test_app_info_validation() {
+        assert!(Validator::validate_app_info("my-indexer/1.2.0").is_ok());
+        assert!(Validator::validate_app_info("my-indexer").is_err());
+        assert!(Validator::validate_app_info("my indexer/1.2").is_err());
+        assert!(Validator::validate_app_info("/1.2").is_err());
+    }
+
+    #[test]
+    fn test_btc_address_validation() {
+        assert!(Validator::validate_btc_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").is_ok());
+        assert!(Validator::validate_btc_address("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy").is_ok());
+        assert!(Validator::validate_btc_address("bc1qm34lsc65zpw79lxes69zkqmk6ee3ewf0j77s3h").is_ok());
+
+        assert!(Validator::validate_btc_address("").is_err());
+        assert!(Validator::validate_btc_address("0x742d35Cc6634C0532925a3b8D4fc24f3C4aD6a8b").is_err());
+        assert!(Validator::validate_btc_address("bc1qm34lsc65zpw79lxes69zkqmk6ee3ewf0j77s3b").is_err());
+    }
+
     #[test]
     fn test_api_key_validation_ckey() {
         // Valid ckey format: ckey_ + 27 hex chars
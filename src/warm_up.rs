@@ -0,0 +1,110 @@
+//! Connection pre-warming and keepalive.
+//!
+//! The first call on a fresh client pays for DNS resolution and the TCP and
+//! TLS handshakes. [`GoldRushClient::warm_up`](crate::GoldRushClient::warm_up)
+//! does that work up front with a `HEAD` request to the base URL, which needs
+//! no API key and costs no credits, and can keep the pooled connection open
+//! with periodic keepalive requests afterwards.
+
+use crate::services::ServiceContext;
+use crate::{Error, Result};
+use std::net::ToSocketAddrs;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// How [`GoldRushClient::warm_up`](crate::GoldRushClient::warm_up) prepares connections.
+#[derive(Debug, Clone)]
+pub struct WarmUpConfig {
+    /// Resolve the base URL's host before connecting, timing it separately.
+    pub resolve_dns: bool,
+    /// Send a keepalive request this often once warmed up; keep it below
+    /// [`ClientConfig::keep_alive_timeout`](crate::ClientConfig::keep_alive_timeout)
+    /// so the pooled connection is never idle long enough to be closed.
+    pub keepalive_interval: Option<Duration>,
+}
+
+impl Default for WarmUpConfig {
+    fn default() -> Self {
+        Self { resolve_dns: true, keepalive_interval: None }
+    }
+}
+
+impl WarmUpConfig {
+    pub fn new() -> Self { Self::default() }
+    pub fn resolve_dns(mut self, resolve: bool) -> Self { self.resolve_dns = resolve; self }
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self { self.keepalive_interval = Some(interval); self }
+}
+
+/// Timings of a warm-up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmUpReport {
+    /// DNS lookup time, when `resolve_dns` is set.
+    pub dns_lookup: Option<Duration>,
+    /// Addresses the host resolved to, when `resolve_dns` is set.
+    pub resolved_addresses: usize,
+    /// Time for the `HEAD` request, including connection setup.
+    pub request: Duration,
+    /// HTTP status of the `HEAD` request; any status means the connection is up.
+    pub status: u16,
+    /// Whether this call started the keepalive task; it is started at most once per client.
+    pub keepalive_started: bool,
+}
+
+pub(crate) async fn warm_up(ctx: &Arc<ServiceContext>) -> Result<WarmUpReport> {
+    let config = &ctx.config.warm_up;
+    let base_url = ctx.config.base_url.clone();
+
+    let (dns_lookup, resolved_addresses) = if config.resolve_dns {
+        let (elapsed, count) = resolve(&base_url).await?;
+        (Some(elapsed), count)
+    } else {
+        (None, 0)
+    };
+
+    let started = Instant::now();
+    let response = ctx.http.head(&base_url).send().await?;
+    let request = started.elapsed();
+    let status = response.status().as_u16();
+    debug!(dns = ?dns_lookup, request = ?request, status = %status, "Connection warmed up");
+
+    let keepalive_started = match config.keepalive_interval {
+        Some(interval) if !ctx.keepalive.swap(true, Ordering::SeqCst) => {
+            spawn_keepalive(ctx, base_url, interval)?;
+            true
+        }
+        _ => false,
+    };
+
+    Ok(WarmUpReport { dns_lookup, resolved_addresses, request, status, keepalive_started })
+}
+
+async fn resolve(base_url: &str) -> Result<(Duration, usize)> {
+    let url = reqwest::Url::parse(base_url).map_err(|e| Error::Config(format!("Invalid base URL: {}", e)))?;
+    let host = url.host_str().ok_or_else(|| Error::Config("Base URL has no host".to_string()))?.to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let started = Instant::now();
+    // std's resolver blocks, so keep it off the async workers.
+    let addresses = tokio::task::spawn_blocking(move || (host.as_str(), port).to_socket_addrs().map(Iterator::count))
+        .await
+        .map_err(|e| Error::Config(format!("DNS lookup task failed: {}", e)))??;
+    Ok((started.elapsed(), addresses))
+}
+
+fn spawn_keepalive(ctx: &Arc<ServiceContext>, base_url: String, interval: Duration) -> Result<()> {
+    let http = ctx.http.clone();
+    ctx.lifecycle.spawn(move |shutdown| async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(interval) => {
+                    if let Err(e) = http.head(&base_url).send().await {
+                        warn!(error = %e, "Keepalive request failed");
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,236 @@
+//! `axum`/`tower` integration.
+//!
+//! [`GoldRushLayer`] shares one client with every request handler. The
+//! [`GoldRush`] extractor hands a handler that client scoped to the incoming
+//! request, and [`GoldRushOptions`] hands it just the [`RequestOptions`]:
+//! selected headers are forwarded (`X-Request-ID` by default, so audit entries
+//! line up with the web service's logs) and a [`CancellationToken`] is
+//! cancelled once the handler returns or the request is dropped, so calls
+//! still running for a disconnected client stop.
+//!
+//! ```rust,no_run
+//! use axum::{extract::Path, http::StatusCode, routing::get, Router};
+//! use goldrush_sdk::web::{GoldRush, GoldRushLayer};
+//! use goldrush_sdk::{ClientConfig, GoldRushClient};
+//!
+//! async fn balances(GoldRush(client): GoldRush, Path(address): Path<String>) -> Result<String, StatusCode> {
+//!     let response = client.balance_service()
+//!         .get_token_balances_for_wallet_address("eth-mainnet", address.as_str(), None)
+//!         .await
+//!         .map_err(|_| StatusCode::BAD_GATEWAY)?;
+//!     Ok(format!("{:.2}", response.data.map(|d| d.total_value()).unwrap_or_default()))
+//! }
+//!
+//! # fn example() -> Result<(), goldrush_sdk::Error> {
+//! let client = GoldRushClient::new("cqt_...", ClientConfig::default())?;
+//! let app: Router = Router::new()
+//!     .route("/balances/:address", get(balances))
+//!     .layer(GoldRushLayer::new(client).forward_header("x-tenant-id"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{CancellationToken, Error, GoldRushClient, RequestOptions};
+use ::axum::extract::FromRequestParts;
+use ::axum::http::{header::HeaderName, request::Parts, Request, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Client and forwarding rules installed in each request's extensions.
+#[derive(Clone)]
+struct Installed {
+    client: GoldRushClient,
+    forward_headers: Arc<[HeaderName]>,
+}
+
+/// Tower layer sharing a [`GoldRushClient`] with request handlers.
+#[derive(Clone)]
+pub struct GoldRushLayer {
+    client: GoldRushClient,
+    forward_headers: Vec<HeaderName>,
+}
+
+impl GoldRushLayer {
+    /// Share `client`, forwarding `X-Request-ID` to the API.
+    pub fn new(client: GoldRushClient) -> Self {
+        Self { client, forward_headers: vec![HeaderName::from_static("x-request-id")] }
+    }
+
+    /// Also forward this incoming header with the SDK's API calls.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid header name.
+    pub fn forward_header(mut self, name: &str) -> Self {
+        let name = HeaderName::try_from(name).expect("invalid header name");
+        if !self.forward_headers.contains(&name) {
+            self.forward_headers.push(name);
+        }
+        self
+    }
+}
+
+impl<S> tower_layer::Layer<S> for GoldRushLayer {
+    type Service = GoldRushService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GoldRushService {
+            inner,
+            installed: Installed {
+                client: self.client.clone(),
+                forward_headers: self.forward_headers.clone().into(),
+            },
+        }
+    }
+}
+
+/// Service produced by [`GoldRushLayer`].
+#[derive(Clone)]
+pub struct GoldRushService<S> {
+    inner: S,
+    installed: Installed,
+}
+
+impl<S, B> tower_service::Service<Request<B>> for GoldRushService<S>
+where
+    S: tower_service::Service<Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let token = CancellationToken::new();
+        request.extensions_mut().insert(self.installed.clone());
+        request.extensions_mut().insert(RequestCancellation(token.clone()));
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            // Dropping the guard cancels SDK calls still running for this request.
+            let _guard = token.drop_guard();
+            response.await
+        })
+    }
+}
+
+#[derive(Clone)]
+struct RequestCancellation(CancellationToken);
+
+/// Why a GoldRush extractor failed.
+#[derive(Debug)]
+pub enum GoldRushRejection {
+    /// The route is not wrapped in a [`GoldRushLayer`].
+    MissingLayer,
+    /// A forwarded header could not be applied.
+    InvalidOptions(Error),
+}
+
+impl IntoResponse for GoldRushRejection {
+    fn into_response(self) -> Response {
+        match self {
+            GoldRushRejection::MissingLayer => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "GoldRushLayer is not installed").into_response()
+            }
+            GoldRushRejection::InvalidOptions(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        }
+    }
+}
+
+/// Per-request [`RequestOptions`] built from the incoming request.
+#[derive(Debug, Clone)]
+pub struct GoldRushOptions(pub RequestOptions);
+
+#[::axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for GoldRushOptions {
+    type Rejection = GoldRushRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let installed = parts.extensions.get::<Installed>().ok_or(GoldRushRejection::MissingLayer)?;
+        Ok(Self(request_options(parts, installed)))
+    }
+}
+
+/// The shared client, scoped to the incoming request.
+#[derive(Clone)]
+pub struct GoldRush(pub GoldRushClient);
+
+#[::axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for GoldRush {
+    type Rejection = GoldRushRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let installed = parts.extensions.get::<Installed>().ok_or(GoldRushRejection::MissingLayer)?;
+        let options = request_options(parts, installed);
+        installed
+            .client
+            .with_request_options(options)
+            .map(Self)
+            .map_err(GoldRushRejection::InvalidOptions)
+    }
+}
+
+fn request_options(parts: &Parts, installed: &Installed) -> RequestOptions {
+    let mut options = RequestOptions::new();
+    for name in installed.forward_headers.iter() {
+        if let Some(value) = parts.headers.get(name).and_then(|v| v.to_str().ok()) {
+            options = options.header(name.as_str(), value);
+        }
+    }
+    if let Some(RequestCancellation(token)) = parts.extensions.get() {
+        options = options.cancellation(token.clone());
+    }
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::axum::body::Body;
+    use ::axum::routing::get;
+    use ::axum::Router;
+    use tower_service::Service;
+
+    async fn call(app: &mut Router, request: Request<Body>) -> Response {
+        std::future::poll_fn(|cx| <Router as Service<Request<Body>>>::poll_ready(app, cx)).await.unwrap();
+        app.call(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_layer_injects_client_and_options() {
+        async fn handler(GoldRush(client): GoldRush, GoldRushOptions(options): GoldRushOptions) -> String {
+            assert!(!client.is_shutting_down());
+            assert!(options.cancellation.is_some());
+            let mut headers: Vec<_> = options.headers.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            headers.sort();
+            headers.join(",")
+        }
+
+        let client = GoldRushClient::new("cqt_rQ4mW8kP2vB7nX3jL9sT5yH1gF", crate::ClientConfig::new("http://127.0.0.1:9")).unwrap();
+        let mut app = Router::new()
+            .route("/", get(handler))
+            .layer(GoldRushLayer::new(client).forward_header("X-Tenant-Id"));
+
+        let request = Request::builder()
+            .uri("/")
+            .header("x-request-id", "abc")
+            .header("x-tenant-id", "acme")
+            .header("x-other", "dropped")
+            .body(Body::empty())
+            .unwrap();
+        let response = call(&mut app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = ::axum::body::to_bytes(response.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], b"x-request-id=abc,x-tenant-id=acme");
+
+        let mut bare = Router::new().route("/", get(|GoldRush(_): GoldRush| async { "unreachable" }));
+        let response = call(&mut bare, Request::builder().uri("/").body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -1,6 +1,6 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, MultiChainBalancesOptions, Error};
+use goldrush_sdk::{GoldRushClient, ClientConfig, MultiChainBalancesOptions, Error, QuoteCurrency};
 
-/// Integration tests for the all-chains service.
+// Integration tests for the all-chains service.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
@@ -36,7 +36,7 @@ async fn test_get_address_activity() {
 async fn test_get_multi_chain_balances() {
     let Some(client) = get_test_client() else { return; };
 
-    let opts = MultiChainBalancesOptions::new().quote_currency("USD");
+    let opts = MultiChainBalancesOptions::new().quote_currency(QuoteCurrency::USD);
 
     let result = client.all_chains_service()
         .get_multi_chain_balances("0xfc43f5f9dd45258b3aff31bdbe6561d97e8b71de", Some(opts))
@@ -1,9 +1,9 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, BalancesOptions, Error};
+use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, BalancesOptions, Error, QuoteCurrency};
 
-/// Integration tests for the balances service.
-///
-/// These tests require a valid API key set as the GOLDRUSH_API_KEY environment variable.
-/// If the env var is not set, tests will be skipped.
+// Integration tests for the balances service.
+//
+// These tests require a valid API key set as the GOLDRUSH_API_KEY environment variable.
+// If the env var is not set, tests will be skipped.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
@@ -51,7 +51,7 @@ async fn test_get_token_balances_with_options() {
     };
 
     let options = BalancesOptions::new()
-        .quote_currency("USD")
+        .quote_currency(QuoteCurrency::USD)
         .page_size(5)
         .no_spam(true);
 
@@ -1,6 +1,6 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, Error};
+use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, GasEventType, Error};
 
-/// Integration tests for the base service.
+// Integration tests for the base service.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
@@ -79,7 +79,7 @@ async fn test_get_gas_prices() {
     let Some(client) = get_test_client() else { return; };
 
     let result = client.base_service()
-        .get_gas_prices(Chain::EthereumMainnet, "erc20")
+        .get_gas_prices(Chain::EthereumMainnet, GasEventType::Erc20)
         .await;
 
     match result {
@@ -95,3 +95,24 @@ async fn test_get_gas_prices() {
         Err(e) => println!("Gas prices error (may be expected): {:?}", e),
     }
 }
+
+#[tokio::test]
+async fn test_get_token_metadata() {
+    let Some(client) = get_test_client() else { return; };
+
+    let result = client.base_service()
+        .get_token_metadata(Chain::EthereumMainnet, "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+        .await;
+
+    match result {
+        Ok(response) => {
+            if let Some(token) = response.data.as_ref().and_then(|d| d.token()) {
+                println!("  {:?} ({:?}), {:?} decimals", token.contract_name, token.contract_ticker_symbol, token.contract_decimals);
+            }
+        }
+        Err(Error::Api { status: 401, .. }) => {
+            println!("Authentication failed - check your API key");
+        }
+        Err(e) => println!("Token metadata error (may be expected): {:?}", e),
+    }
+}
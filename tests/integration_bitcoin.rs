@@ -1,6 +1,6 @@
 use goldrush_sdk::{GoldRushClient, ClientConfig, Error};
 
-/// Integration tests for the bitcoin service.
+// Integration tests for the bitcoin service.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
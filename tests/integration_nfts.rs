@@ -1,8 +1,8 @@
 use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, NftOptions, Error};
 
-/// Integration tests for the NFTs service.
-///
-/// These tests require a valid API key set as the GOLDRUSH_API_KEY environment variable.
+// Integration tests for the NFTs service.
+//
+// These tests require a valid API key set as the GOLDRUSH_API_KEY environment variable.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
@@ -1,6 +1,6 @@
 use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, QuoteCurrency, PricingOptions, Error};
 
-/// Integration tests for the pricing service.
+// Integration tests for the pricing service.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
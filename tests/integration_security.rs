@@ -1,6 +1,6 @@
 use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, Error};
 
-/// Integration tests for the security service.
+// Integration tests for the security service.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
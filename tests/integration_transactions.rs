@@ -1,8 +1,8 @@
-use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, TxOptions, Error};
+use goldrush_sdk::{GoldRushClient, ClientConfig, Chain, TxOptions, Error, QuoteCurrency};
 
-/// Integration tests for the transactions service.
-///
-/// These tests require a valid API key set as the GOLDRUSH_API_KEY environment variable.
+// Integration tests for the transactions service.
+//
+// These tests require a valid API key set as the GOLDRUSH_API_KEY environment variable.
 
 fn get_test_client() -> Option<GoldRushClient> {
     if let Ok(api_key) = std::env::var("GOLDRUSH_API_KEY") {
@@ -58,7 +58,7 @@ async fn test_get_transactions_with_options() {
 
     let options = TxOptions::new()
         .page_size(3)
-        .quote_currency("USD")
+        .quote_currency(QuoteCurrency::USD)
         .no_logs(true);
 
     let result = client
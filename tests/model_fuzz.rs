@@ -0,0 +1,174 @@
+//! Property tests for model deserialization.
+//!
+//! Each captured payload in `tests/corpus/` is mutated (fields removed, set to
+//! null, replaced by values of another type, unknown fields added) and run
+//! through its model. Decoding may fail, but must never panic, and whatever
+//! decodes must survive a second round trip unchanged.
+
+use goldrush_sdk::testing::{corpus, Payload};
+use proptest::prelude::*;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+type Path = Vec<Segment>;
+
+#[derive(Debug, Clone)]
+enum Mutation {
+    Remove,
+    Null,
+    Replace(Value),
+    AddUnknown(String, Value),
+}
+
+/// Path of every object field in `value`, depth first.
+fn field_paths(value: &Value, prefix: &mut Path, out: &mut Vec<Path>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                prefix.push(Segment::Key(key.clone()));
+                out.push(prefix.clone());
+                field_paths(child, prefix, out);
+                prefix.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                prefix.push(Segment::Index(i));
+                field_paths(child, prefix, out);
+                prefix.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The object holding the field at `path`, and the field's key.
+fn parent<'a>(value: &'a mut Value, path: &[Segment]) -> (&'a mut Map<String, Value>, String) {
+    let (last, init) = path.split_last().expect("field paths are never empty");
+    let mut current = value;
+    for segment in init {
+        current = match segment {
+            Segment::Key(key) => &mut current[key.as_str()],
+            Segment::Index(i) => &mut current[*i],
+        };
+    }
+    let Segment::Key(key) = last else { unreachable!("field paths end in a key") };
+    (current.as_object_mut().expect("field parent is an object"), key.clone())
+}
+
+fn apply(value: &mut Value, path: &[Segment], mutation: &Mutation) {
+    let (object, key) = parent(value, path);
+    match mutation {
+        Mutation::Remove => {
+            object.remove(&key);
+        }
+        Mutation::Null => {
+            object.insert(key, Value::Null);
+        }
+        Mutation::Replace(replacement) => {
+            object.insert(key, replacement.clone());
+        }
+        Mutation::AddUnknown(name, extra) => {
+            object.insert(format!("x_unknown_{}", name), extra.clone());
+        }
+    }
+}
+
+fn scalar() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        any::<f64>().prop_filter("JSON has no NaN or infinity", |f| f.is_finite()).prop_map(Value::from),
+        "[0-9]{0,40}".prop_map(Value::from),
+        ".{0,12}".prop_map(Value::from),
+        Just(Value::Array(Vec::new())),
+        Just(Value::Object(Map::new())),
+    ]
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        Just(Mutation::Remove),
+        Just(Mutation::Null),
+        scalar().prop_map(Mutation::Replace),
+        ("[a-z]{1,8}", scalar()).prop_map(|(name, value)| Mutation::AddUnknown(name, value)),
+    ]
+}
+
+fn payload() -> impl Strategy<Value = &'static Payload> {
+    prop::sample::select(corpus().iter().collect::<Vec<_>>())
+}
+
+/// Mutations as (field selector, mutation) pairs.
+fn mutations() -> impl Strategy<Value = Vec<(usize, Mutation)>> {
+    prop::collection::vec((any::<usize>(), mutation()), 1..4)
+}
+
+/// `payload`'s body with the mutations applied; selectors wrap around the fields present.
+fn mutate(payload: &Payload, mutations: &[(usize, Mutation)]) -> Value {
+    let mut body = payload.value();
+    for (selector, mutation) in mutations {
+        let mut paths = Vec::new();
+        field_paths(&body, &mut Vec::new(), &mut paths);
+        if paths.is_empty() {
+            break;
+        }
+        apply(&mut body, &paths[selector % paths.len()], mutation);
+    }
+    body
+}
+
+fn one_field(payload: &Payload, selector: usize, mutation: Mutation) -> Value {
+    mutate(payload, &[(selector, mutation)])
+}
+
+#[test]
+fn corpus_round_trips_unchanged() {
+    for payload in corpus() {
+        let once = payload.round_trip(payload.value()).unwrap_or_else(|e| panic!("{}: {}", payload.name, e));
+        let twice = payload.round_trip(once.clone()).unwrap();
+        assert_eq!(once, twice, "{}", payload.name);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn mutations_never_panic_and_round_trip(payload in payload(), mutations in mutations()) {
+        let body = mutate(payload, &mutations);
+        if let Ok(once) = payload.round_trip(body) {
+            let twice = payload.round_trip(once.clone());
+            prop_assert!(twice.is_ok(), "{}: re-decoding failed: {:?}", payload.name, twice);
+            prop_assert_eq!(once, twice.unwrap(), "{}", payload.name);
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored(payload in payload(), selector in any::<usize>(), value in scalar()) {
+        let body = one_field(payload, selector, Mutation::AddUnknown("field".to_string(), value));
+        let result = payload.round_trip(body);
+        prop_assert!(result.is_ok(), "{}: {:?}", payload.name, result);
+    }
+
+    #[test]
+    fn null_and_missing_fields_agree(payload in payload(), selector in any::<usize>()) {
+        let nulled = payload.round_trip(one_field(payload, selector, Mutation::Null));
+        let removed = payload.round_trip(one_field(payload, selector, Mutation::Remove));
+        prop_assert_eq!(
+            nulled.is_ok(),
+            removed.is_ok(),
+            "{}: null {:?}, missing {:?}",
+            payload.name,
+            nulled.err(),
+            removed.err()
+        );
+    }
+}